@@ -0,0 +1,186 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Board, Rect};
+
+// A background that repeats across the whole board in place of empty space, for studying how
+// patterns react inside it. Under Conway's rule stripes and chicken wire are still, and zebra
+// stripes and blinkers flip back and forth every generation; the checkerboard dies out under
+// Conway's rule but holds under others.
+//
+// Agars are laid down lined up with the board's top-left corner, so filling part of the board
+// carries on from a fill of the whole. On a wrapped board an agar only meets itself in step
+// across the edges when its tile fits a whole number of times each way; anywhere else the seam
+// sets off reactions all along the edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Agar {
+    // Rows of live cells with a dead row between each.
+    Stripes,
+    // Live rows two deep with two dead rows between them.
+    Zebra,
+    // Pairs of cells in a brick pattern.
+    ChickenWire,
+    // A blinker every fourth cell each way.
+    Blinkers,
+    // Live and dead cells alternating both ways.
+    Checkerboard,
+}
+
+impl Agar {
+    pub const ALL: [Agar; 5] = [
+        Agar::Stripes,
+        Agar::Zebra,
+        Agar::ChickenWire,
+        Agar::Blinkers,
+        Agar::Checkerboard,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Agar::Stripes => "stripes",
+            Agar::Zebra => "zebra",
+            Agar::ChickenWire => "chicken-wire",
+            Agar::Blinkers => "blinkers",
+            Agar::Checkerboard => "checkerboard",
+        }
+    }
+
+    // The cells that repeat, a row a string.
+    fn rows(&self) -> &'static [&'static str] {
+        match self {
+            Agar::Stripes => &["O", "."],
+            Agar::Zebra => &["O", "O", ".", "."],
+            Agar::ChickenWire => &["OO..", "..OO"],
+            Agar::Blinkers => &["OOO.", "....", "....", "...."],
+            Agar::Checkerboard => &["O.", ".O"],
+        }
+    }
+
+    // The width and height of the tile that repeats.
+    pub fn tile_size(&self) -> (usize, usize) {
+        let rows = self.rows();
+        (rows[0].len(), rows.len())
+    }
+
+    // One tile of the agar.
+    pub fn tile(&self) -> Board {
+        let (width, height) = self.tile_size();
+        Board::from_fn(width, height, |x, y| self.is_alive(x, y))
+    }
+
+    // Whether the agar has a live cell at (x, y), counting from the board's top-left corner.
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        let rows = self.rows();
+        let row = rows[y % rows.len()].as_bytes();
+        row[x % row.len()] == b'O'
+    }
+
+    // Whether the agar wraps around an x_size by y_size board without a seam.
+    pub fn seamless(&self, x_size: usize, y_size: usize) -> bool {
+        let (width, height) = self.tile_size();
+        x_size.is_multiple_of(width) && y_size.is_multiple_of(height)
+    }
+
+    // Replace the cells in a region of the board with the agar.
+    pub fn fill(&self, board: &mut Board, region: Rect) {
+        let (x_size, y_size) = (board.x_size(), board.y_size());
+        for x in region.xs().take_while(|&x| x < x_size) {
+            for y in region.ys().take_while(|&y| y < y_size) {
+                board.set(x, y, self.is_alive(x, y));
+            }
+        }
+    }
+}
+
+impl fmt::Display for Agar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Agar {
+    type Err = UnknownAgar;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Agar::ALL
+            .into_iter()
+            .find(|agar| agar.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownAgar(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownAgar(pub String);
+
+impl fmt::Display for UnknownAgar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = Agar::ALL.iter().map(Agar::name).collect();
+        write!(
+            f,
+            "unknown agar {:?}, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownAgar {}
+
+// An agar that can't fill a whole board without a seam where it wraps around.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AgarSeam {
+    pub agar: Agar,
+    pub x_size: usize,
+    pub y_size: usize,
+}
+
+impl fmt::Display for AgarSeam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height) = self.agar.tile_size();
+        write!(
+            f,
+            "{} repeats every {width}x{height} cells, which doesn't fit a {}x{} board a whole \
+             number of times, so it would meet itself out of step at the edges",
+            self.agar, self.x_size, self.y_size
+        )
+    }
+}
+
+impl Error for AgarSeam {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Game;
+
+    #[test]
+    fn test_periods() {
+        // How often each agar repeats in time under Conway's rule, or None if it doesn't last.
+        let periods: Vec<Option<usize>> = Agar::ALL
+            .iter()
+            .map(|agar| {
+                let mut game = Game::new(12, 12);
+                game.fill_agar(*agar).unwrap();
+                let start = game.board().clone();
+                (1..=4).find(|_| {
+                    game.iterate();
+                    *game.board() == start
+                })
+            })
+            .collect();
+        assert_eq!(vec![Some(1), Some(2), Some(1), Some(2), None], periods);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut board = Board::new(6, 4);
+        Agar::ChickenWire.fill(&mut board, Rect::new(2, 1, 10, 2));
+        assert_eq!("......\n..OO..\n....OO\n......\n", board.to_string());
+        assert_eq!("OO..\n..OO\n", Agar::ChickenWire.tile().to_string());
+
+        assert!(Agar::Blinkers.seamless(8, 12));
+        assert!(!Agar::Blinkers.seamless(8, 10));
+        assert_eq!(Ok(Agar::ChickenWire), "Chicken-Wire".parse());
+    }
+}
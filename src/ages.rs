@@ -0,0 +1,64 @@
+use crate::Board;
+
+// How many generations in a row each cell has been live, counting the current one, set up with
+// Game::track_ages(). Still lifes and the stators of oscillators grow old while everything around
+// them comes and goes, so coloring cells by age picks them out at a glance. Ages stop at u16::MAX
+// rather than wrapping back to newborn.
+#[derive(Clone, Debug)]
+pub(crate) struct Ages {
+    // Column by column, like Board, so the age of (x, y) is at x * y_size + y.
+    ages: Vec<u16>,
+}
+
+impl Ages {
+    // Start counting on a board, with every live cell as old as a newborn.
+    pub(crate) fn new(board: &Board) -> Self {
+        Ages {
+            ages: board.columns().flatten().map(u16::from).collect(),
+        }
+    }
+
+    // Age the cells still live after an iteration and reset the ones that died. A board of a
+    // different size starts the count again.
+    pub(crate) fn update(&mut self, board: &Board) {
+        if self.ages.len() != board.cell_count() {
+            *self = Ages::new(board);
+            return;
+        }
+        for (age, alive) in self.ages.iter_mut().zip(board.columns().flatten()) {
+            *age = if alive { age.saturating_add(1) } else { 0 };
+        }
+    }
+
+    // The age of the cell at a flat index, given whether it's live now. Cells edited since the last
+    // iteration are counted as newborn if they were set live, and dead ones are always 0.
+    pub(crate) fn get(&self, index: usize, alive: bool) -> u16 {
+        match alive {
+            true => self.ages.get(index).copied().unwrap_or(0).max(1),
+            false => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ages() {
+        let mut board = Board::from_fn(3, 2, |x, _| x == 0);
+        let mut ages = Ages::new(&board);
+        ages.update(&board);
+        board.set(0, 1, false);
+        board.set(2, 0, true);
+        ages.update(&board);
+        assert_eq!(3, ages.get(0, true));
+        assert_eq!(0, ages.get(1, false));
+        assert_eq!(1, ages.get(4, true));
+        // A cell set live since the last update is newborn, whatever it was before.
+        assert_eq!(1, ages.get(2, true));
+
+        ages.update(&Board::from_fn(2, 2, |_, _| true));
+        assert_eq!(1, ages.get(3, true));
+    }
+}
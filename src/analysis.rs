@@ -0,0 +1,424 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::{Game, Point};
+
+// What a pattern settles into, judged by the shape of its live cells wherever they are on the
+// board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    // Every cell died.
+    Extinct,
+    // The pattern stopped changing.
+    StillLife,
+    // The pattern returns to the same cells every `period` generations.
+    Oscillator { period: usize },
+    // The pattern returns to the same shape every `period` generations, moved by (dx, dy).
+    Spaceship { period: usize, dx: isize, dy: isize },
+    // No repeat turned up before the generation limit.
+    Unsettled,
+}
+
+impl Behavior {
+    // The number of generations between repeats, for patterns that repeat.
+    pub fn period(&self) -> Option<usize> {
+        match *self {
+            Behavior::Extinct | Behavior::StillLife => Some(1),
+            Behavior::Oscillator { period } | Behavior::Spaceship { period, .. } => Some(period),
+            Behavior::Unsettled => None,
+        }
+    }
+
+    pub fn is_settled(&self) -> bool {
+        *self != Behavior::Unsettled
+    }
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Behavior::Extinct => write!(f, "extinct"),
+            Behavior::StillLife => write!(f, "still life"),
+            Behavior::Oscillator { period } => write!(f, "oscillator with period {period}"),
+            Behavior::Spaceship { period, dx, dy } => {
+                write!(
+                    f,
+                    "spaceship moving ({dx}, {dy}) every {period} generations"
+                )
+            }
+            Behavior::Unsettled => write!(f, "unsettled"),
+        }
+    }
+}
+
+// The result of running a game until it repeats or reaches a generation limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub behavior: Behavior,
+    // The first generation of the repeating cycle, or the limit if there wasn't one.
+    pub settled_at: usize,
+    // How many generations were run.
+    pub generations: usize,
+    // The number of live cells once the run stopped.
+    pub population: usize,
+}
+
+// Run the game until its live cells repeat a shape they've had before, for at most max_generations
+// generations. The game is left at the point the repeat was found.
+//
+// Shapes are compared after moving them to the origin, so a spaceship shows up as soon as it
+// repeats rather than when it wraps all the way around the board. A spaceship crossing an edge
+// isn't recognized until it's all the way across, since the wrap splits it in two.
+pub fn analyze(game: &mut Game, max_generations: usize) -> Analysis {
+    analyze_with(game, max_generations, |_| {})
+}
+
+// The same as analyze(), but handing the game to `each` at every generation along the way,
+// starting with the one it's given, for watching a long run as it happens.
+//
+// Cancelling the game's handle stops the analysis where it is, with the game counted as unsettled
+// at whatever generation it reached.
+pub fn analyze_with<F: FnMut(&Game)>(
+    game: &mut Game,
+    max_generations: usize,
+    mut each: F,
+) -> Analysis {
+    // Each shape seen so far, with the generation and top-left corner it was seen at.
+    let mut seen: HashMap<Vec<Point>, (usize, Point)> = HashMap::new();
+    for generation in 0..=max_generations {
+        if generation > 0 {
+            if game.is_cancelled() {
+                return finish(game, Behavior::Unsettled, generation - 1, generation - 1);
+            }
+            game.iterate();
+        }
+        each(game);
+
+        let (shape, corner) = normalize(game);
+        if shape.is_empty() {
+            return finish(game, Behavior::Extinct, generation, generation);
+        }
+
+        if let Some(&(start, first_corner)) = seen.get(&shape) {
+            let period = generation - start;
+            let dx = corner.x as isize - first_corner.x as isize;
+            let dy = corner.y as isize - first_corner.y as isize;
+            let behavior = match (dx, dy) {
+                (0, 0) if period == 1 => Behavior::StillLife,
+                (0, 0) => Behavior::Oscillator { period },
+                _ => Behavior::Spaceship { period, dx, dy },
+            };
+            return finish(game, behavior, start, generation);
+        }
+        seen.insert(shape, (generation, corner));
+    }
+    finish(game, Behavior::Unsettled, max_generations, max_generations)
+}
+
+// Run the game until its board comes back exactly as it was within max_period generations, for at
+// most max_generations generations, and say whether it died out, stopped changing, or oscillates.
+// The game is left at the point the repeat was found.
+//
+// Only the checksums of the last max_period boards are kept, so the run takes the same memory
+// however long it goes, which suits classifying soups by the thousand. A spaceship only repeats
+// exactly once it's wrapped all the way around the board, so it usually goes unsettled here where
+// analyze() would name it.
+pub fn find_cycle(game: &mut Game, max_generations: usize, max_period: usize) -> Analysis {
+    let mut recent = VecDeque::with_capacity(max_period);
+    for generation in 0..=max_generations {
+        if generation > 0 {
+            if game.is_cancelled() {
+                return finish(game, Behavior::Unsettled, generation - 1, generation - 1);
+            }
+            game.iterate();
+        }
+        if game.board().population() == 0 {
+            return finish(game, Behavior::Extinct, generation, generation);
+        }
+
+        let checksum = game.board().checksum();
+        if let Some(back) = recent.iter().rev().position(|&seen| seen == checksum) {
+            let period = back + 1;
+            let behavior = match period {
+                1 => Behavior::StillLife,
+                _ => Behavior::Oscillator { period },
+            };
+            return finish(game, behavior, generation - period, generation);
+        }
+        if recent.len() == max_period {
+            recent.pop_front();
+        }
+        recent.push_back(checksum);
+    }
+    finish(game, Behavior::Unsettled, max_generations, max_generations)
+}
+
+// Run the game until its population has cycled with the same period, at most max_period, for
+// long enough to trust, or until max_generations have passed. Returns the period, or None if the
+// population never settled or the game was cancelled first.
+//
+// Only the population is watched, so spaceships flying off into empty space don't stop a pattern
+// from counting as stable. That's the test apgsearch uses on soups, which usually throw off a few
+// gliders as they settle.
+pub fn stabilize(game: &mut Game, max_generations: usize, max_period: usize) -> Option<usize> {
+    stabilization(game, max_generations, max_period).map(|stabilization| stabilization.period)
+}
+
+// When and how a game's population settled, as found by stabilization().
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stabilization {
+    pub period: usize,
+    // The first generation the population started repeating from.
+    pub settled_at: usize,
+    // How many generations were run before the repeat was trusted.
+    pub generations: usize,
+    pub population: usize,
+}
+
+// The same as stabilize(), but also saying when the population settled and how long it took to be
+// sure of it.
+pub fn stabilization(
+    game: &mut Game,
+    max_generations: usize,
+    max_period: usize,
+) -> Option<Stabilization> {
+    stabilization_with(game, max_generations, max_period, |_| {})
+}
+
+// The same as stabilization(), but handing the game to `each` at every generation along the way,
+// as analyze_with() does.
+pub fn stabilization_with<F: FnMut(&Game)>(
+    game: &mut Game,
+    max_generations: usize,
+    max_period: usize,
+    mut each: F,
+) -> Option<Stabilization> {
+    each(game);
+    let mut history = vec![game.board().population()];
+    for _ in 0..max_generations {
+        if game.is_cancelled() {
+            return None;
+        }
+        game.iterate();
+        each(game);
+        history.push(game.board().population());
+
+        let period = (1..=max_period).find(|&period| {
+            // A quiet stretch of a few dozen generations could just be a lull, so long periods
+            // have to repeat a few times and short ones have to hold for a while.
+            let window = (4 * period).max(30);
+            history.len() > window + period
+                && (history.len() - window..history.len())
+                    .all(|index| history[index] == history[index - period])
+        });
+        if let Some(period) = period {
+            // Walk back past the window to where the repeating started.
+            let mut settled_at = history.len() - 1 - period;
+            while settled_at > 0 && history[settled_at - 1] == history[settled_at - 1 + period] {
+                settled_at -= 1;
+            }
+            return Some(Stabilization {
+                period,
+                settled_at,
+                generations: history.len() - 1,
+                population: game.board().population(),
+            });
+        }
+    }
+    None
+}
+
+fn finish(game: &Game, behavior: Behavior, settled_at: usize, generations: usize) -> Analysis {
+    Analysis {
+        behavior,
+        settled_at,
+        generations,
+        population: game.board().population(),
+    }
+}
+
+// The live cells relative to the top-left corner of their bounding box, along with that corner.
+fn normalize(game: &Game) -> (Vec<Point>, Point) {
+    let Some(bounds) = game.board().bounding_box() else {
+        return (Vec::new(), Point::default());
+    };
+    let shape = game
+        .board()
+        .live_cells()
+        .map(|point| Point::new(point.x - bounds.x, point.y - bounds.y))
+        .collect();
+    (shape, Point::new(bounds.x, bounds.y))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CancelHandle;
+
+    #[test]
+    fn test_stabilization() {
+        // A blinker and a stray cell that dies in the first generation. Only the population is
+        // watched, and the blinker's never changes.
+        let mut game = Game::new(12, 12);
+        game.set([(2, 1), (2, 2), (2, 3), (8, 8)]).unwrap();
+        let stabilization = stabilization(&mut game, 100, 4).unwrap();
+        assert_eq!(
+            Stabilization {
+                period: 1,
+                settled_at: 1,
+                generations: 31,
+                population: 3,
+            },
+            stabilization
+        );
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let analysis = find_cycle(&mut game, 10, 4);
+        assert_eq!(Behavior::Oscillator { period: 2 }, analysis.behavior);
+        assert_eq!((0, 2), (analysis.settled_at, analysis.generations));
+
+        let mut game = Game::new(5, 5);
+        game.set([(0, 0), (3, 3)]).unwrap();
+        let analysis = find_cycle(&mut game, 10, 4);
+        assert_eq!(
+            (Behavior::Extinct, 1),
+            (analysis.behavior, analysis.settled_at)
+        );
+
+        // A glider on an 8 x 8 board only comes back to the same cells after 32 generations, once
+        // it's crossed the whole board.
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut game = Game::new(8, 8);
+        game.set(glider).unwrap();
+        assert_eq!(Behavior::Unsettled, find_cycle(&mut game, 40, 8).behavior);
+        let mut game = Game::new(8, 8);
+        game.set(glider).unwrap();
+        assert_eq!(
+            Behavior::Oscillator { period: 32 },
+            find_cycle(&mut game, 40, 32).behavior
+        );
+    }
+
+    #[test]
+    fn test_analyze_with() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let mut populations = Vec::new();
+        analyze_with(&mut game, 10, |game| {
+            populations.push((game.stats().generation, game.board().population()))
+        });
+        assert_eq!(vec![(0, 3), (1, 3), (2, 3)], populations);
+    }
+
+    #[test]
+    fn test_still_life() {
+        let mut game = Game::new(6, 6);
+        game.set([(1, 1), (2, 1), (1, 2), (2, 2)]).unwrap();
+        let analysis = analyze(&mut game, 10);
+        assert_eq!(Behavior::StillLife, analysis.behavior);
+        assert_eq!(
+            (0, 1, 4),
+            (
+                analysis.settled_at,
+                analysis.generations,
+                analysis.population
+            )
+        );
+    }
+
+    #[test]
+    fn test_oscillator() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let analysis = analyze(&mut game, 10);
+        assert_eq!(Behavior::Oscillator { period: 2 }, analysis.behavior);
+        assert_eq!(Some(2), analysis.behavior.period());
+        assert_eq!(2, analysis.generations);
+    }
+
+    #[test]
+    fn test_spaceship() {
+        let mut game = Game::new(20, 20);
+        game.set([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]).unwrap();
+        let analysis = analyze(&mut game, 10);
+        assert_eq!(
+            Behavior::Spaceship {
+                period: 4,
+                dx: 1,
+                dy: 1
+            },
+            analysis.behavior
+        );
+        assert_eq!(
+            "spaceship moving (1, 1) every 4 generations",
+            analysis.behavior.to_string()
+        );
+    }
+
+    #[test]
+    fn test_stabilize() {
+        // A blinker and a glider flying away from it keep the same population, so they count as
+        // stable even though the glider never repeats.
+        let mut game = Game::new(40, 40);
+        game.set([
+            (30, 30),
+            (30, 31),
+            (30, 32),
+            (1, 0),
+            (2, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+        ])
+        .unwrap();
+        assert_eq!(Some(1), stabilize(&mut game, 100, 10));
+        assert_eq!(8, game.board().population());
+
+        // The R-pentomino takes over a thousand generations to settle.
+        let mut game = Game::new(80, 80);
+        game.set([(40, 39), (41, 39), (39, 40), (40, 40), (40, 41)])
+            .unwrap();
+        assert_eq!(None, stabilize(&mut game, 300, 10));
+    }
+
+    #[test]
+    fn test_extinct_and_unsettled() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 2)]).unwrap();
+        let analysis = analyze(&mut game, 10);
+        assert_eq!(Behavior::Extinct, analysis.behavior);
+        assert_eq!(1, analysis.settled_at);
+
+        // The R-pentomino takes over a thousand generations to settle.
+        let mut game = Game::new(40, 40);
+        game.set([(20, 19), (21, 19), (19, 20), (20, 20), (20, 21)])
+            .unwrap();
+        let analysis = analyze(&mut game, 20);
+        assert_eq!(Behavior::Unsettled, analysis.behavior);
+        assert!(!analysis.behavior.is_settled());
+        assert_eq!(20, analysis.generations);
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let mut game = Game::new(40, 40);
+        game.set([(20, 19), (21, 19), (19, 20), (20, 20), (20, 21)])
+            .unwrap();
+        let cancel = CancelHandle::new();
+        game.set_cancel(Some(cancel.clone()));
+        let analysis = analyze_with(&mut game, 1000, |game| {
+            if game.stats().generation == 5 {
+                cancel.cancel();
+            }
+        });
+        assert_eq!(Behavior::Unsettled, analysis.behavior);
+        assert_eq!((5, 5), (analysis.generations, game.stats().generation));
+
+        assert_eq!(None, stabilize(&mut game, 1000, 4));
+        assert_eq!(5, game.stats().generation);
+    }
+}
@@ -0,0 +1,51 @@
+use egui::Ui;
+
+use gol::{Agar, Game};
+
+use super::macros::Action;
+
+// Fills the board with an agar, a repeating background to set patterns loose in.
+pub struct AgarPanel {
+    agar: Agar,
+}
+
+impl Default for AgarPanel {
+    fn default() -> Self {
+        AgarPanel {
+            agar: Agar::ChickenWire,
+        }
+    }
+}
+
+impl AgarPanel {
+    // Show the panel, returning the fill once Fill is clicked. Fill is only offered when the agar
+    // fits the board without a seam.
+    pub fn show(&mut self, ui: &mut Ui, game: &Game) -> Option<Action> {
+        let mut fill = None;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("agar")
+                .selected_text(self.agar.name())
+                .show_ui(ui, |ui| {
+                    for agar in Agar::ALL {
+                        ui.selectable_value(&mut self.agar, agar, agar.name());
+                    }
+                });
+            let seamless = self.agar.seamless(game.x_size(), game.y_size());
+            if ui
+                .add_enabled(seamless, egui::Button::new("Fill"))
+                .clicked()
+            {
+                fill = Some(Action::Agar(self.agar));
+            }
+        });
+        if !self.agar.seamless(game.x_size(), game.y_size()) {
+            let (width, height) = self.agar.tile_size();
+            ui.label(format!(
+                "The board has to be a multiple of {width}x{height} for {} to wrap around \
+                 without a seam.",
+                self.agar
+            ));
+        }
+        fill
+    }
+}
@@ -0,0 +1,158 @@
+use egui::Ui;
+
+use gol::Point;
+
+// The prefix marking an annotation among a pattern's comments, as in "#C [12,4] reflector".
+const PREFIX: &str = "C [";
+
+// A text label pinned to a cell.
+pub struct Annotation {
+    pub at: Point,
+    pub text: String,
+}
+
+// The labels attached to the board, for documenting the parts of guns and circuits. They're saved
+// with the board as pattern comments, so they come back when the pattern is opened again.
+#[derive(Default)]
+pub struct Annotations {
+    notes: Vec<Annotation>,
+    // Whether the labels are shown on the board.
+    pub visible: bool,
+    // A label being written, and the cell it's for.
+    draft: Option<(Point, String)>,
+}
+
+impl Annotations {
+    // Read the annotations out of a pattern's comments, ignoring every other comment.
+    pub fn from_comments(comments: &[String]) -> Self {
+        let notes: Vec<Annotation> = comments
+            .iter()
+            .filter_map(|comment| parse(comment))
+            .collect();
+        Annotations {
+            visible: !notes.is_empty(),
+            notes,
+            draft: None,
+        }
+    }
+
+    // The annotations as pattern comments.
+    pub fn to_comments(&self) -> impl Iterator<Item = String> + '_ {
+        self.notes
+            .iter()
+            .map(|note| format!("{PREFIX}{},{}] {}", note.at.x, note.at.y, note.text))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.notes.iter()
+    }
+
+    // The label on the cell at (x, y), if it has one.
+    pub fn at(&self, x: usize, y: usize) -> Option<&str> {
+        self.notes
+            .iter()
+            .find(|note| note.at == Point::new(x, y))
+            .map(|note| note.text.as_str())
+    }
+
+    // Start writing a label for the cell at (x, y), starting from its current label.
+    pub fn edit(&mut self, x: usize, y: usize) {
+        let text = self.at(x, y).unwrap_or_default().to_string();
+        self.draft = Some((Point::new(x, y), text));
+        self.visible = true;
+    }
+
+    // Show the label being written, if there is one, and the list of labels when they're visible.
+    pub fn show(&mut self, ui: &mut Ui) {
+        if let Some((at, text)) = &mut self.draft {
+            let mut done = None;
+            ui.horizontal(|ui| {
+                ui.label(format!("Label for ({}, {}):", at.x, at.y));
+                let response = ui.text_edit_singleline(text);
+                if ui.button("Save").clicked()
+                    || response.lost_focus()
+                        && ui.input(|input| input.key_pressed(egui::Key::Enter))
+                {
+                    done = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    done = Some(false);
+                }
+            });
+            if let Some(save) = done {
+                let (at, text) = self.draft.take().unwrap_or_default();
+                if save {
+                    self.set(at, text);
+                }
+            }
+        }
+
+        if !self.visible || self.notes.is_empty() {
+            return;
+        }
+        let mut removed = None;
+        ui.collapsing("Labels", |ui| {
+            for (index, note) in self.notes.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("({}, {}) {}", note.at.x, note.at.y, note.text));
+                    if ui.small_button("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+        });
+        if let Some(index) = removed {
+            self.notes.remove(index);
+        }
+    }
+
+    // Label a cell, replacing any label it had, or remove its label if the text is blank.
+    fn set(&mut self, at: Point, text: String) {
+        self.notes.retain(|note| note.at != at);
+        let text = text.trim();
+        if !text.is_empty() {
+            self.notes.push(Annotation {
+                at,
+                text: text.to_string(),
+            });
+        }
+    }
+}
+
+// Whether a pattern comment is an annotation rather than some other comment.
+pub fn is_annotation(comment: &str) -> bool {
+    parse(comment).is_some()
+}
+
+fn parse(comment: &str) -> Option<Annotation> {
+    let (at, text) = comment.strip_prefix(PREFIX)?.split_once("] ")?;
+    let (x, y) = at.split_once(',')?;
+    Some(Annotation {
+        at: Point::new(x.trim().parse().ok()?, y.trim().parse().ok()?),
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_comments_round_trip() {
+        let comments = vec![
+            "N Gosper glider gun".to_string(),
+            "C [12,4] reflector".to_string(),
+            "C [3, 20] eater #2".to_string(),
+            "C a plain comment".to_string(),
+        ];
+        let annotations = Annotations::from_comments(&comments);
+        assert!(annotations.visible);
+        assert_eq!(Some("reflector"), annotations.at(12, 4));
+        assert_eq!(Some("eater #2"), annotations.at(3, 20));
+        assert_eq!(None, annotations.at(0, 0));
+        assert_eq!(
+            vec!["C [12,4] reflector", "C [3,20] eater #2"],
+            annotations.to_comments().collect::<Vec<_>>()
+        );
+    }
+}
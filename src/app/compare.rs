@@ -0,0 +1,135 @@
+use egui::{Context, Ui};
+
+use gol::{Game, LifeLike, Rect};
+
+use super::detail;
+
+// The width or height of each board in the comparison, whichever is larger, in points.
+const VIEW_SIZE: f32 = 240.0;
+
+// A copy of the game run under another rule, stepped whenever the game itself steps so the two
+// can be watched diverging from the same start.
+pub struct Comparison {
+    game: Game,
+    rule: String,
+    error: Option<String>,
+}
+
+impl Comparison {
+    // Start a comparison from the game's current board. HighLife is the usual thing to hold Life
+    // up against, so that's the rule it starts with, unless the game is already running HighLife.
+    pub fn new(game: &Game) -> Self {
+        let highlife = LifeLike::new(&[3, 6], &[2, 3]);
+        let rule = if game.life_like() == Some(highlife) {
+            LifeLike::CONWAY
+        } else {
+            highlife
+        };
+        let mut comparison = Comparison {
+            game: game.clone(),
+            rule: rule.to_string(),
+            error: None,
+        };
+        comparison.game.set_rule(rule);
+        comparison
+    }
+
+    // Run the copy forward as many generations as the game just ran.
+    pub fn step(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.game.iterate();
+        }
+    }
+
+    // Start again from the game's current board, under whatever rule has been typed in.
+    fn restart(&mut self, game: &Game) {
+        match self.rule.parse::<LifeLike>() {
+            Ok(rule) => {
+                self.game = game.clone();
+                self.game.set_rule(rule);
+                self.error = None;
+            }
+            Err(err) => self.error = Some(format!("Couldn't read the rule: {err}")),
+        }
+    }
+
+    // Show both boards side by side in a window. Returns false once the window's been closed.
+    pub fn show(&mut self, ctx: &Context, game: &Game) -> bool {
+        // Transforms can reshape the board, and the copy can't follow along.
+        if (self.game.x_size(), self.game.y_size()) != (game.x_size(), game.y_size()) {
+            self.restart(game);
+        }
+
+        let mut open = true;
+        egui::Window::new("Compare Rules")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Rule:");
+                    ui.text_edit_singleline(&mut self.rule);
+                    if ui.button("Restart").clicked() {
+                        self.restart(game);
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.label(error);
+                }
+
+                ui.horizontal(|ui| {
+                    board(ui, game);
+                    ui.separator();
+                    board(ui, &self.game);
+                });
+            });
+        open
+    }
+}
+
+// One side of the comparison, with its rule and how it's doing.
+fn board(ui: &mut Ui, game: &Game) {
+    ui.vertical(|ui| {
+        let rule = game
+            .life_like()
+            .map_or_else(|| "custom rule".to_string(), |rule| rule.to_string());
+        let stats = game.stats();
+        ui.label(format!(
+            "{rule}: generation {}, population {}",
+            stats.generation, stats.population
+        ));
+        let region = Rect::new(0, 0, game.x_size(), game.y_size());
+        detail::paint(ui, game, region, VIEW_SIZE);
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_comparison() {
+        // The middle cell has six live neighbors, so it's born under HighLife but not Life.
+        let mut game = Game::new(8, 8);
+        game.set([(1, 1), (2, 1), (3, 1), (1, 3), (2, 3), (3, 3)])
+            .unwrap();
+        let mut comparison = Comparison::new(&game);
+        assert_eq!("B36/S23", comparison.rule);
+
+        game.iterate();
+        comparison.step(1);
+        assert_eq!(1, comparison.game.stats().generation);
+        assert!(!game.board()[(2, 2)]);
+        assert!(comparison.game.board()[(2, 2)]);
+
+        comparison.rule = "B3/Q23".to_string();
+        comparison.restart(&game);
+        assert!(comparison.error.is_some());
+        assert_eq!(1, comparison.game.stats().generation);
+
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        assert_eq!(
+            Some(LifeLike::CONWAY),
+            Comparison::new(&game).game.life_like()
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use egui::{vec2, Align2, Color32, Context, Painter, Pos2, Sense, Ui, Vec2};
+
+use gol::{Game, Rect, RenderTarget};
+
+// The width or height of the magnified view, whichever is larger, in points.
+const VIEW_SIZE: f32 = 240.0;
+
+// Show the selected part of the board magnified in a window in the bottom-right corner, so a small
+// reaction can be watched up close alongside the whole board.
+pub fn show(ctx: &Context, game: &Game, selection: Option<Rect>) {
+    egui::Window::new("Detail")
+        .anchor(Align2::RIGHT_BOTTOM, vec2(-8.0, -8.0))
+        .resizable(false)
+        .show(ctx, |ui| {
+            let Some(region) = selection.map(|region| clip(region, game)) else {
+                ui.label("Pick two corners with the Select tool to magnify them.");
+                return;
+            };
+            if region.is_empty() {
+                ui.label("The selection is off the edge of the board.");
+                return;
+            }
+
+            paint(ui, game, region, VIEW_SIZE);
+
+            ui.label(format!(
+                "{}x{} at ({}, {})",
+                region.width, region.height, region.x, region.y
+            ));
+        });
+}
+
+// Draw a region of the board scaled up so its longer side is `view_size` points.
+pub fn paint(ui: &mut Ui, game: &Game, region: Rect, view_size: f32) {
+    let cell = (view_size / region.width.max(region.height) as f32).max(1.0);
+    let size = vec2(region.width as f32, region.height as f32) * cell;
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    painter.rect_filled(response.rect, 0.0, ui.visuals().extreme_bg_color);
+    let mut target = Cells {
+        painter: &painter,
+        origin: response.rect.min,
+        cell,
+        live: ui.visuals().strong_text_color(),
+    };
+    gol::render(game.board(), region, &mut target);
+}
+
+// Draws live cells as squares on an egui painter, each with a small gap around it.
+struct Cells<'a> {
+    painter: &'a Painter,
+    origin: Pos2,
+    cell: f32,
+    live: Color32,
+}
+
+impl RenderTarget for Cells<'_> {
+    // The background is painted along with the space for the view.
+    fn begin_frame(&mut self, _width: usize, _height: usize) {}
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        for x in x..x + length {
+            let min = self.origin + vec2(x as f32, y as f32) * self.cell;
+            let area = egui::Rect::from_min_size(min, Vec2::splat(self.cell));
+            self.painter
+                .rect_filled(area.shrink(self.cell * 0.05), 0.0, self.live);
+        }
+    }
+}
+
+// The part of a rectangle that's on the board, since the board can shrink after it's selected.
+fn clip(region: Rect, game: &Game) -> Rect {
+    let x = region.x.min(game.x_size());
+    let y = region.y.min(game.y_size());
+    Rect::new(
+        x,
+        y,
+        region.width.min(game.x_size() - x),
+        region.height.min(game.y_size() - y),
+    )
+}
@@ -0,0 +1,31 @@
+use egui::{Id, Ui};
+
+use gol::GolWidget;
+
+// Fitting the whole board into view: once with the Fit button, or every frame with Auto Fit on so
+// the board follows the window as it's resized. Otherwise the zoom and pan stay wherever they're
+// put by scrolling and dragging.
+#[derive(Default)]
+pub struct Fit {
+    auto: bool,
+}
+
+impl Fit {
+    // The Fit button and the auto-fit checkbox, for the board widget with the given id.
+    pub fn show(&mut self, ui: &mut Ui, id: Id) {
+        ui.horizontal(|ui| {
+            if ui.button("Fit").clicked() {
+                GolWidget::reset_view(ui, id);
+            }
+            ui.checkbox(&mut self.auto, "Auto Fit");
+            ui.weak("Scroll to zoom, drag to pan.");
+        });
+    }
+
+    // Refit the board if auto-fit is on. Called before drawing it.
+    pub fn update(&self, ui: &Ui, id: Id) {
+        if self.auto {
+            GolWidget::reset_view(ui, id);
+        }
+    }
+}
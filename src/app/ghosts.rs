@@ -0,0 +1,46 @@
+use egui::{Color32, Painter};
+
+use gol::{Board, CellRects};
+
+// Draw faint copies of the live cells along each edge of a wrapped board just past the opposite
+// edge, where the cells there see them as neighbors, so a pattern heading for the edge shows what
+// it's about to run into. `cells` is where the board's cells were drawn.
+pub fn show(painter: &Painter, board: &Board, cells: &CellRects, color: Color32) {
+    for (x, y) in ghosts(board) {
+        let rect = cells.rect(x, y);
+        painter.rect_filled(rect.shrink(rect.width() * 0.2), 2.0, color);
+    }
+}
+
+// The positions one cell outside the board, from (-1, -1) to (x_size, y_size), whose wrapped
+// cell on the opposite edge is live.
+fn ghosts(board: &Board) -> Vec<(isize, isize)> {
+    let (x_size, y_size) = (board.x_size() as isize, board.y_size() as isize);
+    let mut ghosts = Vec::new();
+    for y in -1..=y_size {
+        for x in -1..=x_size {
+            let outside = x < 0 || y < 0 || x == x_size || y == y_size;
+            let wrapped = (x.rem_euclid(x_size) as usize, y.rem_euclid(y_size) as usize);
+            if outside && board[wrapped] {
+                ghosts.push((x, y));
+            }
+        }
+    }
+    ghosts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ghosts() {
+        // A cell in the top-left corner shows up past the right edge, the bottom edge, and the
+        // bottom-right corner.
+        let board: Board = "O..\n...\n".parse().unwrap();
+        assert_eq!(vec![(3, 0), (0, 2), (3, 2)], ghosts(&board));
+
+        let board: Board = "...\n.O.\n...\n".parse().unwrap();
+        assert!(ghosts(&board).is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use egui::Ui;
+
+use gol::Pattern;
+
+use super::annotations;
+
+// The name, author, and description of the pattern the board was opened from. The comments they
+// came in are written back out whenever the board's saved or copied, so a pattern passed through
+// the GUI keeps its provenance.
+#[derive(Default)]
+pub struct PatternInfo {
+    name: Option<String>,
+    author: Option<String>,
+    description: Vec<String>,
+    // Every comment the pattern came with but its labels, which Annotations keeps, as written.
+    comments: Vec<String>,
+}
+
+impl PatternInfo {
+    pub fn new(pattern: &Pattern) -> Self {
+        let mut kept = Pattern::new(0, 0);
+        for comment in pattern.comments() {
+            if !annotations::is_annotation(comment) {
+                kept.add_comment(comment.clone());
+            }
+        }
+        PatternInfo {
+            name: kept.name().map(str::to_string),
+            author: kept.author().map(str::to_string),
+            description: kept.description().map(str::to_string).collect(),
+            comments: kept.comments().to_vec(),
+        }
+    }
+
+    // The comments to save with the board, ahead of its labels.
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    // Show what's known about the pattern, if anything is.
+    pub fn show(&self, ui: &mut Ui) {
+        if self.name.is_none() && self.author.is_none() && self.description.is_empty() {
+            return;
+        }
+        let title = self.name.as_deref().unwrap_or("Pattern Info");
+        ui.collapsing(title, |ui| {
+            if let Some(author) = &self.author {
+                ui.label(format!("By {author}"));
+            }
+            for line in &self.description {
+                ui.label(line);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_info() {
+        let pattern: Pattern = "#N Glider\n#O Richard K. Guy\n#C [1,0] nose\n#C Moves c/4.\n\
+                                x = 3, y = 3\nbo$2bo$3o!"
+            .parse()
+            .unwrap();
+        let info = PatternInfo::new(&pattern);
+        assert_eq!(Some("Glider"), info.name.as_deref());
+        assert_eq!(Some("Richard K. Guy"), info.author.as_deref());
+        assert_eq!(vec!["Moves c/4."], info.description);
+        assert_eq!(
+            ["N Glider", "O Richard K. Guy", "C Moves c/4."],
+            info.comments()
+        );
+    }
+}
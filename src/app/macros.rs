@@ -0,0 +1,423 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use egui::Ui;
+
+use gol::{Agar, BlendMode, Game, LifeLike, Pattern, Point};
+
+use super::replay::ReplayPanel;
+use crate::config;
+
+// An edit to the board, the way a macro remembers it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    // Flip one cell.
+    Toggle(Point),
+    // Flood fill the region around a cell with live or dead cells.
+    Fill(Point, bool),
+    // Kill every one of the cells, as cutting a selection does.
+    Erase(Vec<Point>),
+    // Paste a pattern with its top-left corner at a cell.
+    Stamp(Point, Pattern),
+    Transform(Transform),
+    Clear,
+    // Switch to another rule.
+    Rule(LifeLike),
+    // Fill the whole board with a random soup of the given density from a seed.
+    Soup(f64, u64),
+    // Fill the whole board with an agar, if it fits without a seam.
+    Agar(Agar),
+}
+
+// The ways the Transform menu turns or mirrors the whole board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl Transform {
+    const ALL: [Transform; 5] = [
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Transform::Rotate90 => "rotate90",
+            Transform::Rotate180 => "rotate180",
+            Transform::Rotate270 => "rotate270",
+            Transform::FlipHorizontal => "fliph",
+            Transform::FlipVertical => "flipv",
+        }
+    }
+}
+
+impl Action {
+    // Make the edit. Cells that aren't on the board, as when a macro recorded on a bigger board is
+    // replayed on a smaller one, are left alone.
+    pub fn apply(&self, game: &mut Game) {
+        let on_board =
+            |game: &Game, point: &Point| point.x < game.x_size() && point.y < game.y_size();
+        match self {
+            Action::Toggle(point) => {
+                if on_board(game, point) {
+                    game.toggle(point.x, point.y);
+                }
+            }
+            Action::Fill(point, alive) => {
+                if on_board(game, point) {
+                    game.flood_fill(point.x, point.y, *alive);
+                }
+            }
+            Action::Erase(points) => {
+                for point in points {
+                    if on_board(game, point) {
+                        *game.cell(point.x, point.y) = false;
+                    }
+                }
+            }
+            // A pattern too big for the board starts a bigger game with it in the top-left
+            // corner, the way pasting does.
+            Action::Stamp(at, pattern) => {
+                if game
+                    .merge_at(&pattern.to_board(), *at, BlendMode::Or)
+                    .is_err()
+                {
+                    super::paste(game, pattern);
+                }
+            }
+            Action::Transform(transform) => match transform {
+                Transform::Rotate90 => game.rotate90(),
+                Transform::Rotate180 => game.rotate180(),
+                Transform::Rotate270 => game.rotate270(),
+                Transform::FlipHorizontal => game.flip_horizontal(),
+                Transform::FlipVertical => game.flip_vertical(),
+            },
+            Action::Clear => game.clear(),
+            Action::Rule(rule) => game.set_rule(*rule),
+            Action::Soup(density, seed) => {
+                game.randomize(*density, Some(*seed));
+            }
+            // A board the agar doesn't fit is left as it is.
+            Action::Agar(agar) => {
+                let _ = game.fill_agar(*agar);
+            }
+        }
+    }
+}
+
+// A list of edits that can be replayed on any board, for building the same test setup again
+// without clicking it all out. Saved as text, one action a line:
+//
+//     toggle 3 4
+//     fill 0 0 live
+//     erase 1,2 1,3
+//     transform rotate90
+//     clear
+//     rule B36/S23
+//     soup 0.5 42
+//     agar chicken-wire
+//     stamp 2 5
+//     x = 3, y = 1
+//     3o!
+//
+// where a stamp gives the cell for the pattern's top-left corner, and is followed by the pattern as
+// RLE, running to the line ending in "!".
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Macro {
+    actions: Vec<Action>,
+}
+
+impl Macro {
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn push(&mut self, action: Action) {
+        self.actions.push(action);
+    }
+
+    pub fn apply(&self, game: &mut Game) {
+        for action in &self.actions {
+            action.apply(game);
+        }
+    }
+}
+
+impl fmt::Display for Macro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for action in &self.actions {
+            match action {
+                Action::Toggle(point) => writeln!(f, "toggle {} {}", point.x, point.y)?,
+                Action::Fill(point, alive) => {
+                    let state = if *alive { "live" } else { "dead" };
+                    writeln!(f, "fill {} {} {state}", point.x, point.y)?;
+                }
+                Action::Erase(points) => {
+                    write!(f, "erase")?;
+                    for point in points {
+                        write!(f, " {},{}", point.x, point.y)?;
+                    }
+                    writeln!(f)?;
+                }
+                Action::Stamp(at, pattern) => {
+                    let rle = pattern.to_string();
+                    writeln!(f, "stamp {} {}\n{}", at.x, at.y, rle.trim_end())?;
+                }
+                Action::Transform(transform) => writeln!(f, "transform {}", transform.name())?,
+                Action::Clear => writeln!(f, "clear")?,
+                Action::Rule(rule) => writeln!(f, "rule {rule}")?,
+                Action::Soup(density, seed) => writeln!(f, "soup {density} {seed}")?,
+                Action::Agar(agar) => writeln!(f, "agar {agar}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Macro {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut actions = Vec::new();
+        let mut lines = s.lines().enumerate();
+        while let Some((index, line)) = lines.next() {
+            let invalid = || format!("line {}: can't read {line:?}", index + 1);
+            let number = |word: Option<&str>| word.and_then(|word| word.parse().ok());
+            let point = |x: Option<&str>, y: Option<&str>| Some(Point::new(number(x)?, number(y)?));
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let action = match words.as_slice() {
+                [] => continue,
+                ["toggle", x, y] => Action::Toggle(point(Some(x), Some(y)).ok_or_else(invalid)?),
+                ["fill", x, y, state @ ("live" | "dead")] => Action::Fill(
+                    point(Some(x), Some(y)).ok_or_else(invalid)?,
+                    *state == "live",
+                ),
+                ["erase", points @ ..] => Action::Erase(
+                    points
+                        .iter()
+                        .map(|pair| {
+                            let mut parts = pair.split(',');
+                            point(parts.next(), parts.next())
+                        })
+                        .collect::<Option<_>>()
+                        .ok_or_else(invalid)?,
+                ),
+                ["transform", name] => Action::Transform(
+                    Transform::ALL
+                        .into_iter()
+                        .find(|transform| transform.name() == *name)
+                        .ok_or_else(invalid)?,
+                ),
+                ["clear"] => Action::Clear,
+                ["rule", rule] => Action::Rule(rule.parse().map_err(|_| invalid())?),
+                ["soup", density, seed] => Action::Soup(
+                    density.parse().map_err(|_| invalid())?,
+                    seed.parse().map_err(|_| invalid())?,
+                ),
+                ["agar", agar] => Action::Agar(agar.parse().map_err(|_| invalid())?),
+                ["stamp", at @ ..] => {
+                    let at = match at {
+                        [] => Point::new(0, 0),
+                        [x, y] => point(Some(x), Some(y)).ok_or_else(invalid)?,
+                        _ => return Err(invalid()),
+                    };
+                    let mut rle = String::new();
+                    for (_, line) in lines.by_ref() {
+                        rle.push_str(line);
+                        rle.push('\n');
+                        if line.trim_end().ends_with('!') {
+                            break;
+                        }
+                    }
+                    let pattern = rle
+                        .parse()
+                        .map_err(|err| format!("line {}: {err}", index + 1))?;
+                    Action::Stamp(at, pattern)
+                }
+                _ => return Err(invalid()),
+            };
+            actions.push(action);
+        }
+        Ok(Macro { actions })
+    }
+}
+
+// Records the edits made on the board and plays them back, saving macros by name in the config
+// directory. Every edit goes through here, so it also feeds the session replay being recorded.
+pub struct MacroPanel {
+    // The edits made since recording started, while it's on.
+    recording: Option<Vec<Action>>,
+    // The last macro recorded or loaded.
+    current: Option<Macro>,
+    name: String,
+    message: Option<String>,
+    pub replays: ReplayPanel,
+}
+
+impl Default for MacroPanel {
+    fn default() -> Self {
+        MacroPanel {
+            recording: None,
+            current: None,
+            name: "harness".to_string(),
+            message: None,
+            replays: ReplayPanel::default(),
+        }
+    }
+}
+
+impl MacroPanel {
+    // Make an edit, remembering it if a macro or a replay is being recorded.
+    pub fn perform(&mut self, game: &mut Game, action: Action) {
+        action.apply(game);
+        self.replays.record(game, &action);
+        if let Some(recording) = &mut self.recording {
+            recording.push(action);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, game: &mut Game) {
+        ui.horizontal(|ui| match &self.recording {
+            Some(actions) => {
+                ui.label(format!("Recording, {} edits so far.", actions.len()));
+                if ui.button("Stop Recording").clicked() {
+                    let actions = self.recording.take().unwrap_or_default();
+                    self.current = Some(Macro { actions });
+                    self.message = None;
+                }
+            }
+            None => {
+                if ui.button("Record").clicked() {
+                    self.recording = Some(Vec::new());
+                    self.message = None;
+                }
+                if let Some(current) = &self.current {
+                    ui.label(format!("{} edits.", current.len()));
+                    if ui.button("Replay").clicked() {
+                        current.apply(game);
+                    }
+                    if ui.button("Replay on Empty Board").clicked() {
+                        game.clear();
+                        current.apply(game);
+                    }
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.name);
+            if ui.button("Save").clicked() {
+                self.message = Some(match (&self.current, self.path()) {
+                    (None, _) => "Record a macro to save first.".to_string(),
+                    (Some(_), None) => "There's no config directory to save macros in.".to_string(),
+                    (Some(current), Some(path)) => {
+                        let saved = path
+                            .parent()
+                            .map_or(Ok(()), fs::create_dir_all)
+                            .and_then(|_| fs::write(&path, current.to_string()));
+                        match saved {
+                            Ok(()) => format!("Saved to {}.", path.display()),
+                            Err(err) => format!("Couldn't save {}: {err}", path.display()),
+                        }
+                    }
+                });
+            }
+            if ui.button("Load").clicked() {
+                let loaded = self
+                    .path()
+                    .ok_or_else(|| "There's no config directory to load macros from.".to_string())
+                    .and_then(|path| {
+                        let text = fs::read_to_string(&path)
+                            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))?;
+                        text.parse::<Macro>()
+                            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))
+                    });
+                match loaded {
+                    Ok(loaded) => {
+                        self.message = Some(format!("Loaded {} edits.", loaded.len()));
+                        self.current = Some(loaded);
+                    }
+                    Err(err) => self.message = Some(err),
+                }
+            }
+        });
+
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+    }
+
+    // Where the macro with the current name is saved.
+    fn path(&self) -> Option<PathBuf> {
+        let name = self.name.trim();
+        let dir = config::dir()?.join("macros");
+        Some(dir.join(format!("{name}.macro")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let mut panel = MacroPanel::default();
+        let mut game = Game::new(6, 4);
+        panel.recording = Some(Vec::new());
+        panel.perform(&mut game, Action::Toggle(Point::new(1, 0)));
+        panel.perform(&mut game, Action::Fill(Point::new(5, 3), true));
+        panel.perform(
+            &mut game,
+            Action::Erase(vec![Point::new(0, 0), Point::new(5, 0)]),
+        );
+        let domino = Pattern::from_fn(2, 1, |_, _| 1);
+        panel.perform(&mut game, Action::Stamp(Point::new(0, 0), domino));
+        panel.perform(&mut game, Action::Transform(Transform::FlipVertical));
+        let recorded = Macro {
+            actions: panel.recording.take().unwrap(),
+        };
+        assert_eq!(5, recorded.len());
+
+        // Saving and loading gives back the same macro, which builds the same board again.
+        let text = recorded.to_string();
+        assert!(text.starts_with("toggle 1 0\nfill 5 3 live\nerase 0,0 5,0\nstamp 0 0\n"));
+        let loaded: Macro = text.parse().unwrap();
+        assert_eq!(recorded, loaded);
+        let mut fresh = Game::new(6, 4);
+        loaded.apply(&mut fresh);
+        assert_eq!(game.board(), fresh.board());
+
+        // Cells off a smaller board are skipped.
+        let mut small = Game::new(2, 2);
+        loaded.apply(&mut small);
+        assert_eq!("..\nOO\n", small.board().to_string());
+
+        assert!("toggle 1".parse::<Macro>().is_err());
+        assert!("transform spin".parse::<Macro>().is_err());
+    }
+
+    #[test]
+    fn test_agar() {
+        let agar: Macro = "agar zebra\n".parse().unwrap();
+        assert_eq!("agar zebra\n", agar.to_string());
+        let mut game = Game::new(4, 4);
+        agar.apply(&mut game);
+        assert_eq!("OOOO\nOOOO\n....\n....\n", game.board().to_string());
+
+        // A board the agar would leave a seam on is left alone.
+        let mut game = Game::new(4, 6);
+        agar.apply(&mut game);
+        assert_eq!(0, game.board().population());
+    }
+}
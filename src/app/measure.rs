@@ -0,0 +1,62 @@
+use gol::Point;
+
+// The offset between two cells, for lining up glider lanes and spaceship tracks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Measurement {
+    pub fn dx(&self) -> isize {
+        self.to.x as isize - self.from.x as isize
+    }
+
+    pub fn dy(&self) -> isize {
+        self.to.y as isize - self.from.y as isize
+    }
+
+    // The number of king moves between the cells, which is also how many generations light
+    // takes to cross between them.
+    pub fn chebyshev(&self) -> usize {
+        self.dx().unsigned_abs().max(self.dy().unsigned_abs())
+    }
+
+    pub fn euclidean(&self) -> f64 {
+        (self.dx() as f64).hypot(self.dy() as f64)
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "({}, {}) to ({}, {}): \u{394}x {}, \u{394}y {}, Chebyshev {}, Euclidean {:.2}",
+            self.from.x,
+            self.from.y,
+            self.to.x,
+            self.to.y,
+            self.dx(),
+            self.dy(),
+            self.chebyshev(),
+            self.euclidean()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_measurement() {
+        let measurement = Measurement {
+            from: Point::new(10, 2),
+            to: Point::new(7, 6),
+        };
+        assert_eq!((-3, 4), (measurement.dx(), measurement.dy()));
+        assert_eq!(4, measurement.chebyshev());
+        assert_eq!(5.0, measurement.euclidean());
+        assert_eq!(
+            "(10, 2) to (7, 6): \u{394}x -3, \u{394}y 4, Chebyshev 4, Euclidean 5.00",
+            measurement.describe()
+        );
+    }
+}
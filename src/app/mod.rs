@@ -0,0 +1,928 @@
+use std::fs;
+use std::time::{Duration, Instant};
+
+use eframe::{self, Frame};
+use egui::{Color32, Context, Id, Stroke, ThemePreference};
+
+use gol::{Board, Game, GolWidget, LifeLike, Pattern, Point, Rect, Ticker, Topology};
+
+use crate::cli::gui::Launch;
+use crate::config::{self, Config, Theme};
+use agar::AgarPanel;
+use annotations::Annotations;
+use compare::Comparison;
+use fit::Fit;
+use info::PatternInfo;
+use macros::{Action, MacroPanel, Transform};
+use measure::Measurement;
+use playback::Loop;
+use preferences::Preferences;
+use resize::ResizePanel;
+use rle_panel::RlePanel;
+use screensaver::Screensaver;
+use seed::SeedPanel;
+use selection::Selection;
+use session::SessionPanel;
+use stamp::Stamp;
+use start::Start;
+use torus::TorusView;
+use tutorial::Tutorial;
+
+mod agar;
+mod annotations;
+mod compare;
+mod detail;
+mod fit;
+mod ghosts;
+mod info;
+mod macros;
+mod measure;
+mod playback;
+mod plot;
+mod preferences;
+mod replay;
+mod resize;
+mod rle_panel;
+mod rule_table;
+mod screensaver;
+mod seed;
+mod selection;
+mod session;
+mod stamp;
+mod start;
+mod torus;
+mod tutorial;
+
+// How many generations apart the GUI's history keeps whole boards. Stepping back rebuilds a board
+// from the nearest one, so this bounds how much work a step takes.
+const HISTORY_INTERVAL: usize = 32;
+
+enum State {
+    // The width, height, and rulestring typed in for a new game.
+    Pending(String, String, String),
+    Ready(Game),
+    Tutorial(Tutorial),
+}
+
+// What clicking a cell does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    // Flip the cell.
+    Toggle,
+    // Flip the cell and the whole region of same-state cells around it.
+    Fill,
+    // Pick one corner of a rectangular selection, then the other.
+    Select,
+    // Drag around the cells to select.
+    Lasso,
+    // Write a label for the cell.
+    Label,
+    // Pick two cells to measure the distance between.
+    Measure,
+    // Place the stamp with its top-left corner on the cell.
+    Stamp,
+}
+
+pub struct App {
+    state: State,
+    tool: Tool,
+    config: Config,
+    // Why the last paste couldn't be read as a pattern, if it couldn't.
+    paste_error: Option<String>,
+    last_autosave: Instant,
+    // Whether the game steps on its own, and how many generations it runs each frame to keep to
+    // its speed.
+    running: bool,
+    ticker: Ticker,
+    // The selected part of the board, and the first corner of the next selection once it's been
+    // picked.
+    selection: Option<Selection>,
+    corner: Option<(usize, usize)>,
+    // The cells the lasso has been dragged over so far.
+    lasso: Vec<Point>,
+    // The pattern the stamp tool places, once one has been picked up from a selection.
+    stamp: Option<Stamp>,
+    // Whether to show the selection magnified.
+    detail: bool,
+    // The board wrapped onto a torus, while it's shown.
+    torus: Option<TorusView>,
+    // How big the board's cells are drawn.
+    fit: Fit,
+    // Whether to shade every cell by how many live neighbors it has.
+    neighbors: bool,
+    // Whether to show the cells across each wrapped edge just outside the opposite one.
+    ghosts: bool,
+    annotations: Annotations,
+    // The name, author, and description of the pattern the board was opened from.
+    info: PatternInfo,
+    // The first cell of a measurement in progress, the last measurement taken, and the ones kept
+    // on the board as guides.
+    measure_from: Option<Point>,
+    measurement: Option<Measurement>,
+    guides: Vec<Measurement>,
+    // The oscillator being shown on repeat, or why the board couldn't be looped.
+    playback: Option<Loop>,
+    playback_error: Option<String>,
+    // A copy of the game running under another rule alongside it.
+    comparison: Option<Comparison>,
+    seeds: SeedPanel,
+    agars: AgarPanel,
+    // Recording edits to play back later.
+    macros: MacroPanel,
+    sessions: SessionPanel,
+    resizing: ResizePanel,
+    rle: RlePanel,
+    start: Start,
+    // Running soup after soup for an idle display, when it's on.
+    screensaver: Option<Screensaver>,
+    preferences: Option<Preferences>,
+}
+
+impl App {
+    pub fn new(ctx: &Context, config: Config, launch: Launch) -> Self {
+        apply_theme(ctx, config.theme);
+        let mut app = App {
+            state: App::pending(&config),
+            tool: Tool::Toggle,
+            config,
+            paste_error: None,
+            last_autosave: Instant::now(),
+            running: launch.run,
+            ticker: Ticker::new(30.0),
+            selection: None,
+            corner: None,
+            lasso: Vec::new(),
+            stamp: None,
+            detail: false,
+            torus: None,
+            fit: Fit::default(),
+            annotations: Annotations::default(),
+            info: PatternInfo::default(),
+            neighbors: false,
+            ghosts: false,
+            measure_from: None,
+            measurement: None,
+            guides: Vec::new(),
+            playback: None,
+            playback_error: None,
+            comparison: None,
+            seeds: SeedPanel::default(),
+            agars: AgarPanel::default(),
+            macros: MacroPanel::default(),
+            sessions: SessionPanel::default(),
+            resizing: ResizePanel::default(),
+            rle: RlePanel::default(),
+            start: Start::default(),
+            screensaver: None,
+            preferences: None,
+        };
+
+        if launch.skip_setup {
+            let mut game = App::new_game(&app.config, app.config.width, app.config.height);
+            if let Some((pattern, rule)) = launch.pattern {
+                paste(&mut game, &pattern);
+                app.annotations = Annotations::from_comments(pattern.comments());
+                app.info = PatternInfo::new(&pattern);
+                game.set_rule(rule.unwrap_or(app.config.rule));
+                game.set_backend(app.config.backend);
+                game.set_threads(app.config.threads);
+                keep_history(&mut game, &app.config);
+            }
+            if launch.screensaver {
+                let screensaver = Screensaver::new(&mut game);
+                apply_theme(ctx, screensaver.theme());
+                app.screensaver = Some(screensaver);
+                app.running = true;
+            }
+            app.state = State::Ready(game);
+        }
+        app
+    }
+
+    fn pending(config: &Config) -> State {
+        State::Pending(
+            config.width.to_string(),
+            config.height.to_string(),
+            config.rule.to_string(),
+        )
+    }
+
+    // A new game of the given size, set up with the configured rule, backend, and threads.
+    fn new_game(config: &Config, x_size: usize, y_size: usize) -> Game {
+        let mut game = Game::new(x_size, y_size);
+        game.set_rule(config.rule);
+        game.set_backend(config.backend);
+        game.set_threads(config.threads);
+        game.set_growth(config.growth());
+        game.set_topology(config.topology());
+        keep_history(&mut game, config);
+        game
+    }
+
+    // Save the board to autosave.rle in the config directory once every autosave interval.
+    fn autosave(&mut self, ctx: &Context) {
+        let interval = Duration::from_secs(self.config.autosave_interval);
+        let State::Ready(game) = &self.state else {
+            return;
+        };
+        if interval.is_zero() {
+            return;
+        }
+
+        if self.last_autosave.elapsed() >= interval {
+            self.last_autosave = Instant::now();
+            if let Some(dir) = config::dir() {
+                let mut pattern = Pattern::from(game);
+                for comment in self.info.comments() {
+                    pattern.add_comment(comment.clone());
+                }
+                for comment in self.annotations.to_comments() {
+                    pattern.add_comment(comment);
+                }
+                let saved = fs::create_dir_all(&dir)
+                    .and_then(|_| fs::write(dir.join("autosave.rle"), pattern.to_string()));
+                if let Err(err) = saved {
+                    eprintln!("gol: couldn't autosave to {}: {err}", dir.display());
+                }
+            }
+        }
+        ctx.request_repaint_after(interval.saturating_sub(self.last_autosave.elapsed()));
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        if let (State::Ready(game), true) = (&mut self.state, self.rle.open) {
+            if let Some(pattern) = self.rle.show(ctx, game, self.selection.as_ref()) {
+                self.macros
+                    .perform(game, Action::Stamp(Point::default(), pattern));
+            }
+        }
+
+        egui::TopBottomPanel::top("Control Panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Game of Life");
+                if ui.button("Preferences").clicked() {
+                    self.preferences = Some(Preferences::new(&self.config));
+                }
+            });
+
+            // if we haven't initialized the game yet then we will show the x & y value loading
+            // screen with a button to click. Once that value is shown then we'll show the game
+            // board with some other buttons to run it.
+            match &mut self.state {
+                State::Pending(ref mut x_value, ref mut y_value, ref mut rule_value) => {
+                    ui.label("X Value:");
+                    ui.text_edit_singleline(x_value);
+                    ui.label("Y Value:");
+                    ui.text_edit_singleline(y_value);
+                    ui.label("Rule:").on_hover_text(
+                        "B3/S23 for Life, B36/S23 for HighLife, B3678/S34678 for Day & Night",
+                    );
+                    ui.text_edit_singleline(rule_value);
+                    self.start.show(ui);
+
+                    if ui.button("Set Game Size").clicked() {
+                        let x_size: usize = match x_value.parse() {
+                            Ok(x_size) => x_size,
+                            Err(err) => {
+                                ui.label(format!("Error parsing X Value: {}", err));
+                                return;
+                            }
+                        };
+
+                        let y_size: usize = match y_value.parse() {
+                            Ok(y_size) => y_size,
+                            Err(err) => {
+                                ui.label(format!("Error parsing Y Value: {}", err));
+                                return;
+                            }
+                        };
+
+                        let rule = match LifeLike::parse(rule_value) {
+                            Ok(rule) => rule,
+                            Err(err) => {
+                                ui.label(format!("Error parsing Rule: {}", err));
+                                return;
+                            }
+                        };
+
+                        if let Some(board) = self.start.seed(x_size, y_size) {
+                            let mut game =
+                                App::new_game(&self.config, board.x_size(), board.y_size());
+                            *game.board_mut() = board;
+                            game.set_rule(rule);
+                            self.info = self.start.take_info();
+                            self.state = State::Ready(game);
+                        }
+                        return;
+                    }
+
+                    if ui.button("Start Tutorial").clicked() {
+                        self.state = State::Tutorial(Tutorial::new());
+                    }
+                }
+                State::Tutorial(tutorial) => {
+                    if !tutorial.show(ui) {
+                        self.state = App::pending(&self.config);
+                    }
+                }
+                State::Ready(game) => {
+                    let keys = &self.config.keys;
+                    let (step, play, clear, explain, rotate, flip) = ui.input(|input| {
+                        (
+                            input.key_pressed(keys.step),
+                            input.key_pressed(keys.play),
+                            input.key_pressed(keys.clear),
+                            input.key_pressed(keys.explain),
+                            input.key_pressed(keys.rotate_stamp),
+                            input.key_pressed(keys.flip_stamp),
+                        )
+                    });
+                    // How many generations the game runs this frame, so a comparison can keep up.
+                    let mut steps = 0;
+                    if step {
+                        game.iterate();
+                        steps += 1;
+                    }
+                    if play {
+                        self.running = !self.running;
+                        self.ticker.reset();
+                    }
+                    if clear {
+                        self.macros.perform(game, Action::Clear);
+                    }
+                    if explain {
+                        game.set_explain(!game.is_explaining());
+                    }
+                    if let (Tool::Stamp, Some(stamp)) = (self.tool, &mut self.stamp) {
+                        if rotate {
+                            stamp.rotate();
+                        }
+                        if flip {
+                            stamp.flip();
+                        }
+                    }
+                    if let Some(playback) = &mut self.playback {
+                        if playback.show(game) {
+                            ui.ctx().request_repaint_after(playback.until_next());
+                        } else {
+                            self.playback = None;
+                        }
+                    } else if self.running {
+                        let now = Duration::from_secs_f64(ui.input(|input| input.time));
+                        for _ in 0..self.ticker.steps(now) {
+                            game.iterate();
+                            steps += 1;
+                            if let Some(screensaver) = &mut self.screensaver {
+                                if screensaver.watch(game) {
+                                    apply_theme(ui.ctx(), screensaver.theme());
+                                }
+                            }
+                        }
+                        if let Some(next) = self.ticker.until_next() {
+                            ui.ctx().request_repaint_after(next);
+                        }
+                    }
+
+                    if self.screensaver.is_some()
+                        && ui.input(|input| input.key_pressed(egui::Key::Escape))
+                    {
+                        self.screensaver = None;
+                        self.running = false;
+                        apply_theme(ui.ctx(), self.config.theme);
+                    }
+                    if let Some(screensaver) = &self.screensaver {
+                        ui.label(format!(
+                            "Screensaver: soup {}. Press Escape to stop.",
+                            screensaver.soups()
+                        ));
+                    }
+
+                    self.fit.show(ui, board_id());
+
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.tool, Tool::Toggle, "Toggle");
+                        ui.selectable_value(&mut self.tool, Tool::Fill, "Bucket Fill");
+                        ui.selectable_value(&mut self.tool, Tool::Select, "Select");
+                        ui.selectable_value(&mut self.tool, Tool::Lasso, "Lasso");
+                        ui.selectable_value(&mut self.tool, Tool::Label, "Label");
+                        ui.selectable_value(&mut self.tool, Tool::Measure, "Measure");
+                        ui.selectable_value(&mut self.tool, Tool::Stamp, "Stamp");
+                        ui.separator();
+
+                        if ui.button("Clear Board").clicked() {
+                            self.macros.perform(game, Action::Clear);
+                        }
+                        if let Some(soup) = self.seeds.show_randomize(ui) {
+                            self.macros.perform(game, soup);
+                        }
+
+                        if ui.button("Run Once").clicked() {
+                            game.iterate();
+                            steps += 1;
+                        }
+
+                        let back = ui
+                            .add_enabled(game.history_len() > 0, egui::Button::new("Step Back"))
+                            .on_hover_text("Go back a generation")
+                            .on_disabled_hover_text("There are no earlier generations kept");
+                        if back.clicked() && game.step_back().is_ok() {
+                            self.running = false;
+                        }
+
+                        let label = if self.running { "Pause" } else { "Play" };
+                        let play = ui.button(label).on_hover_text(format!(
+                            "Run generations continuously, or press {}",
+                            self.config.keys.play.name()
+                        ));
+                        if play.clicked() {
+                            self.running = !self.running;
+                            self.ticker.reset();
+                        }
+                        let mut speed = self.ticker.rate();
+                        let slider = egui::Slider::new(&mut speed, 1.0..=240.0)
+                            .logarithmic(true)
+                            .suffix(" gen/s");
+                        if ui.add(slider).changed() {
+                            self.ticker.set_rate(speed);
+                        }
+
+                        let label = if self.playback.is_some() {
+                            "Stop Loop"
+                        } else {
+                            "Loop"
+                        };
+                        if ui.button(label).clicked() {
+                            self.playback_error = None;
+                            if self.playback.take().is_none() {
+                                match Loop::detect(game) {
+                                    Ok(playback) => {
+                                        self.playback = Some(playback);
+                                        self.running = false;
+                                    }
+                                    Err(behavior) => {
+                                        self.playback_error = Some(format!(
+                                            "Only oscillators loop, this is {behavior}."
+                                        ))
+                                    }
+                                }
+                            }
+                        }
+
+                        ui.menu_button("Transform", |ui| {
+                            let transforms = [
+                                ("Rotate Clockwise", Transform::Rotate90),
+                                ("Rotate Half Turn", Transform::Rotate180),
+                                ("Rotate Counterclockwise", Transform::Rotate270),
+                                ("Flip Horizontally", Transform::FlipHorizontal),
+                                ("Flip Vertically", Transform::FlipVertical),
+                            ];
+                            for (label, transform) in transforms {
+                                if ui.button(label).clicked() {
+                                    self.macros.perform(game, Action::Transform(transform));
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+
+                        ui.menu_button("Resize", |ui| {
+                            if self.resizing.show(ui, game) {
+                                // A selection can run off a board that's shrunk.
+                                self.selection = None;
+                                ui.close_menu();
+                            }
+                        });
+
+                        ui.menu_button("View", |ui| {
+                            ui.checkbox(&mut self.detail, "Detail View");
+                            ui.checkbox(&mut self.annotations.visible, "Show Labels");
+                            ui.checkbox(&mut self.neighbors, "Neighbor Counts");
+                            let mut ages = game.is_tracking_ages();
+                            if ui.checkbox(&mut ages, "Color by Age").changed() {
+                                game.track_ages(ages);
+                            }
+                            ui.checkbox(&mut self.ghosts, "Wrapped Edges");
+                            ui.checkbox(&mut self.rle.open, "RLE Panel");
+                            if ui.button("Torus").clicked() {
+                                self.torus.get_or_insert_with(TorusView::default);
+                                ui.close_menu();
+                            }
+                            if ui.button("Screensaver").clicked() {
+                                let screensaver = Screensaver::new(game);
+                                apply_theme(ui.ctx(), screensaver.theme());
+                                self.screensaver = Some(screensaver);
+                                self.playback = None;
+                                self.running = true;
+                                self.ticker.reset();
+                                ui.close_menu();
+                            }
+                        });
+                        if ui.button("Compare Rules").clicked() {
+                            self.comparison = Some(Comparison::new(game));
+                        }
+
+                        let mut explain = game.is_explaining();
+                        if ui.checkbox(&mut explain, "Explain Changes").changed() {
+                            game.set_explain(explain);
+                        }
+
+                        // Copies as RLE, the same text Golly copies, so it pastes straight in.
+                        // Only the live part of the board is copied.
+                        if ui.button("Copy RLE").clicked() {
+                            let mut pattern = Pattern::from(&*game);
+                            for comment in self.info.comments() {
+                                pattern.add_comment(comment.clone());
+                            }
+                            pattern.trim();
+                            ui.ctx().copy_text(pattern.to_string());
+                        }
+                    });
+
+                    if let Some(selection) = &self.selection {
+                        let bounds = selection.bounds();
+                        let mut clear = false;
+                        ui.horizontal(|ui| {
+                            ui.label(format!(
+                                "Selected {} cells in {}x{} at ({}, {})",
+                                selection.len(),
+                                bounds.width,
+                                bounds.height,
+                                bounds.x,
+                                bounds.y
+                            ));
+                            // Copies and cuts only take the selected cells, so nothing next to an
+                            // irregular selection comes along.
+                            if ui.button("Copy").clicked() {
+                                ui.ctx().copy_text(selection.extract(game).to_string());
+                            }
+                            if ui.button("Cut").clicked() {
+                                ui.ctx().copy_text(selection.extract(game).to_string());
+                                self.macros.perform(game, Action::Erase(selection.cells()));
+                            }
+                            if ui.button("Use as Stamp").clicked() {
+                                self.stamp = Some(Stamp::new(selection.extract(game)));
+                                self.tool = Tool::Stamp;
+                            }
+                            clear = ui.button("Clear Selection").clicked();
+                        });
+                        if clear {
+                            self.selection = None;
+                        }
+                    } else if self.corner.is_some() {
+                        ui.label("Pick the selection's other corner.");
+                    }
+                    if self.tool == Tool::Stamp {
+                        let chosen = self
+                            .stamp
+                            .as_ref()
+                            .and_then(|stamp| stamp.pattern().name())
+                            .unwrap_or("Library")
+                            .to_string();
+                        egui::ComboBox::from_id_salt("Library")
+                            .selected_text(&chosen)
+                            .show_ui(ui, |ui| {
+                                for name in Pattern::names() {
+                                    if ui.selectable_label(chosen == name, name).clicked() {
+                                        self.stamp = Pattern::named(name).map(Stamp::new);
+                                    }
+                                }
+                            });
+                        let keys = &self.config.keys;
+                        ui.label(match &self.stamp {
+                            Some(stamp) => format!(
+                                "Stamping a {}x{} pattern. {} turns it and {} mirrors it.",
+                                stamp.pattern().width(),
+                                stamp.pattern().height(),
+                                keys.rotate_stamp.name(),
+                                keys.flip_stamp.name()
+                            ),
+                            None => "Pick a pattern from the library, or select some cells and \
+                                     pick Use as Stamp."
+                                .to_string(),
+                        });
+                    }
+
+                    self.info.show(ui);
+                    self.annotations.show(ui);
+
+                    if let Some(playback) = &self.playback {
+                        ui.label(format!(
+                            "Looping an oscillator with period {}, frame {} of {}.",
+                            playback.period(),
+                            playback.frame() + 1,
+                            playback.period()
+                        ));
+                    } else if let Some(error) = &self.playback_error {
+                        ui.label(error);
+                    }
+
+                    if let Some(measurement) = self.measurement {
+                        ui.horizontal(|ui| {
+                            ui.label(measurement.describe());
+                            if ui.button("Keep as Guide").clicked() {
+                                self.guides.push(measurement);
+                                self.measurement = None;
+                            }
+                        });
+                    } else if let Some(from) = self.measure_from {
+                        ui.label(format!("Measuring from ({}, {}).", from.x, from.y));
+                    }
+                    if !self.guides.is_empty() && ui.button("Clear Guides").clicked() {
+                        self.guides.clear();
+                    }
+
+                    if let Some(comparison) = &mut self.comparison {
+                        comparison.step(steps);
+                    }
+
+                    let stats = game.stats();
+                    ui.label(format!(
+                        "Generation {}, population {}, temperature {:.1}%",
+                        stats.generation,
+                        stats.population,
+                        stats.temperature * 100.0
+                    ));
+
+                    let pasted = ui.input(|input| {
+                        input.events.iter().find_map(|event| match event {
+                            egui::Event::Paste(text) => Some(text.clone()),
+                            _ => None,
+                        })
+                    });
+                    if let Some(text) = pasted {
+                        match text.parse::<Pattern>() {
+                            Ok(pattern) => {
+                                self.macros
+                                    .perform(game, Action::Stamp(Point::default(), pattern));
+                                self.paste_error = None;
+                            }
+                            Err(err) => self.paste_error = Some(format!("Couldn't paste: {err}")),
+                        }
+                    }
+                    if let Some(err) = &self.paste_error {
+                        ui.label(err);
+                    }
+
+                    ui.collapsing("Population", |ui| {
+                        // Stats are kept from when the plot's first opened.
+                        game.record_stats(plot::PLOT_GENERATIONS);
+                        if let Some(series) = game.stats_series() {
+                            plot::show(ui, series);
+                        }
+                    });
+
+                    ui.collapsing("Rule", |ui| {
+                        if let Some(rule) = rule_table::show(ui, game) {
+                            self.macros.perform(game, Action::Rule(rule));
+                        }
+                    });
+                    ui.collapsing("Random Soup", |ui| {
+                        if let Some(soup) = self.seeds.show(ui) {
+                            self.macros.perform(game, soup);
+                        }
+                    });
+                    ui.collapsing("Agar", |ui| {
+                        if let Some(agar) = self.agars.show(ui, game) {
+                            self.macros.perform(game, agar);
+                        }
+                    });
+                    ui.collapsing("Macros", |ui| self.macros.show(ui, game));
+                    ui.collapsing("Session", |ui| {
+                        if let Some(mut loaded) = self.sessions.show(ui, game) {
+                            loaded.set_backend(self.config.backend);
+                            loaded.set_threads(self.config.threads);
+                            loaded.set_growth(self.config.growth());
+                            keep_history(&mut loaded, &self.config);
+                            *game = loaded;
+                            self.running = false;
+                        }
+                    });
+                    ui.collapsing("Replay", |ui| {
+                        if let Some(replay) = self.macros.replays.show(ui, game) {
+                            let start = replay.start();
+                            let mut played =
+                                App::new_game(&self.config, start.width(), start.height());
+                            replay.play(&mut played);
+                            *game = played;
+                            self.running = false;
+                        }
+                    });
+
+                    if game.is_explaining() {
+                        egui::ScrollArea::vertical()
+                            .max_height(150.0)
+                            .show(ui, |ui| {
+                                for explanation in game.explanations() {
+                                    ui.label(explanation.to_string());
+                                }
+                            });
+                    }
+                }
+            };
+        });
+
+        if let State::Ready(game) = &mut self.state {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                self.fit.update(ui, board_id());
+                let mut widget = GolWidget::new(game.board());
+                widget.set_id(board_id());
+                // The lasso is drawn by dragging with the primary button, so that only pans the
+                // view while another tool is out. The widget's own painting is ignored, since
+                // clicks go to the tools.
+                widget.set_painting(self.tool == Tool::Lasso);
+                widget.set_scroll_zoom(true);
+                if game.is_tracking_ages() {
+                    widget.set_ages(game);
+                }
+                let output = widget.show(ui);
+                let painter = ui.painter_at(output.response.rect);
+                let cell_rect = |x: usize, y: usize| output.cells.rect(x as isize, y as isize);
+
+                if self.neighbors {
+                    for x in output.visible.xs() {
+                        for y in output.visible.ys() {
+                            let count = game.live_neighbors(x, y);
+                            painter.rect_filled(cell_rect(x, y), 2.0, neighbor_color(count));
+                        }
+                    }
+                }
+                if self.annotations.visible {
+                    let stroke = ui.visuals().selection.stroke;
+                    for note in self.annotations.iter() {
+                        let rect = cell_rect(note.at.x, note.at.y);
+                        painter.circle_stroke(rect.center(), rect.height() * 0.6, stroke);
+                    }
+                    let label = output.hovered.and_then(|(x, y)| self.annotations.at(x, y));
+                    if let Some(label) = label {
+                        output.response.clone().on_hover_text(label);
+                    }
+                }
+
+                if let Some((x, y)) = output.hovered {
+                    if self.tool == Tool::Lasso
+                        && ui.input(|input| input.pointer.primary_down())
+                        && self.lasso.last() != Some(&Point::new(x, y))
+                    {
+                        self.lasso.push(Point::new(x, y));
+                    }
+                }
+                if let Some((x, y)) = output.clicked {
+                    let alive = game.board()[(x, y)];
+                    match self.tool {
+                        Tool::Toggle => self.macros.perform(game, Action::Toggle(Point::new(x, y))),
+                        Tool::Fill => self
+                            .macros
+                            .perform(game, Action::Fill(Point::new(x, y), !alive)),
+                        Tool::Select => match self.corner.take() {
+                            Some(corner) => {
+                                self.selection = Some(Selection::rect(corners(corner, (x, y))));
+                            }
+                            None => self.corner = Some((x, y)),
+                        },
+                        Tool::Lasso => {}
+                        Tool::Label => self.annotations.edit(x, y),
+                        Tool::Measure => match self.measure_from.take() {
+                            Some(from) => {
+                                self.measurement = Some(Measurement {
+                                    from,
+                                    to: Point::new(x, y),
+                                });
+                            }
+                            None => self.measure_from = Some(Point::new(x, y)),
+                        },
+                        Tool::Stamp => {
+                            if let Some(stamp) = &self.stamp {
+                                let at = Point::new(x, y);
+                                let pattern = stamp.pattern().clone();
+                                self.macros.perform(game, Action::Stamp(at, pattern));
+                            }
+                        }
+                    }
+                }
+
+                let size = (game.x_size(), game.y_size());
+                let center = |point: Point| {
+                    (point.x < size.0 && point.y < size.1)
+                        .then(|| cell_rect(point.x, point.y).center())
+                };
+                let stroke = Stroke::new(2.0, ui.visuals().selection.bg_fill);
+                for guide in self.guides.iter().chain(&self.measurement) {
+                    if let (Some(from), Some(to)) = (center(guide.from), center(guide.to)) {
+                        painter.line_segment([from, to], stroke);
+                    }
+                }
+                // Growing boards don't wrap, so there's nothing across their edges to show.
+                if self.ghosts && game.growth().is_none() {
+                    let faded = ui.visuals().strong_text_color().gamma_multiply(0.3);
+                    ghosts::show(&painter, game.board(), &output.cells, faded);
+                }
+                if let (Some(stamp), Tool::Stamp, Some((x, y))) =
+                    (&self.stamp, self.tool, output.hovered)
+                {
+                    let color = ui.visuals().selection.bg_fill.gamma_multiply(0.6);
+                    let wraps = game.topology() == Topology::Toroidal;
+                    for point in stamp.cells_at((x, y), size, wraps) {
+                        painter.rect_filled(cell_rect(point.x, point.y), 2.0, color);
+                    }
+                }
+
+                if !self.lasso.is_empty() && !ui.input(|input| input.pointer.primary_down()) {
+                    self.selection = Selection::lasso(&self.lasso);
+                    self.lasso.clear();
+                }
+            });
+        }
+
+        if let (State::Ready(game), true) = (&self.state, self.detail) {
+            detail::show(ctx, game, self.selection.as_ref().map(Selection::bounds));
+        }
+        if let (State::Ready(game), Some(torus)) = (&self.state, &mut self.torus) {
+            if !torus.show(ctx, game.board()) {
+                self.torus = None;
+            }
+        }
+        if let Some(preferences) = &mut self.preferences {
+            let mut open = true;
+            if let Some(config) = preferences.show(ctx, &mut open) {
+                apply_theme(ctx, config.theme);
+                if let State::Ready(game) = &mut self.state {
+                    game.set_backend(config.backend);
+                    game.set_threads(config.threads);
+                    game.set_topology(config.topology());
+                    keep_history(game, &config);
+                }
+                self.config = config;
+            }
+            if !open {
+                self.preferences = None;
+            }
+        }
+        if let (State::Ready(game), Some(comparison)) = (&self.state, &mut self.comparison) {
+            if !comparison.show(ctx, game) {
+                self.comparison = None;
+            }
+        }
+
+        self.autosave(ctx);
+    }
+}
+
+fn apply_theme(ctx: &Context, theme: Theme) {
+    ctx.set_theme(match theme {
+        Theme::System => ThemePreference::System,
+        Theme::Light => ThemePreference::Light,
+        Theme::Dark => ThemePreference::Dark,
+    });
+}
+
+// The id the board widget keeps its zoom and pan under.
+fn board_id() -> Id {
+    Id::new("Board")
+}
+
+// The rectangle with two opposite corners at the given cells.
+fn corners(a: (usize, usize), b: (usize, usize)) -> Rect {
+    let (left, top) = (a.0.min(b.0), a.1.min(b.1));
+    Rect::new(left, top, a.0.max(b.0) - left + 1, a.1.max(b.1) - top + 1)
+}
+
+// The shade for a cell with the given number of live neighbors, running from a faint blue with none
+// to a strong red with all eight. It's translucent so the cell itself still shows through.
+fn neighbor_color(count: u8) -> Color32 {
+    let heat = f32::from(count.min(8)) / 8.0;
+    Color32::from_rgba_unmultiplied(
+        (255.0 * heat) as u8,
+        64,
+        (255.0 * (1.0 - heat)) as u8,
+        (48.0 + 144.0 * heat) as u8,
+    )
+}
+
+// Keep the configured number of generations to step back through, or stop keeping them.
+fn keep_history(game: &mut Game, config: &Config) {
+    game.set_history_capacity(config.history + 1);
+    game.record_history(if config.history == 0 {
+        0
+    } else {
+        HISTORY_INTERVAL
+    });
+}
+
+// Paste a pattern into the top-left corner of the board, over whatever is already there. A
+// pattern too big for the board starts a new game big enough to hold both.
+fn paste(game: &mut Game, pattern: &Pattern) {
+    let x_size = game.x_size().max(pattern.width());
+    let y_size = game.y_size().max(pattern.height());
+    let board = Board::from_fn(x_size, y_size, |x, y| {
+        let pasted = x < pattern.width() && y < pattern.height() && pattern.is_alive(x, y);
+        let existing = x < game.x_size() && y < game.y_size() && game.board()[(x, y)];
+        pasted || existing
+    });
+
+    if (x_size, y_size) == (game.x_size(), game.y_size()) {
+        *game.board_mut() = board;
+    } else {
+        *game = Game::from(board);
+    }
+}
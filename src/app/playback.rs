@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use gol::{analyze, Behavior, Board, Game};
+
+// How long each frame of a loop stays on screen.
+const FRAME_TIME: Duration = Duration::from_millis(150);
+
+// How far to run a copy of the game looking for the oscillator before giving up.
+const MAX_GENERATIONS: usize = 1000;
+
+// One period of an oscillator, worked out ahead of time so it can be shown on repeat without
+// simulating anything, and without noise or anything else nudging it out of its cycle.
+pub struct Loop {
+    frames: Vec<Board>,
+    frame: usize,
+    shown_at: Instant,
+}
+
+impl Loop {
+    // Look for an oscillator in a copy of the game, and record one period of it if there is one,
+    // putting the game's board on the first frame. Anything else is handed back to say what the
+    // game turned out to be instead.
+    pub fn detect(game: &mut Game) -> Result<Loop, Behavior> {
+        let mut copy = game.clone();
+        let period = match analyze(&mut copy, MAX_GENERATIONS).behavior {
+            Behavior::Oscillator { period } => period,
+            behavior => return Err(behavior),
+        };
+        let playback = Loop::record(&mut copy, period);
+        *game.board_mut() = playback.frames[0].clone();
+        Ok(playback)
+    }
+
+    fn record(game: &mut Game, period: usize) -> Loop {
+        let mut frames = Vec::with_capacity(period);
+        for _ in 0..period {
+            frames.push(game.board().clone());
+            game.iterate();
+        }
+        Loop {
+            frames,
+            frame: 0,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.frames.len()
+    }
+
+    // The frame being shown, counting from zero.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    // Put the next frame on the game's board once the last one has been up long enough. Returns
+    // false without touching the board if it's been changed since the last frame went up, since
+    // the loop no longer shows what's there.
+    pub fn show(&mut self, game: &mut Game) -> bool {
+        if game.board() != &self.frames[self.frame] {
+            return false;
+        }
+        if self.shown_at.elapsed() >= FRAME_TIME {
+            self.frame = (self.frame + 1) % self.frames.len();
+            self.shown_at = Instant::now();
+            *game.board_mut() = self.frames[self.frame].clone();
+        }
+        true
+    }
+
+    // How long until the next frame is due.
+    pub fn until_next(&self) -> Duration {
+        FRAME_TIME.saturating_sub(self.shown_at.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let mut game = Game::new(5, 5);
+        for y in 1..4 {
+            game.toggle(2, y);
+        }
+        let start = game.board().clone();
+        let mut playback = Loop::detect(&mut game).unwrap();
+        assert_eq!(2, playback.period());
+        assert_eq!(&start, game.board());
+        assert_ne!(playback.frames[0], playback.frames[1]);
+
+        playback.shown_at -= FRAME_TIME;
+        assert!(playback.show(&mut game));
+        assert_eq!(1, playback.frame());
+        assert_eq!(&playback.frames[1], game.board());
+
+        game.toggle(0, 0);
+        assert!(!playback.show(&mut game));
+
+        let mut block = Game::new(4, 4);
+        for (x, y) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            block.toggle(x, y);
+        }
+        assert_eq!(
+            Err(Behavior::StillLife),
+            Loop::detect(&mut block).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use egui::{pos2, vec2, Sense, Shape, Stroke, Ui};
+
+use gol::StatsSeries;
+
+// How many generations the plot looks back over.
+pub const PLOT_GENERATIONS: usize = 10_000;
+const HEIGHT: f32 = 80.0;
+
+// A line chart of the population over the generations the game has kept stats for, scaled to fit
+// the panel, with the range of each axis written underneath. Methuselahs like the R-pentomino show
+// up as a long wander before the line goes flat.
+pub fn show(ui: &mut Ui, series: &StatsSeries) {
+    let points: Vec<(usize, usize)> = series.populations().collect();
+    if points.len() < 2 {
+        ui.weak("Run the game to plot its population.");
+        return;
+    }
+    let (first, last) = (points[0].0, points[points.len() - 1].0);
+    let most = points.iter().map(|&(_, population)| population).max();
+    let most = most.unwrap_or(0).max(1);
+
+    let size = vec2(ui.available_width(), HEIGHT);
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let span = (last - first).max(1) as f32;
+    let line = points
+        .iter()
+        .map(|&(generation, population)| {
+            pos2(
+                rect.left() + (generation - first) as f32 / span * rect.width(),
+                rect.bottom() - population as f32 / most as f32 * rect.height(),
+            )
+        })
+        .collect();
+    let stroke = Stroke::new(1.5, ui.visuals().selection.bg_fill);
+    painter.add(Shape::line(line, stroke));
+
+    let peak = series.peak().map_or(0, |stats| stats.generation);
+    ui.weak(format!(
+        "Generations {first} to {last}, population up to {most} at generation {peak}"
+    ));
+}
@@ -0,0 +1,177 @@
+use egui::{Context, Key};
+
+use gol::{Backend, LifeLike};
+
+use crate::config::{self, Config, Theme};
+
+// A window for changing the settings in the config file. Edits are kept to one side until they're
+// saved, so a half-typed rule or key name never reaches the running app.
+pub struct Preferences {
+    draft: Config,
+    rule: String,
+    keys: [String; 6],
+    error: Option<String>,
+}
+
+impl Preferences {
+    pub fn new(config: &Config) -> Self {
+        Preferences {
+            draft: config.clone(),
+            rule: config.rule.to_string(),
+            keys: [
+                config.keys.step,
+                config.keys.play,
+                config.keys.clear,
+                config.keys.explain,
+                config.keys.rotate_stamp,
+                config.keys.flip_stamp,
+            ]
+            .map(|key| key.name().to_string()),
+            error: None,
+        }
+    }
+
+    // Show the window. Returns the new settings once they've been saved to the config file, and
+    // sets `open` to false when the window is closed.
+    pub fn show(&mut self, ctx: &Context, open: &mut bool) -> Option<Config> {
+        let mut saved = None;
+        egui::Window::new("Preferences")
+            .open(open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("Preferences")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Theme");
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(&mut self.draft.theme, Theme::System, "System");
+                            ui.selectable_value(&mut self.draft.theme, Theme::Light, "Light");
+                            ui.selectable_value(&mut self.draft.theme, Theme::Dark, "Dark");
+                        });
+                        ui.end_row();
+
+                        ui.label("Rule");
+                        ui.text_edit_singleline(&mut self.rule);
+                        ui.end_row();
+
+                        ui.label("Board size");
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut self.draft.width).range(1..=1000));
+                            ui.label("x");
+                            ui.add(egui::DragValue::new(&mut self.draft.height).range(1..=1000));
+                        });
+                        ui.end_row();
+
+                        ui.label("Autosave every");
+                        ui.add(
+                            egui::DragValue::new(&mut self.draft.autosave_interval)
+                                .suffix(" s")
+                                .range(0..=3600),
+                        )
+                        .on_hover_text("0 turns autosave off");
+                        ui.end_row();
+
+                        ui.label("Step back up to");
+                        ui.add(
+                            egui::DragValue::new(&mut self.draft.history)
+                                .suffix(" generations")
+                                .range(0..=10000),
+                        )
+                        .on_hover_text("0 turns history off");
+                        ui.end_row();
+
+                        ui.label("Backend");
+                        egui::ComboBox::from_id_salt("Backend")
+                            .selected_text(self.draft.backend.name())
+                            .show_ui(ui, |ui| {
+                                for backend in
+                                    [Backend::Auto].into_iter().chain(Backend::available())
+                                {
+                                    ui.selectable_value(
+                                        &mut self.draft.backend,
+                                        backend,
+                                        backend.name(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        let names = [
+                            "Step",
+                            "Play",
+                            "Clear",
+                            "Explain",
+                            "Rotate stamp",
+                            "Flip stamp",
+                        ];
+                        for (key, name) in self.keys.iter_mut().zip(names) {
+                            ui.label(format!("{name} key"));
+                            ui.text_edit_singleline(key);
+                            ui.end_row();
+                        }
+                    });
+
+                if ui.button("Save").clicked() {
+                    match self.read().and_then(|config| config.save().map(|_| config)) {
+                        Ok(config) => {
+                            self.error = None;
+                            saved = Some(config);
+                        }
+                        Err(err) => self.error = Some(err),
+                    }
+                }
+                if let Some(path) = config::path() {
+                    ui.label(format!("Settings are kept in {}", path.display()));
+                }
+                if let Some(error) = &self.error {
+                    ui.label(error);
+                }
+            });
+        saved
+    }
+
+    // The settings as they've been filled in, or what's wrong with them.
+    fn read(&self) -> Result<Config, String> {
+        let mut config = self.draft.clone();
+        config.rule = self
+            .rule
+            .parse::<LifeLike>()
+            .map_err(|err| format!("Couldn't read the rule: {err}"))?;
+        let [step, play, clear, explain, rotate_stamp, flip_stamp] =
+            self.keys.clone().map(|name| {
+                Key::from_name(name.trim())
+                    .ok_or_else(|| format!("There's no key called {name:?}."))
+            });
+        config.keys.step = step?;
+        config.keys.play = play?;
+        config.keys.clear = clear?;
+        config.keys.explain = explain?;
+        config.keys.rotate_stamp = rotate_stamp?;
+        config.keys.flip_stamp = flip_stamp?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut preferences = Preferences::new(&Config::default());
+        preferences.rule = "B36/S23".to_string();
+        preferences.keys[0] = "S".to_string();
+        preferences.keys[1] = "Enter".to_string();
+        let config = preferences.read().unwrap();
+        assert_eq!(LifeLike::new(&[3, 6], &[2, 3]), config.rule);
+        assert_eq!(Key::S, config.keys.step);
+        assert_eq!(Key::Enter, config.keys.play);
+        assert_eq!(Key::Delete, config.keys.clear);
+
+        preferences.keys[2] = "Nope".to_string();
+        assert!(preferences.read().is_err());
+        preferences.keys[2] = "Delete".to_string();
+        preferences.rule = "B9".to_string();
+        assert!(preferences.read().is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use egui::Ui;
+
+use gol::{Game, LifeLike, Pattern};
+
+use super::macros::{Action, Macro};
+use crate::config;
+
+// A session recorded so it can be played back exactly, for turning a report of odd behavior into
+// something that can be stepped through. It holds the board and rule the session started from and
+// every edit made along the way, with the generation it was made at, so playing it back runs the
+// same generations between the same edits. Saved as text, the first board as RLE and then the edits
+// in macro form, headed by how many generations into the session they were made:
+//
+//     x = 10, y = 8, rule = B3/S23
+//     3o!
+//     at 12
+//     toggle 3 4
+//     rule B36/S23
+//     at 40
+//     fill 0 0 live
+//     end 100
+//
+// where end gives how many generations the session ran in all.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    start: Pattern,
+    edits: Vec<(usize, Macro)>,
+    end: usize,
+    // The game's generation when recording started, which the recorded generations count from.
+    started_at: usize,
+}
+
+impl Replay {
+    // Start recording a session from the game as it is.
+    pub fn new(game: &Game) -> Self {
+        let generation = game.stats().generation;
+        Replay {
+            start: Pattern::from(game),
+            edits: Vec::new(),
+            end: 0,
+            started_at: generation,
+        }
+    }
+
+    // The board the session started from, with its rule.
+    pub fn start(&self) -> &Pattern {
+        &self.start
+    }
+
+    // The number of edits recorded.
+    pub fn len(&self) -> usize {
+        self.edits.iter().map(|(_, edits)| edits.len()).sum()
+    }
+
+    // How many generations the session has run.
+    pub fn generations(&self) -> usize {
+        self.end
+    }
+
+    // Remember an edit just made to the game.
+    pub fn record(&mut self, game: &Game, action: &Action) {
+        let at = self.observe(game);
+        match self.edits.last_mut() {
+            Some((last, edits)) if *last == at => edits.push(action.clone()),
+            _ => {
+                let mut edits = Macro::default();
+                edits.push(action.clone());
+                self.edits.push((at, edits));
+            }
+        }
+    }
+
+    // Catch up with the generations the game has run since the last edit, returning how far into
+    // the session it is.
+    pub fn observe(&mut self, game: &Game) -> usize {
+        self.end = game.stats().generation.saturating_sub(self.started_at);
+        self.end
+    }
+
+    // Play the session back on a new game the size of its first board, set up with the backend and
+    // boundary the session ran with. The game ends up where the session did.
+    pub fn play(&self, game: &mut Game) {
+        *game.board_mut() = self.start.to_board();
+        if let Some(rule) = self
+            .start
+            .rule()
+            .and_then(|rule| rule.parse::<LifeLike>().ok())
+        {
+            game.set_rule(rule);
+        }
+        let mut generation = 0;
+        for (at, edits) in &self.edits {
+            while generation < *at {
+                game.iterate();
+                generation += 1;
+            }
+            edits.apply(game);
+        }
+        while generation < self.end {
+            game.iterate();
+            generation += 1;
+        }
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.start)?;
+        for (at, edits) in &self.edits {
+            write!(f, "at {at}\n{edits}")?;
+        }
+        writeln!(f, "end {}", self.end)
+    }
+}
+
+impl FromStr for Replay {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().peekable();
+        let mut rle = String::new();
+        for (_, line) in lines.by_ref() {
+            rle.push_str(line);
+            rle.push('\n');
+            if line.trim_end().ends_with('!') {
+                break;
+            }
+        }
+        let start = rle
+            .parse()
+            .map_err(|err| format!("the first board: {err}"))?;
+
+        let mut edits = Vec::new();
+        let mut end = None;
+        while let Some((index, line)) = lines.next() {
+            let invalid = || format!("line {}: can't read {line:?}", index + 1);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                [] => continue,
+                ["end", generation] => end = Some(generation.parse().map_err(|_| invalid())?),
+                ["at", generation] => {
+                    let at: usize = generation.parse().map_err(|_| invalid())?;
+                    if edits.last().is_some_and(|(last, _)| *last > at) || end.is_some() {
+                        return Err(format!("line {}: edits out of order", index + 1));
+                    }
+                    // The group's edits run up to the next "at" or the end, and are read as a
+                    // macro, with line numbers in errors counted from the top of the file.
+                    let mut text = "\n".repeat(index + 1);
+                    while let Some((_, line)) = lines
+                        .next_if(|(_, line)| !line.starts_with("at ") && !line.starts_with("end "))
+                    {
+                        text.push_str(line);
+                        text.push('\n');
+                    }
+                    edits.push((at, text.parse()?));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        let end = end.ok_or_else(|| "missing the end line".to_string())?;
+        if edits.last().is_some_and(|(last, _)| *last > end) {
+            return Err("edits after the end".to_string());
+        }
+        Ok(Replay {
+            start,
+            edits,
+            end,
+            started_at: 0,
+        })
+    }
+}
+
+// Records the session into a replay and saves it by name in the config directory, or loads one to
+// play back.
+pub struct ReplayPanel {
+    recording: Option<Replay>,
+    name: String,
+    message: Option<String>,
+}
+
+impl Default for ReplayPanel {
+    fn default() -> Self {
+        ReplayPanel {
+            recording: None,
+            name: "session".to_string(),
+            message: None,
+        }
+    }
+}
+
+impl ReplayPanel {
+    // Add an edit just made to the replay, if one's being recorded.
+    pub fn record(&mut self, game: &Game, action: &Action) {
+        if let Some(recording) = &mut self.recording {
+            recording.record(game, action);
+        }
+    }
+
+    // Show the panel, returning a replay to play back once one's loaded.
+    pub fn show(&mut self, ui: &mut Ui, game: &Game) -> Option<Replay> {
+        let mut loaded = None;
+        ui.horizontal(|ui| match &mut self.recording {
+            Some(recording) => {
+                recording.observe(game);
+                ui.label(format!(
+                    "Recording, {} edits over {} generations.",
+                    recording.len(),
+                    recording.generations()
+                ));
+                if ui.button("Stop and Save").clicked() {
+                    self.message = Some(self.save());
+                    self.recording = None;
+                }
+            }
+            None => {
+                if ui.button("Record Session").clicked() {
+                    self.recording = Some(Replay::new(game));
+                    self.message = None;
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.name);
+            if self.recording.is_none() && ui.button("Load and Play").clicked() {
+                let replay = self
+                    .path()
+                    .ok_or_else(|| "There's no config directory to load replays from.".to_string())
+                    .and_then(|path| {
+                        let text = fs::read_to_string(&path)
+                            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))?;
+                        text.parse::<Replay>()
+                            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))
+                    });
+                match replay {
+                    Ok(replay) => {
+                        self.message = Some(format!(
+                            "Played back {} edits over {} generations.",
+                            replay.len(),
+                            replay.generations()
+                        ));
+                        loaded = Some(replay);
+                    }
+                    Err(err) => self.message = Some(err),
+                }
+            }
+        });
+
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+        loaded
+    }
+
+    // Save the replay being recorded, returning what happened.
+    fn save(&self) -> String {
+        let (Some(recording), Some(path)) = (&self.recording, self.path()) else {
+            return "There's no config directory to save replays in.".to_string();
+        };
+        let saved = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, recording.to_string()));
+        match saved {
+            Ok(()) => format!("Saved to {}.", path.display()),
+            Err(err) => format!("Couldn't save {}: {err}", path.display()),
+        }
+    }
+
+    // Where the replay with the current name is saved.
+    fn path(&self) -> Option<PathBuf> {
+        let name = self.name.trim();
+        let dir = config::dir()?.join("replays");
+        Some(dir.join(format!("{name}.replay")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gol::Point;
+
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let mut game = Game::random(12, 10, 0.3, 8);
+        game.iterate();
+        let mut replay = Replay::new(&game);
+        let start = game.clone();
+
+        let edits = [
+            (3, Action::Toggle(Point::new(4, 4))),
+            (3, Action::Rule(LifeLike::new(&[3, 6], &[2, 3]))),
+            (7, Action::Fill(Point::new(0, 0), true)),
+            (12, Action::Soup(0.4, 99)),
+        ];
+        for (at, action) in edits {
+            while game.stats().generation < at {
+                game.iterate();
+            }
+            action.apply(&mut game);
+            replay.record(&game, &action);
+        }
+        game.iterate();
+        replay.observe(&game);
+        assert_eq!(4, replay.len());
+        assert_eq!(12, replay.generations());
+
+        // Saving and loading gives back the same session, which ends on the same board.
+        let text = replay.to_string();
+        assert!(text.contains("at 2\ntoggle 4 4\nrule B36/S23\nat 6\nfill 0 0 live\n"));
+        assert!(text.ends_with("soup 0.4 99\nend 12\n"));
+        let loaded: Replay = text.parse().unwrap();
+        assert_eq!(replay.edits, loaded.edits);
+
+        let mut played = Game::new(12, 10);
+        loaded.play(&mut played);
+        assert_eq!(start.board(), &loaded.start().to_board());
+        assert_eq!(game.board(), played.board());
+        assert_eq!(game.life_like(), played.life_like());
+
+        assert!("x = 1, y = 1\no!\nat 3\n".parse::<Replay>().is_err());
+        assert!("x = 1, y = 1\no!\nat 3\nend 2\n".parse::<Replay>().is_err());
+        assert!("x = 1, y = 1\no!\nat 3\nspin\nend 4\n"
+            .parse::<Replay>()
+            .is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use egui::Ui;
+
+use gol::{Anchor, Game};
+
+// Changing the board's size once the game's started, keeping what's been drawn on it. The size
+// fields start at the board's own size and go back to it after each resize.
+#[derive(Default)]
+pub struct ResizePanel {
+    // The size typed in, until it's applied.
+    size: Option<(usize, usize)>,
+    anchor: Anchor,
+}
+
+impl ResizePanel {
+    // Show the size fields, returning whether the board was resized.
+    pub fn show(&mut self, ui: &mut Ui, game: &mut Game) -> bool {
+        let (x_size, y_size) = self.size.get_or_insert((game.x_size(), game.y_size()));
+        ui.horizontal(|ui| {
+            ui.label("Width:");
+            ui.add(egui::DragValue::new(x_size).range(1..=4096));
+            ui.label("Height:");
+            ui.add(egui::DragValue::new(y_size).range(1..=4096));
+        });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.anchor, Anchor::TopLeft, "Keep Top Left");
+            ui.radio_value(&mut self.anchor, Anchor::Center, "Keep Centered");
+        });
+
+        let (x_size, y_size) = (*x_size, *y_size);
+        if !ui.button("Resize").clicked() {
+            return false;
+        }
+        self.size = None;
+        game.resize(x_size, y_size, self.anchor).is_ok()
+    }
+}
@@ -0,0 +1,68 @@
+use egui::{Context, TextEdit};
+
+use gol::{Game, Pattern};
+
+use super::selection::Selection;
+
+// A side panel for moving patterns in and out as RLE text, for when the system clipboard can't be
+// reached, as in a browser: paste RLE into a box to load it, and copy the selection from another.
+#[derive(Default)]
+pub struct RlePanel {
+    pub open: bool,
+    text: String,
+    error: Option<String>,
+}
+
+impl RlePanel {
+    // Show the panel, returning the pattern to place on the board once Load is clicked.
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        game: &Game,
+        selection: Option<&Selection>,
+    ) -> Option<Pattern> {
+        let mut loaded = None;
+        egui::SidePanel::right("RLE").show(ctx, |ui| {
+            ui.heading("RLE");
+            ui.label("Paste a pattern as RLE and load it into the top-left corner of the board.");
+            ui.add(
+                TextEdit::multiline(&mut self.text)
+                    .code_editor()
+                    .desired_rows(8)
+                    .hint_text("x = 3, y = 3\nbo$2bo$3o!"),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Load").clicked() {
+                    match self.text.parse::<Pattern>() {
+                        Ok(pattern) => {
+                            loaded = Some(pattern);
+                            self.error = None;
+                        }
+                        Err(err) => self.error = Some(format!("Couldn't load: {err}")),
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    self.text.clear();
+                    self.error = None;
+                }
+            });
+            if let Some(error) = &self.error {
+                ui.label(error);
+            }
+
+            ui.separator();
+            let Some(selection) = selection else {
+                ui.label("Select part of the board to see it as RLE here.");
+                return;
+            };
+            ui.label("The selection:");
+            let rle = selection.extract(game).to_string();
+            ui.add(
+                TextEdit::multiline(&mut rle.as_str())
+                    .code_editor()
+                    .desired_rows(8),
+            );
+        });
+        loaded
+    }
+}
@@ -0,0 +1,63 @@
+use egui::Ui;
+
+use gol::{Game, LifeLike};
+
+// Show the game's rule as a table of what happens to dead and live cells with each number of live
+// neighbors, with a checkbox for each. Ticking or unticking one returns the changed rule to switch
+// the game to straight away, even while it runs, for trying out rules by feel.
+pub fn show(ui: &mut Ui, game: &Game) -> Option<LifeLike> {
+    let Some(mut rule) = game.life_like() else {
+        ui.label("This rule depends on where the live neighbors are, not just how many there are.");
+        return ui
+            .button("Edit as B3/S23")
+            .clicked()
+            .then_some(LifeLike::CONWAY);
+    };
+
+    let mut changed = false;
+    egui::Grid::new("Rule Table")
+        .striped(true)
+        .min_col_width(24.0)
+        .show(ui, |ui| {
+            ui.label("Neighbors");
+            for count in 0..=8 {
+                ui.label(count.to_string());
+            }
+            ui.end_row();
+
+            ui.label("Dead cell");
+            for count in 0..=8 {
+                let mut born = rule.births_on(count);
+                if entry(ui, &mut born, "born", "stays dead") {
+                    rule.set_birth(count, born);
+                    changed = true;
+                }
+            }
+            ui.end_row();
+
+            ui.label("Live cell");
+            for count in 0..=8 {
+                let mut survives = rule.survives_on(count);
+                if entry(ui, &mut survives, "survives", "dies") {
+                    rule.set_survival(count, survives);
+                    changed = true;
+                }
+            }
+            ui.end_row();
+        });
+
+    ui.horizontal(|ui| {
+        ui.label(format!("Rule: {rule}"));
+        if rule != LifeLike::CONWAY && ui.button("Reset to B3/S23").clicked() {
+            rule = LifeLike::CONWAY;
+            changed = true;
+        }
+    });
+    changed.then_some(rule)
+}
+
+// A checkbox for one entry of the table, returning whether it was clicked.
+fn entry(ui: &mut Ui, alive: &mut bool, active: &str, inactive: &str) -> bool {
+    let hover = if *alive { active } else { inactive };
+    ui.checkbox(alive, "").on_hover_text(hover).changed()
+}
@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+
+use gol::{Game, SeedSource, Soup};
+
+use super::seed::fresh_seed;
+use crate::config::Theme;
+
+// The themes the screensaver takes turns with, one per soup.
+const THEMES: [Theme; 2] = [Theme::Dark, Theme::Light];
+// The longest population cycle that counts as settled. Longer ones are rare in soups.
+const MAX_PERIOD: usize = 15;
+// How long the population has to have repeated before the board counts as settled, in generations.
+const SETTLED_FOR: usize = 60;
+const DENSITY: f64 = 0.35;
+
+// Runs random soups one after another for an idle display, starting a new one with the next theme
+// whenever the board dies out or settles down.
+pub struct Screensaver {
+    // The population of each recent generation, newest last.
+    populations: VecDeque<usize>,
+    theme: usize,
+    soups: usize,
+}
+
+impl Screensaver {
+    // Start the screensaver, filling the board with its first soup.
+    pub fn new(game: &mut Game) -> Self {
+        let mut screensaver = Screensaver {
+            populations: VecDeque::new(),
+            theme: 0,
+            soups: 0,
+        };
+        screensaver.reseed(game);
+        screensaver
+    }
+
+    pub fn theme(&self) -> Theme {
+        THEMES[self.theme]
+    }
+
+    // How many soups have been shown, counting the current one.
+    pub fn soups(&self) -> usize {
+        self.soups
+    }
+
+    // Look at the game after a generation, filling the board with a new soup if it's settled.
+    // Returns whether it did, so the new theme can be put on.
+    pub fn watch(&mut self, game: &mut Game) -> bool {
+        self.populations.push_back(game.board().population());
+        if self.populations.len() > SETTLED_FOR + MAX_PERIOD {
+            self.populations.pop_front();
+        }
+        if game.board().population() > 0 && !self.settled() {
+            return false;
+        }
+        self.reseed(game);
+        self.theme = (self.theme + 1) % THEMES.len();
+        true
+    }
+
+    // Whether the population has gone round the same cycle for the last SETTLED_FOR generations,
+    // the same test `gol stabilize` uses, so gliders flying off don't keep a settled soup going.
+    fn settled(&self) -> bool {
+        let populations = &self.populations;
+        (1..=MAX_PERIOD).any(|period| {
+            populations.len() >= SETTLED_FOR + period
+                && (populations.len() - SETTLED_FOR..populations.len())
+                    .all(|index| populations[index] == populations[index - period])
+        })
+    }
+
+    fn reseed(&mut self, game: &mut Game) {
+        let soup = Soup::new(DENSITY, fresh_seed()).seed(game.x_size(), game.y_size());
+        if let Ok(soup) = soup {
+            *game.board_mut() = soup;
+        }
+        self.populations.clear();
+        self.soups += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_watch() {
+        let mut game = Game::new(16, 16);
+        let mut screensaver = Screensaver::new(&mut game);
+        assert!(game.board().population() > 0);
+        assert_eq!((1, Theme::Dark), (screensaver.soups(), screensaver.theme()));
+
+        // A blinker's population never changes, so it's settled once it's been watched long enough.
+        game.clear_and_set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        for _ in 0..SETTLED_FOR {
+            game.iterate();
+            assert!(!screensaver.watch(&mut game));
+        }
+        game.iterate();
+        assert!(screensaver.watch(&mut game));
+        assert_eq!(
+            (2, Theme::Light),
+            (screensaver.soups(), screensaver.theme())
+        );
+
+        // An empty board is reseeded straight away.
+        game.clear();
+        assert!(screensaver.watch(&mut game));
+        assert_eq!((3, Theme::Dark), (screensaver.soups(), screensaver.theme()));
+    }
+}
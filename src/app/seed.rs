@@ -0,0 +1,109 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use egui::Ui;
+
+use super::macros::Action;
+
+// Fills the board with random soups, keeping the seed each one came from in view so a good one can
+// be made again, or passed on to someone else as just a number. The Randomize button and the
+// density slider sit with the other controls, and picking a seed has a panel of its own.
+pub struct SeedPanel {
+    seed: String,
+    density: f64,
+    // The seed of the soup on the board, if it came from here.
+    current: Option<u64>,
+    error: Option<String>,
+}
+
+impl Default for SeedPanel {
+    fn default() -> Self {
+        SeedPanel {
+            seed: fresh_seed().to_string(),
+            density: 0.5,
+            current: None,
+            error: None,
+        }
+    }
+}
+
+impl SeedPanel {
+    // Show the panel, returning the soup to fill the board with once one's picked.
+    pub fn show(&mut self, ui: &mut Ui) -> Option<Action> {
+        let mut soup = None;
+        match self.current {
+            Some(seed) => ui.label(format!("The board was filled from seed {seed}.")),
+            None => ui.label("The board didn't come from a seed."),
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Seed:");
+            ui.text_edit_singleline(&mut self.seed);
+            if ui.button("Fill").clicked() {
+                match self.seed.trim().parse() {
+                    Ok(seed) => soup = Some(self.fill(seed)),
+                    Err(err) => self.error = Some(format!("Couldn't read the seed: {err}")),
+                }
+            }
+            if ui.button("Reroll").clicked() {
+                let seed = fresh_seed();
+                self.seed = seed.to_string();
+                soup = Some(self.fill(seed));
+            }
+        });
+
+        if let Some(error) = &self.error {
+            ui.label(error);
+        }
+        soup
+    }
+
+    // Show the Randomize button with the density slider, returning a soup from a fresh seed if it
+    // was clicked.
+    pub fn show_randomize(&mut self, ui: &mut Ui) -> Option<Action> {
+        let randomize = ui
+            .button("Randomize")
+            .on_hover_text("Fill the board with a random soup from a new seed")
+            .clicked();
+        ui.add(egui::Slider::new(&mut self.density, 0.0..=1.0).text("Density"));
+        randomize.then(|| {
+            let seed = fresh_seed();
+            self.seed = seed.to_string();
+            self.fill(seed)
+        })
+    }
+
+    // The soup from a seed at the current density, remembering the seed as the board's.
+    fn fill(&mut self, seed: u64) -> Action {
+        self.current = Some(seed);
+        self.error = None;
+        Action::Soup(self.density, seed)
+    }
+}
+
+// A seed nobody picked, from the randomness the standard library seeds its hash maps with.
+pub(super) fn fresh_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+#[cfg(test)]
+mod test {
+    use gol::Game;
+
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        let mut panel = SeedPanel::default();
+        let mut first = Game::new(16, 16);
+        panel.fill(42).apply(&mut first);
+        assert_eq!(Some(42), panel.current);
+        assert!(first.board().population() > 0);
+
+        let mut second = Game::new(16, 16);
+        panel.fill(42).apply(&mut second);
+        assert_eq!(first.board(), second.board());
+        panel.fill(43).apply(&mut second);
+        assert_ne!(first.board(), second.board());
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use gol::{Game, Pattern, Point, Rect};
+
+// A set of selected cells, either a rectangle or whatever shape was drawn around them.
+pub struct Selection {
+    cells: HashSet<Point>,
+    bounds: Rect,
+}
+
+impl Selection {
+    pub fn rect(rect: Rect) -> Self {
+        let cells = rect
+            .xs()
+            .flat_map(|x| rect.ys().map(move |y| Point::new(x, y)))
+            .collect();
+        Selection {
+            cells,
+            bounds: rect,
+        }
+    }
+
+    // The cells on a freehand path and every cell whose center it loops around, joining the
+    // path's ends. None if the path is empty.
+    pub fn lasso(path: &[Point]) -> Option<Self> {
+        let bounds = Rect::bounding(path.iter().copied())?;
+        let mut cells: HashSet<Point> = path.iter().copied().collect();
+        for x in bounds.xs() {
+            for y in bounds.ys() {
+                if encloses(path, x as f32 + 0.5, y as f32 + 0.5) {
+                    cells.insert(Point::new(x, y));
+                }
+            }
+        }
+        Some(Selection { cells, bounds })
+    }
+
+    // The smallest rectangle holding every selected cell.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.cells.contains(&Point::new(x, y))
+    }
+
+    // The selected cells as a pattern the size of the selection's bounds, with everything outside
+    // the selection left dead. Cells that have fallen off a shrunken board are left out.
+    pub fn extract(&self, game: &Game) -> Pattern {
+        let Rect { x, y, .. } = self.bounds;
+        let mut pattern = Pattern::from_fn(self.bounds.width, self.bounds.height, |px, py| {
+            let (cx, cy) = (x + px, y + py);
+            let on_board = cx < game.x_size() && cy < game.y_size();
+            (on_board && self.contains(cx, cy) && game.board()[(cx, cy)]) as u8
+        });
+        if let Some(rule) = game.life_like() {
+            pattern.set_rule(rule.to_string());
+        }
+        pattern
+    }
+
+    // The selected cells, in column order.
+    pub fn cells(&self) -> Vec<Point> {
+        let mut cells: Vec<Point> = self.cells.iter().copied().collect();
+        cells.sort_by_key(|point| (point.x, point.y));
+        cells
+    }
+}
+
+// Whether a closed path through the centers of the given cells goes around the point, by counting
+// how many of its edges a ray from the point to the right crosses.
+fn encloses(path: &[Point], x: f32, y: f32) -> bool {
+    let center = |point: &Point| (point.x as f32 + 0.5, point.y as f32 + 0.5);
+    let mut inside = false;
+    for (index, a) in path.iter().enumerate() {
+        let (ax, ay) = center(a);
+        let (bx, by) = center(&path[(index + 1) % path.len()]);
+        if (ay > y) != (by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lasso() {
+        // A loop around the middle of a 5x5 square, drawn through the cells around it.
+        let path: Vec<Point> = [
+            (1, 1),
+            (2, 0),
+            (3, 1),
+            (4, 2),
+            (3, 3),
+            (2, 4),
+            (1, 3),
+            (0, 2),
+        ]
+        .into_iter()
+        .map(Point::from)
+        .collect();
+        let selection = Selection::lasso(&path).unwrap();
+        assert_eq!(Rect::new(0, 0, 5, 5), selection.bounds());
+        assert!(selection.contains(2, 2) && selection.contains(1, 2) && selection.contains(2, 1));
+        assert!(!selection.contains(0, 0) && !selection.contains(4, 4));
+        assert_eq!(13, selection.len());
+        assert!(Selection::lasso(&[]).is_none());
+    }
+}
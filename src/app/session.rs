@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::PathBuf;
+
+use egui::Ui;
+
+use gol::Game;
+
+use crate::config;
+
+// Saving the game under a name to pick up again later, with its generation, rule, and the board
+// before this one, which an RLE of the board alone would lose. Sessions are kept in the config
+// directory like macros.
+#[derive(Default)]
+pub struct SessionPanel {
+    name: String,
+    message: Option<String>,
+}
+
+impl SessionPanel {
+    // Show the panel, returning the game that was loaded, if one was.
+    pub fn show(&mut self, ui: &mut Ui, game: &Game) -> Option<Game> {
+        let mut loaded = None;
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.name);
+            if ui.button("Save").clicked() {
+                self.message = Some(match self.path() {
+                    None => "There's no config directory to save sessions in.".to_string(),
+                    Some(path) => {
+                        let saved = path
+                            .parent()
+                            .map_or(Ok(()), fs::create_dir_all)
+                            .map_err(|err| err.to_string())
+                            .and_then(|_| game.save_to_file(&path).map_err(|err| err.to_string()));
+                        match saved {
+                            Ok(()) => format!("Saved to {}.", path.display()),
+                            Err(err) => format!("Couldn't save {}: {err}", path.display()),
+                        }
+                    }
+                });
+            }
+            if ui.button("Load").clicked() {
+                let read = self
+                    .path()
+                    .ok_or_else(|| "There's no config directory to load sessions from.".to_string())
+                    .and_then(|path| {
+                        Game::load_from_file(&path)
+                            .map_err(|err| format!("Couldn't read {}: {err}", path.display()))
+                    });
+                match read {
+                    Ok(game) => {
+                        self.message =
+                            Some(format!("Loaded generation {}.", game.stats().generation));
+                        loaded = Some(game);
+                    }
+                    Err(err) => self.message = Some(err),
+                }
+            }
+        });
+
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+        loaded
+    }
+
+    // Where the session with the current name is saved.
+    fn path(&self) -> Option<PathBuf> {
+        let name = self.name.trim();
+        let dir = config::dir()?.join("sessions");
+        Some(dir.join(format!("{name}.toml")))
+    }
+}
@@ -0,0 +1,76 @@
+use gol::{Orientation, Pattern, Point};
+
+// A pattern picked up to be placed on the board with the stamp tool. It can be turned and mirrored
+// before it's placed, and is previewed under the pointer so it can be lined up first.
+pub struct Stamp {
+    pattern: Pattern,
+}
+
+impl Stamp {
+    pub fn new(pattern: Pattern) -> Self {
+        Stamp { pattern }
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    // Turn it a quarter turn clockwise.
+    pub fn rotate(&mut self) {
+        self.pattern = self.pattern.oriented(Orientation::Rotate90);
+    }
+
+    // Mirror it left to right.
+    pub fn flip(&mut self) {
+        self.pattern = self.pattern.oriented(Orientation::FlipHorizontal);
+    }
+
+    // The board cells its live cells would land on with its top-left corner at (x, y), wrapping
+    // around the edges of an x_size by y_size board, or cut off at them if it doesn't wrap, the
+    // way placing it does.
+    pub fn cells_at(
+        &self,
+        (x, y): (usize, usize),
+        (x_size, y_size): (usize, usize),
+        wraps: bool,
+    ) -> impl Iterator<Item = Point> + '_ {
+        self.pattern
+            .live_cells()
+            .map(move |(px, py, _)| (x + px, y + py))
+            .filter(move |&(x, y)| wraps || (x < x_size && y < y_size))
+            .map(move |(x, y)| Point::new(x % x_size, y % y_size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stamp() {
+        // An L tromino: two cells down the left and one to the right of the bottom.
+        let mut stamp = Stamp::new(Pattern::from_fn(2, 2, |x, y| (x == 0 || y == 1) as u8));
+        let cells = |stamp: &Stamp| {
+            let mut cells: Vec<_> = stamp.cells_at((3, 1), (4, 4), true).collect();
+            cells.sort_by_key(|point| (point.x, point.y));
+            cells
+        };
+        assert_eq!(
+            vec![Point::new(0, 2), Point::new(3, 1), Point::new(3, 2)],
+            cells(&stamp)
+        );
+
+        stamp.rotate();
+        assert_eq!("OO\nO.\n", stamp.pattern().to_board().to_string());
+        stamp.flip();
+        assert_eq!("OO\n.O\n", stamp.pattern().to_board().to_string());
+        assert_eq!(
+            vec![Point::new(0, 1), Point::new(0, 2), Point::new(3, 1)],
+            cells(&stamp)
+        );
+        assert_eq!(
+            vec![Point::new(3, 1)],
+            stamp.cells_at((3, 1), (4, 4), false).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::mem;
+
+use egui::Ui;
+
+use gol::{Board, Image, Pattern, SeedError, SeedSource, Soup};
+
+use super::info::PatternInfo;
+use super::seed::fresh_seed;
+
+// What a new board starts with.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Empty,
+    Soup,
+    Pattern,
+    Image,
+}
+
+impl Kind {
+    const ALL: [Kind; 4] = [Kind::Empty, Kind::Soup, Kind::Pattern, Kind::Image];
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Empty => "Empty Board",
+            Kind::Soup => "Random Soup",
+            Kind::Pattern => "Pattern File",
+            Kind::Image => "PNG Image",
+        }
+    }
+}
+
+// The setup screen's choice of what to fill a new board with.
+pub struct Start {
+    kind: Kind,
+    density: f64,
+    path: String,
+    // Why the last board couldn't be filled, if it couldn't.
+    error: Option<String>,
+    // What the last pattern file filled in said about itself.
+    info: PatternInfo,
+}
+
+impl Default for Start {
+    fn default() -> Self {
+        Start {
+            kind: Kind::Empty,
+            density: 0.5,
+            path: String::new(),
+            error: None,
+            info: PatternInfo::default(),
+        }
+    }
+}
+
+impl Start {
+    pub fn show(&mut self, ui: &mut Ui) {
+        egui::ComboBox::from_label("Start With")
+            .selected_text(self.kind.name())
+            .show_ui(ui, |ui| {
+                for kind in Kind::ALL {
+                    ui.selectable_value(&mut self.kind, kind, kind.name());
+                }
+            });
+        match self.kind {
+            Kind::Empty => {}
+            Kind::Soup => {
+                ui.add(egui::Slider::new(&mut self.density, 0.0..=1.0).text("Density"));
+            }
+            Kind::Pattern | Kind::Image => {
+                ui.horizontal(|ui| {
+                    ui.label("Path:");
+                    ui.text_edit_singleline(&mut self.path);
+                });
+            }
+        }
+        if let Some(error) = &self.error {
+            ui.label(error);
+        }
+    }
+
+    // Fill a board of the given size from the chosen source, or say why it couldn't be.
+    pub fn seed(&mut self, x_size: usize, y_size: usize) -> Option<Board> {
+        let path = self.path.trim();
+        let mut source: Box<dyn SeedSource> = match self.kind {
+            Kind::Empty => Box::new(|_, _| false),
+            Kind::Soup => Box::new(Soup::new(self.density, fresh_seed())),
+            Kind::Pattern => match Pattern::load(path) {
+                Ok(pattern) => {
+                    self.info = PatternInfo::new(&pattern);
+                    Box::new(pattern)
+                }
+                Err(err) => {
+                    self.error = Some(SeedError::from(err).to_string());
+                    return None;
+                }
+            },
+            Kind::Image => Box::new(Image::new(path)),
+        };
+        let seeded = source.seed(x_size, y_size);
+        self.error = seeded.as_ref().err().map(SeedError::to_string);
+        seeded.ok()
+    }
+
+    // Take what the pattern file the last board was filled from said about itself.
+    pub fn take_info(&mut self) -> PatternInfo {
+        mem::take(&mut self.info)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seed() {
+        let mut start = Start::default();
+        assert_eq!(Some(Board::new(4, 3)), start.seed(4, 3));
+
+        start.kind = Kind::Image;
+        start.path = "no/such/image.png".to_string();
+        assert_eq!(None, start.seed(4, 3));
+        assert!(start.error.is_some());
+
+        start.kind = Kind::Soup;
+        let soup = start.seed(4, 3).unwrap();
+        assert_eq!((4, 3), (soup.x_size(), soup.y_size()));
+        assert_eq!(None, start.error);
+    }
+}
@@ -0,0 +1,177 @@
+use std::f32::consts::TAU;
+
+use egui::{vec2, Color32, Context, Sense, Shape, Stroke};
+
+use gol::Board;
+
+// The size of the view, in points.
+const VIEW_SIZE: f32 = 320.0;
+// The radius of the ring through the middle of the tube, and of the tube itself, with the view
+// spanning -1 to 1.
+const MAJOR: f32 = 0.6;
+const MINOR: f32 = 0.28;
+// How far the torus leans towards the viewer, in radians, and how fast it spins, in radians a
+// second.
+const TILT: f32 = 1.0;
+const SPIN: f32 = 0.4;
+
+// The board wrapped onto a slowly turning torus, which is the shape a board with wrapping edges
+// really has: its columns run around the ring and its rows around the tube, so cells on opposite
+// edges meet. Drawn as a quad per cell, back to front.
+#[derive(Default)]
+pub struct TorusView {
+    paused: bool,
+    // How far it's turned, in radians.
+    angle: f32,
+    // The time of the last frame drawn, in seconds.
+    last_frame: Option<f64>,
+}
+
+impl TorusView {
+    // Show the view in a window, returning false once it's closed.
+    pub fn show(&mut self, ctx: &Context, board: &Board) -> bool {
+        let mut open = true;
+        egui::Window::new("Torus")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let now = ui.input(|input| input.time);
+                if let (Some(last), false) = (self.last_frame, self.paused) {
+                    self.angle = (self.angle + (now - last) as f32 * SPIN) % TAU;
+                    ui.ctx().request_repaint();
+                }
+                self.last_frame = Some(now);
+
+                let (response, painter) =
+                    ui.allocate_painter(vec2(VIEW_SIZE, VIEW_SIZE), Sense::hover());
+                painter.rect_filled(response.rect, 0.0, ui.visuals().extreme_bg_color);
+                let center = response.rect.center();
+                let live = ui.visuals().strong_text_color();
+                let dead = ui.visuals().widgets.inactive.bg_fill;
+                for quad in quads(board, self.angle) {
+                    let color = if quad.alive { live } else { dead };
+                    let points = quad
+                        .corners
+                        .map(|[x, y]| center + vec2(x, -y) * VIEW_SIZE * 0.5)
+                        .to_vec();
+                    painter.add(Shape::convex_polygon(
+                        points,
+                        shade(color, quad.light),
+                        Stroke::NONE,
+                    ));
+                }
+
+                ui.horizontal(|ui| {
+                    let label = if self.paused { "Spin" } else { "Stop" };
+                    if ui.button(label).clicked() {
+                        self.paused = !self.paused;
+                    }
+                    ui.label("Columns run around the ring and rows around the tube.");
+                });
+            });
+        open
+    }
+}
+
+// One cell on the torus, projected onto the view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Quad {
+    // The corners, from -1 to 1 across the view with y up.
+    corners: [[f32; 2]; 4],
+    // How far towards the viewer its middle is, for drawing back to front.
+    depth: f32,
+    // How squarely it faces the light, from 0 to 1.
+    light: f32,
+    alive: bool,
+}
+
+// The quads for every cell facing the viewer, back to front, with the torus turned to `angle`.
+fn quads(board: &Board, angle: f32) -> Vec<Quad> {
+    let (x_size, y_size) = (board.x_size(), board.y_size());
+    let corner = |x: usize, y: usize| {
+        let u = x as f32 / x_size as f32 * TAU + angle;
+        let v = y as f32 / y_size as f32 * TAU;
+        project(u, v)
+    };
+    let mut quads = Vec::with_capacity(x_size * y_size);
+    for x in 0..x_size {
+        for y in 0..y_size {
+            let corners = [
+                corner(x, y),
+                corner(x + 1, y),
+                corner(x + 1, y + 1),
+                corner(x, y + 1),
+            ];
+            // The normal of the quad, from the cross product of its diagonals.
+            let [a, b, c, d] = corners;
+            let (p, q) = (sub(c, a), sub(d, b));
+            let normal = [
+                p[1] * q[2] - p[2] * q[1],
+                p[2] * q[0] - p[0] * q[2],
+                p[0] * q[1] - p[1] * q[0],
+            ];
+            if normal[2] <= 0.0 {
+                continue;
+            }
+            let length = normal.iter().map(|n| n * n).sum::<f32>().sqrt();
+            quads.push(Quad {
+                corners: corners.map(|[x, y, _]| [x, y]),
+                depth: corners.iter().map(|corner| corner[2]).sum::<f32>() / 4.0,
+                light: (normal[2] / length).clamp(0.0, 1.0),
+                alive: board[(x, y)],
+            });
+        }
+    }
+    quads.sort_by(|a, b| a.depth.total_cmp(&b.depth));
+    quads
+}
+
+// The point at angle u around the ring and v around the tube, leaned towards the viewer, with z
+// pointing out of the view.
+fn project(u: f32, v: f32) -> [f32; 3] {
+    let ring = MAJOR + MINOR * v.cos();
+    let (x, y, z) = (ring * u.cos(), ring * u.sin(), MINOR * v.sin());
+    [
+        x,
+        y * TILT.cos() - z * TILT.sin(),
+        y * TILT.sin() + z * TILT.cos(),
+    ]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+// Darken a color the further its quad faces away from the light, which is behind the viewer.
+fn shade(color: Color32, light: f32) -> Color32 {
+    color.gamma_multiply(0.35 + 0.65 * light)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quads() {
+        let mut board = Board::new(24, 12);
+        board.set(0, 0, true);
+        let quads = quads(&board, 0.3);
+
+        // Only the side facing the viewer is drawn, back to front.
+        assert!(!quads.is_empty() && quads.len() < 24 * 12);
+        assert!(quads.windows(2).all(|pair| pair[0].depth <= pair[1].depth));
+        assert!(quads
+            .iter()
+            .all(|quad| quad.corners.iter().flatten().all(|c| c.abs() <= 1.0)));
+        assert!(quads.iter().filter(|quad| quad.alive).count() <= 1);
+    }
+
+    #[test]
+    fn test_project() {
+        // The outside of the ring at u = 0 is the farthest point out along x.
+        let [x, _, _] = project(0.0, 0.0);
+        assert!((x - (MAJOR + MINOR)).abs() < 1e-6);
+        let [x, _, _] = project(0.0, TAU / 2.0);
+        assert!((x - (MAJOR - MINOR)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,206 @@
+use egui::{Color32, Rect, Sense, Stroke, Ui, Vec2};
+
+use gol::{Explanation, Game};
+
+const CELL_SIZE: f32 = 28.0;
+
+// One scripted stage of the tutorial.
+enum Step {
+    // Some text to read before moving on.
+    Read(&'static str),
+    // Highlight a cell and its neighbors and ask whether it will be live next generation.
+    Predict { x: usize, y: usize },
+    // Advance the board one generation and show why each cell changed.
+    Advance,
+}
+
+const SCRIPT: &[Step] = &[
+    Step::Read(
+        "Welcome to the Game of Life! Each square on the board is a cell, which is either live \
+         (filled in) or dead (empty).",
+    ),
+    Step::Read(
+        "Every generation, each cell looks at its eight neighbors: the cells touching it, \
+         including diagonally.",
+    ),
+    Step::Read(
+        "A live cell with two or three live neighbors survives. A dead cell with exactly three \
+         live neighbors is born. Every other cell is dead in the next generation.",
+    ),
+    Step::Predict { x: 3, y: 3 },
+    Step::Predict { x: 3, y: 2 },
+    Step::Predict { x: 2, y: 3 },
+    Step::Advance,
+    Step::Predict { x: 3, y: 2 },
+    Step::Predict { x: 4, y: 3 },
+    Step::Advance,
+    Step::Read(
+        "That pattern is called a blinker: it flips between the same two shapes forever. You're \
+         ready to build your own patterns!",
+    ),
+];
+
+// A guided walk through the rules on a small board holding a blinker.
+pub struct Tutorial {
+    game: Game,
+    step: usize,
+
+    // The player's prediction for the current Predict step, once they've made one.
+    prediction: Option<bool>,
+    // Whether the current Advance step has been run yet.
+    advanced: bool,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        let mut game = Game::new(7, 7);
+        game.set([(3, 2), (3, 3), (3, 4)])
+            .expect("the blinker fits on the board");
+        game.set_explain(true);
+        Tutorial {
+            game,
+            step: 0,
+            prediction: None,
+            advanced: false,
+        }
+    }
+
+    // Show the tutorial, returning false once the player leaves it.
+    pub fn show(&mut self, ui: &mut Ui) -> bool {
+        let Some(step) = SCRIPT.get(self.step) else {
+            return false;
+        };
+
+        let highlight = match step {
+            Step::Predict { x, y } => Some((*x, *y)),
+            _ => None,
+        };
+        self.paint_board(ui, highlight);
+
+        let can_continue = match step {
+            Step::Read(text) => {
+                ui.label(*text);
+                true
+            }
+            Step::Predict { x, y } => self.show_prediction(ui, *x, *y),
+            Step::Advance => self.show_advance(ui),
+        };
+
+        let mut keep_going = true;
+        ui.horizontal(|ui| {
+            let last = self.step + 1 == SCRIPT.len();
+            let next = if last { "Finish" } else { "Next" };
+            if ui
+                .add_enabled(can_continue, egui::Button::new(next))
+                .clicked()
+            {
+                self.step += 1;
+                self.prediction = None;
+                self.advanced = false;
+                keep_going = !last;
+            }
+
+            if ui.button("Exit Tutorial").clicked() {
+                keep_going = false;
+            }
+        });
+        keep_going
+    }
+
+    fn show_prediction(&mut self, ui: &mut Ui, x: usize, y: usize) -> bool {
+        let alive = self.game.board()[(x, y)];
+        let neighbors = self.game.live_neighbors(x, y);
+        ui.label(format!(
+            "The highlighted cell is {} with {neighbors} live neighbor{}. Will it be live in the \
+             next generation?",
+            if alive { "live" } else { "dead" },
+            if neighbors == 1 { "" } else { "s" },
+        ));
+
+        let Some(prediction) = self.prediction else {
+            ui.horizontal(|ui| {
+                if ui.button("Live").clicked() {
+                    self.prediction = Some(true);
+                }
+                if ui.button("Dead").clicked() {
+                    self.prediction = Some(false);
+                }
+            });
+            return false;
+        };
+
+        // Look ahead on a copy of the game so the real board only moves on Advance steps.
+        let mut next = self.game.clone();
+        next.iterate();
+        let will_live = next.board()[(x, y)];
+        let reason = next
+            .explanations()
+            .iter()
+            .find(|explanation| (explanation.point.x, explanation.point.y) == (x, y))
+            .map(Explanation::to_string)
+            .unwrap_or_else(|| format!("it stays {}", if alive { "live" } else { "dead" }));
+
+        if prediction == will_live {
+            ui.colored_label(Color32::DARK_GREEN, format!("Correct! {reason}."));
+        } else {
+            ui.colored_label(Color32::DARK_RED, format!("Not quite: {reason}."));
+        }
+        true
+    }
+
+    fn show_advance(&mut self, ui: &mut Ui) -> bool {
+        if !self.advanced {
+            ui.label("Let's see what happens. Step the board forward one generation.");
+            if ui.button("Step").clicked() {
+                self.game.iterate();
+                self.advanced = true;
+            }
+            return false;
+        }
+
+        ui.label("Here's why each cell changed:");
+        for explanation in self.game.explanations() {
+            ui.label(explanation.to_string());
+        }
+        true
+    }
+
+    fn paint_board(&self, ui: &mut Ui, highlight: Option<(usize, usize)>) {
+        let board = self.game.board();
+        let size = Vec2::new(
+            board.x_size() as f32 * CELL_SIZE,
+            board.y_size() as f32 * CELL_SIZE,
+        );
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+        let origin = response.rect.min;
+
+        let cell_rect = |x: usize, y: usize| {
+            Rect::from_min_size(
+                origin + Vec2::new(x as f32 * CELL_SIZE, y as f32 * CELL_SIZE),
+                Vec2::splat(CELL_SIZE),
+            )
+            .shrink(1.0)
+        };
+
+        for x in 0..board.x_size() {
+            for y in 0..board.y_size() {
+                let neighbor = highlight.is_some_and(|(hx, hy)| {
+                    (hx, hy) != (x, y) && hx.abs_diff(x) <= 1 && hy.abs_diff(y) <= 1
+                });
+                let fill = match (board[(x, y)], neighbor) {
+                    (true, _) => Color32::from_gray(30),
+                    (false, true) => Color32::LIGHT_BLUE,
+                    (false, false) => Color32::from_gray(220),
+                };
+                painter.rect_filled(cell_rect(x, y), 2.0, fill);
+                if neighbor {
+                    painter.rect_stroke(cell_rect(x, y), 2.0, Stroke::new(2.0, Color32::BLUE));
+                }
+            }
+        }
+
+        if let Some((x, y)) = highlight {
+            painter.rect_stroke(cell_rect(x, y), 2.0, Stroke::new(3.0, Color32::GOLD));
+        }
+    }
+}
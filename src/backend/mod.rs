@@ -0,0 +1,379 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(not(feature = "parallel"))]
+use std::thread;
+
+use crate::rule::RuleTable;
+use crate::{trace, Board, Changes, Neighborhood, Point, Rect};
+
+use neighbor_count::NeighborCounts;
+use table::WindowTable;
+
+mod bitwise;
+mod naive;
+mod neighbor_count;
+mod table;
+
+// The engine used to compute each generation. Every backend produces identical results; they only
+// differ in speed for different kinds of boards. Auto picks one based on the board being stepped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Backend {
+    #[default]
+    Auto,
+    Naive,
+    // Keeps a per-cell live neighbor count, updating it only around cells that changed. Only
+    // applies to rules that depend on nothing but the number of live neighbors; others fall back
+    // to Naive.
+    NeighborCount,
+    // Slides a 3x3 window down each column and looks the next state up in a table precomputed
+    // from the rule, with no per-cell branching on the neighborhood.
+    Table,
+    // Counts the neighbors of 64 cells at a time with bitwise operations on the packed cells. Only
+    // applies to rules that depend on nothing but the number of live neighbors; others fall back
+    // to Table.
+    Bitwise,
+}
+
+// Below this fraction of changed cells per generation, Auto prefers the neighbor-count backend.
+const QUIET_ACTIVITY: f64 = 0.02;
+
+impl Backend {
+    // Every concrete backend, that is every one but Auto, for listing in front ends. They're all
+    // always built in, since none of them needs anything beyond the standard library.
+    pub fn available() -> Vec<Backend> {
+        vec![
+            Backend::Naive,
+            Backend::NeighborCount,
+            Backend::Table,
+            Backend::Bitwise,
+        ]
+    }
+
+    // Pick the concrete backend to step a board with under the given rule, given the fraction of
+    // cells that changed in the last generation if it's known. Explicit choices are kept unless
+    // they can't run the rule.
+    pub(crate) fn resolve(self, table: &RuleTable, activity: Option<f64>) -> Backend {
+        let totalistic = table.totalistic().is_some();
+        match self {
+            // Neighbor counts only pay off when few cells change each generation.
+            Backend::Auto if totalistic && activity.is_some_and(|a| a < QUIET_ACTIVITY) => {
+                Backend::NeighborCount
+            }
+            Backend::Auto if totalistic => Backend::Bitwise,
+            Backend::Auto => Backend::Table,
+            Backend::NeighborCount if !totalistic => Backend::Naive,
+            Backend::Bitwise if !totalistic => Backend::Table,
+            backend => backend,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Auto => "auto",
+            Backend::Naive => "naive",
+            Backend::NeighborCount => "neighbor-count",
+            Backend::Table => "table",
+            Backend::Bitwise => "bitwise",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = UnknownBackend;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Backend::Auto]
+            .into_iter()
+            .chain(Backend::available())
+            .find(|backend| backend.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownBackend(s.to_string()))
+    }
+}
+
+// A backend name that isn't one of the backends' names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownBackend(pub String);
+
+impl fmt::Display for UnknownBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown backend {:?}", self.0)
+    }
+}
+
+impl Error for UnknownBackend {}
+
+// State some backends carry from one generation to the next. It describes the board they last
+// produced, so it has to be invalidated whenever the board is edited between iterations.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Cache {
+    counts: Option<NeighborCounts>,
+
+    // The window table only depends on the rule, so it survives edits to the board.
+    windows: Option<Box<WindowTable>>,
+
+    // The fraction of cells that changed in the last generation, used to pick a backend.
+    activity: Option<f64>,
+
+    // The bounding box of the live cells on the last board produced, when it's known.
+    bounds: Option<Rect>,
+
+    // The backend the last generation ran on, so a change of backend can be reported.
+    resolved: Option<Backend>,
+}
+
+impl Cache {
+    pub(crate) fn invalidate(&mut self) {
+        self.counts = None;
+        self.bounds = None;
+    }
+
+    pub(crate) fn activity(&self) -> Option<f64> {
+        self.activity
+    }
+
+    // Drop everything derived from the rule, for when the rule changes.
+    pub(crate) fn invalidate_rule(&mut self) {
+        self.invalidate();
+        self.windows = None;
+    }
+}
+
+// The region of the board that could change in the next generation: the bounding box of the live
+// cells grown by one in every direction. When the grown box reaches an edge it spans that whole
+// axis, since neighbors wrap around. Only when a rule births dead cells with no live neighbors can
+// anything change outside the box, and then the whole board is active.
+fn active_region(table: &RuleTable, board: &Board, bounds: Option<Rect>) -> Option<Rect> {
+    let everything = Rect::new(0, 0, board.x_size(), board.y_size());
+    if table.next(false, Neighborhood::default()) {
+        return Some(everything);
+    }
+
+    let bounds = bounds.or_else(|| board.bounding_box())?;
+    let grow = |start: usize, len: usize, size: usize| {
+        if start == 0 || start + len >= size {
+            (0, size)
+        } else {
+            (start - 1, len + 2)
+        }
+    };
+    let (x, width) = grow(bounds.x, bounds.width, board.x_size());
+    let (y, height) = grow(bounds.y, bounds.height, board.y_size());
+    Some(Rect::new(x, y, width, height))
+}
+
+// Compute the next generation from previous into current using the given backend, on up to
+// `threads` threads for the backends that can split the work. Backends that step regions may
+// leave cells outside `clip` dead, for when something else fills them in.
+pub(crate) fn step(
+    backend: Backend,
+    threads: usize,
+    clip: Option<Rect>,
+    table: &RuleTable,
+    previous: &Board,
+    current: &mut Board,
+    cache: &mut Cache,
+) -> Changes {
+    let resolved = backend.resolve(table, cache.activity);
+    if cache.resolved != Some(resolved) {
+        trace::backend_selected(backend, resolved);
+        cache.resolved = Some(resolved);
+    }
+    if resolved != Backend::NeighborCount {
+        cache.counts = None;
+    }
+    if !matches!(resolved, Backend::Table | Backend::Bitwise) {
+        cache.bounds = None;
+    }
+
+    let changes = match (resolved, table.totalistic()) {
+        (Backend::NeighborCount, Some(totalistic)) => {
+            let counts = cache
+                .counts
+                .get_or_insert_with(|| NeighborCounts::new(previous));
+            neighbor_count::step(totalistic, previous, current, counts)
+        }
+        (Backend::Table | Backend::Bitwise, totalistic) => {
+            let region = active_region(table, previous, cache.bounds.take())
+                .and_then(|region| clip.map_or(Some(region), |clip| region.intersection(clip)));
+            match region {
+                Some(region) => {
+                    let (changes, bounds) = match totalistic {
+                        Some(totalistic) if resolved == Backend::Bitwise => {
+                            bitwise::step(totalistic, previous, current, region, threads)
+                        }
+                        _ => {
+                            let windows = cache
+                                .windows
+                                .get_or_insert_with(|| Box::new(WindowTable::new(table)));
+                            table::step(windows, previous, current, region, threads)
+                        }
+                    };
+                    cache.bounds = bounds;
+                    changes
+                }
+                // An empty board under a rule that can't birth from nothing stays empty.
+                None => Changes::default(),
+            }
+        }
+        _ => naive::step(table, previous, current),
+    };
+
+    let cells = previous.cell_count().max(1);
+    cache.activity = Some((changes.births + changes.deaths) as f64 / cells as f64);
+    changes
+}
+
+// Step the region's columns of `current` with `step_band`, which is given the packed words of a
+// run of columns and the x of the first, and returns the changes and the top and bottom live cell
+// of each column. The columns are split into bands stepped on up to `threads` threads at once, so
+// long as each band gets at least `min_cells` cells. Returns the changes along with the bounding
+// box of the live cells written.
+fn in_bands<F>(
+    current: &mut Board,
+    region: Rect,
+    threads: usize,
+    min_cells: usize,
+    step_band: F,
+) -> (Changes, Option<Rect>)
+where
+    F: Fn(&mut [u64], usize) -> (Changes, Vec<Point>) + Sync,
+{
+    let words = current.column_words();
+    let cells = &mut current.words_mut()[region.x * words..(region.x + region.width) * words];
+
+    let threads = threads.min(region.width * region.height / min_cells);
+    if threads <= 1 {
+        let (changes, live) = step_band(cells, region.x);
+        return (changes, Rect::bounding(live));
+    }
+
+    let band = region.width.div_ceil(threads);
+    let bands = run_bands(cells, band, words, region.x, &step_band);
+
+    let mut changes = Changes::default();
+    let mut live = Vec::new();
+    for (band_changes, band_live) in bands {
+        changes.births += band_changes.births;
+        changes.deaths += band_changes.deaths;
+        live.extend(band_live);
+    }
+    (changes, Rect::bounding(live))
+}
+
+// Step `cells` in bands of `band` columns of `words` words each, the first band starting at `x`,
+// each band on a thread of its own.
+#[cfg(not(feature = "parallel"))]
+fn run_bands<F>(
+    cells: &mut [u64],
+    band: usize,
+    words: usize,
+    x: usize,
+    step_band: &F,
+) -> Vec<(Changes, Vec<Point>)>
+where
+    F: Fn(&mut [u64], usize) -> (Changes, Vec<Point>) + Sync,
+{
+    thread::scope(|scope| {
+        let handles: Vec<_> = cells
+            .chunks_mut(band * words)
+            .enumerate()
+            .map(|(index, cells)| scope.spawn(move || step_band(cells, x + index * band)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("a band of columns panicked"))
+            .collect()
+    })
+}
+
+// With the parallel feature the bands go to rayon's thread pool, which keeps its threads between
+// generations rather than starting new ones each time.
+#[cfg(feature = "parallel")]
+fn run_bands<F>(
+    cells: &mut [u64],
+    band: usize,
+    words: usize,
+    x: usize,
+    step_band: &F,
+) -> Vec<(Changes, Vec<Point>)>
+where
+    F: Fn(&mut [u64], usize) -> (Changes, Vec<Point>) + Sync,
+{
+    use rayon::prelude::*;
+
+    cells
+        .par_chunks_mut(band * words)
+        .enumerate()
+        .map(|(index, cells)| step_band(cells, x + index * band))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backend_parse() {
+        assert_eq!(Backend::Auto, "auto".parse().unwrap());
+        assert_eq!(Backend::Naive, "Naive".parse().unwrap());
+        assert_eq!(Backend::NeighborCount, "neighbor-count".parse().unwrap());
+        assert_eq!(Backend::Bitwise, "bitwise".parse().unwrap());
+        assert_eq!(
+            UnknownBackend("gpu".to_string()),
+            "gpu".parse::<Backend>().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_backend_resolve() {
+        let table = RuleTable::new(&crate::LifeLike::CONWAY);
+        assert_eq!(Backend::Bitwise, Backend::Auto.resolve(&table, None));
+        assert_eq!(Backend::Bitwise, Backend::Auto.resolve(&table, Some(0.3)));
+        assert_eq!(
+            Backend::NeighborCount,
+            Backend::Auto.resolve(&table, Some(0.001))
+        );
+        for backend in Backend::available() {
+            assert_eq!(backend, backend.resolve(&table, None));
+        }
+    }
+
+    #[test]
+    fn test_active_region() {
+        let conway = RuleTable::new(&crate::LifeLike::CONWAY);
+        let board: Board = "......\n..O...\n......\n......\n".parse().unwrap();
+        assert_eq!(
+            Some(Rect::new(1, 0, 3, 3)),
+            active_region(&conway, &board, None)
+        );
+        assert_eq!(None, active_region(&conway, &Board::new(3, 3), None));
+
+        let corner: Board = "O..\n...\n...\n".parse().unwrap();
+        assert_eq!(
+            Some(Rect::new(0, 0, 3, 3)),
+            active_region(&conway, &corner, None)
+        );
+
+        let b0 = RuleTable::new(&crate::LifeLike::new(&[0], &[]));
+        assert_eq!(
+            Some(Rect::new(0, 0, 3, 3)),
+            active_region(&b0, &Board::new(3, 3), None)
+        );
+    }
+
+    #[test]
+    fn test_backend_resolve_non_totalistic() {
+        let rule = |_: bool, neighborhood: crate::Neighborhood| neighborhood.get(0, 1);
+        let table = RuleTable::new(&rule);
+        assert_eq!(Backend::Table, Backend::Auto.resolve(&table, Some(0.0)));
+        assert_eq!(Backend::Naive, Backend::NeighborCount.resolve(&table, None));
+        assert_eq!(Backend::Table, Backend::Bitwise.resolve(&table, None));
+    }
+}
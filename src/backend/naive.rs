@@ -0,0 +1,21 @@
+use crate::rule::RuleTable;
+use crate::{Board, Changes};
+
+// The straightforward engine: visit every cell, gather its neighborhood from the previous board,
+// and look up its next state in the rule's table.
+pub(crate) fn step(rule: &RuleTable, previous: &Board, current: &mut Board) -> Changes {
+    let mut changes = Changes::default();
+    for x in 0..previous.x_size() {
+        for y in 0..previous.y_size() {
+            let was_live = previous[(x, y)];
+            let is_live = rule.next(was_live, previous.neighborhood(x, y));
+            match (was_live, is_live) {
+                (false, true) => changes.births += 1,
+                (true, false) => changes.deaths += 1,
+                _ => {}
+            }
+            current.set(x, y, is_live);
+        }
+    }
+    changes
+}
@@ -0,0 +1,100 @@
+use crate::{Board, Changes};
+
+// The live neighbor count of every cell on a board, kept up to date incrementally: when a cell is
+// born or dies only its eight neighbors' counts change, so a quiet board costs one count lookup
+// per cell instead of eight neighbor lookups.
+#[derive(Clone, Debug)]
+pub(crate) struct NeighborCounts {
+    counts: Vec<u8>,
+}
+
+impl NeighborCounts {
+    pub(crate) fn new(board: &Board) -> Self {
+        let mut counts = vec![0; board.cell_count()];
+        for point in board.live_cells() {
+            let index = board.flat_index(point.x, point.y);
+            for neighbor in board.neighbor_indices(index).into_iter().flatten() {
+                counts[neighbor] += 1;
+            }
+        }
+        NeighborCounts { counts }
+    }
+}
+
+// Step a totalistic rule, given as its answer for [alive][count]. The counts must describe the
+// previous board and are left describing the current one.
+pub(crate) fn step(
+    rule: &[[bool; 9]; 2],
+    previous: &Board,
+    current: &mut Board,
+    counts: &mut NeighborCounts,
+) -> Changes {
+    let mut changes = Changes::default();
+    let mut changed = Vec::new();
+    let mut index = 0;
+    for x in 0..previous.x_size() {
+        for y in 0..previous.y_size() {
+            let was_live = previous.get(x, y);
+            let is_live = rule[was_live as usize][counts.counts[index] as usize];
+            current.set(x, y, is_live);
+            if was_live != is_live {
+                changed.push((index, is_live));
+                if is_live {
+                    changes.births += 1;
+                } else {
+                    changes.deaths += 1;
+                }
+            }
+            index += 1;
+        }
+    }
+
+    for (index, born) in changed {
+        // Go by the previous board's edges, since the one being written may not have the game's
+        // topology yet.
+        for neighbor in previous.neighbor_indices(index).into_iter().flatten() {
+            if born {
+                counts.counts[neighbor] += 1;
+            } else {
+                counts.counts[neighbor] -= 1;
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_neighbor_counts_new() {
+        let board: Board = "...\nOOO\n...\n".parse().unwrap();
+        let counts = NeighborCounts::new(&board);
+        for x in 0..3 {
+            for y in 0..3 {
+                let index = x * 3 + y;
+                assert_eq!(board.live_neighbors(x, y), counts.counts[index]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_keeps_counts_current() {
+        let conway = [
+            [false, false, false, true, false, false, false, false, false],
+            [false, false, true, true, false, false, false, false, false],
+        ];
+        let mut previous: Board = ".....\n.....\n.OOO.\n.....\n.....\n".parse().unwrap();
+        let mut current = Board::new(5, 5);
+        let mut counts = NeighborCounts::new(&previous);
+
+        for _ in 0..3 {
+            let changes = step(&conway, &previous, &mut current, &mut counts);
+            assert_eq!(2, changes.births);
+            assert_eq!(2, changes.deaths);
+            assert_eq!(NeighborCounts::new(&current).counts, counts.counts);
+            std::mem::swap(&mut previous, &mut current);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::rule::RuleTable;
+use crate::{Board, Changes, Neighborhood, Point, Rect};
+
+use super::in_bands;
+
+// The next state of a cell for every 3x3 window of cells centered on it. A window packs its rows
+// top to bottom three bits at a time, each row's left cell in its lowest bit, so walking down a
+// column only needs to shift the next row in at the top instead of gathering eight neighbors.
+#[derive(Clone, Debug)]
+pub(crate) struct WindowTable {
+    next: [bool; 512],
+}
+
+impl WindowTable {
+    pub(crate) fn new(rule: &RuleTable) -> Self {
+        let mut next = [false; 512];
+        for (window, next) in next.iter_mut().enumerate() {
+            let alive = window & (1 << 4) != 0;
+            // Drop the center bit to line the rest up with the Neighborhood bit order.
+            let neighbors = (window & 0b1111) | (window >> 5) << 4;
+            *next = rule.next(alive, Neighborhood::from_bits(neighbors as u8));
+        }
+        WindowTable { next }
+    }
+}
+
+// The fewest cells worth handing a thread of their own. Below this, starting the thread costs more
+// than stepping the cells.
+const MIN_CELLS_PER_THREAD: usize = 1 << 16;
+
+// Step every cell in the region, which must contain every cell that could change. Cells outside
+// of it are left untouched. Returns the changes along with the bounding box of the live cells
+// written. The region's columns are split into bands stepped on up to `threads` threads at once.
+pub(crate) fn step(
+    table: &WindowTable,
+    previous: &Board,
+    current: &mut Board,
+    region: Rect,
+    threads: usize,
+) -> (Changes, Option<Rect>) {
+    in_bands(
+        current,
+        region,
+        threads,
+        MIN_CELLS_PER_THREAD,
+        |cells, x| step_band(table, previous, cells, x, region),
+    )
+}
+
+// Step the region's rows of the columns starting at `x` that `cells` holds, returning the changes
+// and the top and bottom live cell of each column.
+fn step_band(
+    table: &WindowTable,
+    previous: &Board,
+    cells: &mut [u64],
+    x: usize,
+    region: Rect,
+) -> (Changes, Vec<Point>) {
+    let wrap = previous.wrap();
+    // Stands in for the columns past a dead edge.
+    let dead = vec![0; previous.column_words()];
+    let column = |x: Option<usize>| x.map_or(dead.as_slice(), |x| previous.column(x));
+    let cell = |column: &[u64], y: usize| (column[y / 64] >> (y % 64) & 1) as usize;
+    let mut changes = Changes::default();
+    let mut live = Vec::new();
+    for (x, next_column) in (x..).zip(cells.chunks_mut(previous.column_words())) {
+        let left = column(wrap.left[x]);
+        let middle = previous.column(x);
+        let right = column(wrap.right[x]);
+        let row = |y: usize| cell(left, y) | cell(middle, y) << 1 | cell(right, y) << 2;
+        let row_at = |y: Option<usize>| y.map_or(0, row);
+
+        // Prime the window with the rows above and at the top of the region.
+        let mut window = row_at(wrap.up[region.y]) << 3 | row(region.y) << 6;
+        let (mut top, mut bottom) = (None, 0);
+        for y in region.ys() {
+            window = window >> 3 | row_at(wrap.down[y]) << 6;
+
+            let is_live = table.next[window];
+            match (cell(middle, y) == 1, is_live) {
+                (false, true) => changes.births += 1,
+                (true, false) => changes.deaths += 1,
+                _ => {}
+            }
+            if is_live {
+                top.get_or_insert(y);
+                bottom = y;
+            }
+            let bit = 1 << (y % 64);
+            if is_live {
+                next_column[y / 64] |= bit;
+            } else {
+                next_column[y / 64] &= !bit;
+            }
+        }
+
+        if let Some(top) = top {
+            live.extend([Point::new(x, top), Point::new(x, bottom)]);
+        }
+    }
+    (changes, live)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LifeLike;
+
+    #[test]
+    fn test_window_table() {
+        let table = WindowTable::new(&RuleTable::new(&LifeLike::CONWAY));
+        // A dead center with three live cells in the row above is born.
+        assert!(table.next[0b000_000_111]);
+        // A live center with one neighbor dies.
+        assert!(!table.next[0b000_011_000]);
+        // A live center with two neighbors survives.
+        assert!(table.next[0b100_010_001]);
+    }
+
+    #[test]
+    fn test_window_table_neighbor_order() {
+        // Copy the cell to the right, which pins down which window bit maps to which neighbor.
+        let rule = |_: bool, neighborhood: Neighborhood| neighborhood.get(1, 0);
+        let table = WindowTable::new(&RuleTable::new(&rule));
+        assert!(table.next[0b000_100_000]);
+        assert!(!table.next[0b000_001_000]);
+        assert!(!table.next[0b100_000_100]);
+    }
+
+    #[test]
+    fn test_step_matches_naive() {
+        let rule = RuleTable::new(&LifeLike::CONWAY);
+        let table = WindowTable::new(&rule);
+        let previous = Board::from_fn(7, 5, |x, y| (x * 7 + y * 3) % 4 == 0);
+
+        let mut expected = Board::new(7, 5);
+        let expected_changes = super::super::naive::step(&rule, &previous, &mut expected);
+        let mut current = Board::new(7, 5);
+        let everything = Rect::new(0, 0, 7, 5);
+        let (changes, bounds) = step(&table, &previous, &mut current, everything, 1);
+
+        assert_eq!(expected, current);
+        assert_eq!(expected_changes, changes);
+        assert_eq!(expected.bounding_box(), bounds);
+    }
+
+    #[test]
+    fn test_step_region() {
+        let rule = RuleTable::new(&LifeLike::CONWAY);
+        let table = WindowTable::new(&rule);
+        let previous: Board = "......\n......\n..O...\n..O...\n..O...\n......\n"
+            .parse()
+            .unwrap();
+
+        let mut current = Board::new(6, 6);
+        let (changes, bounds) = step(&table, &previous, &mut current, Rect::new(1, 1, 3, 5), 1);
+        assert_eq!(2, changes.births);
+        assert_eq!(2, changes.deaths);
+        assert_eq!(Some(Rect::new(1, 3, 3, 1)), bounds);
+        assert_eq!(
+            "......\n......\n......\n.OOO..\n......\n......\n",
+            current.to_string()
+        );
+    }
+
+    #[test]
+    fn test_step_threads() {
+        let rule = RuleTable::new(&LifeLike::CONWAY);
+        let table = WindowTable::new(&rule);
+        let previous = Board::from_fn(600, 250, |x, y| (x * 7 + y * 3) % 5 < 2);
+        let region = Rect::new(3, 1, 597, 249);
+
+        let mut expected = Board::new(600, 250);
+        let single = step(&table, &previous, &mut expected, region, 1);
+        let mut current = Board::new(600, 250);
+        assert_eq!(single, step(&table, &previous, &mut current, region, 8));
+        assert_eq!(expected, current);
+    }
+}
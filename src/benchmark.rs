@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Game, Pattern};
+
+// The standard patterns for measuring and checking backends against each other: the long-lived
+// methuselahs everyone quotes lifespans for, a symmetric start, and patterns that grow without end.
+// Each is built from its cells here rather than read from a file, so every build runs exactly the
+// same boards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Benchmark {
+    // Five cells that take 1103 generations to settle on an unbounded plane.
+    RPentomino,
+    // Seven cells that take 5206 generations to settle, leaving 633 cells behind.
+    Acorn,
+    // Nine cells that take 17331 generations to settle.
+    Rabbits,
+    // Rows of 4, 8, 12, 8, and 4 cells, a gap apart, which stay symmetric the whole way.
+    Diamond,
+    // Bill Gosper's glider gun, which fires a glider every 30 generations, so its work keeps
+    // growing as the stream fills the board.
+    GliderGun,
+    // The smallest pattern known to grow forever, ten cells that turn into a block-laying switch
+    // engine.
+    SwitchEngine,
+}
+
+impl Benchmark {
+    pub const ALL: [Benchmark; 6] = [
+        Benchmark::RPentomino,
+        Benchmark::Acorn,
+        Benchmark::Rabbits,
+        Benchmark::Diamond,
+        Benchmark::GliderGun,
+        Benchmark::SwitchEngine,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Benchmark::RPentomino => "r-pentomino",
+            Benchmark::Acorn => "acorn",
+            Benchmark::Rabbits => "rabbits",
+            Benchmark::Diamond => "diamond",
+            Benchmark::GliderGun => "glider-gun",
+            Benchmark::SwitchEngine => "switch-engine",
+        }
+    }
+
+    // The pattern's cells, under Conway's rule.
+    pub fn pattern(&self) -> Pattern {
+        let rows: &[&str] = match self {
+            Benchmark::RPentomino => &[".OO", "OO.", ".O."],
+            Benchmark::Acorn => &[".O.....", "...O...", "OO..OOO"],
+            Benchmark::Rabbits => &["O...OOO", "OOO..O.", ".O....."],
+            Benchmark::Diamond => {
+                return Pattern::from_fn(12, 9, |x, y| {
+                    let half = [2, 4, 6, 4, 2][y / 2];
+                    u8::from(y % 2 == 0 && x + half >= 6 && x < 6 + half)
+                })
+            }
+            Benchmark::GliderGun => &[
+                "........................O...........",
+                "......................O.O...........",
+                "............OO......OO............OO",
+                "...........O...O....OO............OO",
+                "OO........O.....O...OO..............",
+                "OO........O...O.OO....O.O...........",
+                "..........O.....O.......O...........",
+                "...........O...O....................",
+                "............OO......................",
+            ],
+            Benchmark::SwitchEngine => &[
+                "......O.", "....O.OO", "....O.O.", "....O...", "..O.....", "O.O.....",
+            ],
+        };
+        let mut pattern = Pattern::from_fn(rows[0].len(), rows.len(), |x, y| {
+            u8::from(rows[y].as_bytes()[x] == b'O')
+        });
+        pattern.set_rule("B3/S23");
+        pattern
+    }
+
+    // The width and height of the board it's run on, big enough that what it sends off doesn't
+    // wrap around into it for its first thousand generations or so.
+    pub fn size(&self) -> usize {
+        match self {
+            Benchmark::RPentomino | Benchmark::Diamond => 256,
+            Benchmark::Acorn | Benchmark::GliderGun => 384,
+            Benchmark::Rabbits | Benchmark::SwitchEngine => 512,
+        }
+    }
+
+    // A game with the pattern in the middle of a board of its size.
+    pub fn game(&self) -> Game {
+        let size = self.size();
+        Game::seeded(&mut self.pattern(), size, size).expect("benchmarks fit their boards")
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Benchmark {
+    type Err = UnknownBenchmark;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Benchmark::ALL
+            .into_iter()
+            .find(|benchmark| benchmark.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownBenchmark(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownBenchmark(pub String);
+
+impl fmt::Display for UnknownBenchmark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = Benchmark::ALL.iter().map(Benchmark::name).collect();
+        write!(
+            f,
+            "unknown benchmark {:?}, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownBenchmark {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        let populations: Vec<usize> = Benchmark::ALL
+            .iter()
+            .map(|benchmark| benchmark.pattern().live_cells().count())
+            .collect();
+        assert_eq!(vec![5, 7, 9, 36, 36, 10], populations);
+
+        // The gun fires its first glider within 30 generations and is back where it started.
+        let mut game = Benchmark::GliderGun.game();
+        for _ in 0..30 {
+            game.iterate();
+        }
+        assert_eq!(41, game.board().population());
+
+        // The R-pentomino's well-known opening.
+        let mut game = Benchmark::RPentomino.game();
+        let populations: Vec<usize> = (0..6)
+            .map(|_| {
+                game.iterate();
+                game.board().population()
+            })
+            .collect();
+        assert_eq!(vec![6, 7, 9, 8, 9, 12], populations);
+    }
+
+    #[test]
+    fn test_parse() {
+        for benchmark in Benchmark::ALL {
+            assert_eq!(Ok(benchmark), benchmark.to_string().parse());
+        }
+        assert_eq!(Ok(Benchmark::Acorn), "Acorn".parse());
+        assert_eq!(
+            Err(UnknownBenchmark("breeder".to_string())),
+            "breeder".parse::<Benchmark>()
+        );
+    }
+}
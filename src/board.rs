@@ -0,0 +1,1089 @@
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::ops::{Deref, DerefMut, Index};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::{Neighborhood, Point, Rect, Topology};
+
+// A rectangular grid of live and dead cells, without any of the stepping state a Game carries.
+// Boards can be built, edited, compared, and printed on their own.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Board {
+    size: BoardSize,
+
+    // The cells are packed 64 to a word in one contiguous vec, column by column. Each column takes a
+    // whole number of words, with the cell at (x, y) in bit y % 64 of the column's word y / 64, so
+    // the engines can work on 64 cells of a column at once. The bits past the bottom of a column are
+    // always 0, so whole words can be counted and compared.
+    cells: Vec<u64>,
+}
+
+impl Board {
+    // Build a new, empty board.
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        Board {
+            size: BoardSize::new(x_size, y_size),
+            cells: make_board(x_size, y_size),
+        }
+    }
+
+    // Build a new board, setting each cell live or dead according to f(x, y).
+    pub fn from_fn<F: FnMut(usize, usize) -> bool>(x_size: usize, y_size: usize, mut f: F) -> Self {
+        let mut board = Board::new(x_size, y_size);
+        for x in 0..x_size {
+            for y in 0..y_size {
+                board.set(x, y, f(x, y));
+            }
+        }
+        board
+    }
+
+    pub fn x_size(&self) -> usize {
+        self.size.x_size
+    }
+
+    pub fn y_size(&self) -> usize {
+        self.size.y_size
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self[(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        let (word, bit) = self.size.bit(x, y);
+        if alive {
+            self.cells[word] |= bit;
+        } else {
+            self.cells[word] &= !bit;
+        }
+    }
+
+    // The cell at (x, y), for editing through a reference as if it were a bool.
+    pub fn cell_mut(&mut self, x: usize, y: usize) -> CellMut<'_> {
+        let alive = self.get(x, y);
+        CellMut {
+            board: self,
+            x,
+            y,
+            alive,
+        }
+    }
+
+    // Set every cell on the board to the same state.
+    pub fn fill(&mut self, alive: bool) {
+        if !alive {
+            self.clear();
+            return;
+        }
+        let last = self.size.last_word_mask();
+        for column in self.cells.chunks_mut(self.size.words.max(1)) {
+            column.fill(!0);
+            if let Some(word) = column.last_mut() {
+                *word = last;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        clear_board(&mut self.cells);
+    }
+
+    // Count the live neighbors of the cell at (x, y), treating the edges of the board the way its
+    // topology says to.
+    pub fn live_neighbors(&self, x: usize, y: usize) -> u8 {
+        self.neighborhood(x, y).count()
+    }
+
+    // The states of the eight neighbors of the cell at (x, y), treating the edges of the board the
+    // way its topology says to.
+    pub fn neighborhood(&self, x: usize, y: usize) -> Neighborhood {
+        let bits = self
+            .size
+            .neighbors(x, y)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, neighbor)| neighbor.is_some_and(|neighbor| self[neighbor]))
+            .fold(0, |bits, (bit, _)| bits | 1 << bit);
+        Neighborhood::from_bits(bits)
+    }
+
+    // How many cells are live.
+    pub fn population(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // The coordinates of every live cell, in column order.
+    pub fn live_cells(&self) -> impl Iterator<Item = Point> + '_ {
+        let words = self.size.words;
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let (x, base) = (index / words, index % words * 64);
+                set_bits(word).map(move |bit| Point::new(x, base + bit))
+            })
+    }
+
+    // The smallest rectangle containing every live cell, or None if the board is empty.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let mut xs = None;
+        let mut ys: Option<(usize, usize)> = None;
+        for x in 0..self.x_size() {
+            let Some((top, bottom)) = column_span(self.column(x)) else {
+                continue;
+            };
+            xs = Some(xs.map_or((x, x), |(left, _)| (left, x)));
+            ys = Some(ys.map_or((top, bottom), |(min, max)| (min.min(top), max.max(bottom))));
+        }
+
+        let ((left, right), (top, bottom)) = (xs?, ys?);
+        Some(Rect::new(left, top, right - left + 1, bottom - top + 1))
+    }
+
+    // A copy of the cells inside a rectangle, which has to lie within the board.
+    pub fn crop(&self, rect: Rect) -> Board {
+        Board::from_fn(rect.width, rect.height, |x, y| {
+            self.get(rect.x + x, rect.y + y)
+        })
+    }
+
+    // A 64-bit FNV-1a hash of the board's size and cells, the same on every platform, build, and
+    // backend, so two runs can be checked for bit-identical boards by comparing checksums.
+    pub fn checksum(&self) -> u64 {
+        const PRIME: u64 = 0x100_0000_01b3;
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let size = [self.x_size() as u64, self.y_size() as u64];
+        for byte in size.iter().flat_map(|value| value.to_le_bytes()) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+        }
+        // Eight cells a byte, first cell in the lowest bit, which is how the words already hold them.
+        let bytes = self.y_size().div_ceil(8);
+        for x in 0..self.x_size() {
+            let column = self.column(x).iter().flat_map(|word| word.to_le_bytes());
+            for byte in column.take(bytes) {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+            }
+        }
+        hash
+    }
+
+    // The position of (x, y) in the flat cell storage.
+    pub(crate) fn flat_index(&self, x: usize, y: usize) -> usize {
+        self.size.index(x, y)
+    }
+
+    // How many cells the board has, one more than the largest flat index.
+    pub(crate) fn cell_count(&self) -> usize {
+        self.x_size() * self.y_size()
+    }
+
+    // The cell at a flat index, for code that works on indices directly.
+    pub(crate) fn alive_at(&self, index: usize) -> bool {
+        let point = self.size.point(index);
+        self.get(point.x, point.y)
+    }
+
+    pub(crate) fn set_at(&mut self, index: usize, alive: bool) {
+        let point = self.size.point(index);
+        self.set(point.x, point.y, alive);
+    }
+
+    // How many words each column of cells takes.
+    pub(crate) fn column_words(&self) -> usize {
+        self.size.words
+    }
+
+    // The packed words of the column at x, top cells first.
+    pub(crate) fn column(&self, x: usize) -> &[u64] {
+        let words = self.size.words;
+        &self.cells[x * words..(x + 1) * words]
+    }
+
+    // The bits of each column's last word that hold cells.
+    pub(crate) fn last_word_mask(&self) -> u64 {
+        self.size.last_word_mask()
+    }
+
+    // Every column's packed words, one after the other, for engines that write whole columns.
+    pub(crate) fn words_mut(&mut self) -> &mut [u64] {
+        &mut self.cells
+    }
+
+    // The neighbor index tables for this board's size and topology.
+    pub(crate) fn wrap(&self) -> &WrapTable {
+        &self.size.wrap
+    }
+
+    // What lies past the board's edges. Boards start out toroidal.
+    pub fn topology(&self) -> Topology {
+        self.size.topology
+    }
+
+    // Change what lies past the board's edges, leaving the cells as they are.
+    pub fn set_topology(&mut self, topology: Topology) {
+        if topology != self.size.topology {
+            self.size = BoardSize::with_topology(self.x_size(), self.y_size(), topology);
+        }
+    }
+
+    // The flat indices of the eight neighbors of the cell at a flat index, in Neighborhood bit
+    // order, with None for neighbors past a dead edge.
+    pub(crate) fn neighbor_indices(&self, index: usize) -> [Option<usize>; 8] {
+        let point = self.size.point(index);
+        self.size
+            .neighbors(point.x, point.y)
+            .map(|neighbor| neighbor.map(|(x, y)| self.size.index(x, y)))
+    }
+
+    // Every coordinate whose state differs between this board and another of the same size.
+    pub fn diff<'a>(&'a self, other: &'a Board) -> impl Iterator<Item = Point> + 'a {
+        assert_eq!(
+            self.size, other.size,
+            "can only diff boards of the same size"
+        );
+        let words = self.size.words;
+        self.cells
+            .iter()
+            .zip(&other.cells)
+            .enumerate()
+            .flat_map(move |(index, (ours, theirs))| {
+                let (x, base) = (index / words, index % words * 64);
+                set_bits(ours ^ theirs).map(move |bit| Point::new(x, base + bit))
+            })
+    }
+
+    // Set the cell at (x, y), and every cell joined to it through cells of the same state, to
+    // `alive`, returning how many cells changed. Cells join up through their edges but not their
+    // corners, so a diagonal line of cells is enough to fence a region in. The fill carries on
+    // across the board's edges when its topology joins them up.
+    pub fn flood_fill(&mut self, x: usize, y: usize, alive: bool) -> usize {
+        let target = self.get(x, y);
+        if target == alive {
+            return 0;
+        }
+
+        let wrap = Arc::clone(&self.size.wrap);
+        let mut filled = 0;
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if self.get(x, y) != target {
+                continue;
+            }
+            self.set(x, y, alive);
+            filled += 1;
+            stack.extend(
+                [
+                    wrap.left[x].map(|x| (x, y)),
+                    wrap.right[x].map(|x| (x, y)),
+                    wrap.up[y].map(|y| (x, y)),
+                    wrap.down[y].map(|y| (x, y)),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+        }
+        filled
+    }
+
+    // The number of cells that differ between this board and another of the same size.
+    pub fn hamming_distance(&self, other: &Board) -> usize {
+        self.diff(other).count()
+    }
+
+    // The fraction of cells that match between this board and another of the same size, from 0.0
+    // when every cell differs to 1.0 when the boards are identical.
+    pub fn similarity(&self, other: &Board) -> f64 {
+        if self.cell_count() == 0 {
+            return 1.0;
+        }
+        1.0 - self.hamming_distance(other) as f64 / self.cell_count() as f64
+    }
+
+    // Combine another board's cells into this one, with the other board's top-left corner at `at`.
+    // Only the cells the other board covers change, and it can't be any bigger than this board. On
+    // a toroidal board it wraps around the edges, and on any other the cells that land past them
+    // are left out.
+    pub fn blend(&mut self, other: &Board, at: Point, mode: BlendMode) -> Result<(), GridError> {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        if other.x_size() > x_size || other.y_size() > y_size {
+            return Err(GridError::TooLarge {
+                limit: (x_size, y_size),
+                found: (other.x_size(), other.y_size()),
+            });
+        }
+
+        let wraps = self.topology() == Topology::Toroidal;
+        for x in 0..other.x_size() {
+            for y in 0..other.y_size() {
+                let (x_at, y_at) = (at.x + x, at.y + y);
+                if !wraps && (x_at >= x_size || y_at >= y_size) {
+                    continue;
+                }
+                let (x_at, y_at) = (x_at % x_size, y_at % y_size);
+                let below = self.get(x_at, y_at);
+                self.set(x_at, y_at, mode.apply(below, other.get(x, y)));
+            }
+        }
+        Ok(())
+    }
+
+    // Snapshot the board in row-major (visual) order, so grid[y][x] is the cell at (x, y).
+    pub fn to_grid(&self) -> Vec<Vec<bool>> {
+        self.rows().map(Iterator::collect).collect()
+    }
+
+    // Iterate over the rows of the board from top to bottom, each yielding its cells from left to
+    // right.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        (0..self.size.y_size).map(move |y| (0..self.size.x_size).map(move |x| self.get(x, y)))
+    }
+
+    // Iterate over the columns of the board from left to right, each yielding its cells from top to
+    // bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        (0..self.size.x_size).map(move |x| (0..self.size.y_size).map(move |y| self.get(x, y)))
+    }
+}
+
+// Cells are read by indexing, but written with set() or cell_mut(), since a cell packed into a
+// word has no place of its own to borrow mutably.
+impl Index<(usize, usize)> for Board {
+    type Output = bool;
+
+    fn index(&self, (x, y): (usize, usize)) -> &bool {
+        let (word, bit) = self.size.bit(x, y);
+        if self.cells[word] & bit != 0 {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+// A cell borrowed from a board for editing. It reads and writes like a &mut bool and stores its
+// state back into the board when it's dropped.
+pub struct CellMut<'a> {
+    board: &'a mut Board,
+    x: usize,
+    y: usize,
+    alive: bool,
+}
+
+impl Deref for CellMut<'_> {
+    type Target = bool;
+
+    fn deref(&self) -> &bool {
+        &self.alive
+    }
+}
+
+impl DerefMut for CellMut<'_> {
+    fn deref_mut(&mut self) -> &mut bool {
+        &mut self.alive
+    }
+}
+
+impl Drop for CellMut<'_> {
+    fn drop(&mut self) {
+        self.board.set(self.x, self.y, self.alive);
+    }
+}
+
+// Build a board from a row-major grid, the same layout produced by Board::to_grid. Every row must
+// have the same, non-zero length.
+impl TryFrom<Vec<Vec<bool>>> for Board {
+    type Error = GridError;
+
+    fn try_from(grid: Vec<Vec<bool>>) -> Result<Self, Self::Error> {
+        let y_size = grid.len();
+        let x_size = grid.first().map_or(0, Vec::len);
+        if x_size == 0 {
+            return Err(GridError::Empty);
+        }
+
+        let mut board = Board::new(x_size, y_size);
+        for (y, row) in grid.into_iter().enumerate() {
+            if row.len() != x_size {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected: x_size,
+                    found: row.len(),
+                });
+            }
+
+            for (x, cell) in row.into_iter().enumerate() {
+                board.set(x, y, cell);
+            }
+        }
+        Ok(board)
+    }
+}
+
+// Boards print as one line per row, with 'O' for live cells and '.' for dead ones.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", if cell { 'O' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse the format written by Display back into a board.
+impl FromStr for Board {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = s
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| match c {
+                        'O' => Ok(true),
+                        '.' => Ok(false),
+                        found => Err(GridError::InvalidCell { x, y, found }),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<bool>>, GridError>>()?;
+        Board::try_from(grid)
+    }
+}
+
+// How blending combines a cell with the cell placed over it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    // Live if either cell is.
+    #[default]
+    Or,
+    // Live if both cells are.
+    And,
+    // Live if exactly one cell is.
+    Xor,
+    // The placed cell, live or dead, replaces the one under it.
+    Replace,
+}
+
+impl BlendMode {
+    pub fn apply(&self, below: bool, above: bool) -> bool {
+        match self {
+            BlendMode::Or => below || above,
+            BlendMode::And => below && above,
+            BlendMode::Xor => below != above,
+            BlendMode::Replace => above,
+        }
+    }
+}
+
+// The ways a grid can fail to describe a board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GridError {
+    // The grid has no rows or no columns.
+    Empty,
+    // A row's length doesn't match the length of the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // A character in a printed board that isn't a cell.
+    InvalidCell {
+        x: usize,
+        y: usize,
+        found: char,
+    },
+    // The board isn't the size it has to be, given as (x_size, y_size).
+    WrongSize {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    // The board is bigger than the one it's placed on, given as (x_size, y_size).
+    TooLarge {
+        limit: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} cells, expected {expected}"),
+            GridError::InvalidCell { x, y, found } => {
+                write!(f, "unexpected character {found:?} at ({x}, {y})")
+            }
+            GridError::WrongSize { expected, found } => write!(
+                f,
+                "board is {}x{}, expected {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            GridError::TooLarge { limit, found } => write!(
+                f,
+                "board is {}x{}, bigger than the {}x{} board it's placed on",
+                found.0, found.1, limit.0, limit.1
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+fn make_board(x_size: usize, y_size: usize) -> Vec<u64> {
+    vec![0; x_size * y_size.div_ceil(64)]
+}
+
+fn clear_board(board: &mut [u64]) {
+    board.fill(0);
+}
+
+// The positions of the set bits in a word, lowest first.
+pub(crate) fn set_bits(mut word: u64) -> impl Iterator<Item = usize> {
+    iter::from_fn(move || {
+        (word != 0).then(|| {
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+            bit
+        })
+    })
+}
+
+// The top and bottom live cell of a column of packed words, or None if it's empty.
+pub(crate) fn column_span(column: &[u64]) -> Option<(usize, usize)> {
+    let first = column.iter().position(|&word| word != 0)?;
+    let last = column.iter().rposition(|&word| word != 0)?;
+    let top = first * 64 + column[first].trailing_zeros() as usize;
+    let bottom = last * 64 + 63 - column[last].leading_zeros() as usize;
+    Some((top, bottom))
+}
+
+#[derive(Clone)]
+struct BoardSize {
+    x_size: usize,
+    y_size: usize,
+    topology: Topology,
+
+    // How many words of cells each column takes.
+    words: usize,
+
+    // Precomputed neighbor indices along each axis, so neighbor lookups in the inner loop are plain
+    // table reads with no edge checks. Shared between boards of the same size.
+    wrap: Arc<WrapTable>,
+}
+
+// For each x, the x of the column to its left and right, and for each y, the y of the row above
+// and below it, following the topology at the edges of the board. Neighbors past a dead edge are
+// None.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct WrapTable {
+    pub(crate) left: Vec<Option<usize>>,
+    pub(crate) right: Vec<Option<usize>>,
+    pub(crate) up: Vec<Option<usize>>,
+    pub(crate) down: Vec<Option<usize>>,
+}
+
+impl WrapTable {
+    pub(crate) fn new(x_size: usize, y_size: usize, topology: Topology) -> Self {
+        WrapTable {
+            left: (0..x_size).map(|x| topology.before(x, x_size)).collect(),
+            right: (0..x_size).map(|x| topology.after(x, x_size)).collect(),
+            up: (0..y_size).map(|y| topology.before(y, y_size)).collect(),
+            down: (0..y_size).map(|y| topology.after(y, y_size)).collect(),
+        }
+    }
+}
+
+// The wrap table is derived from the sizes and topology, and the topology is a property of where
+// the board is used rather than of its cells, so the sizes alone decide equality.
+impl PartialEq for BoardSize {
+    fn eq(&self, other: &Self) -> bool {
+        (self.x_size, self.y_size) == (other.x_size, other.y_size)
+    }
+}
+
+impl Eq for BoardSize {}
+
+impl Hash for BoardSize {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.x_size, self.y_size).hash(state);
+    }
+}
+
+impl fmt::Debug for BoardSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoardSize")
+            .field("x_size", &self.x_size)
+            .field("y_size", &self.y_size)
+            .finish()
+    }
+}
+
+impl BoardSize {
+    fn new(x_size: usize, y_size: usize) -> Self {
+        BoardSize::with_topology(x_size, y_size, Topology::Toroidal)
+    }
+
+    fn with_topology(x_size: usize, y_size: usize, topology: Topology) -> Self {
+        BoardSize {
+            x_size,
+            y_size,
+            topology,
+            words: y_size.div_ceil(64),
+            wrap: Arc::new(WrapTable::new(x_size, y_size, topology)),
+        }
+    }
+
+    fn check(&self, x: usize, y: usize) {
+        assert!(
+            x < self.x_size && y < self.y_size,
+            "coordinate ({x}, {y}) is outside of the {}x{} board",
+            self.x_size,
+            self.y_size
+        );
+    }
+
+    // The flat index of (x, y), counting cells column by column.
+    fn index(&self, x: usize, y: usize) -> usize {
+        self.check(x, y);
+        x * self.y_size + y
+    }
+
+    // The word holding the cell at (x, y), and the bit it's in.
+    fn bit(&self, x: usize, y: usize) -> (usize, u64) {
+        self.check(x, y);
+        (x * self.words + y / 64, 1 << (y % 64))
+    }
+
+    // The bits of a column's last word that hold cells.
+    fn last_word_mask(&self) -> u64 {
+        match self.y_size % 64 {
+            0 => !0,
+            rest => (1 << rest) - 1,
+        }
+    }
+
+    // The (x, y) coordinate of a flat index.
+    fn point(&self, index: usize) -> Point {
+        Point::new(index / self.y_size, index % self.y_size)
+    }
+
+    // All eight neighbors of (x, y), following the topology at the edges of the board, with None
+    // for neighbors past a dead edge.
+    fn neighbors(&self, x: usize, y: usize) -> [Option<(usize, usize)>; 8] {
+        [
+            self.top_left(x, y),
+            self.top(x, y),
+            self.top_right(x, y),
+            self.left(x, y),
+            self.right(x, y),
+            self.bottom_left(x, y),
+            self.bottom(x, y),
+            self.bottom_right(x, y),
+        ]
+    }
+
+    fn top_left(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (-1, -1)
+        Some((self.wrap.left[x]?, self.wrap.up[y]?))
+    }
+
+    fn top(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (0, -1)
+        self.wrap.up[y].map(|y| (x, y))
+    }
+
+    fn top_right(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (1, -1)
+        Some((self.wrap.right[x]?, self.wrap.up[y]?))
+    }
+
+    fn left(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (-1, 0)
+        self.wrap.left[x].map(|x| (x, y))
+    }
+
+    fn right(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (1, 0)
+        self.wrap.right[x].map(|x| (x, y))
+    }
+
+    fn bottom_left(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (-1, 1)
+        Some((self.wrap.left[x]?, self.wrap.down[y]?))
+    }
+
+    fn bottom(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (0, 1)
+        self.wrap.down[y].map(|y| (x, y))
+    }
+
+    fn bottom_right(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        // translate (1, 1)
+        Some((self.wrap.right[x]?, self.wrap.down[y]?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_board_size_top_left() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 1)), size.top_left(0, 0));
+        assert_eq!(Some((0, 0)), size.top_left(1, 1));
+        assert_eq!(Some((0, 1)), size.top_left(1, 0));
+        assert_eq!(Some((1, 0)), size.top_left(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_top() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((0, 1)), size.top(0, 0));
+        assert_eq!(Some((1, 0)), size.top(1, 1));
+        assert_eq!(Some((1, 1)), size.top(1, 0));
+        assert_eq!(Some((0, 0)), size.top(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_top_right() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 1)), size.top_right(0, 0));
+        assert_eq!(Some((0, 0)), size.top_right(1, 1));
+        assert_eq!(Some((0, 1)), size.top_right(1, 0));
+        assert_eq!(Some((1, 0)), size.top_right(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_left() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 0)), size.left(0, 0));
+        assert_eq!(Some((0, 1)), size.left(1, 1));
+        assert_eq!(Some((0, 0)), size.left(1, 0));
+        assert_eq!(Some((1, 1)), size.left(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_right() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 0)), size.left(0, 0));
+        assert_eq!(Some((0, 1)), size.left(1, 1));
+        assert_eq!(Some((0, 0)), size.left(1, 0));
+        assert_eq!(Some((1, 1)), size.left(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_bottom_left() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 1)), size.bottom_left(0, 0));
+        assert_eq!(Some((0, 0)), size.bottom_left(1, 1));
+        assert_eq!(Some((0, 1)), size.bottom_left(1, 0));
+        assert_eq!(Some((1, 0)), size.bottom_left(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_bottom() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((0, 1)), size.bottom(0, 0));
+        assert_eq!(Some((1, 0)), size.bottom(1, 1));
+        assert_eq!(Some((1, 1)), size.bottom(1, 0));
+        assert_eq!(Some((0, 0)), size.bottom(0, 1));
+    }
+
+    #[test]
+    fn test_board_size_bottom_right() {
+        let size = BoardSize::new(2, 2);
+        assert_eq!(Some((1, 1)), size.bottom_right(0, 0));
+        assert_eq!(Some((0, 0)), size.bottom_right(1, 1));
+        assert_eq!(Some((0, 1)), size.bottom_right(1, 0));
+        assert_eq!(Some((1, 0)), size.bottom_right(0, 1));
+    }
+
+    #[test]
+    fn test_wrap_table() {
+        let wrap = WrapTable::new(3, 1, Topology::Toroidal);
+        assert_eq!(vec![Some(2), Some(0), Some(1)], wrap.left);
+        assert_eq!(vec![Some(1), Some(2), Some(0)], wrap.right);
+        assert_eq!(vec![Some(0)], wrap.up);
+        assert_eq!(vec![Some(0)], wrap.down);
+        assert!(WrapTable::new(0, 0, Topology::Toroidal).left.is_empty());
+
+        let wrap = WrapTable::new(3, 1, Topology::FixedDead);
+        assert_eq!(vec![None, Some(0), Some(1)], wrap.left);
+        assert_eq!(vec![None], wrap.down);
+        let wrap = WrapTable::new(3, 1, Topology::Mirror);
+        assert_eq!(vec![Some(1), Some(2), Some(2)], wrap.right);
+    }
+
+    #[test]
+    fn test_board_size_eq_ignores_table() {
+        assert_eq!(BoardSize::new(3, 2), BoardSize::new(3, 2));
+        assert_ne!(BoardSize::new(3, 2), BoardSize::new(2, 3));
+    }
+
+    #[test]
+    fn test_make_board() {
+        assert_eq!(2, make_board(2, 3).len());
+        assert_eq!(6, make_board(2, 130).len());
+        assert!(make_board(0, 5).is_empty());
+    }
+
+    #[test]
+    fn test_clear_board() {
+        let mut board = make_board(1, 1);
+        board[0] = 1;
+        clear_board(&mut board);
+        assert_eq!(0, board[0]);
+    }
+
+    #[test]
+    fn test_packed_columns() {
+        // Columns longer than a word spill into the next one, and padding bits stay clear.
+        let mut board = Board::new(3, 70);
+        board.set(1, 0, true);
+        board.set(1, 64, true);
+        board.set(2, 69, true);
+        assert_eq!(&[1, 1], board.column(1));
+        assert_eq!(&[0, 1 << 5], board.column(2));
+        assert_eq!(
+            vec![Point::new(1, 0), Point::new(1, 64), Point::new(2, 69)],
+            board.live_cells().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(Rect::new(1, 0, 2, 70)), board.bounding_box());
+
+        board.fill(true);
+        assert_eq!(210, board.population());
+        assert_eq!(&[!0, (1 << 6) - 1], board.column(0));
+        assert_eq!(Some((3, 66)), column_span(&[1 << 3, 1 << 2]));
+        assert_eq!(None, column_span(&[0, 0]));
+    }
+
+    #[test]
+    fn test_cell_mut() {
+        let mut board = Board::new(2, 2);
+        *board.cell_mut(1, 0) = true;
+        assert!(board.get(1, 0));
+        let mut cell = board.cell_mut(1, 0);
+        *cell = !*cell;
+        drop(cell);
+        assert_eq!(0, board.population());
+    }
+
+    #[test]
+    fn test_board_size_index() {
+        let size = BoardSize::new(3, 2);
+        assert_eq!(0, size.index(0, 0));
+        assert_eq!(1, size.index(0, 1));
+        assert_eq!(2, size.index(1, 0));
+        assert_eq!(5, size.index(2, 1));
+        assert_eq!(Point::new(2, 1), size.point(5));
+        assert_eq!(Point::new(1, 0), size.point(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_board_size_index_out_of_bounds() {
+        let size = BoardSize::new(3, 2);
+        size.index(0, 2);
+    }
+
+    #[test]
+    fn test_board_fill() {
+        let mut board = Board::new(2, 3);
+        board.fill(true);
+        assert!(board.columns().flatten().all(|cell| cell));
+        board.clear();
+        assert!(board.columns().flatten().all(|cell| !cell));
+    }
+
+    #[test]
+    fn test_board_index() {
+        let mut board = Board::new(2, 2);
+        board.set(1, 0, true);
+        assert!(board[(1, 0)]);
+        assert!(board.get(1, 0));
+        board.set(1, 0, false);
+        assert!(!board[(1, 0)]);
+    }
+
+    #[test]
+    fn test_board_bounding_box() {
+        assert_eq!(None, Board::new(3, 3).bounding_box());
+        let board: Board = "....\n..O.\n....\n.O..\n".parse().unwrap();
+        assert_eq!(Some(Rect::new(1, 1, 2, 3)), board.bounding_box());
+    }
+
+    #[test]
+    fn test_board_live_cells() {
+        let board: Board = "..O\nO..\n".parse().unwrap();
+        assert_eq!(2, board.population());
+        assert_eq!(
+            vec![Point::new(0, 1), Point::new(2, 0)],
+            board.live_cells().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_board_diff() {
+        let a: Board = "O.\n..\n".parse().unwrap();
+        let b: Board = "..\n.O\n".parse().unwrap();
+        assert_eq!(
+            vec![Point::new(0, 0), Point::new(1, 1)],
+            a.diff(&b).collect::<Vec<_>>()
+        );
+        assert_eq!(0, a.diff(&a).count());
+        assert_eq!(2, a.hamming_distance(&b));
+        assert_eq!(0.5, a.similarity(&b));
+        assert_eq!(1.0, a.similarity(&a));
+    }
+
+    #[test]
+    fn test_board_blend() {
+        let base: Board = "OO.\n...\nO..\n".parse().unwrap();
+        let layer: Board = "O.\n.O\n".parse().unwrap();
+        for (mode, expected) in [
+            (BlendMode::Or, "OO.\n.O.\nO..\n"),
+            (BlendMode::And, "O..\n...\nO..\n"),
+            (BlendMode::Xor, ".O.\n.O.\nO..\n"),
+            (BlendMode::Replace, "O..\n.O.\nO..\n"),
+        ] {
+            let mut board = base.clone();
+            board.blend(&layer, Point::new(0, 0), mode).unwrap();
+            assert_eq!(expected, board.to_string(), "{mode:?}");
+        }
+
+        // Placed past the bottom-right corner, the layer wraps around to the other edges.
+        let mut board = Board::new(3, 3);
+        board
+            .blend(&layer, Point::new(2, 2), BlendMode::Or)
+            .unwrap();
+        assert_eq!("O..\n...\n..O\n", board.to_string());
+
+        // Past the edges of a board that doesn't wrap, the layer is cut off instead.
+        let mut board = Board::new(3, 3);
+        board.set_topology(Topology::FixedDead);
+        board
+            .blend(&layer, Point::new(2, 2), BlendMode::Or)
+            .unwrap();
+        assert_eq!("...\n...\n..O\n", board.to_string());
+        board
+            .blend(&layer, Point::new(5, 0), BlendMode::Or)
+            .unwrap();
+        assert_eq!(1, board.population());
+
+        assert_eq!(
+            Err(GridError::TooLarge {
+                limit: (2, 2),
+                found: (3, 3)
+            }),
+            layer.clone().blend(&base, Point::default(), BlendMode::Or)
+        );
+    }
+
+    #[test]
+    fn test_board_flood_fill() {
+        let mut board: Board = ".....\n.OOO.\n.O.O.\n.OOO.\n.....\n".parse().unwrap();
+        assert_eq!(1, board.flood_fill(2, 2, true));
+        assert_eq!(0, board.flood_fill(2, 2, true));
+
+        // The outside wraps around the edges, so it's all one region.
+        assert_eq!(16, board.flood_fill(0, 0, true));
+        assert_eq!(25, board.population());
+
+        // A diagonal wall is enough to hold the fill in.
+        let mut board: Board = ".....\n..O..\n.O.O.\n..O..\n.....\n".parse().unwrap();
+        assert_eq!(1, board.flood_fill(2, 2, true));
+        assert_eq!(20, board.flood_fill(0, 0, true));
+    }
+
+    #[test]
+    fn test_board_live_neighbors() {
+        let board: Board = "...\nOOO\n...\n".parse().unwrap();
+        assert_eq!(2, board.live_neighbors(1, 1));
+        assert_eq!(3, board.live_neighbors(1, 0));
+        // wraps around the top edge to the bottom
+        assert_eq!(3, board.live_neighbors(1, 2));
+    }
+
+    #[test]
+    fn test_board_display_round_trip() {
+        let board = Board::from_fn(3, 2, |x, y| x == y);
+        let printed = board.to_string();
+        assert_eq!("O..\n.O.\n", printed);
+        assert_eq!(board, printed.parse().unwrap());
+    }
+
+    #[test]
+    fn test_board_parse_errors() {
+        assert_eq!(
+            GridError::InvalidCell {
+                x: 1,
+                y: 0,
+                found: '*'
+            },
+            "O*".parse::<Board>().unwrap_err()
+        );
+        assert_eq!(
+            GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            },
+            "OO\nO".parse::<Board>().unwrap_err()
+        );
+        assert_eq!(GridError::Empty, "".parse::<Board>().unwrap_err());
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let grid = vec![
+            vec![false, true, false],
+            vec![false, false, true],
+            vec![true, true, true],
+        ];
+        let board = Board::try_from(grid.clone()).unwrap();
+        assert_eq!(3, board.x_size());
+        assert_eq!(3, board.y_size());
+        assert!(board[(1, 0)]);
+        assert!(board[(2, 1)]);
+        assert_eq!(grid, board.to_grid());
+    }
+
+    #[test]
+    fn test_grid_errors() {
+        assert_eq!(GridError::Empty, Board::try_from(vec![]).unwrap_err());
+        assert_eq!(GridError::Empty, Board::try_from(vec![vec![]]).unwrap_err());
+        assert_eq!(
+            GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            },
+            Board::try_from(vec![vec![true, true], vec![true]]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_checksum() {
+        let board: Board = ".O.\n..O\nOOO\n".parse().unwrap();
+        assert_eq!(board.checksum(), board.clone().checksum());
+        assert_ne!(board.checksum(), Board::new(3, 3).checksum());
+        assert_ne!(Board::new(4, 6).checksum(), Board::new(6, 4).checksum());
+        assert_eq!(0xe69c_17ec_a7df_696d, Board::new(2, 2).checksum());
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// A flag for stopping a long run from somewhere else, such as another thread or a Ctrl+C handler.
+// Clones share the flag, so one can be given to a game with Game::set_cancel and another kept to
+// cancel it with. A cancelled game stops running at the next generation it checks, leaving the
+// board as it was after the last one finished.
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        CancelHandle::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // Clear the flag so the handle can be used for another run.
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let handle = CancelHandle::new();
+        let shared = handle.clone();
+        assert!(!shared.is_cancelled());
+
+        std::thread::spawn(move || handle.cancel()).join().unwrap();
+        assert!(shared.is_cancelled());
+        shared.reset();
+        assert!(!shared.is_cancelled());
+    }
+}
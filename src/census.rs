@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+
+use crate::component::components;
+use crate::{analyze, Behavior, Board, Game, Pattern, Point, Topology};
+
+// The fewest generations an object is run on its own to see what it is.
+const MIN_RUN: usize = 30;
+
+// Common objects' codes and names, for printing tallies people can read at a glance.
+const NAMES: &[(&str, &str)] = &[
+    ("xs4_2o$2o", "block"),
+    ("xs6_b2o$o2bo$b2o", "beehive"),
+    ("xs7_b2o$o2bo$obo$bo", "loaf"),
+    ("xs5_2o$obo$bo", "boat"),
+    ("xs6_2o$obo$b2o", "ship"),
+    ("xs4_bo$obo$bo", "tub"),
+    ("xs8_b2o$o2bo$o2bo$b2o", "pond"),
+    ("xp2_3o", "blinker"),
+    ("xp2_2b2o$3bo$o$2o", "beacon"),
+    ("xp2_3o$b3o", "toad"),
+    ("xq4_3o$o$bo", "glider"),
+];
+
+// A tally of the objects found on boards, keyed by a code describing each object's shape.
+//
+// Codes follow apgsearch's prefixes: "xs" and the population for still lives, "xp" and the period
+// for oscillators, "xq" and the period for spaceships, and "zz" for objects that didn't settle on
+// their own. After an underscore comes the object's RLE, in whichever phase and orientation gives
+// the shortest (then alphabetically first) text, so the same object always gets the same code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Census {
+    counts: HashMap<String, usize>,
+}
+
+impl Census {
+    pub fn new() -> Self {
+        Census::default()
+    }
+
+    // Separate the game's current board into objects and add each to the tally. The board should
+    // have settled with the given period, as found by stabilize(), so that each object's phases
+    // can be seen together.
+    //
+    // Objects are the groups of cells that touch, counting every cell live at any point in the
+    // period. Objects close enough to touch get counted together as one. Some objects, like the
+    // aircraft carrier, come in pieces that don't touch, so a group that doesn't settle on its own
+    // is tried together with the groups within two cells of it. On a wrapping board, the board is
+    // first moved so that a row and a column nothing reaches lie along its edges, so objects
+    // sitting across an edge are counted whole rather than in pieces.
+    pub fn add(&mut self, game: &Game, period: usize) {
+        let period = period.max(1);
+        let mut game = game.clone();
+        let mut ever_live = footprint(&game, period);
+        if game.topology() == Topology::Toroidal {
+            let (dx, dy) = seam_shift(&ever_live);
+            if (dx, dy) != (0, 0) {
+                game.translate(dx, dy);
+                ever_live = footprint(&game, period);
+            }
+        }
+
+        let board = game.board();
+        let groups: Vec<Vec<Point>> = components(&ever_live)
+            .into_iter()
+            .map(|component| {
+                component
+                    .cells
+                    .into_iter()
+                    .filter(|&point| board[point.into()])
+                    .collect::<Vec<Point>>()
+            })
+            .filter(|cells| !cells.is_empty())
+            .collect();
+        let mut counted = vec![false; groups.len()];
+        for index in 0..groups.len() {
+            if counted[index] {
+                continue;
+            }
+            counted[index] = true;
+            let mut code = classify(&game, &groups[index], period);
+            if code.starts_with("zz") {
+                let near: Vec<usize> = (0..groups.len())
+                    .filter(|&other| !counted[other] && near(&groups[index], &groups[other]))
+                    .collect();
+                if !near.is_empty() {
+                    let mut cells = groups[index].clone();
+                    for &other in &near {
+                        cells.extend(&groups[other]);
+                    }
+                    let joined = classify(&game, &cells, period);
+                    if !joined.starts_with("zz") {
+                        for other in near {
+                            counted[other] = true;
+                        }
+                        code = joined;
+                    }
+                }
+            }
+            *self.counts.entry(code).or_default() += 1;
+        }
+    }
+
+    // Add another census's tallies to this one.
+    pub fn merge(&mut self, other: &Census) {
+        for (code, count) in &other.counts {
+            *self.counts.entry(code.clone()).or_default() += count;
+        }
+    }
+
+    pub fn count(&self, code: &str) -> usize {
+        self.counts.get(code).copied().unwrap_or_default()
+    }
+
+    // The number of objects counted.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // Every code with its count, the most common first.
+    pub fn sorted(&self) -> Vec<(&str, usize)> {
+        let mut counts: Vec<(&str, usize)> = self
+            .counts
+            .iter()
+            .map(|(code, &count)| (code.as_str(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+}
+
+// The common name of the object with the given code, if it has one.
+pub fn object_name(code: &str) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|&&(known, _)| known == code)
+        .map(|&(_, name)| name)
+}
+
+// Every cell live on the board at some point in the next `period` generations.
+fn footprint(game: &Game, period: usize) -> Board {
+    let mut ever_live = game.board().clone();
+    let mut future = game.clone();
+    for _ in 1..period {
+        future.iterate();
+        for point in future.board().live_cells() {
+            ever_live.set(point.x, point.y, true);
+        }
+    }
+    ever_live
+}
+
+// Whether any cell of one group is within two cells of one in the other, across or diagonally.
+fn near(cells: &[Point], others: &[Point]) -> bool {
+    cells.iter().any(|cell| {
+        others
+            .iter()
+            .any(|other| cell.x.abs_diff(other.x) <= 2 && cell.y.abs_diff(other.y) <= 2)
+    })
+}
+
+// How far to move a wrapping board so that an empty column and an empty row lie along its left
+// and top edges, leaving nothing touching across the seams. Either stays put if the board has no
+// empty column or row.
+fn seam_shift(board: &Board) -> (isize, isize) {
+    let column = (0..board.x_size())
+        .find(|&x| (0..board.y_size()).all(|y| !board[(x, y)]))
+        .unwrap_or_default();
+    let row = (0..board.y_size())
+        .find(|&y| (0..board.x_size()).all(|x| !board[(x, y)]))
+        .unwrap_or_default();
+    (-(column as isize), -(row as isize))
+}
+
+// The code for an object, found by running it on its own, with the same rule as the game. Objects
+// get at least MIN_RUN generations to repeat, since spaceships like gliders keep the board's
+// population steady without repeating within its period.
+//
+// Only an object that repeats from the phase it was found in is a still life, oscillator, or
+// spaceship, and its code comes from the phases of that cycle. One that has to change first to
+// settle, like a piece of a larger object or a pre-block, or that dies out, gets a "zz" code
+// from the cells it was found with.
+fn classify(game: &Game, cells: &[Point], period: usize) -> String {
+    let run = period.max(MIN_RUN) + 1;
+    let margin = run + 2;
+    let shape = normalize(cells.iter().copied());
+    let width = shape
+        .iter()
+        .map(|point| point.x + 1)
+        .max()
+        .unwrap_or_default();
+    let height = shape
+        .iter()
+        .map(|point| point.y + 1)
+        .max()
+        .unwrap_or_default();
+    let mut board = Board::new(width + 2 * margin, height + 2 * margin);
+    for point in &shape {
+        board.set(point.x + margin, point.y + margin, true);
+    }
+
+    let mut isolated = game.with_board(board);
+    let mut phase = isolated.clone();
+    let analysis = analyze(&mut isolated, run);
+    let (prefix, phases) = match analysis.behavior {
+        _ if analysis.settled_at != 0 => ("zz".to_string(), 1),
+        Behavior::StillLife => (format!("xs{}", shape.len()), 1),
+        Behavior::Oscillator { period } => (format!("xp{period}"), period),
+        Behavior::Spaceship { period, .. } => (format!("xq{period}"), period),
+        Behavior::Extinct | Behavior::Unsettled => ("zz".to_string(), 1),
+    };
+
+    let mut best: Option<String> = None;
+    for _ in 0..phases {
+        let cells = normalize(phase.board().live_cells());
+        for orientation in 0..8 {
+            let rle = rle_data(&orient(&cells, orientation));
+            if best
+                .as_ref()
+                .is_none_or(|best| (rle.len(), &rle) < (best.len(), best))
+            {
+                best = Some(rle);
+            }
+        }
+        phase.iterate();
+    }
+    format!("{prefix}_{}", best.unwrap_or_default())
+}
+
+// Move the points so the top-left of their bounding box is at the origin.
+fn normalize<I: IntoIterator<Item = Point>>(points: I) -> Vec<Point> {
+    let points: Vec<Point> = points.into_iter().collect();
+    let left = points.iter().map(|point| point.x).min().unwrap_or_default();
+    let top = points.iter().map(|point| point.y).min().unwrap_or_default();
+    points
+        .into_iter()
+        .map(|point| Point::new(point.x - left, point.y - top))
+        .collect()
+}
+
+// One of the eight rotations and reflections of the points, numbered 0 to 7, moved back to the
+// origin.
+fn orient(points: &[Point], orientation: usize) -> Vec<Point> {
+    let extent = points
+        .iter()
+        .map(|point| point.x.max(point.y))
+        .max()
+        .unwrap_or_default();
+    let oriented = points.iter().map(|&Point { x, y }| {
+        let (x, y) = if orientation & 4 != 0 { (y, x) } else { (x, y) };
+        let x = if orientation & 1 != 0 { extent - x } else { x };
+        let y = if orientation & 2 != 0 { extent - y } else { y };
+        Point::new(x, y)
+    });
+    normalize(oriented)
+}
+
+// The cell runs of RLE for the points, on one line and without the closing '!'.
+fn rle_data(points: &[Point]) -> String {
+    let width = points
+        .iter()
+        .map(|point| point.x + 1)
+        .max()
+        .unwrap_or_default();
+    let height = points
+        .iter()
+        .map(|point| point.y + 1)
+        .max()
+        .unwrap_or_default();
+    let mut pattern = Pattern::new(width, height);
+    for point in points {
+        pattern.set(point.x, point.y, 1);
+    }
+    let text = pattern.to_string();
+    let data = text.split_once('\n').map_or("", |(_, data)| data);
+    data.replace('\n', "").trim_end_matches('!').to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LifeLike;
+
+    fn census_of(board: &str, period: usize) -> Census {
+        let game = Game::from(board.parse::<Board>().unwrap());
+        let mut census = Census::new();
+        census.add(&game, period);
+        census
+    }
+
+    #[test]
+    fn test_still_lifes() {
+        let census = census_of(
+            "..........\n\
+             .OO....O..\n\
+             .OO...O.O.\n\
+             .......O..\n\
+             ..........\n\
+             ...OO.....\n\
+             ...OO.....\n\
+             ..........\n",
+            1,
+        );
+        assert_eq!(3, census.total());
+        assert_eq!(2, census.count("xs4_2o$2o"));
+        assert_eq!(1, census.count("xs4_bo$obo$bo"));
+        assert_eq!(
+            vec![("xs4_2o$2o", 2), ("xs4_bo$obo$bo", 1)],
+            census.sorted()
+        );
+        assert_eq!(Some("block"), object_name("xs4_2o$2o"));
+    }
+
+    #[test]
+    fn test_every_phase_and_orientation_matches() {
+        let vertical = census_of(".....\n..O..\n..O..\n..O..\n.....\n", 2);
+        let horizontal = census_of(".....\n.....\n.OOO.\n.....\n.....\n", 2);
+        assert_eq!(vertical, horizontal);
+        assert_eq!(1, vertical.count("xp2_3o"));
+
+        let ship = census_of("......\n.OO...\n.O.O..\n..OO..\n......\n", 1);
+        let flipped = census_of("......\n..OO..\n.O.O..\n.OO...\n......\n", 1);
+        assert_eq!(ship, flipped);
+    }
+
+    #[test]
+    fn test_named_objects() {
+        let glider = census_of(
+            "........\n..O.....\n...O....\n.OOO....\n........\n........\n",
+            4,
+        );
+        assert_eq!(vec![("xq4_3o$o$bo", 1)], glider.sorted());
+        let glider = census_of(
+            "........\n..O.....\n...O....\n.OOO....\n........\n........\n",
+            1,
+        );
+        assert_eq!(vec![("xq4_3o$o$bo", 1)], glider.sorted());
+
+        for (board, period, name) in [
+            ("......\n..OO..\n.O..O.\n..OO..\n......\n", 1, "beehive"),
+            (
+                ".......\n..OO...\n.O..O..\n..O.O..\n...O...\n.......\n",
+                1,
+                "loaf",
+            ),
+            ("......\n.OO...\n.O.O..\n..O...\n......\n", 1, "boat"),
+            ("......\n.OO...\n.O.O..\n..OO..\n......\n", 1, "ship"),
+            (
+                "......\n..OO..\n.O..O.\n.O..O.\n..OO..\n......\n",
+                1,
+                "pond",
+            ),
+            (
+                "......\n.OO...\n.O....\n....O.\n...OO.\n......\n",
+                2,
+                "beacon",
+            ),
+            (
+                "......\n......\n..OOO.\n.OOO..\n......\n......\n",
+                2,
+                "toad",
+            ),
+        ] {
+            let census = census_of(board, period);
+            let (code, count) = census.sorted()[0];
+            assert_eq!((Some(name), 1), (object_name(code), count), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_merge_and_rule() {
+        let mut census = census_of(".....\n.OO..\n.OO..\n.....\n", 1);
+        census.merge(&census.clone());
+        assert_eq!(2, census.count("xs4_2o$2o"));
+
+        // Under B36/S125 (2x2), a lone block is still, but a blinker dies.
+        let mut game = Game::from(".....\n.OOO.\n.....\n".parse::<Board>().unwrap());
+        game.set_rule(LifeLike::new(&[3, 6], &[1, 2, 5]));
+        let mut census = Census::new();
+        census.add(&game, 1);
+        assert_eq!(vec![("zz_3o", 1)], census.sorted());
+    }
+
+    #[test]
+    fn test_unsettled_objects() {
+        // A pre-block turns into a block, but isn't one yet.
+        let census = census_of(".....\n.OO..\n.O...\n.....\n", 1);
+        assert_eq!(vec![("zz_2o$o", 1)], census.sorted());
+    }
+
+    #[test]
+    fn test_objects_in_pieces() {
+        // An aircraft carrier's two halves don't touch, and neither settles on its own.
+        let census = census_of(".......\n.OO....\n.O..O..\n...OO..\n.......\n", 1);
+        assert_eq!(vec![("xs6_2b2o$o2bo$2o", 1)], census.sorted());
+    }
+
+    #[test]
+    fn test_objects_across_the_seam() {
+        // A glider crossing the corner of a wrapping board, and a block split across its sides.
+        let board = "O.............OO\n\
+                     ................\n\
+                     ................\n\
+                     ................\n\
+                     ................\n\
+                     ................\n\
+                     O..............O\n\
+                     O..............O\n\
+                     ................\n\
+                     ................\n\
+                     ................\n\
+                     ................\n\
+                     ...............O\n\
+                     O...............\n";
+        for period in [1, 4] {
+            let census = census_of(board, period);
+            assert_eq!(
+                vec![("xq4_3o$o$bo", 1), ("xs4_2o$2o", 1)],
+                census.sorted(),
+                "{period}"
+            );
+        }
+    }
+}
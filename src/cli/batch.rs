@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use gol::{analyze, Analysis, LifeLike};
+
+use super::interrupt;
+use super::progress::Progress;
+use super::{json_string, load_game, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol batch [--rule RULE] [--generations N] [--margin N] [--format csv|json] [--quiet] PATH...
+
+Runs every pattern file given, or every .rle, .cells, .lif, and .life file found in the given
+directories, until it settles or the generation limit is reached, then prints one row per pattern
+with its final population, whether it settled, and its period. Each pattern runs its own rule
+unless --rule is given.";
+
+// One pattern's results.
+struct Row {
+    path: PathBuf,
+    rule: LifeLike,
+    analysis: Analysis,
+}
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations = args.option("generations")?.unwrap_or(1000);
+    let margin = args.option("margin")?.unwrap_or(32);
+    let format = args.option("format")?.unwrap_or_else(|| "csv".to_string());
+    if format != "csv" && format != "json" {
+        return Err(format!("unknown --format {format:?}, expected csv or json"));
+    }
+    let quiet = args.flag("quiet");
+    let paths = args.finish()?;
+    if paths.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    let mut files = Vec::new();
+    for path in paths.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            files.extend(pattern_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+
+    let cancel = interrupt::handle();
+    let total = files.len();
+    let mut rows = Vec::new();
+    let mut failed = 0;
+    let mut progress = Progress::new(total, "patterns", quiet);
+    for path in files {
+        match load_game(&path, rule, margin, config) {
+            Ok((mut game, rule)) => {
+                game.set_cancel(Some(cancel.clone()));
+                let analysis = analyze(&mut game, generations);
+                // A pattern cut off partway through hasn't really been run, so it's left out.
+                if cancel.is_cancelled() {
+                    break;
+                }
+                rows.push(Row {
+                    analysis,
+                    path,
+                    rule,
+                });
+            }
+            Err(err) => {
+                eprintln!("gol: {err}");
+                failed += 1;
+            }
+        }
+        progress.tick();
+    }
+    progress.finish();
+
+    if format == "json" {
+        print_json(&rows);
+    } else {
+        print_csv(&rows);
+    }
+
+    if cancel.is_cancelled() {
+        let done = rows.len() + failed;
+        return Err(format!("interrupted after {done} of {total} patterns"));
+    }
+    if failed > 0 {
+        return Err(format!("{failed} pattern(s) couldn't be loaded"));
+    }
+    Ok(())
+}
+
+// The pattern files directly inside a directory, sorted so reports come out in a stable order.
+fn pattern_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| {
+                ["rle", "cells", "lif", "life"]
+                    .iter()
+                    .any(|pattern| ext.eq_ignore_ascii_case(pattern))
+            })
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn print_csv(rows: &[Row]) {
+    println!("file,rule,generations,population,settled,period");
+    for row in rows {
+        let file = row.path.display().to_string();
+        let file = if file.contains([',', '"', '\n']) {
+            format!("\"{}\"", file.replace('"', "\"\""))
+        } else {
+            file
+        };
+        let period = row.analysis.behavior.period();
+        println!(
+            "{file},{},{},{},{},{}",
+            row.rule,
+            row.analysis.generations,
+            row.analysis.population,
+            row.analysis.behavior.is_settled(),
+            period.map(|period| period.to_string()).unwrap_or_default()
+        );
+    }
+}
+
+fn print_json(rows: &[Row]) {
+    println!("[");
+    for (index, row) in rows.iter().enumerate() {
+        let period = row.analysis.behavior.period();
+        println!(
+            "  {{\"file\": {}, \"rule\": \"{}\", \"generations\": {}, \"population\": {}, \
+             \"settled\": {}, \"period\": {}}}{}",
+            json_string(&row.path.display().to_string()),
+            row.rule,
+            row.analysis.generations,
+            row.analysis.population,
+            row.analysis.behavior.is_settled(),
+            period.map_or("null".to_string(), |period| period.to_string()),
+            if index + 1 < rows.len() { "," } else { "" }
+        );
+    }
+    println!("]");
+}
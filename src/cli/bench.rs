@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use gol::{Backend, Benchmark, Game};
+
+use super::Args;
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol bench [--gens N] [--backend NAME] [BENCHMARK...]
+
+Runs each standard benchmark pattern for --gens generations on every backend, or just the one
+given, and prints how long each took. Every backend has to end on the same board as the first,
+or the run stops with an error. The benchmarks are r-pentomino, acorn, rabbits, diamond,
+glider-gun, and switch-engine, and all of them run unless some are named. --threads 1 times
+them on one thread.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let generations = args.option("gens")?.unwrap_or(1000);
+    let backends = match args.option::<Backend>("backend")? {
+        Some(backend) => vec![backend],
+        None => Backend::available(),
+    };
+    let names = args.finish()?;
+    let benchmarks = if names.is_empty() {
+        Benchmark::ALL.to_vec()
+    } else {
+        names
+            .iter()
+            .map(|name| name.parse().map_err(|err| format!("{err}\n\n{USAGE}")))
+            .collect::<Result<_, _>>()?
+    };
+
+    // Any game resolves the thread count the same way.
+    let mut game = Game::new(1, 1);
+    game.set_threads(config.threads);
+    println!(
+        "{generations} generations each, on up to {} threads",
+        game.threads()
+    );
+    for benchmark in benchmarks {
+        // The board every backend has to agree on, from whichever ran first.
+        let mut expected = None;
+        for &backend in &backends {
+            let (elapsed, checksum) = time(benchmark, backend, config.threads, generations);
+            println!(
+                "{:<14} {:<15} {:>9.1} ms {:>10.0} gens/s",
+                benchmark.name(),
+                backend.name(),
+                elapsed.as_secs_f64() * 1000.0,
+                rate(generations, elapsed)
+            );
+            match expected {
+                None => expected = Some((backend, checksum)),
+                Some((first, expected)) if expected != checksum => {
+                    return Err(format!(
+                        "{benchmark}: {backend} ended on checksum {checksum:016x}, but {first} \
+                         ended on {expected:016x}"
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+// Run a benchmark on a backend with up to the given number of threads, returning how long the
+// generations took and the checksum of the board they ended on.
+fn time(
+    benchmark: Benchmark,
+    backend: Backend,
+    threads: usize,
+    generations: usize,
+) -> (Duration, u64) {
+    let mut game = benchmark.game();
+    game.set_backend(backend);
+    game.set_threads(threads);
+    let start = Instant::now();
+    for _ in 0..generations {
+        game.iterate();
+    }
+    (start.elapsed(), game.board().checksum())
+}
+
+// Generations a second, or 0 for a run too quick to time.
+fn rate(generations: usize, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        generations as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backends_agree() {
+        let checksums: Vec<u64> = Backend::available()
+            .into_iter()
+            .map(|backend| time(Benchmark::Acorn, backend, 2, 20).1)
+            .collect();
+        assert!(checksums.windows(2).all(|pair| pair[0] == pair[1]));
+        assert_eq!(0.0, rate(10, Duration::ZERO));
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use gol::{object_name, stabilization_with, Census, LifeLike};
+
+use super::population::{self, Population};
+use super::telemetry::{self, Telemetry};
+use super::{json_string, load_game, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol census [--rule RULE] [--gens N] [--margin N] [--max-period N] [--format table|json]
+                  [--telemetry FILE] [--population FILE] PATTERN
+
+Runs the pattern until its population settles, then counts the still lifes, oscillators, and
+spaceships it left behind.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations = args.option("gens")?.unwrap_or(5000);
+    let margin = args.option("margin")?.unwrap_or(64);
+    let max_period = args.option("max-period")?.unwrap_or(30);
+    let format = args
+        .option("format")?
+        .unwrap_or_else(|| "table".to_string());
+    if format != "table" && format != "json" {
+        return Err(format!(
+            "unknown --format {format:?}, expected table or json"
+        ));
+    }
+    let mut telemetry = Telemetry::open(&mut args)?;
+    let mut population = Population::open(&mut args)?;
+    let paths = args.finish()?;
+    let [path] = paths.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+
+    let (mut game, rule) = load_game(Path::new(path), rule, margin, config)?;
+    let stabilization = stabilization_with(&mut game, generations, max_period, |game| {
+        telemetry::record(&mut telemetry, game);
+        population::record(&mut population, game);
+    });
+    telemetry::finish(telemetry)?;
+    population::finish(population)?;
+    let Some(period) = stabilization.map(|stabilization| stabilization.period) else {
+        return Err(format!("{path}: unsettled after {generations} generations"));
+    };
+    let mut census = Census::new();
+    census.add(&game, period);
+
+    if format == "json" {
+        println!("{}", json(&census, rule));
+    } else {
+        println!("{} objects under {rule}", census.total());
+        for (code, count) in census.sorted() {
+            match object_name(code) {
+                Some(name) => println!("{count:>8}  {code} ({name})"),
+                None => println!("{count:>8}  {code}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn json(census: &Census, rule: LifeLike) -> String {
+    let objects: Vec<String> = census
+        .sorted()
+        .into_iter()
+        .map(|(code, count)| {
+            let name = object_name(code).map_or("null".to_string(), json_string);
+            format!(
+                "{{\"code\": {}, \"name\": {name}, \"count\": {count}}}",
+                json_string(code)
+            )
+        })
+        .collect();
+    format!(
+        "{{\"rule\": \"{rule}\", \"total\": {}, \"objects\": [{}]}}",
+        census.total(),
+        objects.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use gol::Game;
+
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        // Two blocks, far enough apart to count separately.
+        let mut game = Game::new(12, 12);
+        game.set([
+            (1, 1),
+            (1, 2),
+            (2, 1),
+            (2, 2),
+            (7, 7),
+            (7, 8),
+            (8, 7),
+            (8, 8),
+        ])
+        .unwrap();
+        let mut census = Census::new();
+        census.add(&game, 1);
+        assert_eq!(
+            "{\"rule\": \"B3/S23\", \"total\": 2, \"objects\": \
+             [{\"code\": \"xs4_2o$2o\", \"name\": \"block\", \"count\": 2}]}",
+            json(&census, LifeLike::CONWAY)
+        );
+    }
+}
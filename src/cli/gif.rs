@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use gol::{GifWriter, LifeLike};
+
+use super::population::{self, Population};
+use super::progress::Progress;
+use super::telemetry::{self, Telemetry};
+use super::{load_game, render, Args};
+use crate::config::{Config, Theme};
+
+const USAGE: &str = "\
+usage: gol gif [--gens N] [--fps N] [--scale N] [--margin N] [--rule RULE] [--theme light|dark]
+               [--quiet] [--telemetry FILE] [--population FILE] PATTERN -o OUTPUT.gif
+
+Runs the pattern for --gens generations and saves every one of them as a frame of a looping
+animation, with --margin empty cells around the pattern for it to grow into.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations: usize = args.option("gens")?.unwrap_or(100);
+    let fps: u16 = args.option("fps")?.unwrap_or(10);
+    let scale = args.option("scale")?.unwrap_or(4);
+    let margin = args.option("margin")?.unwrap_or(8);
+    let theme: Theme = args.option("theme")?.unwrap_or(config.theme);
+    let output: Option<String> = args.option("output")?;
+    let quiet = args.flag("quiet");
+    let mut telemetry = Telemetry::open(&mut args)?;
+    let mut population = Population::open(&mut args)?;
+    let paths = args.finish()?;
+    let (input, output) = match (paths.as_slice(), output) {
+        ([input], Some(output)) => (input, output),
+        ([input, o, output] | [o, output, input], None) if o == "-o" => (input, output.clone()),
+        _ => return Err(USAGE.to_string()),
+    };
+    if fps == 0 || scale == 0 {
+        return Err("--fps and --scale must be at least 1".to_string());
+    }
+
+    let (mut game, _) = load_game(Path::new(input), rule, margin, config)?;
+    let file = File::create(&output).map_err(|err| format!("{output}: {err}"))?;
+    let mut writer = GifWriter::new(BufWriter::new(file));
+    writer.set_scale(scale);
+    // GIF delays are in hundredths of a second.
+    writer.set_delay((100 + fps / 2) / fps);
+    let (dead, live) = render::colors(theme);
+    writer.set_colors(dead, live);
+
+    let mut progress = Progress::new(generations + 1, "frames", quiet);
+    let written = (0..=generations)
+        .try_for_each(|generation| {
+            if generation > 0 {
+                game.iterate();
+            }
+            progress.tick();
+            telemetry::record(&mut telemetry, &game);
+            population::record(&mut population, &game);
+            writer.write(game.board())
+        })
+        .and_then(|_| writer.finish().map(|_| ()));
+    progress.finish();
+    written.map_err(|err| format!("{output}: {err}"))?;
+    telemetry::finish(telemetry)?;
+    population::finish(population)
+}
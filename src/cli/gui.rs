@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use gol::{LifeLike, Pattern};
+
+use super::Args;
+use crate::config::Config;
+
+// How the GUI should start, from the options it was launched with.
+#[derive(Default)]
+pub struct Launch {
+    // Skip asking for a board size, since one was given.
+    pub skip_setup: bool,
+    // A pattern to start with, along with the rule it should run if no --rule was given.
+    pub pattern: Option<(Pattern, Option<LifeLike>)>,
+    // Start the simulation running instead of paused.
+    pub run: bool,
+    // Start in screensaver mode, running random soups one after another.
+    pub screensaver: bool,
+}
+
+// Read the GUI's options: --size WxH, --rule, and --threads override the config file's settings,
+// --open loads a pattern onto the board, --run starts the simulation straight away, and
+// --screensaver starts running random soups, a new one each time the last settles.
+pub fn parse(args: &[String], config: &mut Config) -> Result<Launch, String> {
+    let mut args = Args::parse(args, &["run", "screensaver"])?;
+    let size = args.option::<String>("size")?;
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let threads: Option<usize> = args.option("threads")?;
+    let open = args.option::<String>("open")?;
+    let run = args.flag("run");
+    let screensaver = args.flag("screensaver");
+    let positional = args.finish()?;
+    if let Some(arg) = positional.first() {
+        return Err(format!("unexpected argument {arg:?}"));
+    }
+
+    let mut launch = Launch {
+        run,
+        screensaver,
+        skip_setup: screensaver,
+        ..Launch::default()
+    };
+    if let Some(size) = size {
+        (config.width, config.height) =
+            parse_size(&size).ok_or_else(|| format!("invalid --size {size:?}, expected WxH"))?;
+        launch.skip_setup = true;
+    }
+    if let Some(rule) = rule {
+        config.rule = rule;
+    }
+    if let Some(threads) = threads {
+        config.threads = threads;
+    }
+    if let Some(path) = open {
+        let path = Path::new(&path);
+        let pattern = Pattern::load(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let pattern_rule = match (rule, pattern.rule()) {
+            (None, Some(pattern_rule)) => Some(
+                pattern_rule
+                    .parse()
+                    .map_err(|err| format!("{}: {err}", path.display()))?,
+            ),
+            _ => None,
+        };
+        launch.pattern = Some((pattern, pattern_rule));
+        launch.skip_setup = true;
+    }
+    Ok(launch)
+}
+
+// Parse a board size like "120x80".
+pub(super) fn parse_size(size: &str) -> Option<(usize, usize)> {
+    let (width, height) = size.split_once(['x', 'X'])?;
+    let (width, height) = (width.parse().ok()?, height.parse().ok()?);
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut config = Config::default();
+        let launch = parse(
+            &args(&["--size", "120x80", "--rule=B36/S23", "--run"]),
+            &mut config,
+        )
+        .unwrap();
+        assert!(launch.skip_setup && launch.run && launch.pattern.is_none());
+        assert_eq!((120, 80), (config.width, config.height));
+        assert_eq!(LifeLike::new(&[3, 6], &[2, 3]), config.rule);
+
+        let mut config = Config::default();
+        let launch = parse(&args(&["--run"]), &mut config).unwrap();
+        assert!(launch.run && !launch.skip_setup);
+        assert_eq!((10, 10), (config.width, config.height));
+
+        let launch = parse(&args(&["--screensaver"]), &mut Config::default()).unwrap();
+        assert!(launch.screensaver && launch.skip_setup && !launch.run);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for invalid in [
+            &["--size", "120"][..],
+            &["--size", "0x5"],
+            &["--rule", "B9"],
+            &["--open"],
+            &["--speed", "3"],
+            &["pattern.rle"],
+        ] {
+            assert!(
+                parse(&args(invalid), &mut Config::default()).is_err(),
+                "{invalid:?}"
+            );
+        }
+        assert_eq!(None, parse_size("12x"));
+        assert_eq!(Some((3, 4)), parse_size("3X4"));
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use gol::{Blocks, Game, LifeLike, Pattern, Rect, Soup, Text};
+
+use super::{gui, interrupt, Args};
+use crate::config::Config;
+
+// Clear the terminal and put the cursor back in the top-left corner.
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+const USAGE: &str = "\
+usage: gol --headless [--size WxH] [--pattern PATTERN] [--rule RULE] [--iters N] [--fps F]
+                      [--density F] [--seed N] [--ascii]
+
+Runs the game in the terminal instead of a window, drawing each generation with two rows of cells
+to a line of half blocks, or with # and . given --ascii. The board starts with a pattern file or
+one of the built-in patterns, such as glider or pulsar, in the middle, or else a random soup of
+the given density and seed. It's --size cells, defaulting to the configured size, and grows to
+fit a pattern too big for it.
+
+It runs for --iters generations, or until Ctrl+C if that isn't given, showing --fps generations a
+second, 10 by default, or as many as it can with 0. The screen is only cleared between
+generations when the output is a terminal, so piping frames into a file or another program works
+too.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let size = args.option::<String>("size")?;
+    let pattern = args.option::<String>("pattern")?;
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let iters: Option<usize> = args.option("iters")?;
+    let fps: f64 = args.option("fps")?.unwrap_or(10.0);
+    let density = args.option("density")?.unwrap_or(0.3);
+    let seed = args.option("seed")?.unwrap_or(0);
+    let ascii = args.flag("ascii");
+    if !args.finish()?.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if !(fps >= 0.0 && fps.is_finite()) {
+        return Err(format!("invalid --fps {fps}, expected a rate of 0 or more"));
+    }
+    let (x_size, y_size) = match size {
+        Some(size) => gui::parse_size(&size)
+            .ok_or_else(|| format!("invalid --size {size:?}, expected WxH"))?,
+        None => (config.width, config.height),
+    };
+
+    let (mut game, rule) = match pattern {
+        Some(name) => {
+            let mut pattern = load(&name)?;
+            let pattern_rule = pattern
+                .rule()
+                .map(|rule| rule.parse().map_err(|err| format!("{name}: {err}")))
+                .transpose()?;
+            let game = Game::seeded(&mut pattern, x_size, y_size)
+                .map_err(|err| format!("{name}: {err}"))?;
+            (game, rule.or(pattern_rule).unwrap_or(config.rule))
+        }
+        None => {
+            let game = Game::seeded(&mut Soup::new(density, seed), x_size, y_size)
+                .map_err(|err| err.to_string())?;
+            (game, rule.unwrap_or(config.rule))
+        }
+    };
+    game.set_rule(rule);
+    game.set_backend(config.backend);
+    game.set_threads(config.threads);
+    game.set_topology(config.topology());
+
+    let cancel = interrupt::handle();
+    let clear = io::stdout().is_terminal();
+    let period = (fps > 0.0).then(|| Duration::from_secs_f64(1.0 / fps));
+    let mut next = Instant::now();
+    for generation in 0.. {
+        let frame = frame(&game, ascii, clear);
+        io::stdout()
+            .lock()
+            .write_all(frame.as_bytes())
+            .map_err(|err| err.to_string())?;
+        if iters.is_some_and(|iters| generation >= iters) || cancel.is_cancelled() {
+            break;
+        }
+        if let Some(period) = period {
+            next += period;
+            thread::sleep(next.saturating_duration_since(Instant::now()));
+        }
+        game.iterate();
+    }
+    Ok(())
+}
+
+// A pattern file, or one of the built-in patterns if there's no file by that name.
+fn load(name: &str) -> Result<Pattern, String> {
+    let path = Path::new(name);
+    match Pattern::named(name) {
+        Some(pattern) if !path.exists() => Ok(pattern),
+        _ => Pattern::load(path).map_err(|err| format!("{name}: {err}")),
+    }
+}
+
+// One generation as text, with the generation and population under the board, starting with the
+// escape codes to clear the terminal if `clear` is set.
+fn frame(game: &Game, ascii: bool, clear: bool) -> String {
+    let board = game.board();
+    let region = Rect::new(0, 0, board.x_size(), board.y_size());
+    let lines: Vec<String> = if ascii {
+        let mut text = Text::new('#', '.');
+        gol::render(board, region, &mut text);
+        text.lines().collect()
+    } else {
+        let mut blocks = Blocks::new();
+        gol::render(board, region, &mut blocks);
+        blocks.lines().collect()
+    };
+
+    let mut frame = String::new();
+    if clear {
+        frame.push_str(CLEAR);
+    }
+    for line in lines {
+        frame.push_str(&line);
+        frame.push('\n');
+    }
+    let stats = game.stats();
+    let _ = writeln!(
+        frame,
+        "generation {}, population {}",
+        stats.generation,
+        board.population()
+    );
+    if !clear {
+        frame.push('\n');
+    }
+    frame
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frame() {
+        // A blinker, standing up and then lying down.
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        assert_eq!(
+            ".....\n..#..\n..#..\n..#..\n.....\ngeneration 0, population 3\n\n",
+            frame(&game, true, false)
+        );
+        game.iterate();
+        assert_eq!(
+            format!(
+                "{CLEAR}     \n \u{2580}\u{2580}\u{2580} \n     \ngeneration 1, population 3\n"
+            ),
+            frame(&game, false, true)
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        assert_eq!(Some("glider"), load("glider").unwrap().name());
+        assert!(load("no-such-pattern.rle").is_err());
+    }
+}
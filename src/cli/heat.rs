@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use gol::{HeatMap, LifeLike};
+
+use super::progress::Progress;
+use super::{load_game, render, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol heat [--gens N] [--scale N] [--margin N] [--rule RULE] [--quiet] PATTERN
+                [--births FILE.png] [--deaths FILE.png]
+
+Runs the pattern for --gens generations, counting how many times each cell is born and dies, and
+draws the counts as false-color PNGs: black for cells that never changed, through purple, red,
+orange and yellow to white for the busiest. Colors follow the logarithm of the count, so quieter
+areas still show up next to a busy one. At least one of --births and --deaths is needed.";
+
+// The false-color scale, from the fewest changes to the most.
+const GRADIENT: [[u8; 3]; 6] = [
+    [0x00, 0x00, 0x00],
+    [0x4a, 0x10, 0x8c],
+    [0xc8, 0x1e, 0x3a],
+    [0xf5, 0x7f, 0x17],
+    [0xfb, 0xe0, 0x4a],
+    [0xff, 0xff, 0xff],
+];
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations: usize = args.option("gens")?.unwrap_or(100);
+    let scale: usize = args.option("scale")?.unwrap_or(4);
+    let margin = args.option("margin")?.unwrap_or(8);
+    let births: Option<String> = args.option("births")?;
+    let deaths: Option<String> = args.option("deaths")?;
+    let quiet = args.flag("quiet");
+    let paths = args.finish()?;
+    let input = match paths.as_slice() {
+        [input] if births.is_some() || deaths.is_some() => input,
+        _ => return Err(USAGE.to_string()),
+    };
+    if scale == 0 {
+        return Err("--scale must be at least 1".to_string());
+    }
+
+    let (mut game, _) = load_game(Path::new(input), rule, margin, config)?;
+    let mut heat = HeatMap::new(game.x_size(), game.y_size());
+    let mut progress = Progress::new(generations, "generations", quiet);
+    for _ in 0..generations {
+        let before = game.snapshot();
+        game.iterate();
+        if !heat.record(&before, game.board()) {
+            progress.finish();
+            return Err(format!(
+                "{input}: the board grew after {} generations; heat maps need a fixed size \
+                 boundary",
+                heat.generations()
+            ));
+        }
+        progress.tick();
+    }
+    progress.finish();
+
+    for (output, is_births) in [(births, true), (deaths, false)] {
+        let Some(output) = output else { continue };
+        let pixels = if is_births {
+            let max = heat.max_births();
+            draw(&heat, scale, |x, y| color(heat.births(x, y), max))
+        } else {
+            let max = heat.max_deaths();
+            draw(&heat, scale, |x, y| color(heat.deaths(x, y), max))
+        };
+        let (width, height) = (heat.x_size() * scale, heat.y_size() * scale);
+        render::write_png(Path::new(&output), width, height, &pixels)
+            .map_err(|err| format!("{output}: {err}"))?;
+    }
+    Ok(())
+}
+
+// The color for a count, out of the most any cell has.
+fn color(count: u32, max: u32) -> [u8; 3] {
+    if count == 0 {
+        return GRADIENT[0];
+    }
+    let level = (count as f64).ln_1p() / (max as f64).ln_1p();
+    let position = level * (GRADIENT.len() - 1) as f64;
+    let index = (position.floor() as usize).min(GRADIENT.len() - 2);
+    let fraction = position - index as f64;
+    let (low, high) = (GRADIENT[index], GRADIENT[index + 1]);
+    std::array::from_fn(|channel| {
+        let (low, high) = (low[channel] as f64, high[channel] as f64);
+        (low + (high - low) * fraction).round() as u8
+    })
+}
+
+// RGB pixels for the heat map, row by row, each cell a square `scale` pixels across.
+fn draw(heat: &HeatMap, scale: usize, color: impl Fn(usize, usize) -> [u8; 3]) -> Vec<u8> {
+    let width = heat.x_size() * scale;
+    let mut pixels = Vec::with_capacity(width * heat.y_size() * scale * 3);
+    for y in 0..heat.y_size() {
+        let row: Vec<u8> = (0..heat.x_size())
+            .flat_map(|x| color(x, y).repeat(scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&row);
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_color() {
+        assert_eq!(GRADIENT[0], color(0, 10));
+        assert_eq!(GRADIENT[5], color(10, 10));
+        assert_eq!(GRADIENT[5], color(1, 1));
+        // One change out of a busiest 31 is a fifth of the way up the scale, on the purple.
+        assert_eq!(GRADIENT[1], color(1, 31));
+        let middle = color(5, 31);
+        assert!(middle > GRADIENT[2] && middle < GRADIENT[3]);
+    }
+}
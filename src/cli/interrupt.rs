@@ -0,0 +1,41 @@
+use std::sync::{Once, OnceLock};
+
+use gol::CancelHandle;
+
+static HANDLE: OnceLock<CancelHandle> = OnceLock::new();
+static INSTALL: Once = Once::new();
+
+// A handle that's cancelled when Ctrl+C is pressed, so a long command can stop and report what it
+// has so far. The first Ctrl+C puts the default handling back, so a second one ends the process
+// straight away.
+pub fn handle() -> CancelHandle {
+    let handle = HANDLE.get_or_init(CancelHandle::new).clone();
+    INSTALL.call_once(install);
+    handle
+}
+
+#[cfg(unix)]
+fn install() {
+    extern "C" fn interrupted(_: libc::c_int) {
+        if let Some(handle) = HANDLE.get() {
+            handle.cancel();
+        }
+        // SAFETY: signal() is safe to call from a signal handler, and SIG_DFL is a valid handler.
+        unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+        }
+    }
+
+    // SAFETY: the handler only sets an atomic flag and restores the default handler, both of which
+    // are safe to do when interrupting any code.
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            interrupted as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+// Elsewhere Ctrl+C keeps ending the process straight away.
+#[cfg(not(unix))]
+fn install() {}
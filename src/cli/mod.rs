@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use gol::{Game, LifeLike, Pattern};
+
+use crate::config::Config;
+use outcome::{Failure, Outcome, INPUT_ERROR};
+
+mod batch;
+mod bench;
+mod census;
+mod gif;
+pub mod gui;
+mod headless;
+mod heat;
+mod interrupt;
+mod outcome;
+mod period;
+mod population;
+mod progress;
+mod render;
+mod soup;
+mod stabilize;
+mod telemetry;
+mod verify;
+
+const USAGE: &str = "\
+usage: gol [command] [options]
+       gol [--size WxH] [--rule RULE] [--threads N] [--open PATTERN] [--run] [--screensaver]
+       gol --headless [--size WxH] [--pattern PATTERN] [--iters N] [--fps F] [--ascii]
+
+Run without a command to open the GUI, optionally with a board size, a rule, a pattern to load,
+and --run to start it running. --screensaver runs random soups one after another, starting a new
+one whenever the last dies out or settles, until Escape is pressed. Defaults for the rule and
+backend come from gol.toml in the config directory, or the file named by $GOL_CONFIG, and flags
+override them. --headless runs the game in the terminal instead, drawing each generation as text.
+
+commands:
+    batch      run every pattern in a set of files or directories and report how each ends up
+    bench      time the standard benchmark patterns on each backend and check they agree
+    census     count the objects a pattern leaves behind once it settles
+    gif        save a pattern's evolution as an animated GIF
+    heat       draw where a pattern's cells are born and die as false-color PNGs
+    period     say whether a pattern is a still life, oscillator, or spaceship
+    render     draw a pattern as a PNG image
+    soup       run random soups and tally the objects they leave behind
+    stabilize  run a pattern until its population settles and report when and how
+    verify     check a backend's boards match the naive engine's or a recorded run's, bit for bit
+
+Soup options:
+    --soups N  --seed N  --symmetry C1|C2|C4|D2|D2x|D4|D4x|D8  --size N  --density F
+    --margin N  --rule RULE  --generations N  --top N  --quiet
+
+census, gif, period, and stabilize take --telemetry FILE to append a line of JSON per generation
+to FILE, or one every N generations with --telemetry-every N, and --population FILE to write the
+population of every generation to FILE as CSV.
+
+Every command takes --threads N to split each generation across at most N threads, with 1 keeping
+to one thread and 0, the default, using one per core.
+
+period and stabilize take --output json to print their summary as a line of JSON, and exit with
+0 when the pattern stabilized, 2 when it died out, and 3 when it was still active at the
+generation limit. They exit with 4 when their arguments or pattern can't be used, as does an
+unknown command, and any other failure exits with 1.
+
+batch, gif, heat, and soup show a progress bar on stderr while they run, unless it isn't a
+terminal or --quiet is given. Ctrl+C stops batch and soup early with a report of what they'd
+finished.";
+
+// Run a command from the command line, returning the process's exit code.
+pub fn run(args: &[String], config: &Config) -> i32 {
+    let (command, rest) = args.split_first().expect("run needs a command");
+    let (rest, config) = match threads(rest, config) {
+        Ok(split) => split,
+        Err(err) => {
+            eprintln!("gol: {err}");
+            return INPUT_ERROR;
+        }
+    };
+    let (rest, config) = (rest.as_slice(), &config);
+    // Commands without an outcome exit with 0 when they're done.
+    let plain = |result: Result<(), String>| result.map(|()| None).map_err(Failure::Run);
+    let result = match command.as_str() {
+        "batch" => parse(rest, &["quiet"]).and_then(|args| plain(batch::run(args, config))),
+        "bench" => parse(rest, &[]).and_then(|args| plain(bench::run(args, config))),
+        "census" => parse(rest, &[]).and_then(|args| plain(census::run(args, config))),
+        "gif" => parse(rest, &["quiet"]).and_then(|args| plain(gif::run(args, config))),
+        "headless" => parse(rest, &["ascii"]).and_then(|args| plain(headless::run(args, config))),
+        "heat" => parse(rest, &["quiet"]).and_then(|args| plain(heat::run(args, config))),
+        "period" => parse(rest, &[]).and_then(|args| period::run(args, config).map(Some)),
+        "render" => parse(rest, &[]).and_then(|args| plain(render::run(args, config))),
+        "stabilize" => parse(rest, &[]).and_then(|args| stabilize::run(args, config).map(Some)),
+        "soup" => parse(rest, &["quiet"]).and_then(|args| plain(soup::run(args, config))),
+        "verify" => parse(rest, &[]).and_then(|args| plain(verify::run(args, config))),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(None)
+        }
+        _ => Err(Failure::Input(format!(
+            "unknown command {command:?}\n\n{USAGE}"
+        ))),
+    };
+
+    match result {
+        Ok(outcome) => outcome.map_or(0, Outcome::code),
+        Err(err) => {
+            eprintln!("gol: {err}");
+            err.code()
+        }
+    }
+}
+
+// Split a command's arguments into options and values, where a mistake is an input error.
+fn parse(args: &[String], flags: &[&str]) -> Result<Args, Failure> {
+    Ok(Args::parse(args, flags)?)
+}
+
+// Take --threads N out of a command's arguments, since every command takes it, returning the rest
+// along with the config it overrides.
+fn threads(args: &[String], config: &Config) -> Result<(Vec<String>, Config), String> {
+    let mut config = config.clone();
+    let mut rest = Vec::with_capacity(args.len());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--threads") {
+            Some("") => args.next().ok_or("--threads needs a value")?.as_str(),
+            Some(value) if value.starts_with('=') => &value[1..],
+            _ => {
+                rest.push(arg.clone());
+                continue;
+            }
+        };
+        config.threads = value
+            .parse()
+            .map_err(|err| format!("invalid --threads {value:?}: {err}"))?;
+    }
+    Ok((rest, config))
+}
+
+// A command's arguments, split into "--name value" options and plain positional values. Flags are
+// options that take no value.
+struct Args {
+    options: HashMap<String, String>,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String], flags: &[&str]) -> Result<Self, String> {
+        let mut options = HashMap::new();
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                positional.push(arg.clone());
+                continue;
+            };
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, value.to_string()),
+                None if flags.contains(&name) => (name, String::new()),
+                None => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("--{name} needs a value"))?;
+                    (name, value.clone())
+                }
+            };
+            options.insert(name.to_string(), value);
+        }
+        Ok(Args {
+            options,
+            positional,
+        })
+    }
+
+    // Take an option's value, parsed into the type it's used as, if it was given.
+    fn option<T>(&mut self, name: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.options
+            .remove(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|err| format!("invalid --{name} {value:?}: {err}"))
+            })
+            .transpose()
+    }
+
+    // Take a flag, returning whether it was given.
+    fn flag(&mut self, name: &str) -> bool {
+        self.options.remove(name).is_some()
+    }
+
+    // The positional values, once every option the command understands has been taken. Anything
+    // left over is a mistake worth reporting rather than silently ignoring.
+    fn finish(self) -> Result<Vec<String>, String> {
+        if let Some(name) = self.options.keys().min() {
+            return Err(format!("unknown option --{name}"));
+        }
+        Ok(self.positional)
+    }
+}
+
+// Load a pattern file into a new game, with `margin` empty cells around it on every side so it
+// has room to grow before it wraps into itself. The game runs the given rule, or else the pattern's
+// own rule, or else the configured one, on the configured backend.
+fn load_game(
+    path: &Path,
+    rule: Option<LifeLike>,
+    margin: usize,
+    config: &Config,
+) -> Result<(Game, LifeLike), String> {
+    let mut pattern = Pattern::load(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let rule = match (rule, pattern.rule()) {
+        (Some(rule), _) => rule,
+        (None, Some(rule)) => rule
+            .parse()
+            .map_err(|err| format!("{}: {err}", path.display()))?,
+        (None, None) => config.rule,
+    };
+
+    let (x_size, y_size) = (pattern.width() + 2 * margin, pattern.height() + 2 * margin);
+    let mut game = Game::seeded(&mut pattern, x_size, y_size)
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    game.set_rule(rule);
+    game.set_backend(config.backend);
+    game.set_threads(config.threads);
+    game.set_growth(config.growth());
+    game.set_topology(config.topology());
+    Ok((game, rule))
+}
+
+// Quote a string for JSON output.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        Args::parse(&args, &["quiet"])
+    }
+
+    #[test]
+    fn test_args() {
+        let mut args = args(&[
+            "a.rle",
+            "--generations",
+            "50",
+            "--quiet",
+            "--rule=B36/S23",
+            "b.rle",
+        ])
+        .unwrap();
+        assert_eq!(Ok(Some(50)), args.option::<usize>("generations"));
+        assert!(args.flag("quiet"));
+        assert!(!args.flag("verbose"));
+        assert_eq!(
+            Ok(Some(LifeLike::new(&[3, 6], &[2, 3]))),
+            args.option("rule")
+        );
+        assert_eq!(Ok(None), args.option::<usize>("margin"));
+        assert_eq!(
+            Ok(vec!["a.rle".to_string(), "b.rle".to_string()]),
+            args.finish()
+        );
+    }
+
+    #[test]
+    fn test_args_errors() {
+        assert!(args(&["--generations"]).is_err());
+        let mut parsed = args(&["--generations", "many"]).unwrap();
+        assert!(parsed.option::<usize>("generations").is_err());
+        let parsed = args(&["--frames", "3"]).unwrap();
+        assert_eq!(Err("unknown option --frames".to_string()), parsed.finish());
+    }
+
+    #[test]
+    fn test_threads() {
+        let args: Vec<String> = ["--threads", "2", "--gens", "5", "--threads=3", "acorn"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let (rest, config) = threads(&args, &Config::default()).unwrap();
+        assert_eq!(vec!["--gens", "5", "acorn"], rest);
+        assert_eq!(3, config.threads);
+        assert!(threads(&args[..1], &Config::default()).is_err());
+        assert!(threads(&["--threads=all".to_string()], &Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(r#""a \"b\"\\c\n""#, json_string("a \"b\"\\c\n"));
+    }
+}
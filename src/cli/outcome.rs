@@ -0,0 +1,124 @@
+use std::fmt;
+use std::str::FromStr;
+
+use gol::Behavior;
+
+// Exit codes besides the outcomes' own: something went wrong partway through a run, or the
+// command couldn't use what it was given.
+const FAILED: i32 = 1;
+pub const INPUT_ERROR: i32 = 4;
+
+// How a run ended, for the commands that run a pattern until it settles. Each has its own exit
+// code so scripts can branch on what happened without reading the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    // The pattern settled into a still life, an oscillator, or a spaceship.
+    Stabilized,
+    // Every cell died.
+    Extinct,
+    // The pattern was still changing when the generation limit ran out.
+    Active,
+}
+
+impl Outcome {
+    pub fn code(self) -> i32 {
+        match self {
+            Outcome::Stabilized => 0,
+            Outcome::Extinct => 2,
+            Outcome::Active => 3,
+        }
+    }
+}
+
+impl From<Behavior> for Outcome {
+    fn from(behavior: Behavior) -> Self {
+        match behavior {
+            Behavior::Extinct => Outcome::Extinct,
+            Behavior::Unsettled => Outcome::Active,
+            _ => Outcome::Stabilized,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Stabilized => "stabilized",
+            Outcome::Extinct => "extinct",
+            Outcome::Active => "active",
+        })
+    }
+}
+
+// Why a command stopped short, which decides its exit code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    // The arguments or the pattern couldn't be used.
+    Input(String),
+    // Something went wrong once the run had started, such as a file that couldn't be written.
+    Run(String),
+}
+
+impl Failure {
+    pub fn code(&self) -> i32 {
+        match self {
+            Failure::Input(_) => INPUT_ERROR,
+            Failure::Run(_) => FAILED,
+        }
+    }
+}
+
+// Errors from reading arguments and loading patterns are plain strings, so those are what `?`
+// turns into input errors.
+impl From<String> for Failure {
+    fn from(err: String) -> Self {
+        Failure::Input(err)
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Input(err) | Failure::Run(err) => f.write_str(err),
+        }
+    }
+}
+
+// The form of a command's final summary, from --output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    Text,
+    Json,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Output::Text),
+            "json" => Ok(Output::Json),
+            _ => Err("expected text or json".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(Outcome::Extinct, Behavior::Extinct.into());
+        assert_eq!(Outcome::Active, Behavior::Unsettled.into());
+        let glider = Behavior::Spaceship {
+            period: 4,
+            dx: 1,
+            dy: 1,
+        };
+        assert_eq!(0, Outcome::from(glider).code());
+        assert_eq!(3, Outcome::Active.code());
+        assert_eq!(INPUT_ERROR, Failure::from("bad".to_string()).code());
+        assert_eq!(Ok(Output::Json), "json".parse());
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use gol::{analyze_with, Analysis, Behavior, LifeLike};
+
+use super::outcome::{Failure, Outcome, Output};
+use super::population::{self, Population};
+use super::telemetry::{self, Telemetry};
+use super::{json_string, load_game, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol period [--rule RULE] [--generations N] [--margin N] [--telemetry FILE]
+                  [--population FILE] [--output text|json] PATTERN
+
+Runs the pattern until it repeats and prints whether it's a still life, an oscillator, or a
+spaceship, or that it hadn't settled within the generation limit. --output json prints a line of
+JSON instead:
+
+    {\"file\": \"glider.rle\", \"outcome\": \"stabilized\", \"behavior\": \"spaceship\", \
+\"period\": 4, \"dx\": 1, \"dy\": 1, \"settled_at\": 0, \"generations\": 4, \"population\": 5}
+
+The exit code is 0 if the pattern settled, 2 if it died out, 3 if it hadn't settled by the limit,
+and 4 if the arguments or the pattern couldn't be used.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<Outcome, Failure> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations = args.option("generations")?.unwrap_or(1000);
+    let margin = args.option("margin")?.unwrap_or(16);
+    let output = args.option("output")?.unwrap_or(Output::Text);
+    let mut telemetry = Telemetry::open(&mut args)?;
+    let mut population = Population::open(&mut args)?;
+    let paths = args.finish()?;
+    let [path] = paths.as_slice() else {
+        return Err(USAGE.to_string().into());
+    };
+
+    let (mut game, _) = load_game(Path::new(path), rule, margin, config)?;
+    let analysis = analyze_with(&mut game, generations, |game| {
+        telemetry::record(&mut telemetry, game);
+        population::record(&mut population, game);
+    });
+    let outcome = Outcome::from(analysis.behavior);
+    match output {
+        Output::Json => println!("{}", json(path, &analysis)),
+        Output::Text => {
+            match analysis.behavior {
+                Behavior::Unsettled => println!("unstabilized within {generations} generations"),
+                Behavior::Spaceship { period, dx, dy } => {
+                    println!("{} ({})", analysis.behavior, speed(period, dx, dy))
+                }
+                behavior => println!("{behavior}"),
+            }
+            if analysis.behavior.is_settled() && analysis.settled_at > 0 {
+                println!("settled at generation {}", analysis.settled_at);
+            }
+        }
+    }
+    telemetry::finish(telemetry).map_err(Failure::Run)?;
+    population::finish(population).map_err(Failure::Run)?;
+    Ok(outcome)
+}
+
+// The analysis as a line of JSON, with nulls for whatever doesn't apply to the behavior.
+fn json(path: &str, analysis: &Analysis) -> String {
+    let (behavior, (dx, dy)) = match analysis.behavior {
+        Behavior::Extinct => ("extinct", (None, None)),
+        Behavior::StillLife => ("still life", (None, None)),
+        Behavior::Oscillator { .. } => ("oscillator", (None, None)),
+        Behavior::Spaceship { dx, dy, .. } => ("spaceship", (Some(dx), Some(dy))),
+        Behavior::Unsettled => ("unsettled", (None, None)),
+    };
+    let number = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+    let settled_at = analysis
+        .behavior
+        .is_settled()
+        .then(|| analysis.settled_at.to_string());
+    format!(
+        "{{\"file\": {}, \"outcome\": \"{}\", \"behavior\": \"{behavior}\", \"period\": {}, \
+         \"dx\": {}, \"dy\": {}, \"settled_at\": {}, \"generations\": {}, \"population\": {}}}",
+        json_string(path),
+        Outcome::from(analysis.behavior),
+        number(analysis.behavior.period().map(|period| period.to_string())),
+        number(dx.map(|dx| dx.to_string())),
+        number(dy.map(|dy| dy.to_string())),
+        number(settled_at),
+        analysis.generations,
+        analysis.population
+    )
+}
+
+// A spaceship's speed in the usual notation, such as "c/4 diagonal" for a glider or "2c/5
+// orthogonal".
+fn speed(period: usize, dx: isize, dy: isize) -> String {
+    let distance = dx.unsigned_abs().max(dy.unsigned_abs());
+    let divisor = gcd(distance, period);
+    let (distance, period) = (distance / divisor, period / divisor);
+    let numerator = if distance == 1 {
+        String::new()
+    } else {
+        distance.to_string()
+    };
+    let direction = if dx == 0 || dy == 0 {
+        "orthogonal"
+    } else if dx.abs() == dy.abs() {
+        "diagonal"
+    } else {
+        "oblique"
+    };
+    format!("{numerator}c/{period} {direction}")
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_speed() {
+        assert_eq!("c/4 diagonal", speed(4, 1, -1));
+        assert_eq!("c/2 orthogonal", speed(4, -2, 0));
+        assert_eq!("2c/5 orthogonal", speed(5, 0, 2));
+        assert_eq!("c/3 oblique", speed(6, 2, 1));
+    }
+
+    #[test]
+    fn test_json() {
+        let analysis = Analysis {
+            behavior: Behavior::Spaceship {
+                period: 4,
+                dx: 1,
+                dy: 1,
+            },
+            settled_at: 0,
+            generations: 4,
+            population: 5,
+        };
+        assert_eq!(
+            "{\"file\": \"glider.rle\", \"outcome\": \"stabilized\", \"behavior\": \"spaceship\", \
+             \"period\": 4, \"dx\": 1, \"dy\": 1, \"settled_at\": 0, \"generations\": 4, \
+             \"population\": 5}",
+            json("glider.rle", &analysis)
+        );
+        let analysis = Analysis {
+            behavior: Behavior::Unsettled,
+            settled_at: 100,
+            generations: 100,
+            population: 80,
+        };
+        assert!(json("r.rle", &analysis).contains("\"period\": null, \"dx\": null"));
+    }
+}
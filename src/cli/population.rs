@@ -0,0 +1,54 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+use gol::{Game, PopulationWriter};
+
+use super::Args;
+
+// Writes the population of every generation to the CSV file given with --population, a row at a
+// time as the run goes.
+pub struct Population {
+    writer: PopulationWriter<BufWriter<File>>,
+    path: String,
+    // The first write that failed, reported once the run's over rather than stopping it.
+    error: Option<io::Error>,
+}
+
+impl Population {
+    // Take the --population option, creating the file if one was named.
+    pub fn open(args: &mut Args) -> Result<Option<Population>, String> {
+        let Some(path) = args.option::<String>("population")? else {
+            return Ok(None);
+        };
+        let file = File::create(&path).map_err(|err| format!("{path}: {err}"))?;
+        Ok(Some(Population {
+            writer: PopulationWriter::new(BufWriter::new(file)),
+            path,
+            error: None,
+        }))
+    }
+
+    pub fn record(&mut self, game: &Game) {
+        if self.error.is_none() {
+            self.error = self.writer.write(game).err();
+        }
+    }
+
+    pub fn finish(mut self) -> Result<(), String> {
+        match self.error.take().map_or_else(|| self.writer.flush(), Err) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(format!("{}: {err}", self.path)),
+        }
+    }
+}
+
+// Record to the population file if there is one.
+pub fn record(population: &mut Option<Population>, game: &Game) {
+    if let Some(population) = population {
+        population.record(game);
+    }
+}
+
+pub fn finish(population: Option<Population>) -> Result<(), String> {
+    population.map_or(Ok(()), Population::finish)
+}
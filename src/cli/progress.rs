@@ -0,0 +1,95 @@
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// How often the bar is redrawn, so fast runs don't spend their time writing to the terminal.
+const REDRAW: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+// A progress bar on stderr for long runs, with the rate and the time left:
+//
+//     [###########-------------------]  37% 1850/5000 generations, 2310/s, 1s left
+//
+// Nothing is drawn when stderr isn't a terminal or the run is quiet, so piped and logged output
+// stays clean.
+pub struct Progress {
+    total: usize,
+    done: usize,
+    unit: &'static str,
+    start: Instant,
+    drawn: Option<Instant>,
+    visible: bool,
+}
+
+impl Progress {
+    pub fn new(total: usize, unit: &'static str, quiet: bool) -> Self {
+        Progress {
+            total,
+            done: 0,
+            unit,
+            start: Instant::now(),
+            drawn: None,
+            visible: !quiet && io::stderr().is_terminal(),
+        }
+    }
+
+    // Count one more unit of work done.
+    pub fn tick(&mut self) {
+        self.done += 1;
+        if self.visible && self.drawn.is_none_or(|drawn| drawn.elapsed() >= REDRAW) {
+            self.drawn = Some(Instant::now());
+            eprint!(
+                "\r{}",
+                line(self.done, self.total, self.unit, self.start.elapsed())
+            );
+            let _ = io::stderr().flush();
+        }
+    }
+
+    // Clear the bar so whatever's printed next starts on a clean line.
+    pub fn finish(self) {
+        if self.visible && self.drawn.is_some() {
+            eprint!("\r{:width$}\r", "", width = BAR_WIDTH + 80);
+        }
+    }
+}
+
+fn line(done: usize, total: usize, unit: &str, elapsed: Duration) -> String {
+    let fraction = if total == 0 {
+        1.0
+    } else {
+        done.min(total) as f64 / total as f64
+    };
+    let filled = (fraction * BAR_WIDTH as f64) as usize;
+    let rate = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    let left = if rate > 0.0 {
+        format!(
+            ", {}s left",
+            (total.saturating_sub(done) as f64 / rate).ceil()
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        "[{}{}] {:>3}% {done}/{total} {unit}, {rate:.0}/s{left}",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        (fraction * 100.0) as usize,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        assert_eq!(
+            "[######################--------]  75% 300/400 generations, 100/s, 1s left",
+            line(300, 400, "generations", Duration::from_secs(3))
+        );
+        assert_eq!(
+            "[##############################] 100% 0/0 soups, 0/s",
+            line(0, 0, "soups", Duration::from_secs(1))
+        );
+    }
+}
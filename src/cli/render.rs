@@ -0,0 +1,161 @@
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+
+use gol::{Palette, Pattern, Raster, Rect, RenderTarget, Svg, Text};
+
+use super::Args;
+use crate::config::{Config, Theme};
+
+const USAGE: &str = "\
+usage: gol render [--scale N] [--margin N] [--theme light|dark] PATTERN -o OUTPUT
+
+Draws a pattern file as an image, each cell a square --scale pixels across, with --margin empty
+cells around it. The output is an SVG if its name ends in .svg, text on the terminal if it's -, and
+a PNG otherwise. The theme picks dark cells on a light background or the other way around, and
+defaults to the configured one.
+
+Patterns with more than two states, such as Wireworld or Generations patterns, are drawn with a
+color for each state, picked by the rule named in the pattern. SVGs list the states under the
+image, and text shows each state as its number.";
+
+// The background and live cell colors for each theme.
+const LIGHT: ([u8; 3], [u8; 3]) = ([0xff, 0xff, 0xff], [0x20, 0x20, 0x20]);
+const DARK: ([u8; 3], [u8; 3]) = ([0x1b, 0x1b, 0x1b], [0xe0, 0xe0, 0xe0]);
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let scale = args.option("scale")?.unwrap_or(4);
+    let margin = args.option("margin")?.unwrap_or(1);
+    let theme = args.option("theme")?.unwrap_or(config.theme);
+    let output: Option<String> = args.option("output")?;
+    let paths = args.finish()?;
+    let (input, output) = match (paths.as_slice(), output) {
+        ([input], Some(output)) => (input, output),
+        ([input, o, output] | [o, output, input], None) if o == "-o" => (input, output.clone()),
+        _ => return Err(USAGE.to_string()),
+    };
+    if scale == 0 {
+        return Err("--scale must be at least 1".to_string());
+    }
+
+    let pattern = Pattern::load(input).map_err(|err| format!("{input}: {err}"))?;
+    let (background, live) = colors(theme);
+    if output == "-" {
+        let mut text = Text::new('#', '.');
+        render(&pattern, margin, &mut text);
+        for line in text.lines() {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    let result = if output.ends_with(".svg") {
+        let mut svg = Svg::new(scale, background, live);
+        if let Some(palette) = palette(&pattern) {
+            svg.set_legend(&palette);
+        }
+        render(&pattern, margin, &mut svg);
+        fs::write(&output, svg.as_str()).map_err(|err| err.to_string())
+    } else {
+        let mut raster = Raster::new(scale, background, live);
+        render(&pattern, margin, &mut raster);
+        let (width, height) = (raster.width(), raster.height());
+        write_png(Path::new(&output), width, height, raster.pixels())
+    };
+    result.map_err(|err| format!("{output}: {err}"))
+}
+
+// Draw a pattern with `margin` dead cells around it, in its palette if it has more than two states.
+fn render<T: RenderTarget>(pattern: &Pattern, margin: usize, target: &mut T) {
+    let framed = with_margin(pattern, margin);
+    let region = Rect::new(0, 0, framed.width(), framed.height());
+    match palette(pattern) {
+        Some(palette) => gol::render_states(&framed, region, &palette, target),
+        None => gol::render(&framed.to_board(), region, target),
+    }
+}
+
+// The colors for a multi-state pattern's states, going by its rule.
+fn palette(pattern: &Pattern) -> Option<Palette> {
+    (pattern.states() > 2).then(|| Palette::for_rule(pattern.rule(), pattern.states()))
+}
+
+// The pattern with `margin` dead cells around it.
+fn with_margin(pattern: &Pattern, margin: usize) -> Pattern {
+    let (width, height) = (pattern.width() + 2 * margin, pattern.height() + 2 * margin);
+    let mut framed = Pattern::from_fn(width, height, |x, y| {
+        let inside = x >= margin
+            && y >= margin
+            && x - margin < pattern.width()
+            && y - margin < pattern.height();
+        if inside {
+            pattern.get(x - margin, y - margin)
+        } else {
+            0
+        }
+    });
+    framed.set_states(pattern.states());
+    framed
+}
+
+// The background and live cell colors for a theme.
+pub(super) fn colors(theme: Theme) -> ([u8; 3], [u8; 3]) {
+    match theme {
+        Theme::Dark => DARK,
+        // There's no system to follow outside the GUI, so that gets the light theme.
+        Theme::Light | Theme::System => LIGHT,
+    }
+}
+
+// Save RGB pixels, row by row, as a PNG.
+pub(super) fn write_png(
+    path: &Path,
+    width: usize,
+    height: usize,
+    pixels: &[u8],
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer
+        .write_image_data(pixels)
+        .map_err(|err| err.to_string())?;
+    writer.finish().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        // A horizontal domino.
+        let pattern: Pattern = "x = 2, y = 1\n2o!".parse().unwrap();
+        let mut raster = Raster::new(2, DARK.0, DARK.1);
+        render(&pattern, 1, &mut raster);
+        assert_eq!((8, 6), (raster.width(), raster.height()));
+        let pixels = raster.pixels();
+        assert_eq!(8 * 6 * 3, pixels.len());
+
+        let pixel = |x: usize, y: usize| &pixels[(y * 8 + x) * 3..][..3];
+        assert_eq!(DARK.0, pixel(0, 0));
+        assert_eq!(DARK.0, pixel(1, 2));
+        assert_eq!(DARK.1, pixel(2, 2));
+        assert_eq!(DARK.1, pixel(5, 3));
+        assert_eq!(DARK.0, pixel(6, 3));
+        assert_eq!(DARK.0, pixel(3, 4));
+
+        // A Wireworld electron on a wire, in Wireworld's colors.
+        let pattern: Pattern = "x = 3, y = 1, rule = WireWorld\nCBA!".parse().unwrap();
+        let mut text = Text::new('#', '.');
+        render(&pattern, 1, &mut text);
+        assert_eq!(
+            vec![".....", ".321.", "....."],
+            text.lines().collect::<Vec<_>>()
+        );
+        let mut raster = Raster::new(1, DARK.0, DARK.1);
+        render(&pattern, 0, &mut raster);
+        assert_eq!(&[0x00, 0x80, 0xff], &raster.pixels()[6..9]);
+    }
+}
@@ -0,0 +1,77 @@
+use gol::{object_name, soup, stabilize, Board, Census, Game, LifeLike, Symmetry};
+
+use super::interrupt;
+use super::progress::Progress;
+use super::Args;
+use crate::config::Config;
+
+// The longest period a settled soup is checked for. Soups almost never leave anything slower.
+const MAX_PERIOD: usize = 30;
+
+// Search random soups the way apgsearch does: build each soup, run it until its population settles,
+// and tally the objects left behind.
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let soups: u64 = args.option("soups")?.unwrap_or(100);
+    let seed: u64 = args.option("seed")?.unwrap_or(0);
+    let symmetry: Symmetry = args.option("symmetry")?.unwrap_or_default();
+    let size = args.option("size")?.unwrap_or(16);
+    let density = args.option("density")?.unwrap_or(0.5);
+    let margin = args.option("margin")?.unwrap_or(64);
+    let rule: LifeLike = args.option("rule")?.unwrap_or(config.rule);
+    let generations = args.option("generations")?.unwrap_or(5000);
+    let top: usize = args.option("top")?.unwrap_or(20);
+    let quiet = args.flag("quiet");
+    args.finish()?;
+    if size == 0 {
+        return Err("--size must be at least 1".to_string());
+    }
+
+    let cancel = interrupt::handle();
+    let mut census = Census::new();
+    let mut unsettled = 0;
+    let mut searched = 0;
+    let mut progress = Progress::new(soups as usize, "soups", quiet);
+    for index in 0..soups {
+        let cells = soup(size, density, symmetry, seed.wrapping_add(index));
+        let board = Board::from_fn(size + 2 * margin, size + 2 * margin, |x, y| {
+            (margin..margin + size).contains(&x)
+                && (margin..margin + size).contains(&y)
+                && cells[(x - margin, y - margin)]
+        });
+        let mut game = Game::from(board);
+        game.set_rule(rule);
+        game.set_backend(config.backend);
+        game.set_threads(config.threads);
+        game.set_cancel(Some(cancel.clone()));
+
+        let period = stabilize(&mut game, generations, MAX_PERIOD);
+        // A soup cut off partway through hasn't really been searched, so it isn't counted.
+        if cancel.is_cancelled() {
+            break;
+        }
+        match period {
+            Some(period) => census.add(&game, period),
+            None => unsettled += 1,
+        }
+        searched += 1;
+        progress.tick();
+    }
+    progress.finish();
+
+    println!(
+        "{searched} {symmetry} soups under {rule}: {} objects, {unsettled} soups unsettled after \
+         {generations} generations",
+        census.total()
+    );
+    for (code, count) in census.sorted().into_iter().take(top) {
+        match object_name(code) {
+            Some(name) => println!("{count:>8}  {code} ({name})"),
+            None => println!("{count:>8}  {code}"),
+        }
+    }
+
+    if cancel.is_cancelled() {
+        return Err(format!("interrupted after {searched} of {soups} soups"));
+    }
+    Ok(())
+}
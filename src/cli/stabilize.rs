@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use gol::{analyze, find_cycle, stabilization_with, Game, LifeLike};
+
+use super::outcome::{Failure, Outcome, Output};
+use super::population::{self, Population};
+use super::telemetry::{self, Telemetry};
+use super::{json_string, load_game, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol stabilize [--rule RULE] [--gens N] [--margin N] [--max-period N] [--telemetry FILE]
+                     [--population FILE] [--output text|json] PATTERN
+
+Runs the pattern until its population settles into a cycle, or for at most --gens generations,
+and prints a line of JSON with the outcome, the final population, the generation it settled at,
+and the period the board repeats with, or moves with for a lone spaceship. For the R-pentomino
+with boundary = \"dead\" in the config file:
+
+    {\"file\": \"r.rle\", \"rule\": \"B3/S23\", \"outcome\": \"stabilized\", \"settled\": true, \
+\"generations\": 1133, \"settled_at\": 1103, \"period\": 2, \"population\": 133}
+
+The period is null if the board doesn't repeat within --max-period generations once the
+population has, as when gliders are still flying around a wrapping board. --output text prints a
+sentence instead. The exit code is 0 if the pattern stabilized, 2 if it died out, 3 if it was
+still active at the limit, and 4 if the arguments or the pattern couldn't be used.";
+
+pub fn run(mut args: Args, config: &Config) -> Result<Outcome, Failure> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations = args.option("gens")?.unwrap_or(5000);
+    let margin = args.option("margin")?.unwrap_or(64);
+    let max_period = args.option("max-period")?.unwrap_or(30);
+    let output = args.option("output")?.unwrap_or(Output::Json);
+    let mut telemetry = Telemetry::open(&mut args)?;
+    let mut population = Population::open(&mut args)?;
+    let paths = args.finish()?;
+    let [path] = paths.as_slice() else {
+        return Err(USAGE.to_string().into());
+    };
+
+    let (mut game, rule) = load_game(Path::new(path), rule, margin, config)?;
+    let stabilization = stabilization_with(&mut game, generations, max_period, |game| {
+        telemetry::record(&mut telemetry, game);
+        population::record(&mut population, game);
+    });
+    let period = stabilization.and_then(|_| board_period(&game, max_period));
+    let outcome = match stabilization {
+        Some(stabilization) if stabilization.population == 0 => Outcome::Extinct,
+        Some(_) => Outcome::Stabilized,
+        None => Outcome::Active,
+    };
+    match (output, stabilization) {
+        (Output::Json, Some(stabilization)) => println!(
+            "{{\"file\": {}, \"rule\": \"{rule}\", \"outcome\": \"{outcome}\", \"settled\": true, \
+             \"generations\": {}, \"settled_at\": {}, \"period\": {}, \"population\": {}}}",
+            json_string(path),
+            stabilization.generations,
+            stabilization.settled_at,
+            json_period(period),
+            stabilization.population
+        ),
+        (Output::Json, None) => println!(
+            "{{\"file\": {}, \"rule\": \"{rule}\", \"outcome\": \"{outcome}\", \"settled\": false, \
+             \"generations\": {generations}, \"settled_at\": null, \"period\": null, \
+             \"population\": {}}}",
+            json_string(path),
+            game.board().population()
+        ),
+        (Output::Text, Some(stabilization)) if outcome == Outcome::Extinct => {
+            println!(
+                "{path}: died out by generation {}",
+                stabilization.settled_at
+            )
+        }
+        (Output::Text, Some(stabilization)) => match period {
+            Some(period) => println!(
+                "{path}: settled at generation {} into period {period} with {} cells",
+                stabilization.settled_at, stabilization.population
+            ),
+            None => println!(
+                "{path}: settled at generation {} with {} cells, but the board didn't repeat \
+                 within {max_period} generations",
+                stabilization.settled_at, stabilization.population
+            ),
+        },
+        (Output::Text, None) => println!(
+            "{path}: still active after {generations} generations with {} cells",
+            game.board().population()
+        ),
+    }
+    telemetry::finish(telemetry).map_err(Failure::Run)?;
+    population::finish(population).map_err(Failure::Run)?;
+    Ok(outcome)
+}
+
+// How many generations the board takes to come back exactly, or failing that to come back as the
+// same shape somewhere else, as a lone spaceship does. This is the period to report rather than
+// the population's, which can repeat sooner than the cells do: a blinker's and a glider's are the
+// same every generation. The runs are on copies, so the game stays where it settled.
+fn board_period(game: &Game, max_period: usize) -> Option<usize> {
+    find_cycle(&mut game.clone(), max_period, max_period)
+        .behavior
+        .period()
+        .or_else(|| analyze(&mut game.clone(), max_period).behavior.period())
+}
+
+fn json_period(period: Option<usize>) -> String {
+    period.map_or_else(|| "null".to_string(), |period| period.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use gol::stabilization;
+
+    use super::*;
+
+    fn period(cells: &[(usize, usize)]) -> Option<usize> {
+        let mut game = Game::new(40, 40);
+        game.set(cells.iter().copied()).unwrap();
+        stabilization(&mut game, 500, 30).unwrap();
+        board_period(&game, 30)
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(Some(2), period(&[(10, 10), (11, 10), (12, 10)]));
+        assert_eq!(
+            Some(4),
+            period(&[(11, 10), (12, 11), (10, 12), (11, 12), (12, 12)])
+        );
+        assert_eq!(Some(1), period(&[(10, 10), (11, 10), (10, 11), (11, 11)]));
+        // A glider flying past a block settles the population but never the board.
+        assert_eq!(
+            None,
+            period(&[
+                (11, 10),
+                (12, 11),
+                (10, 12),
+                (11, 12),
+                (12, 12),
+                (30, 2),
+                (31, 2),
+                (30, 3),
+                (31, 3)
+            ])
+        );
+    }
+}
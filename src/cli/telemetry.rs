@@ -0,0 +1,103 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+
+use gol::Game;
+
+use super::Args;
+
+// Appends a line of JSON about the game every `every` generations to the file given with
+// --telemetry, for feeding long runs into dashboards:
+//
+//     {"gen":1200,"population":316,"temperature":0.0412,"hash":"9f3c2a7e5d1b0846"}
+//
+// The hash identifies the board's exact contents, so repeats show up as repeated hashes.
+pub struct Telemetry {
+    out: BufWriter<File>,
+    path: String,
+    every: usize,
+    // The first write that failed, reported once the run's over rather than stopping it.
+    error: Option<io::Error>,
+}
+
+impl Telemetry {
+    // Take the --telemetry and --telemetry-every options, opening the file if one was named.
+    pub fn open(args: &mut Args) -> Result<Option<Telemetry>, String> {
+        let path: Option<String> = args.option("telemetry")?;
+        let every: usize = args.option("telemetry-every")?.unwrap_or(1);
+        let Some(path) = path else {
+            return Ok(None);
+        };
+        if every == 0 {
+            return Err("--telemetry-every must be at least 1".to_string());
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| format!("{path}: {err}"))?;
+        Ok(Some(Telemetry {
+            out: BufWriter::new(file),
+            path,
+            every,
+            error: None,
+        }))
+    }
+
+    pub fn record(&mut self, game: &Game) {
+        let stats = game.stats();
+        if self.error.is_some() || !stats.generation.is_multiple_of(self.every) {
+            return;
+        }
+        if let Err(err) = writeln!(self.out, "{}", line(game)) {
+            self.error = Some(err);
+        }
+    }
+
+    pub fn finish(mut self) -> Result<(), String> {
+        match self.error.take().map_or_else(|| self.out.flush(), Err) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(format!("{}: {err}", self.path)),
+        }
+    }
+}
+
+// Record to the telemetry file if there is one.
+pub fn record(telemetry: &mut Option<Telemetry>, game: &Game) {
+    if let Some(telemetry) = telemetry {
+        telemetry.record(game);
+    }
+}
+
+pub fn finish(telemetry: Option<Telemetry>) -> Result<(), String> {
+    telemetry.map_or(Ok(()), Telemetry::finish)
+}
+
+fn line(game: &Game) -> String {
+    let stats = game.stats();
+    format!(
+        "{{\"gen\":{},\"population\":{},\"temperature\":{:.4},\"hash\":\"{:016x}\"}}",
+        stats.generation,
+        stats.population,
+        stats.temperature,
+        game.board().checksum()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let first = line(&game);
+        assert!(first.starts_with("{\"gen\":0,\"population\":3,\"temperature\":0.0000,\"hash\":\""));
+
+        game.iterate();
+        game.iterate();
+        let again = line(&game);
+        assert!(again.starts_with("{\"gen\":2,\"population\":3,\"temperature\":0.1600,"));
+        assert_eq!(first[first.len() - 20..], again[again.len() - 20..]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use gol::{Backend, Game, LifeLike};
+
+use super::{load_game, Args};
+use crate::config::Config;
+
+const USAGE: &str = "\
+usage: gol verify [--rule RULE] [--gens N] [--margin N] [--backend NAME]
+                  [--record FILE | --against FILE] PATTERN
+
+Runs the pattern on a backend and checks every generation's board is bit-identical to a
+reference, stopping with an error at the first generation that differs. The reference is the
+naive backend run alongside it, or with --against, a checksum file written earlier with --record,
+which has a line per generation:
+
+    0 9f3c2a7e5d1b0846
+    1 5e0a7c13b2f4d968";
+
+pub fn run(mut args: Args, config: &Config) -> Result<(), String> {
+    let rule: Option<LifeLike> = args.option("rule")?;
+    let generations = args.option("gens")?.unwrap_or(1000);
+    let margin = args.option("margin")?.unwrap_or(16);
+    let backend: Option<Backend> = args.option("backend")?;
+    let record: Option<String> = args.option("record")?;
+    let against: Option<String> = args.option("against")?;
+    let paths = args.finish()?;
+    let [path] = paths.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+    if record.is_some() && against.is_some() {
+        return Err("--record and --against can't be used together".to_string());
+    }
+
+    let (mut game, _) = load_game(Path::new(path), rule, margin, config)?;
+    if let Some(backend) = backend {
+        game.set_backend(backend);
+    }
+
+    if let Some(record) = record {
+        let file = File::create(&record).map_err(|err| format!("{record}: {err}"))?;
+        let mut out = BufWriter::new(file);
+        for checksum in checksums(&mut game, generations) {
+            writeln!(out, "{} {:016x}", checksum.0, checksum.1)
+                .map_err(|err| format!("{record}: {err}"))?;
+        }
+        out.flush().map_err(|err| format!("{record}: {err}"))?;
+        println!("recorded {} generations to {record}", generations + 1);
+        return Ok(());
+    }
+
+    let expected = match against {
+        Some(against) => {
+            let text = fs::read_to_string(&against).map_err(|err| format!("{against}: {err}"))?;
+            parse(&text).map_err(|err| format!("{against}: {err}"))?
+        }
+        None => {
+            let mut naive = game.clone();
+            naive.set_backend(Backend::Naive);
+            checksums(&mut naive, generations)
+        }
+    };
+    let generations = expected.len().saturating_sub(1);
+    let actual = checksums(&mut game, generations);
+    if let Some((generation, expected, actual)) = diverged(&expected, &actual) {
+        return Err(format!(
+            "{} diverged at generation {generation}: checksum {actual:016x}, expected \
+             {expected:016x}",
+            game.resolved_backend()
+        ));
+    }
+    println!(
+        "{} matched for {} generations",
+        game.resolved_backend(),
+        generations
+    );
+    Ok(())
+}
+
+// The checksum of the board at generation 0 and after each of the given number of generations.
+fn checksums(game: &mut Game, generations: usize) -> Vec<(usize, u64)> {
+    let mut checksums = Vec::with_capacity(generations + 1);
+    checksums.push((game.stats().generation, game.board().checksum()));
+    for _ in 0..generations {
+        game.iterate();
+        checksums.push((game.stats().generation, game.board().checksum()));
+    }
+    checksums
+}
+
+// Read a checksum file back, one "generation checksum" line at a time.
+fn parse(text: &str) -> Result<Vec<(usize, u64)>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            let invalid = || format!("line {}: expected \"GENERATION CHECKSUM\"", number + 1);
+            let (generation, checksum) = line.trim().split_once(' ').ok_or_else(invalid)?;
+            let generation = generation.parse().map_err(|_| invalid())?;
+            let checksum = u64::from_str_radix(checksum.trim(), 16).map_err(|_| invalid())?;
+            Ok((generation, checksum))
+        })
+        .collect()
+}
+
+// The first generation whose checksums differ, with the expected and actual checksums.
+fn diverged(expected: &[(usize, u64)], actual: &[(usize, u64)]) -> Option<(usize, u64, u64)> {
+    expected
+        .iter()
+        .zip(actual)
+        .find(|(expected, actual)| expected != actual)
+        .map(|(expected, actual)| (expected.0, expected.1, actual.1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(vec![(0, 0x9f3c), (1, 0xffff_ffff_ffff_ffff)]),
+            parse("0 9f3c\n1 ffffffffffffffff\n\n")
+        );
+        assert!(parse("0\n").is_err());
+        assert!(parse("zero 9f3c\n").is_err());
+    }
+
+    #[test]
+    fn test_diverged() {
+        let mut game = Game::random(12, 12, 0.4, 5);
+        let expected = checksums(&mut game.clone(), 8);
+        assert_eq!(None, diverged(&expected, &checksums(&mut game.clone(), 8)));
+
+        game.toggle(3, 3);
+        let (generation, _, _) = diverged(&expected, &checksums(&mut game, 8)).unwrap();
+        assert_eq!(0, generation);
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{Board, Point, Rect};
+
+// A group of live cells that touch each other, including diagonally.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    // The group's cells, in column order.
+    pub cells: Vec<Point>,
+    // The smallest rectangle holding every cell in the group.
+    pub bounds: Rect,
+}
+
+impl Component {
+    // The number of cells in the group.
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+// Split the board's live cells into groups that touch, in the order their first cells come in
+// column order. Cells don't touch across the board's edges, since objects that wrap can't be
+// lifted out in one piece.
+pub(crate) fn components(board: &Board) -> Vec<Component> {
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for start in board.live_cells() {
+        if !seen.insert(start) {
+            continue;
+        }
+
+        let mut cells = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            cells.push(point);
+            for x in point.x.saturating_sub(1)..(point.x + 2).min(board.x_size()) {
+                for y in point.y.saturating_sub(1)..(point.y + 2).min(board.y_size()) {
+                    let neighbor = Point::new(x, y);
+                    if board[(x, y)] && seen.insert(neighbor) {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        cells.sort_by_key(|point| (point.x, point.y));
+        let bounds = Rect::bounding(cells.iter().copied()).unwrap_or_default();
+        components.push(Component { cells, bounds });
+    }
+    components
+}
@@ -0,0 +1,333 @@
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use egui::Key;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use gol::{Backend, Growth, LifeLike, Topology};
+
+// Defaults for both the GUI and the command line, read from gol.toml in the user's config
+// directory. Every setting is optional, and command-line flags override whatever the file says:
+//
+//     width = 120
+//     height = 80
+//     rule = "B36/S23"
+//     boundary = "torus"
+//     max_size = 1024
+//     backend = "auto"
+//     threads = 0
+//     theme = "dark"
+//     autosave_interval = 60
+//     history = 256
+//
+//     [keys]
+//     step = "Space"
+//     play = "P"
+//     clear = "Delete"
+//     explain = "E"
+//     rotate_stamp = "R"
+//     flip_stamp = "F"
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // The size of new boards in the GUI.
+    pub width: usize,
+    pub height: usize,
+    #[serde(deserialize_with = "parse", serialize_with = "display")]
+    pub rule: LifeLike,
+    pub boundary: Boundary,
+    // The widest and tallest a growing board gets.
+    pub max_size: usize,
+    #[serde(deserialize_with = "parse", serialize_with = "display")]
+    pub backend: Backend,
+    // How many threads each generation may be split across, or 0 for one per core.
+    pub threads: usize,
+    pub theme: Theme,
+    // Seconds between saves of the GUI's board, or 0 to never save it.
+    pub autosave_interval: u64,
+    // How many generations the GUI keeps to step back through, or 0 to keep none.
+    pub history: usize,
+    pub keys: Keys,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            width: 10,
+            height: 10,
+            rule: LifeLike::CONWAY,
+            boundary: Boundary::Torus,
+            max_size: 1024,
+            backend: Backend::Auto,
+            threads: 0,
+            theme: Theme::System,
+            autosave_interval: 0,
+            history: 256,
+            keys: Keys::default(),
+        }
+    }
+}
+
+impl Config {
+    // How boards should grow, given the boundary setting.
+    pub fn growth(&self) -> Option<Growth> {
+        match self.boundary {
+            Boundary::Grow => Some(Growth::new(2, 32, self.max_size)),
+            Boundary::Torus | Boundary::Dead | Boundary::Mirror => None,
+        }
+    }
+
+    // What lies past the edges of the board, given the boundary setting.
+    pub fn topology(&self) -> Topology {
+        match self.boundary {
+            Boundary::Torus | Boundary::Grow => Topology::Toroidal,
+            Boundary::Dead => Topology::FixedDead,
+            Boundary::Mirror => Topology::Mirror,
+        }
+    }
+
+    // Read the config file, or fall back to the defaults if there isn't one.
+    pub fn load() -> Result<Config, String> {
+        let Some(path) = path() else {
+            return Ok(Config::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|err| format!("{}: {err}", path.display())),
+            Err(_) if !path.exists() => Ok(Config::default()),
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    // Write the settings to the config file, creating its directory if need be.
+    pub fn save(&self) -> Result<(), String> {
+        let path = path().ok_or("there's no config directory to save to")?;
+        let text = self.to_string();
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, text))
+            .map_err(|err| format!("{}: {err}", path.display()))
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = toml::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&text)
+    }
+}
+
+impl FromStr for Config {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+// What happens at the edges of the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Boundary {
+    // The edges wrap around to the opposite side.
+    Torus,
+    // The board grows as live cells come near an edge, up to max_size, then wraps around.
+    Grow,
+    // Everything past the edges is dead.
+    Dead,
+    // The edges reflect the cells just inside them.
+    Mirror,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    // Follow the operating system's light or dark setting.
+    System,
+    Light,
+    Dark,
+}
+
+impl FromStr for Theme {
+    type Err = UnknownTheme;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "system" => Ok(Theme::System),
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            _ => Err(UnknownTheme(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownTheme(pub String);
+
+impl Display for UnknownTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown theme {:?}, expected system, light, or dark",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownTheme {}
+
+// The GUI's keyboard shortcuts, by egui key name such as "Space", "Enter", or "S".
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keys {
+    // Run one generation.
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub step: Key,
+    // Start or stop running generations continuously.
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub play: Key,
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub clear: Key,
+    // Turn explain mode on or off.
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub explain: Key,
+    // Turn the stamp a quarter turn clockwise, or mirror it left to right, before placing it.
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub rotate_stamp: Key,
+    #[serde(deserialize_with = "key", serialize_with = "key_name")]
+    pub flip_stamp: Key,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Keys {
+            step: Key::Space,
+            play: Key::P,
+            clear: Key::Delete,
+            explain: Key::E,
+            rotate_stamp: Key::R,
+            flip_stamp: Key::F,
+        }
+    }
+}
+
+// Where the config file lives: $GOL_CONFIG if it's set, or else gol/gol.toml in the platform's
+// config directory.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("GOL_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    Some(dir()?.join("gol.toml"))
+}
+
+// The directory gol keeps its files in, under the platform's config directory.
+pub fn dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library/Application Support")
+    } else {
+        match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+    Some(base.join("gol"))
+}
+
+// Deserialize a setting through its FromStr implementation.
+fn parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+// Serialize a setting as the text its FromStr implementation reads back.
+fn display<S: Serializer, T: Display>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Key, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Key::from_name(&name).ok_or_else(|| serde::de::Error::custom(format!("unknown key {name:?}")))
+}
+
+fn key_name<S: Serializer>(key: &Key, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(key.name())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config: Config = "width = 120\n\
+                              rule = \"B36/S23\"\n\
+                              backend = \"table\"\n\
+                              threads = 2\n\
+                              theme = \"dark\"\n\
+                              boundary = \"grow\"\n\
+                              [keys]\n\
+                              step = \"S\"\n\
+                              play = \"Enter\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!((120, 10), (config.width, config.height));
+        assert_eq!(LifeLike::new(&[3, 6], &[2, 3]), config.rule);
+        assert_eq!(Backend::Table, config.backend);
+        assert_eq!(2, config.threads);
+        assert_eq!(Theme::Dark, config.theme);
+        assert_eq!(Some(Growth::new(2, 32, 1024)), config.growth());
+        assert_eq!(Key::S, config.keys.step);
+        assert_eq!(Key::Enter, config.keys.play);
+        assert_eq!(Key::Delete, config.keys.clear);
+
+        let config: Config = "boundary = \"dead\"\n".parse().unwrap();
+        assert_eq!(Topology::FixedDead, config.topology());
+        assert_eq!(None, config.growth());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut config = Config {
+            width: 64,
+            rule: LifeLike::new(&[3, 6], &[2, 3]),
+            backend: Backend::NeighborCount,
+            theme: Theme::Light,
+            autosave_interval: 30,
+            history: 12,
+            ..Config::default()
+        };
+        config.keys.explain = Key::X;
+        let read: Config = config.to_string().parse().unwrap();
+        assert_eq!(config.to_string(), read.to_string());
+        assert_eq!(64, read.width);
+        assert_eq!(config.rule, read.rule);
+        assert_eq!(Backend::NeighborCount, read.backend);
+        assert_eq!(12, read.history);
+        assert_eq!(Key::X, read.keys.explain);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for invalid in [
+            "rule = \"B9/S23\"",
+            "backend = \"fast\"",
+            "boundary = \"klein\"",
+            "colour = \"red\"",
+            "[keys]\nstep = \"Spacebar\"",
+        ] {
+            assert!(invalid.parse::<Config>().is_err(), "{invalid}");
+        }
+    }
+}
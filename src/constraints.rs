@@ -0,0 +1,123 @@
+use crate::{Board, Changes};
+
+// Cells that don't follow the rule. Each kind is kept as a mask the size of the board, and only
+// exists once a cell of that kind has been set, so games without any pay nothing for them.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Constraints {
+    // Frozen cells keep their state from one generation to the next. They still count as
+    // neighbors like any other cell.
+    frozen: Option<Board>,
+    // Walls are always dead, so nothing on one side of a wall counts towards a cell on the other.
+    walls: Option<Board>,
+    // Sources are live every generation, whatever the rule says.
+    sources: Option<Board>,
+}
+
+impl Constraints {
+    pub(crate) fn is_frozen(&self, x: usize, y: usize) -> bool {
+        self.frozen.as_ref().is_some_and(|frozen| frozen[(x, y)])
+    }
+
+    pub(crate) fn set_frozen(&mut self, board: &Board, x: usize, y: usize, frozen: bool) {
+        mask(&mut self.frozen, board).set(x, y, frozen);
+    }
+
+    pub(crate) fn clear_frozen(&mut self) {
+        self.frozen = None;
+    }
+
+    pub(crate) fn is_wall(&self, x: usize, y: usize) -> bool {
+        self.walls.as_ref().is_some_and(|walls| walls[(x, y)])
+    }
+
+    pub(crate) fn set_wall(&mut self, board: &Board, x: usize, y: usize, wall: bool) {
+        mask(&mut self.walls, board).set(x, y, wall);
+    }
+
+    pub(crate) fn clear_walls(&mut self) {
+        self.walls = None;
+    }
+
+    pub(crate) fn is_source(&self, x: usize, y: usize) -> bool {
+        self.sources.as_ref().is_some_and(|sources| sources[(x, y)])
+    }
+
+    pub(crate) fn set_source(&mut self, board: &Board, x: usize, y: usize, source: bool) {
+        mask(&mut self.sources, board).set(x, y, source);
+    }
+
+    pub(crate) fn clear_sources(&mut self) {
+        self.sources = None;
+    }
+
+    // Undo whatever the rule did to constrained cells in the step from `previous` to `current`,
+    // keeping the births and deaths in `changes` in line. Returns whether any cell was changed.
+    // Walls go last, so a cell that's somehow both a wall and a source ends up dead.
+    pub(crate) fn apply(
+        &self,
+        previous: &Board,
+        current: &mut Board,
+        changes: &mut Changes,
+    ) -> bool {
+        let mut changed = false;
+        if let Some(frozen) = &self.frozen {
+            for index in masked(frozen) {
+                let state = previous.alive_at(index);
+                changed |= force(previous, current, index, state, changes);
+            }
+        }
+        if let Some(sources) = &self.sources {
+            for index in masked(sources) {
+                changed |= force(previous, current, index, true, changes);
+            }
+        }
+        if let Some(walls) = &self.walls {
+            for index in masked(walls) {
+                changed |= force(previous, current, index, false, changes);
+            }
+        }
+        changed
+    }
+}
+
+// The mask for one kind of constraint, made empty and the size of the board if it doesn't exist
+// yet.
+fn mask<'a>(mask: &'a mut Option<Board>, board: &Board) -> &'a mut Board {
+    mask.get_or_insert_with(|| Board::new(board.x_size(), board.y_size()))
+}
+
+// The flat indices of the cells set in a mask.
+fn masked(mask: &Board) -> impl Iterator<Item = usize> + '_ {
+    mask.live_cells()
+        .map(|point| mask.flat_index(point.x, point.y))
+}
+
+// Set the cell at a flat index to `state`, fixing up the births and deaths counted for it against
+// its state in the previous generation. Returns whether the cell changed.
+pub(crate) fn force(
+    previous: &Board,
+    current: &mut Board,
+    index: usize,
+    state: bool,
+    changes: &mut Changes,
+) -> bool {
+    let before = previous.alive_at(index);
+    let cell = current.alive_at(index);
+    if cell == state {
+        return false;
+    }
+
+    count(before, cell, changes, |count| *count -= 1);
+    count(before, state, changes, |count| *count += 1);
+    current.set_at(index, state);
+    true
+}
+
+// Adjust the birth or death count for a cell going from one state to another, if it changed.
+fn count(before: bool, after: bool, changes: &mut Changes, adjust: impl FnOnce(&mut usize)) {
+    match (before, after) {
+        (false, true) => adjust(&mut changes.births),
+        (true, false) => adjust(&mut changes.deaths),
+        _ => {}
+    }
+}
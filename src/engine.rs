@@ -0,0 +1,179 @@
+use crate::{Game, HashLifeGame, LifeLike, SparseGame, UnboundedRule};
+
+// What every two-state engine can do, whichever way it stores its cells, so code that only sets
+// up a pattern, runs it, and reads it back can be handed a Game, a SparseGame, or a HashLifeGame.
+// Coordinates are i64s, like the unbounded engines', and a Game's board covers the cells from
+// (0, 0) to its size, with every cell off it dead.
+pub trait Engine {
+    fn get(&self, x: i64, y: i64) -> bool;
+
+    // Set the cell at (x, y) live or dead, returning false, and leaving everything as it was, if
+    // the engine has no cell there.
+    fn set(&mut self, x: i64, y: i64, alive: bool) -> bool;
+
+    fn clear(&mut self);
+
+    // Replace the rule, which the unbounded engines refuse to do for rules that birth cells with
+    // no live neighbors.
+    fn set_rule(&mut self, rule: LifeLike) -> Result<(), UnboundedRule>;
+
+    fn population(&self) -> u64;
+
+    // The coordinates of every live cell, in whatever order the engine finds them.
+    fn live_cells(&self) -> Vec<(i64, i64)>;
+
+    // Run the game forward a number of generations.
+    fn advance(&mut self, generations: u64);
+}
+
+// (x, y) as a cell on the game's board, if it's on it.
+fn on_board(game: &Game, x: i64, y: i64) -> Option<(usize, usize)> {
+    let x = usize::try_from(x).ok().filter(|&x| x < game.x_size())?;
+    let y = usize::try_from(y).ok().filter(|&y| y < game.y_size())?;
+    Some((x, y))
+}
+
+impl Engine for Game {
+    fn get(&self, x: i64, y: i64) -> bool {
+        on_board(self, x, y).is_some_and(|(x, y)| self.is_alive(x, y))
+    }
+
+    fn set(&mut self, x: i64, y: i64, alive: bool) -> bool {
+        let Some((x, y)) = on_board(self, x, y) else {
+            return false;
+        };
+        *self.cell(x, y) = alive;
+        true
+    }
+
+    fn clear(&mut self) {
+        Game::clear(self);
+    }
+
+    fn set_rule(&mut self, rule: LifeLike) -> Result<(), UnboundedRule> {
+        Game::set_rule(self, rule);
+        Ok(())
+    }
+
+    fn population(&self) -> u64 {
+        Game::population(self) as u64
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        Game::live_cells(self)
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect()
+    }
+
+    fn advance(&mut self, generations: u64) {
+        for _ in 0..generations {
+            self.iterate();
+        }
+    }
+}
+
+impl Engine for SparseGame {
+    fn get(&self, x: i64, y: i64) -> bool {
+        SparseGame::get(self, x, y)
+    }
+
+    fn set(&mut self, x: i64, y: i64, alive: bool) -> bool {
+        SparseGame::set(self, x, y, alive);
+        true
+    }
+
+    fn clear(&mut self) {
+        SparseGame::clear(self);
+    }
+
+    fn set_rule(&mut self, rule: LifeLike) -> Result<(), UnboundedRule> {
+        SparseGame::set_rule(self, rule)
+    }
+
+    fn population(&self) -> u64 {
+        SparseGame::population(self) as u64
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        SparseGame::live_cells(self).collect()
+    }
+
+    fn advance(&mut self, generations: u64) {
+        for _ in 0..generations {
+            self.iterate();
+        }
+    }
+}
+
+impl Engine for HashLifeGame {
+    fn get(&self, x: i64, y: i64) -> bool {
+        HashLifeGame::get(self, x, y)
+    }
+
+    fn set(&mut self, x: i64, y: i64, alive: bool) -> bool {
+        if !HashLifeGame::in_reach(x, y) {
+            return false;
+        }
+        HashLifeGame::set(self, x, y, alive);
+        true
+    }
+
+    fn clear(&mut self) {
+        HashLifeGame::clear(self);
+    }
+
+    fn set_rule(&mut self, rule: LifeLike) -> Result<(), UnboundedRule> {
+        HashLifeGame::set_rule(self, rule)
+    }
+
+    fn population(&self) -> u64 {
+        HashLifeGame::population(self)
+    }
+
+    fn live_cells(&self) -> Vec<(i64, i64)> {
+        HashLifeGame::live_cells(self).collect()
+    }
+
+    fn advance(&mut self, generations: u64) {
+        HashLifeGame::advance(self, generations);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_engines_agree() {
+        let mut engines: Vec<Box<dyn Engine>> = vec![
+            Box::new(Game::new(40, 40)),
+            Box::new(SparseGame::new()),
+            Box::new(HashLifeGame::new()),
+        ];
+        let mut runs = Vec::new();
+        for engine in &mut engines {
+            engine.set_rule(LifeLike::new(&[3, 6], &[2, 3])).unwrap();
+            for (x, y) in [(11, 10), (12, 11), (10, 12), (11, 12), (12, 12), (30, 30)] {
+                assert!(engine.set(x, y, true));
+            }
+            assert!(engine.get(30, 30));
+            engine.advance(40);
+            let mut cells = engine.live_cells();
+            cells.sort();
+            assert_eq!(cells.len() as u64, engine.population());
+            runs.push(cells);
+        }
+        assert_eq!(runs[0], runs[1]);
+        assert_eq!(runs[0], runs[2]);
+
+        // Only the bounded board has cells it can't set.
+        assert!(!engines[0].set(-1, 0, true));
+        assert!(!engines[0].get(40, 0));
+        assert!(engines[1].set(-1, 0, true));
+        assert!(!engines[2].set(i64::MIN, 0, true));
+        for engine in &mut engines {
+            engine.clear();
+            assert_eq!(0, engine.population());
+        }
+    }
+}
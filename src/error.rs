@@ -0,0 +1,38 @@
+use std::error::Error;
+use std::fmt;
+
+// Why a game couldn't be built or edited as asked, for callers passing along sizes and coordinates
+// that came from someone's input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GolError {
+    // A board needs at least one cell in each direction.
+    EmptyBoard {
+        x_size: usize,
+        y_size: usize,
+    },
+    // A coordinate that's off the board, along with the board's size.
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        x_size: usize,
+        y_size: usize,
+    },
+}
+
+impl fmt::Display for GolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GolError::EmptyBoard { x_size, y_size } => {
+                write!(f, "a {x_size}x{y_size} board has no cells")
+            }
+            GolError::OutOfBounds {
+                x,
+                y,
+                x_size,
+                y_size,
+            } => write!(f, "({x}, {y}) is off the {x_size}x{y_size} board"),
+        }
+    }
+}
+
+impl Error for GolError {}
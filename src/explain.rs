@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::rule::RuleTable;
+use crate::{Board, Point};
+
+// Why a single cell changed state during an iteration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Explanation {
+    pub point: Point,
+    pub reason: Reason,
+    // The number of live neighbors the cell had going into the iteration.
+    pub live_neighbors: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Reason {
+    // A dead cell had the right number of neighbors to come alive.
+    Born,
+    // A live cell had too few neighbors to survive.
+    Underpopulated,
+    // A live cell had too many neighbors to survive.
+    Overcrowded,
+    // A live cell died for some other reason, e.g. a rule with gaps in its survival counts or one
+    // that looks at where its neighbors are rather than how many there are.
+    Died,
+}
+
+impl Explanation {
+    pub fn is_birth(&self) -> bool {
+        self.reason == Reason::Born
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Point { x, y } = self.point;
+        let count = self.live_neighbors;
+        let plural = if count == 1 { "" } else { "s" };
+        match self.reason {
+            Reason::Born => write!(f, "born: {count} live neighbor{plural} at ({x}, {y})"),
+            Reason::Underpopulated => write!(
+                f,
+                "died: underpopulated with {count} neighbor{plural} at ({x}, {y})"
+            ),
+            Reason::Overcrowded => write!(
+                f,
+                "died: overcrowded with {count} neighbor{plural} at ({x}, {y})"
+            ),
+            Reason::Died => write!(f, "died: {count} live neighbor{plural} at ({x}, {y})"),
+        }
+    }
+}
+
+// Explain every cell that changed between two consecutive boards.
+pub(crate) fn explain(table: &RuleTable, previous: &Board, current: &Board) -> Vec<Explanation> {
+    // The range of neighbor counts a live cell survives on, if the rule only depends on counts.
+    let survival = table.totalistic().and_then(|totalistic| {
+        let min = totalistic[1].iter().position(|&survives| survives)?;
+        let max = totalistic[1].iter().rposition(|&survives| survives)?;
+        Some((min as u8, max as u8))
+    });
+
+    previous
+        .diff(current)
+        .map(|point| {
+            let live_neighbors = previous.live_neighbors(point.x, point.y);
+            let reason = match (current[(point.x, point.y)], survival) {
+                (true, _) => Reason::Born,
+                (false, Some((min, _))) if live_neighbors < min => Reason::Underpopulated,
+                (false, Some((_, max))) if live_neighbors > max => Reason::Overcrowded,
+                (false, _) => Reason::Died,
+            };
+            Explanation {
+                point,
+                reason,
+                live_neighbors,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LifeLike, Neighborhood};
+
+    #[test]
+    fn test_explain() {
+        let table = RuleTable::new(&LifeLike::CONWAY);
+        let previous: Board = "......\nO.OOO.\n...O..\n...O..\n......\n".parse().unwrap();
+        let current: Board = "......\n..OOO.\n......\n...O..\n......\n".parse().unwrap();
+
+        let explanations = explain(&table, &previous, &current);
+        assert_eq!(
+            vec![
+                Explanation {
+                    point: Point::new(0, 1),
+                    reason: Reason::Underpopulated,
+                    live_neighbors: 0,
+                },
+                Explanation {
+                    point: Point::new(3, 2),
+                    reason: Reason::Overcrowded,
+                    live_neighbors: 4,
+                },
+            ],
+            explanations
+        );
+    }
+
+    #[test]
+    fn test_explain_birth() {
+        let table = RuleTable::new(&LifeLike::CONWAY);
+        let previous: Board = "OOO\n...\n...\n".parse().unwrap();
+        let current: Board = "OOO\nOOO\n...\n".parse().unwrap();
+        let explanations = explain(&table, &previous, &current);
+        assert_eq!(3, explanations.len());
+        assert!(explanations.iter().all(Explanation::is_birth));
+        assert_eq!(
+            "born: 3 live neighbors at (0, 1)",
+            explanations[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_explain_non_totalistic() {
+        let rule = |alive: bool, _: Neighborhood| !alive;
+        let table = RuleTable::new(&rule);
+        let previous: Board = "O.\n..\n".parse().unwrap();
+        let current: Board = ".O\nOO\n".parse().unwrap();
+        let explanations = explain(&table, &previous, &current);
+        assert_eq!(Reason::Died, explanations[0].reason);
+        assert_eq!(
+            "died: 0 live neighbors at (0, 0)",
+            explanations[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_explanation_display() {
+        let explanation = Explanation {
+            point: Point::new(3, 4),
+            reason: Reason::Overcrowded,
+            live_neighbors: 4,
+        };
+        assert_eq!(
+            "died: overcrowded with 4 neighbors at (3, 4)",
+            explanation.to_string()
+        );
+        let explanation = Explanation {
+            reason: Reason::Underpopulated,
+            live_neighbors: 1,
+            ..explanation
+        };
+        assert_eq!(
+            "died: underpopulated with 1 neighbor at (3, 4)",
+            explanation.to_string()
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use crate::rule::RuleTable;
+use crate::{Board, Changes, GridError, LifeLike, Neighborhood, Rule};
+
+// A game whose W x H board size is fixed at compile time. The cells live inline in the struct, so
+// a FixedGame never allocates, and the compiler knows every loop bound and wrap. It's meant for
+// small boards in tests, on embedded targets, and in the inner loops of searches, where a Game's
+// heap-allocated boards and backend bookkeeping cost more than the stepping itself.
+//
+// Like a Game with the default topology, the board wraps around its edges.
+#[derive(Clone)]
+pub struct FixedGame<const W: usize, const H: usize> {
+    // Stored column by column like Board, so the cell at (x, y) is cells[x][y].
+    cells: [[bool; H]; W],
+    table: RuleTable,
+}
+
+impl<const W: usize, const H: usize> FixedGame<W, H> {
+    // Build a new, empty game running Conway's rule.
+    pub fn new() -> Self {
+        const { assert!(W > 0 && H > 0, "a FixedGame needs at least one cell") };
+        FixedGame {
+            cells: [[false; H]; W],
+            table: RuleTable::new(&LifeLike::CONWAY),
+        }
+    }
+
+    // Build a new game, setting each cell live or dead according to f(x, y).
+    pub fn from_fn<F: FnMut(usize, usize) -> bool>(mut f: F) -> Self {
+        let mut game = FixedGame::new();
+        for (x, column) in game.cells.iter_mut().enumerate() {
+            for (y, cell) in column.iter_mut().enumerate() {
+                *cell = f(x, y);
+            }
+        }
+        game
+    }
+
+    // Replace the rule used for every following iteration.
+    pub fn set_rule<R: Rule>(&mut self, rule: R) {
+        self.table = RuleTable::new(&rule);
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells[x][y]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        self.cells[x][y] = alive;
+    }
+
+    // Flip the cell at (x, y) between live and dead, returning its new state.
+    pub fn toggle(&mut self, x: usize, y: usize) -> bool {
+        self.cells[x][y] = !self.cells[x][y];
+        self.cells[x][y]
+    }
+
+    pub fn clear(&mut self) {
+        self.cells = [[false; H]; W];
+    }
+
+    // How many cells are live.
+    pub fn population(&self) -> usize {
+        self.cells.iter().flatten().filter(|&&live| live).count()
+    }
+
+    // Run a single iteration of the game, reporting how many cells were born and died.
+    pub fn iterate(&mut self) -> Changes {
+        let previous = self.cells;
+        let mut changes = Changes::default();
+        for x in 0..W {
+            let (left, right) = ((x + W - 1) % W, (x + 1) % W);
+            for y in 0..H {
+                let (up, down) = ((y + H - 1) % H, (y + 1) % H);
+                let neighbors = [
+                    previous[left][up],
+                    previous[x][up],
+                    previous[right][up],
+                    previous[left][y],
+                    previous[right][y],
+                    previous[left][down],
+                    previous[x][down],
+                    previous[right][down],
+                ];
+                let bits = neighbors
+                    .iter()
+                    .enumerate()
+                    .fold(0, |bits, (bit, &live)| bits | (live as u8) << bit);
+
+                let alive = previous[x][y];
+                let next = self.table.next(alive, Neighborhood::from_bits(bits));
+                match (alive, next) {
+                    (false, true) => changes.births += 1,
+                    (true, false) => changes.deaths += 1,
+                    _ => {}
+                }
+                self.cells[x][y] = next;
+            }
+        }
+        changes
+    }
+
+    // Run the simulation for a set number of iterations.
+    pub fn run(&mut self, iters: usize) {
+        for _ in 0..iters {
+            self.iterate();
+        }
+    }
+
+    pub fn x_size(&self) -> usize {
+        W
+    }
+
+    pub fn y_size(&self) -> usize {
+        H
+    }
+
+    // A heap-allocated copy of the board, for everything that works on Boards.
+    pub fn to_board(&self) -> Board {
+        Board::from_fn(W, H, |x, y| self.cells[x][y])
+    }
+}
+
+impl<const W: usize, const H: usize> Default for FixedGame<W, H> {
+    fn default() -> Self {
+        FixedGame::new()
+    }
+}
+
+// Copy a board into a fixed game. The board has to be exactly W x H.
+impl<const W: usize, const H: usize> TryFrom<&Board> for FixedGame<W, H> {
+    type Error = GridError;
+
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        let found = (board.x_size(), board.y_size());
+        if found != (W, H) {
+            return Err(GridError::WrongSize {
+                expected: (W, H),
+                found,
+            });
+        }
+        Ok(FixedGame::from_fn(|x, y| board[(x, y)]))
+    }
+}
+
+// Fixed games print like Boards, one line per row with 'O' for live cells and '.' for dead ones.
+impl<const W: usize, const H: usize> fmt::Display for FixedGame<W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..H {
+            for x in 0..W {
+                write!(f, "{}", if self.cells[x][y] { 'O' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Game;
+
+    #[test]
+    fn test_blinker() {
+        let mut game = FixedGame::<5, 5>::new();
+        game.set(2, 1, true);
+        game.set(2, 2, true);
+        game.set(2, 3, true);
+        assert_eq!(
+            Changes {
+                births: 2,
+                deaths: 2
+            },
+            game.iterate()
+        );
+        assert_eq!(".....\n.....\n.OOO.\n.....\n.....\n", game.to_string());
+        assert_eq!(3, game.population());
+    }
+
+    #[test]
+    fn test_matches_game() {
+        let mut game = Game::random(9, 6, 0.4, 21);
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        let mut fixed = FixedGame::<9, 6>::try_from(game.board()).unwrap();
+        fixed.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        for _ in 0..20 {
+            assert_eq!(game.iterate(), fixed.iterate());
+            assert_eq!(*game.board(), fixed.to_board());
+        }
+    }
+
+    #[test]
+    fn test_wrong_size() {
+        let board = Board::new(4, 3);
+        assert_eq!(
+            Err(GridError::WrongSize {
+                expected: (3, 4),
+                found: (4, 3)
+            }),
+            FixedGame::<3, 4>::try_from(&board).map(|_| ())
+        );
+    }
+}
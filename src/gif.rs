@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::Board;
+
+// The smallest LZW code size GIF allows, which is plenty for two colors.
+const MIN_CODE_SIZE: u8 = 2;
+const CLEAR: u16 = 1 << MIN_CODE_SIZE;
+const END: u16 = CLEAR + 1;
+// LZW codes never grow past 12 bits.
+const MAX_CODES: u16 = 1 << 12;
+
+// Writes boards as the frames of a looping two-color animated GIF, one square of `scale` pixels
+// per cell. The header goes out with the first frame, so the settings can be changed up until
+// then, and every frame has to be the same size as the first.
+pub struct GifWriter<W: Write> {
+    out: W,
+    scale: usize,
+    // How long each frame is shown, in hundredths of a second.
+    delay: u16,
+    dead: [u8; 3],
+    live: [u8; 3],
+    // The board size, once the header's been written.
+    size: Option<(usize, usize)>,
+}
+
+impl<W: Write> GifWriter<W> {
+    pub fn new(out: W) -> Self {
+        GifWriter {
+            out,
+            scale: 1,
+            delay: 10,
+            dead: [0xff, 0xff, 0xff],
+            live: [0x00, 0x00, 0x00],
+            size: None,
+        }
+    }
+
+    pub fn set_scale(&mut self, scale: usize) {
+        self.scale = scale.max(1);
+    }
+
+    // Show each frame for the given number of hundredths of a second.
+    pub fn set_delay(&mut self, delay: u16) {
+        self.delay = delay;
+    }
+
+    pub fn set_colors(&mut self, dead: [u8; 3], live: [u8; 3]) {
+        self.dead = dead;
+        self.live = live;
+    }
+
+    // Add a frame showing the board.
+    pub fn write(&mut self, board: &Board) -> io::Result<()> {
+        let size = (board.x_size(), board.y_size());
+        match self.size {
+            None => self.header(size)?,
+            Some(first) if first != size => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "a {}x{} frame can't follow {}x{} ones",
+                        size.0, size.1, first.0, first.1
+                    ),
+                ));
+            }
+            Some(_) => {}
+        }
+
+        let (width, height) = self.pixel_size(size)?;
+        let [delay_low, delay_high] = self.delay.to_le_bytes();
+        // A graphic control extension with the frame's delay, then an image descriptor covering
+        // the whole screen with no local color table.
+        self.out
+            .write_all(&[0x21, 0xf9, 4, 0, delay_low, delay_high, 0, 0])?;
+        self.out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        self.out.write_all(&[0, MIN_CODE_SIZE])?;
+
+        let scale = self.scale;
+        let pixels = (0..size.1 * scale).flat_map(|py| {
+            (0..size.0 * scale).map(move |px| u8::from(board[(px / scale, py / scale)]))
+        });
+        for block in compress(pixels).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])
+    }
+
+    // End the animation, handing back the output. An animation with no frames is left empty.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.size.is_some() {
+            self.out.write_all(&[0x3b])?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn header(&mut self, size: (usize, usize)) -> io::Result<()> {
+        let (width, height) = self.pixel_size(size)?;
+        self.out.write_all(b"GIF89a")?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        // A global color table of two colors, dead cells first.
+        self.out.write_all(&[0x80, 0, 0])?;
+        self.out.write_all(&self.dead)?;
+        self.out.write_all(&self.live)?;
+        // The Netscape extension that makes the animation loop forever.
+        self.out.write_all(&[0x21, 0xff, 11])?;
+        self.out.write_all(b"NETSCAPE2.0")?;
+        self.out.write_all(&[3, 1, 0, 0, 0])?;
+        self.size = Some(size);
+        Ok(())
+    }
+
+    fn pixel_size(&self, (x_size, y_size): (usize, usize)) -> io::Result<(u16, u16)> {
+        let pixels = |cells: usize| {
+            cells
+                .checked_mul(self.scale)
+                .and_then(|pixels| u16::try_from(pixels).ok())
+        };
+        match (pixels(x_size), pixels(y_size)) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "GIFs can't be more than 65535 pixels across",
+            )),
+        }
+    }
+}
+
+// LZW-compress color indexes the way GIF expects, with codes packed least significant bit first.
+fn compress<I: Iterator<Item = u8>>(mut pixels: I) -> Vec<u8> {
+    let mut bits = Bits::default();
+    let Some(first) = pixels.next() else {
+        bits.push(CLEAR, MIN_CODE_SIZE + 1);
+        bits.push(END, MIN_CODE_SIZE + 1);
+        return bits.finish();
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = END + 1;
+    let mut size = MIN_CODE_SIZE + 1;
+    bits.push(CLEAR, size);
+    let mut prefix = u16::from(first);
+    for pixel in pixels {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+
+        bits.push(prefix, size);
+        // Widen the codes at the same point a decoder will, as soon as the next one won't fit.
+        if next >= 1 << size && size < 12 {
+            size += 1;
+        }
+        if next < MAX_CODES {
+            table.insert((prefix, pixel), next);
+            next += 1;
+        } else {
+            bits.push(CLEAR, size);
+            table.clear();
+            next = END + 1;
+            size = MIN_CODE_SIZE + 1;
+        }
+        prefix = u16::from(pixel);
+    }
+    bits.push(prefix, size);
+    if next >= 1 << size && size < 12 {
+        size += 1;
+    }
+    bits.push(END, size);
+    bits.finish()
+}
+
+#[derive(Default)]
+struct Bits {
+    bytes: Vec<u8>,
+    pending: u32,
+    count: u8,
+}
+
+impl Bits {
+    fn push(&mut self, code: u16, size: u8) {
+        self.pending |= u32::from(code) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.pending as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Decode LZW data the way a GIF reader would.
+    fn decompress(bytes: &[u8]) -> Vec<u8> {
+        let mut position = 0;
+        let mut read = |size: u8| {
+            let mut code = 0;
+            for bit in 0..size {
+                let index = position + bit as usize;
+                code |= u16::from(bytes[index / 8] >> (index % 8) & 1) << bit;
+            }
+            position += size as usize;
+            code
+        };
+
+        let mut output = Vec::new();
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut size = MIN_CODE_SIZE + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(size);
+            if code == CLEAR {
+                table = (0..CLEAR).map(|index| vec![index as u8]).collect();
+                table.extend([Vec::new(), Vec::new()]);
+                size = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+            if code == END {
+                return output;
+            }
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = previous.clone();
+                    entry.push(previous[0]);
+                    entry
+                }
+                (None, None) => panic!("code {code} before anything was decoded"),
+            };
+            output.extend(&entry);
+            if let Some(mut previous) = previous.take() {
+                if table.len() < MAX_CODES as usize {
+                    previous.push(entry[0]);
+                    table.push(previous);
+                }
+            }
+            if table.len() >= 1 << size && size < 12 {
+                size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_compress() {
+        let mut state = 12345u32;
+        let noisy: Vec<u8> = (0..200_000)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as u8 & 1
+            })
+            .collect();
+        for pixels in [vec![], vec![1], vec![0; 10000], noisy] {
+            assert_eq!(pixels, decompress(&compress(pixels.iter().copied())));
+        }
+    }
+
+    #[test]
+    fn test_write() {
+        let mut writer = GifWriter::new(Vec::new());
+        writer.set_scale(2);
+        writer
+            .write(&Board::from_fn(3, 2, |x, y| (x, y) == (1, 0)))
+            .unwrap();
+        writer
+            .write(&Board::from_fn(3, 2, |x, y| x == y + 1))
+            .unwrap();
+        assert!(writer.write(&Board::new(2, 2)).is_err());
+
+        let gif = writer.finish().unwrap();
+        assert!(gif.starts_with(b"GIF89a\x06\x00\x04\x00"));
+        assert_eq!(Some(&0x3b), gif.last());
+        assert_eq!(2, gif.windows(2).filter(|w| w == &[0x21, 0xf9]).count());
+    }
+}
@@ -0,0 +1,732 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Palette, StateRule};
+
+// A rule loaded from a Golly .rule file, from either its @TABLE or its @TREE section. See
+// https://golly.sourceforge.io/Help/formats.html#rule for the format.
+#[derive(Clone, Debug)]
+pub struct GollyRule {
+    name: String,
+    states: u8,
+    kind: Kind,
+    // The colors from the file's @COLORS section, by state.
+    colors: Vec<(u8, [u8; 3])>,
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Table(Table),
+    Tree(Tree),
+}
+
+impl GollyRule {
+    // Parse the contents of a .rule file. When a file has both a @TABLE and a @TREE, the table is
+    // used.
+    pub fn parse(source: &str) -> Result<Self, RuleFileError> {
+        let mut name = None;
+        let mut table = None;
+        let mut tree = None;
+        let mut colors = Vec::new();
+
+        let mut section = "";
+        let mut lines = Vec::new();
+        let mut finish = |section: &str, lines: &mut Vec<(usize, &str)>| {
+            let result = match section {
+                "@TABLE" => Table::parse(lines).map(|parsed| table = Some(parsed)),
+                "@TREE" => Tree::parse(lines).map(|parsed| tree = Some(parsed)),
+                "@COLORS" => parse_colors(lines).map(|parsed| colors = parsed),
+                _ => Ok(()),
+            };
+            lines.clear();
+            result
+        };
+
+        for (index, line) in source.lines().enumerate() {
+            let number = index + 1;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('@') {
+                finish(section, &mut lines)?;
+                let mut words = line.split_whitespace();
+                section = words.next().unwrap_or_default();
+                if section == "@RULE" {
+                    name = words.next().map(str::to_string);
+                }
+            } else {
+                lines.push((number, line));
+            }
+        }
+        finish(section, &mut lines)?;
+
+        let name = name.ok_or_else(|| RuleFileError::new(0, "missing @RULE line"))?;
+        let (states, kind) = match (table, tree) {
+            (Some(table), _) => (table.states, Kind::Table(table)),
+            (None, Some(tree)) => (tree.states, Kind::Tree(tree)),
+            (None, None) => {
+                return Err(RuleFileError::new(0, "no @TABLE or @TREE section"));
+            }
+        };
+        Ok(GollyRule {
+            name,
+            states,
+            kind,
+            colors,
+        })
+    }
+
+    // Read and parse a .rule file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RuleFileError> {
+        let source = fs::read_to_string(path).map_err(RuleFileError::from)?;
+        GollyRule::parse(&source)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl StateRule for GollyRule {
+    fn states(&self) -> u8 {
+        self.states
+    }
+
+    fn transition(&self, cell: u8, neighbors: [u8; 8]) -> u8 {
+        match &self.kind {
+            Kind::Table(table) => table.transition(cell, neighbors),
+            Kind::Tree(tree) => tree.transition(cell, neighbors),
+        }
+    }
+
+    // The gradient, with the colors from the file's @COLORS section over it.
+    fn palette(&self) -> Palette {
+        let mut palette = Palette::gradient(self.states);
+        for &(state, color) in &self.colors {
+            if state < self.states {
+                palette.set_color(state, color);
+            }
+        }
+        palette
+    }
+}
+
+// The lines of a @COLORS section, each a state and its red, green and blue.
+fn parse_colors(lines: &[(usize, &str)]) -> Result<Vec<(u8, [u8; 3])>, RuleFileError> {
+    lines
+        .iter()
+        .map(|&(number, line)| {
+            let values: Result<Vec<u8>, _> = line.split_whitespace().map(str::parse).collect();
+            match values.as_deref() {
+                Ok(&[state, red, green, blue]) => Ok((state, [red, green, blue])),
+                _ => Err(RuleFileError::new(
+                    number,
+                    format!("expected a state and its red, green and blue, found {line:?}"),
+                )),
+            }
+        })
+        .collect()
+}
+
+// Which of the eight Moore neighbors (in StateRule order: N, NE, E, SE, S, SW, W, NW) a
+// neighborhood uses, in the order Golly lists them in transitions.
+const MOORE: &[usize] = &[0, 1, 2, 3, 4, 5, 6, 7];
+const VON_NEUMANN: &[usize] = &[0, 2, 4, 6];
+const HEXAGONAL: &[usize] = &[0, 2, 3, 4, 6, 7];
+
+#[derive(Clone, Debug)]
+struct Table {
+    states: u8,
+    neighbors: &'static [usize],
+    symmetry: Symmetry,
+    transitions: Vec<Transition>,
+}
+
+#[derive(Clone, Debug)]
+enum Symmetry {
+    // Each transition also applies to its neighbors rearranged by any of these permutations.
+    Permutations(Vec<Vec<usize>>),
+    // Each transition applies to any rearrangement of its neighbors.
+    Permute,
+}
+
+#[derive(Clone, Debug)]
+struct Transition {
+    cell: Term,
+    neighbors: Vec<Term>,
+    output: Term,
+}
+
+#[derive(Clone, Debug)]
+enum Term {
+    State(u8),
+    // A variable by index, with the states it can take. Every use of the same variable within a
+    // transition has to take the same state.
+    Var(usize, Vec<u8>),
+}
+
+impl Table {
+    fn parse(lines: &[(usize, &str)]) -> Result<Self, RuleFileError> {
+        let mut states = None;
+        let mut neighbors = None;
+        let mut symmetry_name = None;
+        let mut vars: HashMap<String, (usize, Vec<u8>)> = HashMap::new();
+        let mut transitions = Vec::new();
+
+        for &(number, line) in lines {
+            let error = |message: String| RuleFileError::new(number, message);
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "n_states" => {
+                        let count: u16 = value
+                            .parse()
+                            .map_err(|_| error(format!("invalid n_states {value:?}")))?;
+                        if !(2..=255).contains(&count) {
+                            return Err(error(format!("n_states must be 2 to 255, not {count}")));
+                        }
+                        states = Some(count as u8);
+                    }
+                    "neighborhood" => {
+                        neighbors = Some(match value {
+                            "Moore" => MOORE,
+                            "vonNeumann" => VON_NEUMANN,
+                            "hexagonal" => HEXAGONAL,
+                            _ => return Err(error(format!("unsupported neighborhood {value:?}"))),
+                        })
+                    }
+                    "symmetries" => symmetry_name = Some(value.to_string()),
+                    key => return Err(error(format!("unknown table setting {key:?}"))),
+                }
+                continue;
+            }
+
+            let states = states.ok_or_else(|| error("n_states must come first".to_string()))?;
+            let state = |token: &str| -> Result<u8, RuleFileError> {
+                match token.parse::<u8>() {
+                    Ok(state) if state < states => Ok(state),
+                    _ => Err(error(format!("invalid state {token:?}"))),
+                }
+            };
+
+            if let Some(definition) = line.strip_prefix("var ") {
+                let (name, values) = definition
+                    .split_once('=')
+                    .ok_or_else(|| error("expected var name={states}".to_string()))?;
+                let values = values.trim().trim_start_matches('{').trim_end_matches('}');
+                let mut domain = Vec::new();
+                for token in values.split(',').map(str::trim) {
+                    match vars.get(token) {
+                        Some((_, other)) => domain.extend(other),
+                        None => domain.push(state(token)?),
+                    }
+                }
+                domain.sort_unstable();
+                domain.dedup();
+                let index = vars.len();
+                vars.insert(name.trim().to_string(), (index, domain));
+                continue;
+            }
+
+            let neighbors = neighbors.unwrap_or(MOORE);
+            let tokens: Vec<&str> = if line.contains(',') {
+                line.split(',').map(str::trim).collect()
+            } else if line.chars().all(|c| c.is_ascii_digit()) {
+                (0..line.len()).map(|i| &line[i..i + 1]).collect()
+            } else {
+                line.split_whitespace().collect()
+            };
+            if tokens.len() != neighbors.len() + 2 {
+                return Err(error(format!(
+                    "expected {} states in a transition, found {}",
+                    neighbors.len() + 2,
+                    tokens.len()
+                )));
+            }
+
+            let term = |token: &str| match vars.get(token) {
+                Some((index, domain)) => Ok(Term::Var(*index, domain.clone())),
+                None => state(token).map(Term::State),
+            };
+            let inputs = tokens[..tokens.len() - 1]
+                .iter()
+                .map(|token| term(token))
+                .collect::<Result<Vec<_>, _>>()?;
+            let output = term(tokens[tokens.len() - 1])?;
+            if let Term::Var(var, _) = output {
+                let bound = inputs
+                    .iter()
+                    .any(|input| matches!(input, Term::Var(other, _) if *other == var));
+                if !bound {
+                    return Err(error("output variable isn't used as an input".to_string()));
+                }
+            }
+
+            transitions.push(Transition {
+                cell: inputs[0].clone(),
+                neighbors: inputs[1..].to_vec(),
+                output,
+            });
+        }
+
+        let (states, neighbors) = match (states, neighbors) {
+            (Some(states), Some(neighbors)) => (states, neighbors),
+            _ => return Err(RuleFileError::new(0, "missing n_states or neighborhood")),
+        };
+        let symmetry_name = symmetry_name.unwrap_or_else(|| "none".to_string());
+        let symmetry = Symmetry::parse(&symmetry_name, neighbors.len()).ok_or_else(|| {
+            RuleFileError::new(0, format!("unsupported symmetries {symmetry_name:?}"))
+        })?;
+
+        Ok(Table {
+            states,
+            neighbors,
+            symmetry,
+            transitions,
+        })
+    }
+
+    fn transition(&self, cell: u8, moore: [u8; 8]) -> u8 {
+        let neighbors: Vec<u8> = self.neighbors.iter().map(|&index| moore[index]).collect();
+        for transition in &self.transitions {
+            let mut bindings = HashMap::new();
+            if !transition.cell.matches(cell, &mut bindings) {
+                continue;
+            }
+
+            let matched = match &self.symmetry {
+                Symmetry::Permutations(permutations) => permutations.iter().find_map(|order| {
+                    let mut bindings = bindings.clone();
+                    let all = transition
+                        .neighbors
+                        .iter()
+                        .zip(order)
+                        .all(|(term, &index)| term.matches(neighbors[index], &mut bindings));
+                    all.then_some(bindings)
+                }),
+                Symmetry::Permute => {
+                    let mut used = vec![false; neighbors.len()];
+                    match_any_order(&transition.neighbors, &neighbors, &mut used, &mut bindings)
+                        .then_some(bindings)
+                }
+            };
+
+            if let Some(bindings) = matched {
+                return match &transition.output {
+                    Term::State(state) => *state,
+                    Term::Var(var, _) => bindings[var],
+                };
+            }
+        }
+        cell
+    }
+}
+
+impl Term {
+    // Whether the term can take this state, binding variables that haven't been seen yet.
+    fn matches(&self, state: u8, bindings: &mut HashMap<usize, u8>) -> bool {
+        match self {
+            Term::State(expected) => *expected == state,
+            Term::Var(var, domain) => match bindings.get(var) {
+                Some(&bound) => bound == state,
+                None if domain.contains(&state) => {
+                    bindings.insert(*var, state);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+// Match every term against a distinct neighbor, in any order, backtracking over the choices.
+fn match_any_order(
+    terms: &[Term],
+    neighbors: &[u8],
+    used: &mut [bool],
+    bindings: &mut HashMap<usize, u8>,
+) -> bool {
+    let Some((term, rest)) = terms.split_first() else {
+        return true;
+    };
+    for index in 0..neighbors.len() {
+        if used[index] {
+            continue;
+        }
+        let mut attempt = bindings.clone();
+        if term.matches(neighbors[index], &mut attempt) {
+            used[index] = true;
+            if match_any_order(rest, neighbors, used, &mut attempt) {
+                *bindings = attempt;
+                return true;
+            }
+            used[index] = false;
+        }
+    }
+    false
+}
+
+impl Symmetry {
+    // The symmetry named in a table for a neighborhood with this many neighbors, listed in
+    // clockwise order from north.
+    fn parse(name: &str, neighbors: usize) -> Option<Self> {
+        let rotation = |by: usize| (0..neighbors).map(|i| (i + by) % neighbors).collect();
+        let reflection = |order: &Vec<usize>| -> Vec<usize> {
+            order.iter().map(|&i| (neighbors - i) % neighbors).collect()
+        };
+        let rotations = |count: usize| -> Vec<Vec<usize>> {
+            (0..count)
+                .map(|step| rotation(step * neighbors / count))
+                .collect()
+        };
+        let with_reflections = |orders: Vec<Vec<usize>>| -> Vec<Vec<usize>> {
+            let reflected: Vec<_> = orders.iter().map(reflection).collect();
+            orders.into_iter().chain(reflected).collect()
+        };
+
+        let permutations = match (name, neighbors) {
+            ("none", _) => rotations(1),
+            ("permute", _) => return Some(Symmetry::Permute),
+            ("reflect_horizontal", 8 | 4) => with_reflections(rotations(1)),
+            ("rotate4", 8 | 4) => rotations(4),
+            ("rotate4reflect", 8 | 4) => with_reflections(rotations(4)),
+            ("rotate8", 8) => rotations(8),
+            ("rotate8reflect", 8) => with_reflections(rotations(8)),
+            ("rotate2", 6) => rotations(2),
+            ("rotate3", 6) => rotations(3),
+            ("rotate6", 6) => rotations(6),
+            ("rotate6reflect", 6) => with_reflections(rotations(6)),
+            _ => return None,
+        };
+        Some(Symmetry::Permutations(permutations))
+    }
+}
+
+// A decision tree over the neighbors' states: each level picks a child by the state of one
+// neighbor, and the bottom level holds the next state.
+#[derive(Clone, Debug)]
+struct Tree {
+    states: u8,
+    neighbors: usize,
+    nodes: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    fn parse(lines: &[(usize, &str)]) -> Result<Self, RuleFileError> {
+        let mut settings = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut levels = Vec::new();
+        for &(number, line) in lines {
+            let error = |message: String| RuleFileError::new(number, message);
+            if let Some((key, value)) = line.split_once('=') {
+                let value: usize = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| error(format!("invalid value for {}", key.trim())))?;
+                settings.insert(key.trim().to_string(), value);
+                continue;
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<usize>, _>>()
+                .map_err(|_| error("invalid tree node".to_string()))?;
+            let (&level, children) = values
+                .split_first()
+                .ok_or_else(|| error("empty tree node".to_string()))?;
+            // Level 1 nodes hold next states, and every other node points at earlier nodes one
+            // level down.
+            let valid = match level {
+                0 => false,
+                1 => children.iter().all(|&child| child < 256),
+                _ => children
+                    .iter()
+                    .all(|&child| levels.get(child) == Some(&(level - 1))),
+            };
+            if !valid {
+                return Err(error("tree node refers to a missing node".to_string()));
+            }
+            levels.push(level);
+            nodes.push(children.to_vec());
+        }
+
+        let setting = |key: &str| {
+            settings
+                .get(key)
+                .copied()
+                .ok_or_else(|| RuleFileError::new(0, format!("missing {key}")))
+        };
+        let states = setting("num_states")?;
+        let neighbors = setting("num_neighbors")?;
+        if !(2..=255).contains(&states) || !matches!(neighbors, 4 | 8) {
+            return Err(RuleFileError::new(0, "unsupported tree dimensions"));
+        }
+        if nodes.iter().any(|node| node.len() != states) || levels.last() != Some(&(neighbors + 1))
+        {
+            return Err(RuleFileError::new(
+                0,
+                "tree nodes don't match num_states and num_neighbors",
+            ));
+        }
+        let outputs = nodes.iter().zip(&levels).filter(|(_, &level)| level == 1);
+        if outputs
+            .flat_map(|(node, _)| node)
+            .any(|&state| state >= states)
+        {
+            return Err(RuleFileError::new(0, "tree has a state past num_states"));
+        }
+
+        Ok(Tree {
+            states: states as u8,
+            neighbors,
+            nodes,
+        })
+    }
+
+    fn transition(&self, cell: u8, moore: [u8; 8]) -> u8 {
+        let [n, ne, e, se, s, sw, w, nw] = moore;
+        let order = match self.neighbors {
+            8 => vec![nw, ne, sw, se, n, w, e, s, cell],
+            _ => vec![n, w, e, s, cell],
+        };
+
+        // The root is the last node, and each state picks the next node down.
+        let mut node = self.nodes.len() - 1;
+        for (depth, state) in order.into_iter().enumerate() {
+            let next = self.nodes[node][state as usize];
+            if depth == self.neighbors {
+                return next as u8;
+            }
+            node = next;
+        }
+        cell
+    }
+}
+
+// A problem reading a .rule file, with the line it was found on (0 when it isn't tied to one).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleFileError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl RuleFileError {
+    fn new<S: Into<String>>(line: usize, message: S) -> Self {
+        RuleFileError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<io::Error> for RuleFileError {
+    fn from(err: io::Error) -> Self {
+        RuleFileError::new(0, err.to_string())
+    }
+}
+
+impl fmt::Display for RuleFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl Error for RuleFileError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::StateGame;
+
+    const WIREWORLD: &str = "@RULE WireWorld
+
+# A simple electronics rule.
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={0,1,2,3}
+var c={0,1,2,3}
+var d={0,1,2,3}
+var e={0,1,2,3}
+var f={0,1,2,3}
+var g={0,1,2,3}
+var h={0,2,3}
+var i={0,2,3}
+var j={0,2,3}
+var k={0,2,3}
+var l={0,2,3}
+var m={0,2,3}
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+3,1,i,j,k,l,m,h,i,1
+3,1,1,j,k,l,m,h,i,1
+
+@COLORS
+1 255 255 255
+";
+
+    // Conway's Life written as a tree, generated the way Golly's RuleTreeGen does.
+    fn life_tree() -> String {
+        let mut nodes: Vec<(usize, Vec<usize>)> = Vec::new();
+        let mut memo = HashMap::new();
+        fn node(
+            depth: usize,
+            count: usize,
+            nodes: &mut Vec<(usize, Vec<usize>)>,
+            memo: &mut HashMap<(usize, usize), usize>,
+        ) -> usize {
+            if let Some(&index) = memo.get(&(depth, count)) {
+                return index;
+            }
+            let children = if depth == 8 {
+                // The last input is the cell itself; children are the next states.
+                vec![(count == 3) as usize, (count == 2 || count == 3) as usize]
+            } else {
+                (0..2)
+                    .map(|state| node(depth + 1, count + state, nodes, memo))
+                    .collect()
+            };
+            nodes.push((9 - depth, children));
+            memo.insert((depth, count), nodes.len() - 1);
+            nodes.len() - 1
+        }
+        node(0, 0, &mut nodes, &mut memo);
+
+        let mut source = format!(
+            "@RULE LifeTree\n@TREE\nnum_states=2\nnum_neighbors=8\nnum_nodes={}\n",
+            nodes.len()
+        );
+        for (level, children) in nodes {
+            let children: Vec<String> = children.iter().map(usize::to_string).collect();
+            source.push_str(&format!("{level} {}\n", children.join(" ")));
+        }
+        source
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let rule = GollyRule::parse(WIREWORLD).unwrap();
+        assert_eq!("WireWorld", rule.name());
+        assert_eq!(4, rule.states());
+        assert_eq!(2, rule.transition(1, [0; 8]));
+        assert_eq!(3, rule.transition(2, [3; 8]));
+        assert_eq!(1, rule.transition(3, [0, 0, 0, 1, 0, 0, 0, 0]));
+        assert_eq!(1, rule.transition(3, [1, 0, 0, 1, 0, 0, 0, 0]));
+        assert_eq!(3, rule.transition(3, [1, 1, 1, 0, 0, 0, 0, 0]));
+        assert_eq!(0, rule.transition(0, [1; 8]));
+    }
+
+    #[test]
+    fn test_parse_colors() {
+        let source = format!("{WIREWORLD}\n@COLORS\n1 0 128 255\n3 255 128 0\n9 1 1 1\n");
+        let palette = GollyRule::parse(&source).unwrap().palette();
+        assert_eq!(4, palette.len());
+        assert_eq!([0, 128, 255], palette.color(1));
+        assert_eq!(Palette::gradient(4).color(2), palette.color(2));
+        assert_eq!([255, 128, 0], palette.color(3));
+
+        let err = GollyRule::parse(&format!("{WIREWORLD}\n@COLORS\n1 0 128\n")).unwrap_err();
+        assert_eq!(err.line, WIREWORLD.lines().count() + 3);
+    }
+
+    #[test]
+    fn test_table_runs_wireworld() {
+        let rule = GollyRule::parse(WIREWORLD).unwrap();
+        let mut game = StateGame::new(6, 3, rule);
+        for x in 0..6 {
+            game.set(x, 1, 3);
+        }
+        game.set(1, 1, 1);
+        game.set(0, 1, 2);
+        game.iterate();
+        assert_eq!(
+            vec![3, 2, 1, 3, 3, 3],
+            (0..6).map(|x| game.get(x, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_table_symmetry_and_bound_vars() {
+        let source = "@RULE Spread
+@TABLE
+n_states:3
+neighborhood:vonNeumann
+symmetries:rotate4
+var a={1,2}
+var b={0,1,2}
+# a dead cell with the same live state to its north and east copies it
+0,a,a,b,b,a
+";
+        let rule = GollyRule::parse(source).unwrap();
+        // north and east
+        assert_eq!(2, rule.transition(0, [2, 0, 2, 0, 0, 0, 0, 0]));
+        // rotated: south and west
+        assert_eq!(1, rule.transition(0, [0, 0, 0, 0, 1, 0, 1, 0]));
+        // the bound variable has to match
+        assert_eq!(0, rule.transition(0, [1, 0, 2, 0, 0, 0, 0, 0]));
+        // opposite sides aren't a rotation of adjacent ones
+        assert_eq!(0, rule.transition(0, [2, 0, 0, 0, 2, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_table_compact_transitions() {
+        let source = "@RULE Compact\n@TABLE\nn_states:2\nneighborhood:vonNeumann\n011111\n";
+        let rule = GollyRule::parse(source).unwrap();
+        assert_eq!(1, rule.transition(0, [1, 0, 1, 0, 1, 0, 1, 0]));
+        assert_eq!(0, rule.transition(0, [1, 0, 1, 0, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_parse_tree() {
+        let rule = GollyRule::parse(&life_tree()).unwrap();
+        assert_eq!("LifeTree", rule.name());
+        assert_eq!(2, rule.states());
+
+        let mut game = StateGame::new(5, 5, rule);
+        for y in 1..4 {
+            game.set(2, y, 1);
+        }
+        game.iterate();
+        assert_eq!(
+            vec![0, 1, 1, 1, 0],
+            (0..5).map(|x| game.get(x, 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, game.population());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            RuleFileError::new(0, "missing @RULE line"),
+            GollyRule::parse("@TABLE\nn_states:2\nneighborhood:Moore\n").unwrap_err()
+        );
+        assert_eq!(
+            RuleFileError::new(0, "no @TABLE or @TREE section"),
+            GollyRule::parse("@RULE Empty\n").unwrap_err()
+        );
+
+        let err = GollyRule::parse("@RULE Bad\n@TABLE\nn_states:2\nneighborhood:Moore\n0,1,5\n")
+            .unwrap_err();
+        assert_eq!(5, err.line);
+
+        let err = GollyRule::parse(
+            "@RULE Bad\n@TABLE\nn_states:2\nneighborhood:vonNeumann\n0,1,1,1,9,1\n",
+        )
+        .unwrap_err();
+        assert_eq!("line 5: invalid state \"9\"", err.to_string());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::Board;
+
+// Growing the board as live cells near its edges, set with Game::set_growth. Whenever a live cell
+// comes within `margin` cells of an edge, `chunk` dead rows or columns are added on that side, up
+// to `max_width` by `max_height` cells. Past that the board stops growing and wraps around as usual.
+//
+// Growing on the left or top moves every cell right or down; Game::growth_offset() says how far
+// they've moved in all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Growth {
+    pub margin: usize,
+    pub chunk: usize,
+    pub max_width: usize,
+    pub max_height: usize,
+}
+
+impl Growth {
+    // Grow by `chunk` cells whenever a live cell is within `margin` of an edge, up to a square
+    // board of `max_size`.
+    pub fn new(margin: usize, chunk: usize, max_size: usize) -> Self {
+        Growth {
+            margin,
+            chunk,
+            max_width: max_size,
+            max_height: max_size,
+        }
+    }
+
+    // The grown board, along with how many columns and rows were added on the left and top, or
+    // None if it doesn't need to grow or can't.
+    pub(crate) fn grow(&self, board: &Board) -> Option<(Board, usize, usize)> {
+        let bounds = board.bounding_box()?;
+        let (x_size, y_size) = (board.x_size(), board.y_size());
+        let near =
+            |low: usize, high: usize, size: usize| (low < self.margin, size - high < self.margin);
+        let (left, right) = near(bounds.x, bounds.x + bounds.width, x_size);
+        let (top, bottom) = near(bounds.y, bounds.y + bounds.height, y_size);
+
+        // Split what room is left between the sides that need it, the near side first.
+        let share = |low: bool, high: bool, size: usize, max: usize| {
+            let mut room = max.saturating_sub(size);
+            let mut take = |wanted: bool| {
+                let added = if wanted { self.chunk.min(room) } else { 0 };
+                room -= added;
+                added
+            };
+            (take(low), take(high))
+        };
+        let (add_left, add_right) = share(left, right, x_size, self.max_width);
+        let (add_top, add_bottom) = share(top, bottom, y_size, self.max_height);
+        if add_left + add_right + add_top + add_bottom == 0 {
+            return None;
+        }
+
+        let grown = Board::from_fn(
+            x_size + add_left + add_right,
+            y_size + add_top + add_bottom,
+            |x, y| {
+                (add_left..add_left + x_size).contains(&x)
+                    && (add_top..add_top + y_size).contains(&y)
+                    && board[(x - add_left, y - add_top)]
+            },
+        );
+        Some((grown, add_left, add_top))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grow() {
+        let growth = Growth::new(2, 4, 14);
+        let mut board = Board::new(8, 8);
+        board.set(4, 4, true);
+        assert_eq!(None, growth.grow(&board));
+        assert_eq!(None, growth.grow(&Board::new(8, 8)));
+
+        // Near the right and bottom edges, so it grows there and nothing moves.
+        board.set(6, 7, true);
+        let (grown, left, top) = growth.grow(&board).unwrap();
+        assert_eq!((12, 12, 0, 0), (grown.x_size(), grown.y_size(), left, top));
+        assert!(grown[(4, 4)] && grown[(6, 7)]);
+
+        // Near the left edge, where it grows by what's left before the maximum.
+        let mut board = grown;
+        board.set(0, 5, true);
+        let (grown, left, top) = growth.grow(&board).unwrap();
+        assert_eq!((14, 12, 2, 0), (grown.x_size(), grown.y_size(), left, top));
+        assert!(grown[(2, 5)] && grown[(6, 4)] && grown[(8, 7)]);
+
+        // It's as wide as it can get and nowhere near the top or bottom.
+        assert_eq!(None, growth.grow(&grown));
+    }
+}
@@ -0,0 +1,624 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::memo::{self, Memo};
+use crate::rule::{RuleTable, OFFSETS};
+use crate::{Board, LifeLike, Neighborhood, Pattern, Rule, UnboundedRule};
+
+// The nodes for a dead and a live cell, the leaves every other node is built from.
+const DEAD: u32 = 0;
+const ALIVE: u32 = 1;
+// How many nodes are kept, unless the game is given a different budget, before the ones no longer
+// in use are dropped. Each node takes about 30 bytes, counting its entry in the index.
+const DEFAULT_NODE_BUDGET: usize = 1 << 22;
+// The deepest a tree can go, so coordinates from its corner to its middle fit in an i64.
+const MAX_LEVEL: u8 = 62;
+
+// A game on an unbounded plane, like SparseGame, that uses Gosper's HashLife to jump far ahead:
+// millions of generations of a glider gun in a fraction of a second.
+//
+// The plane is a quadtree, where a node at level k is a square 2^k cells across made of four nodes
+// at level k - 1, down to single cells at level 0. Nodes are shared: there's only ever one node for
+// a given arrangement of cells, so an empty quarter of the plane or a thousand copies of the same
+// glider cost one node each. Every node of level 2 and up remembers what its middle half looks like
+// some number of generations later, so a pattern that repeats itself in space or time has most of
+// its future worked out once and looked up after that. Patterns that never repeat, like dense
+// soups, get nothing from this, and a SparseGame or Game runs them faster.
+//
+// Memory is kept within two budgets. The nodes are held to a count, and once a jump needs more
+// than that, the nodes the tree is no longer made of are dropped, which forgets every result too.
+// The results are held to a number of bytes on their own, dropping the ones that have gone longest
+// without being used when there's no room for another.
+//
+// The middle of the tree is always at (0, 0). Coordinates can be negative, and y grows downwards
+// like it does on a Board.
+pub struct HashLifeGame {
+    // Every node, by its id, with leaves at DEAD and ALIVE.
+    nodes: Vec<Node>,
+    // The id of each node of level 1 and up, for sharing them.
+    index: HashMap<Node, u32>,
+    // What the middle of a node looks like 2^j generations on, by the node's id and j.
+    results: Memo<(u32, u8), u32>,
+    // The most nodes there can be before they're compacted.
+    node_budget: usize,
+    // The empty node at each level, as far up as they've been needed.
+    empty: Vec<u32>,
+    root: u32,
+    generation: u64,
+    table: RuleTable,
+}
+
+// A node's level and its quarters: top-left, top-right, bottom-left, and bottom-right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Node {
+    level: u8,
+    children: [u32; 4],
+    population: u64,
+}
+
+impl HashLifeGame {
+    // Build a new, empty game running Conway's rule.
+    pub fn new() -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+        let mut game = HashLifeGame {
+            nodes: vec![leaf(0), leaf(1)],
+            index: HashMap::new(),
+            results: Memo::new(memo::DEFAULT_BUDGET),
+            node_budget: DEFAULT_NODE_BUDGET,
+            empty: vec![DEAD],
+            root: DEAD,
+            generation: 0,
+            table: RuleTable::new(&LifeLike::CONWAY),
+        };
+        game.root = game.empty(3);
+        game
+    }
+
+    // Replace the rule used for every following generation. Rules that birth dead cells with no
+    // live neighbors are refused, as SparseGame refuses them.
+    pub fn set_rule<R: Rule>(&mut self, rule: R) -> Result<(), UnboundedRule> {
+        let table = RuleTable::new(&rule);
+        if table.next(false, Neighborhood::default()) {
+            return Err(UnboundedRule);
+        }
+        self.table = table;
+        // Everything worked out so far was for the old rule.
+        self.results.clear();
+        Ok(())
+    }
+
+    pub fn node_budget(&self) -> usize {
+        self.node_budget
+    }
+
+    // Change how many nodes there can be. A tree too big to fit in the budget goes over it rather
+    // than stop, running one generation at a time.
+    pub fn set_node_budget(&mut self, nodes: usize) {
+        self.node_budget = nodes;
+    }
+
+    // The memory the remembered results may use.
+    pub fn memory_budget(&self) -> usize {
+        self.results.budget()
+    }
+
+    pub fn set_memory_budget(&mut self, bytes: usize) {
+        self.results.set_budget(bytes);
+    }
+
+    // How many nodes make up the tree and everything still remembered about it.
+    pub fn nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    // The number of generations run so far.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        let Some((mut x, mut y)) = self.to_corner(x, y) else {
+            return false;
+        };
+        let mut id = self.root;
+        while self.nodes[id as usize].level > 0 {
+            let node = self.nodes[id as usize];
+            let half = 1 << (node.level - 1);
+            id = node.children[quarter(x, y, half)];
+            (x, y) = (x % half, y % half);
+        }
+        id == ALIVE
+    }
+
+    // Whether (x, y) is inside the biggest tree there can be, 2^62 cells across around (0, 0).
+    // Setting a cell outside it panics.
+    pub fn in_reach(x: i64, y: i64) -> bool {
+        let half = 1 << (MAX_LEVEL - 1);
+        (-half..half).contains(&x) && (-half..half).contains(&y)
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        let (x, y) = loop {
+            match self.to_corner(x, y) {
+                Some(corner) => break corner,
+                None => self.root = self.expand(self.root),
+            }
+        };
+        self.root = self.set_in(self.root, x, y, alive);
+    }
+
+    // Flip the cell at (x, y) between live and dead, returning its new state.
+    pub fn toggle(&mut self, x: i64, y: i64) -> bool {
+        let alive = !self.get(x, y);
+        self.set(x, y, alive);
+        alive
+    }
+
+    pub fn clear(&mut self) {
+        self.root = self.empty(3);
+    }
+
+    // How many cells are live. It's a u64, since a pattern run far enough can outgrow a u32.
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    // The coordinates of every live cell, row by row within each quarter of the plane.
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let mut cells = Vec::new();
+        let half = self.half();
+        self.collect(self.root, -half, -half, &mut cells);
+        cells.into_iter()
+    }
+
+    // The smallest box holding every live cell, as its left and top edges and its width and
+    // height, or None if every cell is dead.
+    pub fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        let mut cells = self.live_cells();
+        let (x, y) = cells.next()?;
+        let (mut left, mut top, mut right, mut bottom) = (x, y, x, y);
+        for (x, y) in cells {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+        Some((
+            left,
+            top,
+            (right - left + 1) as usize,
+            (bottom - top + 1) as usize,
+        ))
+    }
+
+    // Run the game forward any number of generations, as a series of jumps of a power of two
+    // generations each. A jump of 2^j generations takes about as long as one generation of a
+    // SparseGame when the pattern repeats itself, however big j is. Panics if the pattern would
+    // need a tree more than 2^62 cells across, which jumps of 2^60 generations or more always do.
+    pub fn advance(&mut self, generations: u64) {
+        for j in 0..u64::BITS as u8 {
+            if generations & (1 << j) != 0 {
+                self.jump(j);
+            }
+        }
+    }
+
+    // Stamp a pattern onto the plane with its top-left corner at (x, y), adding its live cells to
+    // whatever's already there. Cells in any state but 0 are live.
+    pub fn stamp(&mut self, pattern: &Pattern, x: i64, y: i64) {
+        for (dx, dy, _) in pattern.live_cells() {
+            self.set(x + dx as i64, y + dy as i64, true);
+        }
+    }
+
+    // A dense copy of the width x height cells with their top-left corner at (x, y), for
+    // everything that works on Boards.
+    pub fn crop(&self, x: i64, y: i64, width: usize, height: usize) -> Board {
+        Board::from_fn(width, height, |dx, dy| {
+            self.get(x + dx as i64, y + dy as i64)
+        })
+    }
+
+    // A dense copy of the bounding box of the live cells, which is empty if every cell is dead.
+    // The box's top-left corner is at (0, 0) on the board.
+    pub fn to_board(&self) -> Board {
+        match self.bounding_box() {
+            Some((x, y, width, height)) => self.crop(x, y, width, height),
+            None => Board::new(0, 0),
+        }
+    }
+
+    // Run the game forward 2^j generations.
+    fn jump(&mut self, j: u8) {
+        if self.nodes.len() >= self.node_budget {
+            self.compact();
+        }
+        // The pattern has to be in the middle half of the tree, and the tree then made twice as
+        // big again, so nothing it does in 2^j generations can reach past the middle of the tree,
+        // which is all a jump works out.
+        let root = self.root;
+        while self.level() < j + 2 || !self.padded() {
+            self.root = self.expand(self.root);
+        }
+        self.root = self.expand(self.root);
+        // A single generation can't be split up any further, so it's allowed to go over.
+        let limit = if j == 0 { usize::MAX } else { self.node_budget };
+        match self.successor(self.root, j, limit) {
+            Some(next) => {
+                self.root = next;
+                self.generation = self.generation.wrapping_add(1 << j);
+            }
+            // The jump ran out of nodes partway through. Everything it built is dropped, and it's
+            // run as two jumps half as far, each with the budget to itself.
+            None => {
+                self.root = root;
+                self.compact();
+                self.jump(j - 1);
+                self.jump(j - 1);
+            }
+        }
+    }
+
+    // The middle half of a node of level k, 2^j generations on, where j is at most k - 2. At the
+    // most, 2^(k - 2) generations, it's two jumps through the nine overlapping quarter-sized
+    // squares inside the node, each of which goes half as far. Shorter jumps take the first step
+    // without moving on in time. None if that would take more than `limit` nodes.
+    fn successor(&mut self, id: u32, j: u8, limit: usize) -> Option<u32> {
+        let node = self.nodes[id as usize];
+        let level = node.level;
+        if node.population == 0 {
+            return Some(self.empty(level - 1));
+        }
+        if let Some(result) = self.results.get(&(id, j)) {
+            return Some(result);
+        }
+        if self.nodes.len() >= limit {
+            return None;
+        }
+
+        let result = if level == 2 {
+            self.step_cells(id)
+        } else {
+            let [nw, ne, sw, se] = node
+                .children
+                .map(|child| self.nodes[child as usize].children);
+            let [n00, n02, n20, n22] = node.children;
+            let n01 = self.join([nw[1], ne[0], nw[3], ne[2]]);
+            let n10 = self.join([nw[2], nw[3], sw[0], sw[1]]);
+            let n11 = self.join([nw[3], ne[2], sw[1], se[0]]);
+            let n12 = self.join([ne[2], ne[3], se[0], se[1]]);
+            let n21 = self.join([sw[1], se[0], sw[3], se[2]]);
+            let full = j == level - 2;
+            let squares = [n00, n01, n02, n10, n11, n12, n20, n21, n22];
+            let mut r = [DEAD; 9];
+            for (r, square) in r.iter_mut().zip(squares) {
+                *r = match full {
+                    true => self.successor(square, j - 1, limit)?,
+                    false => self.middle(square),
+                };
+            }
+            let quarters = [
+                [r[0], r[1], r[3], r[4]],
+                [r[1], r[2], r[4], r[5]],
+                [r[3], r[4], r[6], r[7]],
+                [r[4], r[5], r[7], r[8]],
+            ];
+            let step = if full { j - 1 } else { j };
+            let mut next = [DEAD; 4];
+            for (next, quarter) in next.iter_mut().zip(quarters) {
+                let quarter = self.join(quarter);
+                *next = self.successor(quarter, step, limit)?;
+            }
+            self.join(next)
+        };
+        self.results.insert((id, j), result);
+        Some(result)
+    }
+
+    // The middle 2x2 cells of a 4x4 node, one generation on.
+    fn step_cells(&mut self, id: u32) -> u32 {
+        let node = self.nodes[id as usize];
+        let mut cells = [[false; 4]; 4];
+        for (quarter, &child) in node.children.iter().enumerate() {
+            let children = self.nodes[child as usize].children;
+            for (cell, &leaf) in children.iter().enumerate() {
+                let x = quarter % 2 * 2 + cell % 2;
+                let y = quarter / 2 * 2 + cell / 2;
+                cells[y][x] = leaf == ALIVE;
+            }
+        }
+        let next = [(1, 1), (2, 1), (1, 2), (2, 2)].map(|(x, y): (usize, usize)| {
+            let mut bits = 0;
+            for (bit, &(dx, dy)) in OFFSETS.iter().enumerate() {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                if cells[ny as usize][nx as usize] {
+                    bits |= 1 << bit;
+                }
+            }
+            let alive = self.table.next(cells[y][x], Neighborhood::from_bits(bits));
+            if alive {
+                ALIVE
+            } else {
+                DEAD
+            }
+        });
+        self.join(next)
+    }
+
+    // The middle half of a node, as it is now.
+    fn middle(&mut self, id: u32) -> u32 {
+        let [nw, ne, sw, se] = self.nodes[id as usize]
+            .children
+            .map(|child| self.nodes[child as usize].children);
+        self.join([nw[3], ne[2], sw[1], se[0]])
+    }
+
+    // A node twice the size of this one, with this one in its middle.
+    fn expand(&mut self, id: u32) -> u32 {
+        let node = self.nodes[id as usize];
+        assert!(node.level < MAX_LEVEL, "the pattern has grown too big");
+        let empty = self.empty(node.level - 1);
+        let [nw, ne, sw, se] = node.children;
+        let quarters = [
+            self.join([empty, empty, empty, nw]),
+            self.join([empty, empty, ne, empty]),
+            self.join([empty, sw, empty, empty]),
+            self.join([se, empty, empty, empty]),
+        ];
+        self.join(quarters)
+    }
+
+    // Whether every live cell is in the middle half of the tree.
+    fn padded(&self) -> bool {
+        let children = self.nodes[self.root as usize].children;
+        children.iter().enumerate().all(|(quarter, &child)| {
+            let child = self.nodes[child as usize];
+            // The grandchild nearest the middle is the one in the opposite corner.
+            let inner = self.nodes[child.children[3 - quarter] as usize];
+            child.population == inner.population
+        })
+    }
+
+    // The node made of four quarters of the same level, shared with any other just like it.
+    fn join(&mut self, children: [u32; 4]) -> u32 {
+        let level = self.nodes[children[0] as usize].level + 1;
+        let population = children
+            .iter()
+            .map(|&child| self.nodes[child as usize].population)
+            .sum();
+        let node = Node {
+            level,
+            children,
+            population,
+        };
+        if let Some(&id) = self.index.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len() as u32;
+        self.nodes.push(node);
+        self.index.insert(node, id);
+        id
+    }
+
+    fn empty(&mut self, level: u8) -> u32 {
+        while self.empty.len() <= level as usize {
+            let below = self.empty[self.empty.len() - 1];
+            let empty = self.join([below; 4]);
+            self.empty.push(empty);
+        }
+        self.empty[level as usize]
+    }
+
+    // The node with the cell at (x, y), counted from its top-left corner, set live or dead.
+    fn set_in(&mut self, id: u32, x: u64, y: u64, alive: bool) -> u32 {
+        let node = self.nodes[id as usize];
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+        let half = 1 << (node.level - 1);
+        let mut children = node.children;
+        let quarter = quarter(x, y, half);
+        children[quarter] = self.set_in(children[quarter], x % half, y % half, alive);
+        self.join(children)
+    }
+
+    // Add the live cells in a node with its top-left corner at (x, y).
+    fn collect(&self, id: u32, x: i64, y: i64, cells: &mut Vec<(i64, i64)>) {
+        let node = self.nodes[id as usize];
+        match node.level {
+            _ if node.population == 0 => {}
+            0 => cells.push((x, y)),
+            level => {
+                let half = 1 << (level - 1);
+                for (quarter, &child) in node.children.iter().enumerate() {
+                    let (dx, dy) = (quarter as i64 % 2 * half, quarter as i64 / 2 * half);
+                    self.collect(child, x + dx, y + dy, cells);
+                }
+            }
+        }
+    }
+
+    // Keep only the nodes the tree is still made of, and forget every result, so memory doesn't
+    // keep growing on a long run.
+    fn compact(&mut self) {
+        let mut old = HashLifeGame::new();
+        std::mem::swap(self, &mut old);
+        self.table = old.table.clone();
+        self.generation = old.generation;
+        self.node_budget = old.node_budget;
+        self.results.set_budget(old.results.budget());
+        let mut copied = HashMap::new();
+        self.root = self.copy(&old, old.root, &mut copied);
+    }
+
+    fn copy(&mut self, old: &HashLifeGame, id: u32, copied: &mut HashMap<u32, u32>) -> u32 {
+        if id <= ALIVE {
+            return id;
+        }
+        if let Some(&id) = copied.get(&id) {
+            return id;
+        }
+        let children = old.nodes[id as usize]
+            .children
+            .map(|child| self.copy(old, child, copied));
+        let new = self.join(children);
+        copied.insert(id, new);
+        new
+    }
+
+    fn level(&self) -> u8 {
+        self.nodes[self.root as usize].level
+    }
+
+    // Half the width of the tree, the distance from its corner to its middle.
+    fn half(&self) -> i64 {
+        1 << (self.level() - 1)
+    }
+
+    // (x, y) counted from the tree's top-left corner, if it's inside the tree.
+    fn to_corner(&self, x: i64, y: i64) -> Option<(u64, u64)> {
+        let half = self.half();
+        let inside = |n: i64| (-half..half).contains(&n);
+        (inside(x) && inside(y)).then(|| ((x + half) as u64, (y + half) as u64))
+    }
+}
+
+// Which quarter of a node (x, y) is in, counted from the node's top-left corner.
+fn quarter(x: u64, y: u64, half: u64) -> usize {
+    (y >= half) as usize * 2 + (x >= half) as usize
+}
+
+impl Default for HashLifeGame {
+    fn default() -> Self {
+        HashLifeGame::new()
+    }
+}
+
+// Copy a board's live cells onto the plane at the same coordinates, as SparseGame does.
+impl From<&Board> for HashLifeGame {
+    fn from(board: &Board) -> Self {
+        let mut game = HashLifeGame::new();
+        for point in board.live_cells() {
+            game.set(point.x as i64, point.y as i64, true);
+        }
+        game
+    }
+}
+
+// HashLife games print like Boards, covering the bounding box of the live cells.
+impl fmt::Display for HashLifeGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_board())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Game, SparseGame};
+
+    fn cells<I: Iterator<Item = (i64, i64)>>(cells: I) -> Vec<(i64, i64)> {
+        let mut cells: Vec<_> = cells.collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn test_matches_sparse() {
+        let board = Game::random(20, 16, 0.4, 5).board().clone();
+        let mut sparse = SparseGame::from(&board);
+        let mut hashlife = HashLifeGame::from(&board);
+        for rule in [LifeLike::CONWAY, LifeLike::new(&[3, 6], &[2, 3])] {
+            sparse.set_rule(rule).unwrap();
+            hashlife.set_rule(rule).unwrap();
+            for _ in 0..12 {
+                sparse.iterate();
+                hashlife.advance(1);
+                assert_eq!(cells(sparse.live_cells()), cells(hashlife.live_cells()));
+            }
+            // A single jump comes out the same as the generations one at a time.
+            sparse.run(37);
+            hashlife.advance(37);
+            assert_eq!(cells(sparse.live_cells()), cells(hashlife.live_cells()));
+        }
+        assert_eq!(98, hashlife.generation());
+    }
+
+    #[test]
+    fn test_glider_leaves() {
+        let glider = Pattern::from_cells(".O.\n..O\nOOO\n").unwrap();
+        let mut game = HashLifeGame::new();
+        game.stamp(&glider, -2, -2);
+        game.advance(1_000_000);
+        assert_eq!(Some((249_998, 249_998, 3, 3)), game.bounding_box());
+        assert_eq!(5, game.population());
+        assert_eq!(".O.\n..O\nOOO\n", game.to_string());
+        assert!(game.get(249_999, 249_998));
+        assert!(!game.toggle(249_999, 249_998));
+        assert_eq!(4, game.population());
+    }
+
+    #[test]
+    fn test_gun() {
+        let gun = Pattern::named("gosper glider gun").unwrap();
+        let mut game = HashLifeGame::new();
+        game.stamp(&gun, 0, 0);
+        let mut sparse = SparseGame::new();
+        sparse.stamp(&gun, 0, 0);
+        game.advance(300);
+        sparse.run(300);
+        assert_eq!(cells(sparse.live_cells()), cells(game.live_cells()));
+
+        // Far along, the gun has added one more glider every 30 generations.
+        game.advance(1 << 20);
+        let population = game.population();
+        game.advance(30);
+        assert_eq!(population + 5, game.population());
+    }
+
+    #[test]
+    fn test_compact() {
+        let board = Game::random(12, 12, 0.5, 9).board().clone();
+        let mut game = HashLifeGame::from(&board);
+        let mut sparse = SparseGame::from(&board);
+        game.advance(20);
+        game.compact();
+        game.advance(20);
+        sparse.run(40);
+        assert_eq!(cells(sparse.live_cells()), cells(game.live_cells()));
+        assert_eq!(40, game.generation());
+    }
+
+    #[test]
+    fn test_node_budget() {
+        let gun = Pattern::named("gosper glider gun").unwrap();
+        let mut game = HashLifeGame::new();
+        game.set_node_budget(1000);
+        game.set_memory_budget(1 << 16);
+        game.stamp(&gun, 0, 0);
+        let mut sparse = SparseGame::new();
+        sparse.stamp(&gun, 0, 0);
+        // Each jump stops when it runs out of nodes and goes again in smaller steps, so a single
+        // long jump stays within the budget and comes out the same.
+        game.advance(1000);
+        sparse.run(1000);
+        assert!(game.nodes() <= 1000);
+        assert_eq!(cells(sparse.live_cells()), cells(game.live_cells()));
+        assert_eq!(1000, game.generation());
+    }
+
+    #[test]
+    fn test_unbounded_rule() {
+        let mut game = HashLifeGame::new();
+        assert_eq!(
+            Err(UnboundedRule),
+            game.set_rule(LifeLike::new(&[0, 3], &[2, 3]))
+        );
+        assert_eq!(Board::new(0, 0), game.to_board());
+        assert!(!game.get(1 << 40, -3));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::Board;
+
+// How many times each cell has been born and has died over a run, for seeing where the activity was
+// once it's over. Record each generation with the board before and after it:
+//
+//     let before = game.snapshot();
+//     game.iterate();
+//     heat.record(&before, game.board());
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeatMap {
+    x_size: usize,
+    y_size: usize,
+    // Column by column, like Board, so the count for (x, y) is at x * y_size + y.
+    births: Vec<u32>,
+    deaths: Vec<u32>,
+    generations: usize,
+}
+
+impl HeatMap {
+    pub fn new(x_size: usize, y_size: usize) -> Self {
+        HeatMap {
+            x_size,
+            y_size,
+            births: vec![0; x_size * y_size],
+            deaths: vec![0; x_size * y_size],
+            generations: 0,
+        }
+    }
+
+    pub fn x_size(&self) -> usize {
+        self.x_size
+    }
+
+    pub fn y_size(&self) -> usize {
+        self.y_size
+    }
+
+    // How many generations have been recorded.
+    pub fn generations(&self) -> usize {
+        self.generations
+    }
+
+    // Count the births and deaths between two boards. Boards that aren't the heat map's size, such
+    // as after a game grows, are skipped, and the result says whether they were recorded.
+    pub fn record(&mut self, before: &Board, after: &Board) -> bool {
+        let size = (self.x_size, self.y_size);
+        if (before.x_size(), before.y_size()) != size || (after.x_size(), after.y_size()) != size {
+            return false;
+        }
+        let was = before.columns().flatten();
+        let is = after.columns().flatten();
+        for (index, (was, is)) in was.zip(is).enumerate() {
+            match (was, is) {
+                (false, true) => self.births[index] = self.births[index].saturating_add(1),
+                (true, false) => self.deaths[index] = self.deaths[index].saturating_add(1),
+                _ => {}
+            }
+        }
+        self.generations += 1;
+        true
+    }
+
+    pub fn births(&self, x: usize, y: usize) -> u32 {
+        self.births[x * self.y_size + y]
+    }
+
+    pub fn deaths(&self, x: usize, y: usize) -> u32 {
+        self.deaths[x * self.y_size + y]
+    }
+
+    // The most births and the most deaths of any cell.
+    pub fn max_births(&self) -> u32 {
+        self.births.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn max_deaths(&self) -> u32 {
+        self.deaths.iter().copied().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        // A blinker, whose middle cell never changes and whose ends take turns.
+        let mut heat = HeatMap::new(5, 5);
+        let mut board: Board = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let mut game = crate::Game::from(board.clone());
+        for _ in 0..4 {
+            game.iterate();
+            assert!(heat.record(&board, game.board()));
+            board = game.board().clone();
+        }
+        assert_eq!(4, heat.generations());
+        assert_eq!((0, 0), (heat.births(2, 2), heat.deaths(2, 2)));
+        assert_eq!((2, 2), (heat.births(1, 2), heat.deaths(1, 2)));
+        assert_eq!((2, 2), (heat.births(2, 1), heat.deaths(2, 1)));
+        assert_eq!((0, 0), (heat.births(0, 0), heat.deaths(0, 0)));
+        assert_eq!((2, 2), (heat.max_births(), heat.max_deaths()));
+
+        assert!(!heat.record(&Board::new(4, 5), &Board::new(4, 5)));
+        assert_eq!(4, heat.generations());
+    }
+}
@@ -0,0 +1,428 @@
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+
+use crate::Board;
+
+// The memory history may use unless it's given a different budget.
+pub(crate) const DEFAULT_BUDGET: usize = 256 << 20;
+
+// Every generation a game has been through, kept cheaply enough to hold long runs. Most generations
+// are stored as the cells that flipped since the one before, and every `interval` generations the
+// whole board is kept as a keyframe. Any generation can be rebuilt from the nearest keyframe, going
+// forward through the diffs after it or back through the ones before it, since flipping the same
+// cells again undoes a diff. Once it outgrows its memory budget, the policy decides what goes, and
+// once it holds more generations than its capacity, the oldest go like they would in a ring buffer.
+#[derive(Clone, Debug)]
+pub(crate) struct History {
+    interval: usize,
+    // The generation of the first entry.
+    first: usize,
+    entries: Vec<Entry>,
+    // The board recorded last, to diff the next one against.
+    last: Arc<Board>,
+    budget: usize,
+    policy: HistoryPolicy,
+    // The most generations kept, counting the last one recorded.
+    capacity: usize,
+    // Roughly how many bytes the entries take.
+    memory: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    // The whole board, for keyframes.
+    keyframe: Option<Arc<Board>>,
+    // The cells that flipped since the generation before, encoded by encode(). None when the board
+    // changed size, so there's nothing to diff against.
+    diff: Option<Vec<u8>>,
+}
+
+impl Entry {
+    fn keyframe(board: Arc<Board>) -> Self {
+        Entry {
+            keyframe: Some(board),
+            diff: None,
+        }
+    }
+
+    // Roughly how many bytes the entry takes, counting a keyframe's cells even when the game's
+    // still holding the same board.
+    fn memory(&self) -> usize {
+        let keyframe = self.keyframe.as_ref().map_or(0, |board| {
+            mem::size_of::<Board>() + board.x_size() * board.y_size()
+        });
+        mem::size_of::<Entry>() + keyframe + self.diff.as_ref().map_or(0, Vec::len)
+    }
+}
+
+// What history gives up when it outgrows its memory budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HistoryPolicy {
+    // Forget the oldest generations, so seeking can't go back as far.
+    #[default]
+    DropOldest,
+    // Keep fewer whole boards, dropping every other one at a time, so every generation can still
+    // be sought to but seeking takes longer. Once there are no more to give up, the oldest
+    // generations go instead.
+    Thin,
+}
+
+impl History {
+    // Start recording at the given generation, with a keyframe of its board every `interval`
+    // generations.
+    pub(crate) fn new(
+        interval: usize,
+        budget: usize,
+        policy: HistoryPolicy,
+        capacity: usize,
+        generation: usize,
+        board: &Arc<Board>,
+    ) -> Self {
+        let entry = Entry::keyframe(Arc::clone(board));
+        History {
+            interval: interval.max(1),
+            first: generation,
+            memory: entry.memory(),
+            entries: vec![entry],
+            last: Arc::clone(board),
+            budget,
+            policy,
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub(crate) fn interval(&self) -> usize {
+        self.interval
+    }
+
+    // The generations that can be sought to.
+    pub(crate) fn range(&self) -> (usize, usize) {
+        (self.first, self.first + self.entries.len() - 1)
+    }
+
+    pub(crate) fn memory(&self) -> usize {
+        self.memory
+    }
+
+    // Change the budget and policy, giving up whatever no longer fits straight away.
+    pub(crate) fn set_budget(&mut self, budget: usize, policy: HistoryPolicy) {
+        (self.budget, self.policy) = (budget, policy);
+        self.enforce();
+    }
+
+    // Change how many generations are kept, forgetting the oldest straight away if there are more.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.enforce();
+    }
+
+    // Record the board a generation ended on. Anything recorded for that generation or later, from
+    // before the game was sent back to an earlier one, is dropped, since that's no longer where the
+    // game is heading.
+    pub(crate) fn record(&mut self, generation: usize, board: &Arc<Board>) {
+        let keep = generation.saturating_sub(self.first);
+        if keep == 0 {
+            *self = History::new(
+                self.interval,
+                self.budget,
+                self.policy,
+                self.capacity,
+                generation,
+                board,
+            );
+            return;
+        }
+        for entry in self.entries.drain(keep.min(self.entries.len())..) {
+            self.memory -= entry.memory();
+        }
+
+        let resized = (board.x_size(), board.y_size()) != (self.last.x_size(), self.last.y_size());
+        let diff = (!resized).then(|| encode(&self.last, board));
+        let keyframe =
+            (resized || generation.is_multiple_of(self.interval)).then(|| Arc::clone(board));
+        let entry = Entry { keyframe, diff };
+        self.memory += entry.memory();
+        self.entries.push(entry);
+        self.last = Arc::clone(board);
+        self.enforce();
+    }
+
+    // Forget the generations past the capacity, then give up generations or keyframes, as the
+    // policy says, until the history fits its budget or there's only the last generation left.
+    fn enforce(&mut self) {
+        if self.entries.len() > self.capacity {
+            self.forget(self.entries.len() - self.capacity);
+        }
+        while self.memory > self.budget {
+            let freed = match self.policy {
+                HistoryPolicy::DropOldest => self.drop_oldest(),
+                HistoryPolicy::Thin => self.thin() || self.drop_oldest(),
+            };
+            if !freed {
+                break;
+            }
+        }
+    }
+
+    // Forget the generations before the second keyframe, or the older half if there isn't one,
+    // returning whether there were any to forget.
+    fn drop_oldest(&mut self) -> bool {
+        let len = self.entries.len();
+        if len < 2 {
+            return false;
+        }
+        let cut = (1..len)
+            .find(|&index| self.entries[index].keyframe.is_some())
+            .unwrap_or(len / 2);
+        self.forget(cut);
+        true
+    }
+
+    // Forget the oldest `cut` generations, making the first one kept a keyframe.
+    fn forget(&mut self, cut: usize) {
+        if self.entries[cut].keyframe.is_none() {
+            let board = self.board_at(self.first + cut).expect("cut is in range");
+            let entry = &mut self.entries[cut];
+            self.memory -= entry.memory();
+            *entry = Entry::keyframe(Arc::new(board));
+            self.memory += entry.memory();
+        }
+        for entry in self.entries.drain(..cut) {
+            self.memory -= entry.memory();
+        }
+        self.first += cut;
+    }
+
+    // Drop every other keyframe, keeping the first and the ones the board changed size at,
+    // returning whether there were any to drop.
+    fn thin(&mut self) -> bool {
+        let droppable: Vec<usize> = (1..self.entries.len())
+            .filter(|&index| {
+                let entry = &self.entries[index];
+                entry.keyframe.is_some() && entry.diff.is_some()
+            })
+            .step_by(2)
+            .collect();
+        for &index in &droppable {
+            let entry = &mut self.entries[index];
+            self.memory -= entry.memory();
+            entry.keyframe = None;
+            self.memory += entry.memory();
+        }
+        !droppable.is_empty()
+    }
+
+    // Rebuild the board of a generation that's been recorded.
+    pub(crate) fn board_at(&self, generation: usize) -> Result<Board, SeekError> {
+        let (first, last) = self.range();
+        if !(first..=last).contains(&generation) {
+            return Err(SeekError::NotVisited {
+                generation,
+                first,
+                last,
+            });
+        }
+        let target = generation - first;
+
+        // The keyframe at or before the target always gets there. One after it might be closer,
+        // if the board kept its size the whole way back.
+        let before = (0..=target)
+            .rev()
+            .find(|&index| self.entries[index].keyframe.is_some())
+            .expect("the first entry is a keyframe");
+        let after = (target + 1..self.entries.len())
+            .take_while(|&index| self.entries[index].diff.is_some())
+            .find(|&index| self.entries[index].keyframe.is_some())
+            .filter(|&after| after - target < target - before);
+
+        match after {
+            Some(after) => {
+                let mut board = self.keyframe(after);
+                for entry in self.entries[target + 1..=after].iter().rev() {
+                    apply(&mut board, entry.diff.as_deref().unwrap_or_default());
+                }
+                Ok(board)
+            }
+            None => {
+                let mut board = self.keyframe(before);
+                for entry in &self.entries[before + 1..=target] {
+                    apply(&mut board, entry.diff.as_deref().unwrap_or_default());
+                }
+                Ok(board)
+            }
+        }
+    }
+
+    // Send the history back to a generation that's been sought to, so the next one recorded
+    // follows on from it.
+    pub(crate) fn rewind(&mut self, board: &Arc<Board>) {
+        self.last = Arc::clone(board);
+    }
+
+    fn keyframe(&self, index: usize) -> Board {
+        Board::clone(
+            self.entries[index]
+                .keyframe
+                .as_ref()
+                .expect("not a keyframe"),
+        )
+    }
+}
+
+// The flat indices of the cells that differ between two boards of the same size, as the gaps
+// between them in LEB128: seven bits a byte, low bits first, with the top bit set on every byte but
+// a number's last. Most generations only change a few cells close together, so most gaps take a byte.
+fn encode(from: &Board, to: &Board) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut last = 0;
+    for index in from.diff(to).map(|point| from.flat_index(point.x, point.y)) {
+        let mut gap = index - last;
+        last = index;
+        while gap >= 0x80 {
+            bytes.push(gap as u8 | 0x80);
+            gap >>= 7;
+        }
+        bytes.push(gap as u8);
+    }
+    bytes
+}
+
+// Flip the cells an encoded diff lists.
+fn apply(board: &mut Board, diff: &[u8]) {
+    let (mut index, mut gap, mut shift) = (0, 0, 0);
+    for &byte in diff {
+        gap |= usize::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            index += gap;
+            board.set_at(index, !board.alive_at(index));
+            (gap, shift) = (0, 0);
+        }
+    }
+}
+
+// Why a game couldn't seek to a generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeekError {
+    // History isn't being recorded.
+    NotRecording,
+    // There's no generation before this one to step back to, since it's the oldest recorded.
+    AtOldest(usize),
+    // The generation hasn't been recorded. Recorded generations run from first to last.
+    NotVisited {
+        generation: usize,
+        first: usize,
+        last: usize,
+    },
+}
+
+impl fmt::Display for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeekError::NotRecording => write!(f, "history isn't being recorded"),
+            SeekError::AtOldest(generation) => write!(
+                f,
+                "generation {generation} is the oldest in the history, so there's nothing before it"
+            ),
+            SeekError::NotVisited {
+                generation,
+                first,
+                last,
+            } => write!(
+                f,
+                "generation {generation} isn't in the history, which runs from {first} to {last}"
+            ),
+        }
+    }
+}
+
+impl Error for SeekError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        let board = |seed: usize| {
+            Arc::new(Board::from_fn(16, 16, |x, y| {
+                (x * y + seed).is_multiple_of(7)
+            }))
+        };
+        let run = |policy, fraction: usize| {
+            let mut history = History::new(4, usize::MAX, policy, usize::MAX, 0, &board(0));
+            for generation in 1..=64 {
+                history.record(generation, &board(generation));
+            }
+            let unlimited = history.memory();
+            history.set_budget(unlimited * fraction / 4, policy);
+            assert!(history.memory() <= unlimited * fraction / 4);
+            history
+        };
+
+        // Dropping the oldest generations keeps the newest ones.
+        let dropped = run(HistoryPolicy::DropOldest, 1);
+        let (first, last) = dropped.range();
+        assert!(first > 0);
+        assert_eq!(64, last);
+        for generation in first..=last {
+            assert_eq!(*board(generation), dropped.board_at(generation).unwrap());
+        }
+
+        // Thinning keeps every generation, rebuilt from fewer keyframes.
+        let thinned = run(HistoryPolicy::Thin, 3);
+        assert_eq!((0, 64), thinned.range());
+        let keyframes = |history: &History| {
+            history
+                .entries
+                .iter()
+                .filter(|entry| entry.keyframe.is_some())
+                .count()
+        };
+        assert!(keyframes(&thinned) < 17);
+        for generation in 0..=64 {
+            assert_eq!(*board(generation), thinned.board_at(generation).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_capacity() {
+        let board = |seed: usize| {
+            Arc::new(Board::from_fn(8, 8, |x, y| {
+                (x + y * seed).is_multiple_of(5)
+            }))
+        };
+        let mut history = History::new(4, usize::MAX, HistoryPolicy::DropOldest, 10, 0, &board(0));
+        for generation in 1..=25 {
+            history.record(generation, &board(generation));
+        }
+        // Only the last ten are kept, and the oldest of them can be rebuilt though it wasn't a
+        // keyframe when it was recorded.
+        assert_eq!((16, 25), history.range());
+        for generation in 16..=25 {
+            assert_eq!(*board(generation), history.board_at(generation).unwrap());
+        }
+        history.set_capacity(3);
+        assert_eq!((23, 25), history.range());
+        assert_eq!(*board(23), history.board_at(23).unwrap());
+    }
+
+    #[test]
+    fn test_encode() {
+        let from = Board::new(20, 20);
+        let mut to = from.clone();
+        for (x, y) in [(0, 0), (0, 5), (19, 19), (10, 3)] {
+            to.set(x, y, true);
+        }
+        let diff = encode(&from, &to);
+        // The gaps up to (10, 3) and (19, 19) are too big for one byte each.
+        assert_eq!(6, diff.len());
+        let mut board = from.clone();
+        apply(&mut board, &diff);
+        assert_eq!(to, board);
+        apply(&mut board, &diff);
+        assert_eq!(from, board);
+    }
+}
@@ -0,0 +1,253 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{BlendMode, Board, GridError, Orientation, Pattern, Point};
+
+// Patterns laid out on a board, written as placements separated by semicolons or new lines, for
+// building collision experiments from a script instead of a list of coordinates:
+//
+//     glider @ (3, 4) rot90; gun @ (20, 0)
+//     block @ (40, 40)  # comments run to the end of the line
+//
+// Each placement names a pattern, gives the board position of its top-left corner, and can turn or
+// mirror it first with one of rot90, rot180, rot270, fliph, flipv, transpose or antitranspose. The
+// names are only looked up when the layout is placed, so they can stand for files, patterns built
+// in code, or anything else.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Layout {
+    placements: Vec<Placement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub name: String,
+    pub at: Point,
+    pub orientation: Orientation,
+}
+
+impl Layout {
+    pub fn placements(&self) -> &[Placement] {
+        &self.placements
+    }
+
+    // Put every pattern on the board in order, looking each up by name. Patterns wrap around the
+    // edges of a toroidal board and are cut off at any other's, as Board::blend() does, and their
+    // live cells are added to whatever's already there.
+    pub fn place<F>(&self, board: &mut Board, mut patterns: F) -> Result<(), LayoutError>
+    where
+        F: FnMut(&str) -> Option<Pattern>,
+    {
+        for placement in &self.placements {
+            let pattern = patterns(&placement.name)
+                .ok_or_else(|| LayoutError::UnknownPattern(placement.name.clone()))?;
+            let oriented = pattern.oriented(placement.orientation).to_board();
+            board
+                .blend(&oriented, placement.at, BlendMode::Or)
+                .map_err(|err| LayoutError::Grid(placement.name.clone(), err))?;
+        }
+        Ok(())
+    }
+}
+
+// A layout built in code rather than parsed.
+impl From<Vec<Placement>> for Layout {
+    fn from(placements: Vec<Placement>) -> Self {
+        Layout { placements }
+    }
+}
+
+impl FromStr for Layout {
+    type Err = LayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut placements = Vec::new();
+        for (index, line) in s.lines().enumerate() {
+            let line_number = index + 1;
+            let code = line.split('#').next().unwrap_or_default();
+            for statement in code.split(';').map(str::trim) {
+                if !statement.is_empty() {
+                    placements.push(parse_placement(statement, line_number)?);
+                }
+            }
+        }
+        Ok(Layout { placements })
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for placement in &self.placements {
+            let Point { x, y } = placement.at;
+            write!(f, "{} @ ({x}, {y})", placement.name)?;
+            if let Some(name) = orientation_name(placement.orientation) {
+                write!(f, " {name}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// The ways orientations are written in layouts, with Identity left unwritten.
+const ORIENTATIONS: [(&str, Orientation); 7] = [
+    ("rot90", Orientation::Rotate90),
+    ("rot180", Orientation::Rotate180),
+    ("rot270", Orientation::Rotate270),
+    ("fliph", Orientation::FlipHorizontal),
+    ("flipv", Orientation::FlipVertical),
+    ("transpose", Orientation::Transpose),
+    ("antitranspose", Orientation::AntiTranspose),
+];
+
+fn orientation_name(orientation: Orientation) -> Option<&'static str> {
+    ORIENTATIONS
+        .iter()
+        .find(|(_, known)| *known == orientation)
+        .map(|(name, _)| *name)
+}
+
+// One "name @ (x, y) orientation" statement.
+fn parse_placement(statement: &str, line: usize) -> Result<Placement, LayoutError> {
+    let invalid = || LayoutError::InvalidPlacement {
+        line,
+        found: statement.to_string(),
+    };
+    let (name, rest) = statement.split_once('@').ok_or_else(invalid)?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let (position, rest) = rest
+        .trim_start()
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once(')'))
+        .ok_or_else(invalid)?;
+    let (x, y) = position.split_once(',').ok_or_else(invalid)?;
+    let x = x.trim().parse().map_err(|_| invalid())?;
+    let y = y.trim().parse().map_err(|_| invalid())?;
+
+    let mut words = rest.split_whitespace();
+    let orientation = match words.next() {
+        None => Orientation::Identity,
+        Some(word) => ORIENTATIONS
+            .iter()
+            .find(|(name, _)| *name == word)
+            .map(|(_, orientation)| *orientation)
+            .ok_or_else(|| LayoutError::UnknownOrientation {
+                line,
+                found: word.to_string(),
+            })?,
+    };
+    if words.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(Placement {
+        name: name.to_string(),
+        at: Point::new(x, y),
+        orientation,
+    })
+}
+
+// The ways a layout can fail to parse or be placed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    // A statement that isn't in the "name @ (x, y) orientation" form, on the given line (counting
+    // from 1).
+    InvalidPlacement { line: usize, found: String },
+    // A word after the position that isn't one of the orientations.
+    UnknownOrientation { line: usize, found: String },
+    // A name with no pattern to go with it.
+    UnknownPattern(String),
+    // The named pattern doesn't fit on the board.
+    Grid(String, GridError),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::InvalidPlacement { line, found } => write!(
+                f,
+                "invalid placement {found:?} on line {line}, expected the form name @ (x, y)"
+            ),
+            LayoutError::UnknownOrientation { line, found } => {
+                write!(f, "unknown orientation {found:?} on line {line}")
+            }
+            LayoutError::UnknownPattern(name) => write!(f, "no pattern named {name:?}"),
+            LayoutError::Grid(name, err) => write!(f, "can't place {name}: {err}"),
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let layout: Layout = "glider @ (3,4) rot90; gun @ (20, 0)\n\n  block @(40,40) # still\n"
+            .parse()
+            .unwrap();
+        let placements: Vec<_> = layout
+            .placements()
+            .iter()
+            .map(|placement| (placement.name.as_str(), placement.at, placement.orientation))
+            .collect();
+        assert_eq!(
+            vec![
+                ("glider", Point::new(3, 4), Orientation::Rotate90),
+                ("gun", Point::new(20, 0), Orientation::Identity),
+                ("block", Point::new(40, 40), Orientation::Identity),
+            ],
+            placements
+        );
+        assert_eq!(
+            "glider @ (3, 4) rot90\ngun @ (20, 0)\nblock @ (40, 40)\n",
+            layout.to_string()
+        );
+        assert_eq!(Ok(layout.clone()), layout.to_string().parse());
+
+        assert_eq!(
+            Err(LayoutError::InvalidPlacement {
+                line: 2,
+                found: "block (1, 2)".to_string()
+            }),
+            "glider @ (0, 0)\nblock (1, 2)".parse::<Layout>()
+        );
+        assert_eq!(
+            Err(LayoutError::UnknownOrientation {
+                line: 1,
+                found: "spin".to_string()
+            }),
+            "glider @ (0, 0) spin".parse::<Layout>()
+        );
+        assert!("glider @ (0, -1)".parse::<Layout>().is_err());
+        assert!("glider @ (0, 0) rot90 rot90".parse::<Layout>().is_err());
+    }
+
+    #[test]
+    fn test_place() {
+        let layout: Layout = "domino @ (1, 1); domino @ (3, 0) rot90; domino @ (5, 4)"
+            .parse()
+            .unwrap();
+        let domino = Pattern::from_fn(2, 1, |_, _| 1);
+        let patterns = |name: &str| (name == "domino").then(|| domino.clone());
+        let mut board = Board::new(6, 5);
+        layout.place(&mut board, patterns).unwrap();
+        // The last domino wraps around to the left edge.
+        assert_eq!(
+            "...O..\n.OOO..\n......\n......\nO....O\n",
+            board.to_string()
+        );
+
+        let layout: Layout = "blinker @ (0, 0)".parse().unwrap();
+        assert_eq!(
+            Err(LayoutError::UnknownPattern("blinker".to_string())),
+            layout.place(&mut board, patterns)
+        );
+        let layout: Layout = "domino @ (0, 0)".parse().unwrap();
+        assert!(layout.place(&mut Board::new(1, 1), patterns).is_err());
+    }
+}
@@ -1,371 +1,2674 @@
 use std::mem;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use ages::Ages;
+use backend::Cache;
+use constraints::Constraints;
+use history::History;
+use mirror::Mirror;
+use report::Recorder;
+use rng::Rng;
+use rule::RuleTable;
+use tiles::Tiles;
+use zones::Zones;
+
+pub use agar::{Agar, AgarSeam, UnknownAgar};
+pub use analysis::{
+    analyze, analyze_with, find_cycle, stabilization, stabilization_with, stabilize, Analysis,
+    Behavior, Stabilization,
+};
+pub use backend::{Backend, UnknownBackend};
+pub use benchmark::{Benchmark, UnknownBenchmark};
+pub use board::{BlendMode, Board, CellMut, GridError};
+pub use cancel::CancelHandle;
+pub use census::{object_name, Census};
+pub use component::Component;
+pub use engine::Engine;
+pub use error::GolError;
+pub use explain::{Explanation, Reason};
+pub use fixed::FixedGame;
+pub use gif::GifWriter;
+pub use golly::{GollyRule, RuleFileError};
+pub use growth::Growth;
+pub use hashlife::HashLifeGame;
+pub use heat::HeatMap;
+pub use history::{HistoryPolicy, SeekError};
+pub use layout::{Layout, LayoutError, Placement};
+pub use link::{decode_link, encode_link, LinkError, MAX_LINK_SIDE};
+pub use mirror::UnfitSymmetry;
+pub use multistate::{StateGame, StateRule};
+pub use ndjson::{CellList, NdjsonWriter};
+pub use palette::Palette;
+pub use pattern::{Pattern, PatternError, MAX_PATTERN_CELLS};
+#[cfg(feature = "bevy")]
+pub use plugin::{GamePlugin, LifeGame, LifeSprite};
+pub use population::PopulationWriter;
+pub use render::{render, render_ages, render_states, Blocks, Raster, RenderTarget, Svg, Text};
+pub use report::RunReport;
+pub use rule::{LifeLike, Neighborhood, Rule, RuleProblem, RuleStringError};
+#[cfg(feature = "serde")]
+pub use save::SaveError;
+pub use search::Orientation;
+pub use seed::{Image, PatternFile, SeedError, SeedSource, Soup};
+pub use snapshot::Snapshot;
+pub use soup::{soup, Symmetry, UnknownSymmetry};
+pub use sparse::{SparseGame, UnboundedRule};
+pub use stats::{Stats, StatsSeries};
+#[cfg(feature = "async")]
+pub use stream::Generations;
+pub use ticker::Ticker;
+pub use tiles::TileStats;
+pub use topology::{Topology, UnknownTopology};
+#[cfg(feature = "wasm")]
+pub use wasm::JsGame;
+#[cfg(feature = "gui")]
+pub use widget::{CellRects, Edit, GolWidget, WidgetOutput};
+
+mod agar;
+mod ages;
+mod analysis;
+mod backend;
+mod benchmark;
+mod board;
+mod cancel;
+mod census;
+mod component;
+mod constraints;
+mod engine;
+mod error;
+mod explain;
+mod fixed;
+mod gif;
+mod golly;
+mod growth;
+mod hashlife;
+mod heat;
+mod history;
+mod layout;
+mod library;
+mod link;
+mod memo;
+mod mirror;
+mod multistate;
+mod ndjson;
+mod palette;
+mod pattern;
+#[cfg(feature = "bevy")]
+mod plugin;
+mod population;
+mod render;
+mod report;
+mod rng;
+mod rule;
+#[cfg(feature = "serde")]
+mod save;
+mod search;
+mod seed;
+mod snapshot;
+mod soup;
+mod sparse;
+mod stats;
+#[cfg(feature = "async")]
+mod stream;
+mod ticker;
+mod tiles;
+mod topology;
+mod trace;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "gui")]
+mod widget;
+mod zones;
+
+#[derive(Clone)]
 pub struct Game {
-    size: BoardSize,
+    // previous and current are the game boards. Each iteration swaps them and computes the new
+    // current board from the previous one.
+    // Shared with any snapshots taken of them, and copied before writing only if one's still
+    // around.
+    previous: Arc<Board>,
+    current: Arc<Board>,
+
+    // The rule deciding each cell's next state. Shared so that games stay cheap to clone.
+    rule: Arc<dyn Rule>,
+    table: Arc<RuleTable>,
+
+    backend: Backend,
+    // How many threads the backend may split each generation across, or 0 for one per core.
+    threads: usize,
+    cache: Cache,
+
+    // When explaining, the reasons each cell changed in the last iteration.
+    explanations: Option<Vec<Explanation>>,
+
+    // Random flips applied after every iteration, when noise is on.
+    noise: Option<Noise>,
+
+    // Cells that don't follow the rule.
+    constraints: Constraints,
+    // Parts of the board that follow a rule of their own.
+    zones: Zones,
+    // The symmetry the board's held to, when it's held to one.
+    mirror: Option<Mirror>,
+    // What lies past the board's edges.
+    topology: Topology,
 
-    // previous and current are the game boards. In order to make (x,y) coordinates make sense with
-    // the nested vectors as vec[x][y] then the outer vec represents the columns (x values) and the
-    // inner vec represents the rows (y values).
-    previous: Vec<Vec<bool>>,
-    current: Vec<Vec<bool>>,
+    // The number of iterations run, and what changed in the last one.
+    generation: usize,
+    last_changes: Changes,
+
+    // Per-tile statistics, when they're being kept.
+    tiles: Option<Tiles>,
+    ages: Option<Ages>,
+    series: Option<StatsSeries>,
+
+    // Stops long runs early when cancelled.
+    cancel: Option<CancelHandle>,
+
+    // How the board grows as live cells near its edges, if it does, and how far that's moved
+    // everything right and down.
+    growth: Option<Growth>,
+    growth_offset: (usize, usize),
+
+    // Every generation run since history recording started, when it's on, and the most memory it
+    // may take and what it gives up to stay within that.
+    history: Option<History>,
+    history_budget: (usize, HistoryPolicy),
+    history_capacity: usize,
+}
+
+// The chance of each cell flipping after an iteration, and the generator deciding which do.
+#[derive(Clone)]
+struct Noise {
+    rate: f64,
+    rng: Rng,
 }
 
 impl Game {
-    // Build a new, empty game board.
+    // Build a new, empty game board. Sizes aren't checked: a width or height of 0 gives a board
+    // with no cells, which can be run but never changes. Use try_new() to refuse those.
     pub fn new(x_size: usize, y_size: usize) -> Self {
+        Game::from(Board::new(x_size, y_size))
+    }
+
+    // Build a new, empty game board, refusing one with no cells, for sizes that came from someone's
+    // input. This is the only difference from Game::new().
+    pub fn try_new(x_size: usize, y_size: usize) -> Result<Self, GolError> {
+        if x_size == 0 || y_size == 0 {
+            return Err(GolError::EmptyBoard { x_size, y_size });
+        }
+        Ok(Game::new(x_size, y_size))
+    }
+
+    // Build a new, empty game board running a rule other than Conway's, such as HighLife from
+    // LifeLike::parse("B36/S23").
+    pub fn with_rule<R: Rule + 'static>(x_size: usize, y_size: usize, rule: R) -> Self {
+        let mut game = Game::new(x_size, y_size);
+        game.set_rule(rule);
+        game
+    }
+
+    // Build a new, empty game board with the given edges. Game::new() boards are toroidal.
+    pub fn new_with_topology(x_size: usize, y_size: usize, topology: Topology) -> Self {
+        let mut game = Game::new(x_size, y_size);
+        game.set_topology(topology);
+        game
+    }
+
+    // Build a new game board, setting each cell live or dead according to f(x, y).
+    pub fn from_fn<F: FnMut(usize, usize) -> bool>(x_size: usize, y_size: usize, f: F) -> Self {
+        Game::from(Board::from_fn(x_size, y_size, f))
+    }
+
+    // Build a new game board filled with a random soup, where each cell is live with probability
+    // `density`. The same seed always produces the same board.
+    pub fn random(x_size: usize, y_size: usize, density: f64, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        Game::from_fn(x_size, y_size, |_, _| rng.chance(density))
+    }
+
+    // A new game with its first board taken from a source. The board can come out bigger than asked
+    // for when the source doesn't fit.
+    pub fn seeded<S: SeedSource + ?Sized>(
+        source: &mut S,
+        x_size: usize,
+        y_size: usize,
+    ) -> Result<Self, SeedError> {
+        source.seed(x_size, y_size).map(Game::from)
+    }
+
+    // The game's generations as an async stream of snapshots, stepped on a thread of its own. See
+    // Generations.
+    #[cfg(feature = "async")]
+    pub fn stream(self) -> Generations {
+        Generations::new(self)
+    }
+
+    // A game on a different board that shares this game's rule and backend.
+    pub(crate) fn with_board(&self, board: Board) -> Game {
         Game {
-            size: BoardSize { x_size, y_size },
-            previous: make_board(x_size, y_size),
-            current: make_board(x_size, y_size),
+            previous: Arc::new(Board::new(board.x_size(), board.y_size())),
+            current: Arc::new(board),
+            rule: Arc::clone(&self.rule),
+            table: Arc::clone(&self.table),
+            backend: self.backend,
+            threads: self.threads,
+            cache: Cache::default(),
+            explanations: None,
+            noise: None,
+            constraints: Constraints::default(),
+            zones: Zones::default(),
+            mirror: None,
+            topology: self.topology,
+            generation: 0,
+            last_changes: Changes::default(),
+            tiles: None,
+            ages: None,
+            series: None,
+            cancel: self.cancel.clone(),
+            growth: self.growth,
+            growth_offset: (0, 0),
+            history: None,
+            history_budget: self.history_budget,
+            history_capacity: self.history_capacity,
+        }
+    }
+
+    // Run the simulation for a set number of iterations, or until it's cancelled, reporting how it
+    // went. The population is followed from each iteration's births and deaths, so the report
+    // costs nothing per generation.
+    pub fn run(&mut self, iters: usize) -> RunReport {
+        let run = trace::run_started(iters, self.generation);
+        let mut recorder = Recorder::new(self);
+        for done in 1..=iters {
+            if self.is_cancelled() {
+                break;
+            }
+            let changes = self.iterate();
+            let population = recorder.population() + changes.births - changes.deaths;
+            recorder.observe(self.generation, population);
+            if done.is_multiple_of(trace::CHECKPOINT) {
+                trace::checkpoint(self.generation, population);
+            }
         }
+        trace::run_finished(run, self.generation, recorder.population());
+        recorder.finish(self)
+    }
+
+    // Run until the population settles, as stabilize() judges it, or max_generations pass, and
+    // report how it went, with when it settled and its period if it did.
+    //
+    // A population can repeat sooner than the board does, as a blinker's does every generation, so
+    // once it settles the board is run on until it repeats exactly, as find_cycle() judges it, and
+    // the period is the board's. A board that doesn't repeat within max_period generations, such as
+    // one with gliders flying off across it, keeps the population's period.
+    pub fn run_until_stable(&mut self, max_generations: usize, max_period: usize) -> RunReport {
+        let mut recorder = Recorder::new(self);
+        let start = self.generation;
+        let stabilization = stabilization_with(self, max_generations, max_period, |game| {
+            recorder.observe(game.generation, game.board().population())
+        });
+        if let Some(stabilization) = stabilization {
+            let cycle = find_cycle(self, max_period, max_period);
+            // The population only repeats itself from here, so there's no new peak to catch.
+            recorder.observe(self.generation, self.board().population());
+            let period = cycle.behavior.period().unwrap_or(stabilization.period);
+            recorder.settled(start + stabilization.settled_at, period);
+        }
+        recorder.finish(self)
+    }
+
+    // Run until the board repeats within max_period generations, as find_cycle() judges it, or
+    // max_generations pass, and say what it settled into.
+    pub fn run_until_cycle(&mut self, max_generations: usize, max_period: usize) -> Analysis {
+        find_cycle(self, max_generations, max_period)
     }
 
-    // Run the simulation for a set number of iterations.
-    pub fn run(&mut self, iters: usize) {
-        println!("running with {iters} iterations!");
-        for _ in 0..iters {
-            self.iterate()
+    // Run as many generations as fit in the time budget, returning how many that was, for front
+    // ends that have a few milliseconds a frame to spare rather than a number of steps in mind. A
+    // generation that starts within the budget is finished, so the last one can run over it.
+    pub fn run_for(&mut self, budget: Duration) -> usize {
+        let start = Instant::now();
+        let mut steps = 0;
+        while start.elapsed() < budget && !self.is_cancelled() {
+            self.iterate();
+            steps += 1;
         }
+        steps
     }
 
-    // Run a single iteration of the game.
-    pub fn iterate(&mut self) {
+    // Run a single iteration of the game, reporting how many cells were born and died.
+    pub fn iterate(&mut self) -> Changes {
+        // Boards swapped in from elsewhere start out toroidal, so bring the one about to be stepped
+        // from into line.
+        keep_topology(&mut self.current, self.topology);
+
         // Swap the boards so we can work off the current coming into this iteration.
         mem::swap(&mut self.current, &mut self.previous);
 
-        // Clear the current board (now that we've saved the current state to previous).
-        clear_board(&mut self.current);
+        // Clear the current board (now that we've saved the current state to previous). If a
+        // snapshot still holds it, start a new one rather than copying cells about to be cleared.
+        match Arc::get_mut(&mut self.current) {
+            Some(current) => current.clear(),
+            None => self.current = Arc::new(Board::new(self.x_size(), self.y_size())),
+        }
+        keep_topology(&mut self.current, self.topology);
+
+        let mut changes = backend::step(
+            self.backend,
+            self.threads(),
+            self.mirror.as_ref().map(Mirror::domain),
+            &self.table,
+            &self.previous,
+            Arc::make_mut(&mut self.current),
+            &mut self.cache,
+        );
+        if self.zones.apply(
+            &self.previous,
+            Arc::make_mut(&mut self.current),
+            &mut changes,
+        ) {
+            self.cache.invalidate();
+        }
+        self.constrain(&mut changes);
+
+        if self.explanations.is_some() {
+            self.explanations = Some(explain::explain(&self.table, &self.previous, &self.current));
+        }
+
+        if let Some(noise) = &mut self.noise {
+            self.cache.invalidate();
+            let current = Arc::make_mut(&mut self.current);
+            for x in 0..current.x_size() {
+                for y in 0..current.y_size() {
+                    if noise.rng.chance(noise.rate) {
+                        let alive = !current.get(x, y);
+                        current.set(x, y, alive);
+                        if alive {
+                            changes.births += 1;
+                        } else {
+                            changes.deaths += 1;
+                        }
+                    }
+                }
+            }
+            self.constrain(&mut changes);
+        }
 
-        for x in 0..self.size.x_size {
-            for y in 0..self.size.y_size {
-                self.current[x][y] = self.is_live(x, y);
+        // Symmetry comes last, so it holds whatever else changed cells.
+        if let Some(mirror) = &self.mirror {
+            if mirror.apply(
+                &self.previous,
+                Arc::make_mut(&mut self.current),
+                &mut changes,
+            ) {
+                self.cache.invalidate();
             }
         }
+
+        self.finish_iteration(changes)
+    }
+
+    // Put constrained cells back the way they have to be after the rule or noise has changed them.
+    fn constrain(&mut self, changes: &mut Changes) {
+        if self
+            .constraints
+            .apply(&self.previous, Arc::make_mut(&mut self.current), changes)
+        {
+            self.cache.invalidate();
+        }
+    }
+
+    // Hold the board to a symmetry, making it symmetric now and keeping it that way every
+    // generation. Only the fundamental domain, the half or quarter of the board the rest mirrors,
+    // is stepped where the backend can step part of the board, and the rest is copied from it, so
+    // the symmetry stays exact and the work shrinks with it. Toggling or setting a cell edits its
+    // whole orbit; edits made through cell() or board_mut() are mirrored from the domain at the
+    // next generation. Symmetry::C1 turns it off. Quarter turns and diagonal mirrors need a square
+    // board, and if the board grows out of square, the symmetry is dropped.
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) -> Result<(), UnfitSymmetry> {
+        if symmetry == Symmetry::C1 {
+            self.mirror = None;
+            return Ok(());
+        }
+        let mirror = Mirror::new(symmetry, self.x_size(), self.y_size())?;
+        if mirror.symmetrize(Arc::make_mut(&mut self.current)) {
+            self.cache.invalidate();
+        }
+        self.mirror = Some(mirror);
+        Ok(())
+    }
+
+    // The symmetry the board's held to, or Symmetry::C1 if it isn't.
+    pub fn symmetry(&self) -> Symmetry {
+        self.mirror.as_ref().map_or(Symmetry::C1, Mirror::symmetry)
+    }
+
+    // Change what lies past the board's edges from the next generation on. Boards loaded or
+    // resized into the game afterwards keep to it too.
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        Arc::make_mut(&mut self.current).set_topology(topology);
+        self.cache.invalidate();
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    // Run a different rule inside a rectangle of the board, such as Life on the left half and
+    // Seeds on the right. Each cell follows the rule of the zone it's in, or the game's own rule
+    // outside every zone, and the last zone added wins where zones overlap. Neighbors are counted
+    // across zone edges as usual, so patterns can cross from one zone into another.
+    pub fn add_zone<R: Rule + 'static>(&mut self, region: Rect, rule: R) {
+        self.zones.add(region, rule);
+    }
+
+    // The rule the cell at (x, y) follows.
+    pub fn rule_at(&self, x: usize, y: usize) -> &dyn Rule {
+        self.zones.rule_at(x, y).unwrap_or(self.rule.as_ref())
+    }
+
+    pub fn has_zones(&self) -> bool {
+        !self.zones.is_empty()
+    }
+
+    // Go back to one rule for the whole board.
+    pub fn clear_zones(&mut self) {
+        self.zones.clear();
+    }
+
+    // Freeze the cell at (x, y) in its current state, or let it follow the rule again. Frozen
+    // cells never change as the game runs, but still count as neighbors, so they can pin a
+    // boundary in place while the rest of the board evolves. They can still be edited directly.
+    pub fn set_frozen(&mut self, x: usize, y: usize, frozen: bool) {
+        self.constraints.set_frozen(&self.current, x, y, frozen);
+    }
+
+    pub fn is_frozen(&self, x: usize, y: usize) -> bool {
+        self.constraints.is_frozen(x, y)
     }
 
-    fn is_live(&self, x: usize, y: usize) -> bool {
-        let mut live_neighbors = 0;
+    // Let every frozen cell follow the rule again.
+    pub fn clear_frozen(&mut self) {
+        self.constraints.clear_frozen();
+    }
 
-        let (check_x, check_y) = self.size.top_left(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // Make the cell at (x, y) a wall, killing it if it's live, or an ordinary cell again. Walls
+    // are always dead, so they never count as neighbors and a line of them keeps the cells on
+    // either side from seeing each other. Walls have to meet edge to edge to seal a space off,
+    // since cells touch diagonally across a gap at a corner.
+    pub fn set_wall(&mut self, x: usize, y: usize, wall: bool) {
+        self.constraints.set_wall(&self.current, x, y, wall);
+        if wall && self.current[(x, y)] {
+            *self.cell(x, y) = false;
         }
+    }
+
+    pub fn is_wall(&self, x: usize, y: usize) -> bool {
+        self.constraints.is_wall(x, y)
+    }
+
+    // Turn every wall back into an ordinary cell.
+    pub fn clear_walls(&mut self) {
+        self.constraints.clear_walls();
+    }
 
-        let (check_x, check_y) = self.size.top(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // Make the cell at (x, y) a source, bringing it to life if it's dead, or an ordinary cell
+    // again. Sources are live every generation whatever the rule says, so they keep feeding
+    // their neighbors; with walls around them they make fountains and emitters.
+    pub fn set_source(&mut self, x: usize, y: usize, source: bool) {
+        self.constraints.set_source(&self.current, x, y, source);
+        if source && !self.current[(x, y)] && !self.is_wall(x, y) {
+            *self.cell(x, y) = true;
         }
+    }
+
+    pub fn is_source(&self, x: usize, y: usize) -> bool {
+        self.constraints.is_source(x, y)
+    }
+
+    // Turn every source back into an ordinary cell.
+    pub fn clear_sources(&mut self) {
+        self.constraints.clear_sources();
+    }
 
-        let (check_x, check_y) = self.size.top_right(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // Run a single iteration inside a rectangle only, leaving every cell outside it as it is.
+    // Cells at the rectangle's edge still see their neighbors outside it, which don't change. The
+    // rectangle is clipped to the board, and noise isn't applied.
+    pub fn iterate_region(&mut self, region: Rect) -> Changes {
+        self.previous = Arc::clone(&self.current);
+        self.cache.invalidate();
+
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let mut changes = Changes::default();
+        let current = Arc::make_mut(&mut self.current);
+        for x in region.xs().take_while(|&x| x < x_size) {
+            for y in region.ys().take_while(|&y| y < y_size) {
+                let alive = self.previous[(x, y)];
+                let table = self.zones.table_at(x, y).unwrap_or(&self.table);
+                let next = table.next(alive, self.previous.neighborhood(x, y));
+                match (alive, next) {
+                    (false, true) => changes.births += 1,
+                    (true, false) => changes.deaths += 1,
+                    _ => {}
+                }
+                current.set(x, y, next);
+            }
         }
+        self.constrain(&mut changes);
 
-        let (check_x, check_y) = self.size.left(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+        if self.explanations.is_some() {
+            self.explanations = Some(explain::explain(&self.table, &self.previous, &self.current));
         }
+        self.finish_iteration(changes)
+    }
 
-        let (check_x, check_y) = self.size.right(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // Count the iteration that just ran and bring the statistics up to date.
+    fn finish_iteration(&mut self, changes: Changes) -> Changes {
+        self.generation += 1;
+        self.last_changes = changes;
+        if let Some(tiles) = &mut self.tiles {
+            tiles.update(&self.previous, &self.current, self.generation);
+        }
+        if let Some(ages) = &mut self.ages {
+            ages.update(&self.current);
+        }
+        if let Some((board, left, top)) = self.growth.and_then(|growth| growth.grow(&self.current))
+        {
+            self.resize_to(board);
+            self.growth_offset.0 += left;
+            self.growth_offset.1 += top;
+        }
+        if let Some(history) = &mut self.history {
+            history.record(self.generation, &self.current);
+        }
+        if self.series.is_some() {
+            let stats = self.stats();
+            if let Some(series) = &mut self.series {
+                series.record(stats);
+            }
         }
+        changes
+    }
+
+    // Record every generation from now on, so seek() can go back to any of them, or stop with an
+    // interval of 0. Generations are kept as the cells that changed, with the whole board every
+    // `interval` generations, so a shorter interval makes seeking faster and takes more memory.
+    // What's recorded is each board as the generation finished, before any edits made to it.
+    pub fn record_history(&mut self, interval: usize) {
+        self.history = match &self.history {
+            _ if interval == 0 => None,
+            Some(history) if history.interval() == interval => return,
+            _ => Some(History::new(
+                interval,
+                self.history_budget.0,
+                self.history_budget.1,
+                self.history_capacity,
+                self.generation,
+                &self.current,
+            )),
+        };
+    }
 
-        let (check_x, check_y) = self.size.bottom_left(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // The most memory, in bytes, history may take, and what it gives up to stay within it: the
+    // oldest generations, or the whole boards kept between them. It starts at 256 MiB, dropping
+    // the oldest generations.
+    pub fn history_budget(&self) -> (usize, HistoryPolicy) {
+        self.history_budget
+    }
+
+    pub fn set_history_budget(&mut self, bytes: usize, policy: HistoryPolicy) {
+        self.history_budget = (bytes, policy);
+        if let Some(history) = &mut self.history {
+            history.set_budget(bytes, policy);
         }
+    }
+
+    // The most generations history keeps, counting the current one. Past that the oldest are
+    // forgotten as new ones are recorded, so a long run holds its last few generations to step
+    // back through rather than all of them. It starts unlimited, leaving the budget to bound it.
+    pub fn history_capacity(&self) -> usize {
+        self.history_capacity
+    }
 
-        let (check_x, check_y) = self.size.bottom(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    pub fn set_history_capacity(&mut self, generations: usize) {
+        self.history_capacity = generations.max(1);
+        if let Some(history) = &mut self.history {
+            history.set_capacity(self.history_capacity);
         }
+    }
+
+    // Roughly how many bytes the recorded history takes.
+    pub fn history_memory(&self) -> usize {
+        self.history.as_ref().map_or(0, History::memory)
+    }
+
+    pub fn is_recording_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    // The first and last generations seek() can go to, if history is being recorded.
+    pub fn history_range(&self) -> Option<(usize, usize)> {
+        self.history.as_ref().map(History::range)
+    }
 
-        let (check_x, check_y) = self.size.bottom_right(x, y);
-        if self.previous[check_x][check_y] {
-            live_neighbors += 1;
+    // How many generations step_back() can go back from here, which is 0 when history isn't being
+    // recorded.
+    pub fn history_len(&self) -> usize {
+        self.history_range()
+            .map_or(0, |(first, _)| self.generation.saturating_sub(first))
+    }
+
+    // Go back to the generation before this one, as seek() would. Stepping forward again from
+    // there runs a new generation in place of the one stepped back from.
+    pub fn step_back(&mut self) -> Result<(), SeekError> {
+        if self.history.is_some() && self.history_len() == 0 {
+            return Err(SeekError::AtOldest(self.generation));
         }
+        self.seek(self.generation.saturating_sub(1))
+    }
+
+    // Go back, or forward again, to any generation recorded since history recording started,
+    // rebuilding its board from the nearest whole board kept. Running on from there replaces the
+    // generations recorded after it.
+    pub fn seek(&mut self, generation: usize) -> Result<(), SeekError> {
+        let history = self.history.as_mut().ok_or(SeekError::NotRecording)?;
+        let board = Arc::new(history.board_at(generation)?);
+        history.rewind(&board);
 
-        let is_live = self.previous[x][y];
-        match (is_live, live_neighbors) {
-            (true, 0 | 1) => false, // live with <2 neighbors dies
-            (true, 2 | 3) => true,  // live with 2 or 3 neighbors lives
-            (false, 3) => true,     // dead with 3 neighbors lives
-            _ => false,             // everything else dies
+        self.cache.invalidate();
+        if (board.x_size(), board.y_size()) != (self.x_size(), self.y_size()) {
+            self.previous = Arc::new(Board::new(board.x_size(), board.y_size()));
+            self.constraints = Constraints::default();
+            self.zones.clear();
+        }
+        self.current = board;
+        self.generation = generation;
+        self.last_changes = Changes::default();
+        if let Some(tiles) = &mut self.tiles {
+            *tiles = Tiles::new(tiles.size(), &self.current);
+        }
+        if let Some(ages) = &mut self.ages {
+            *ages = Ages::new(&self.current);
+        }
+        if let Some(series) = &mut self.series {
+            series.rewind(generation);
+        }
+        if let Some(explanations) = &mut self.explanations {
+            explanations.clear();
         }
+        Ok(())
     }
 
-    pub fn clear(&mut self) {
-        clear_board(&mut self.current);
-        clear_board(&mut self.previous);
+    // Grow the board as live cells come near its edges instead of letting them wrap around, or
+    // stop with None. It's checked after every iteration. Growing is a resize like expand(), so it
+    // drops constrained cells and rule zones.
+    pub fn set_growth(&mut self, growth: Option<Growth>) {
+        self.growth = growth;
     }
 
-    // Set up a fresh state, clearing any previous state from the game board.
-    pub fn clear_and_set<I: Iterator<Item = (usize, usize)>>(&mut self, pairs: I) {
-        clear_board(&mut self.current);
-        clear_board(&mut self.previous);
+    pub fn growth(&self) -> Option<Growth> {
+        self.growth
+    }
 
-        for (x, y) in pairs {
-            if x > self.size.x_size || y > self.size.y_size {
-                panic!("unexpected input coordinate");
-            }
+    // How many columns and rows growing has added on the left and top, which is how far it's moved
+    // every cell right and down.
+    pub fn growth_offset(&self) -> (usize, usize) {
+        self.growth_offset
+    }
+
+    // Keep statistics for each size x size tile of the board, updated every iteration, or stop
+    // keeping them with a size of 0. They cost one pass over the board per iteration, so front
+    // ends can see where the action is without going over every cell themselves.
+    pub fn track_tiles(&mut self, size: usize) {
+        self.tiles = (size > 0).then(|| Tiles::new(size, &self.current));
+    }
+
+    // The statistics for every tile, row by row from the top-left, as of the last iteration. Empty
+    // unless tiles are being tracked.
+    pub fn tile_stats(&self) -> &[TileStats] {
+        self.tiles.as_ref().map(Tiles::stats).unwrap_or_default()
+    }
 
-            self.current[x][y] = true
+    // Count how many generations each cell has been live for, updated every iteration, or stop
+    // counting. Counting starts with every live cell newborn, and starts again whenever the board
+    // changes size or the game seeks to another generation.
+    pub fn track_ages(&mut self, track: bool) {
+        self.ages = track.then(|| Ages::new(&self.current));
+    }
+
+    pub fn is_tracking_ages(&self) -> bool {
+        self.ages.is_some()
+    }
+
+    // How many generations in a row the cell at (x, y) has been live, counting this one: 1 for a
+    // cell born in the last iteration or set live since, and 0 for a dead cell. Live cells are
+    // always 1 unless ages are being tracked.
+    pub fn age(&self, x: usize, y: usize) -> u16 {
+        let alive = self.current.get(x, y);
+        match &self.ages {
+            Some(ages) => ages.get(self.current.flat_index(x, y), alive),
+            None => alive as u16,
         }
     }
 
-    pub fn set<I: Iterator<Item = (usize, usize)>>(&mut self, pairs: I) {
-        for (x, y) in pairs {
-            if x > self.size.x_size || y > self.size.y_size {
-                panic!("unexpected input coordinate");
+    // The generation count, population, and the births, deaths, and temperature of the last
+    // iteration. Everything but the population comes from what the iteration already counted.
+    pub fn stats(&self) -> Stats {
+        Stats::new(
+            self.generation,
+            self.current.population(),
+            self.x_size() * self.y_size(),
+            self.last_changes,
+        )
+    }
+
+    // Keep the stats of every generation from now on, up to the most recent `capacity` of them, as
+    // a series for plotting, or stop with a capacity of 0. Going back to an earlier generation
+    // with seek() drops the ones after it.
+    pub fn record_stats(&mut self, capacity: usize) {
+        self.series = match &mut self.series {
+            _ if capacity == 0 => None,
+            Some(series) if series.capacity() == capacity => return,
+            _ => Some(StatsSeries::new(capacity, self.stats())),
+        };
+    }
+
+    // The stats of each generation since record_stats() was called, if they're being kept.
+    pub fn stats_series(&self) -> Option<&StatsSeries> {
+        self.series.as_ref()
+    }
+
+    // Flip each cell with probability `rate` after every iteration, to see how patterns hold up
+    // under noise. The flips count as births and deaths in the iteration's Changes, and the same
+    // seed always flips the same cells. A rate of 0 turns noise off.
+    pub fn set_noise(&mut self, rate: f64, seed: u64) {
+        self.noise = (rate > 0.0).then(|| Noise {
+            rate,
+            rng: Rng::new(seed),
+        });
+    }
+
+    // The chance of each cell flipping after an iteration, or 0 if noise is off.
+    pub fn noise(&self) -> f64 {
+        self.noise.as_ref().map_or(0.0, |noise| noise.rate)
+    }
+
+    // Turn explain mode on or off. While it's on, every iteration records why each changed cell
+    // was born or died, which costs an extra pass over the board.
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explanations = explain.then(Vec::new);
+    }
+
+    pub fn is_explaining(&self) -> bool {
+        self.explanations.is_some()
+    }
+
+    // The reasons each cell changed in the last iteration, in column order. Empty unless explain
+    // mode was on for that iteration.
+    pub fn explanations(&self) -> &[Explanation] {
+        self.explanations.as_deref().unwrap_or_default()
+    }
+
+    // Replace the rule used for every following iteration. Conway's B3/S23 is used by default.
+    pub fn set_rule<R: Rule + 'static>(&mut self, rule: R) {
+        self.table = Arc::new(RuleTable::new(&rule));
+        self.cache.invalidate_rule();
+        self.rule = Arc::new(rule);
+    }
+
+    pub fn rule(&self) -> &dyn Rule {
+        self.rule.as_ref()
+    }
+
+    // The current rule in Life-like birth/survival form, if it only depends on neighbor counts.
+    pub fn life_like(&self) -> Option<LifeLike> {
+        self.table.life_like()
+    }
+
+    // Let long runs be stopped with a handle: run(), run_for() and the analyses check it every
+    // generation and stop once it's cancelled. Clones of the game, and games made from it, share
+    // the handle.
+    pub fn set_cancel(&mut self, cancel: Option<CancelHandle>) {
+        self.cancel = cancel;
+    }
+
+    // Whether the game's cancel handle has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelHandle::is_cancelled)
+    }
+
+    // Choose the engine used to compute each generation. Backend::Auto (the default) picks one
+    // based on the board.
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    // Limit how many threads a generation is split across, where the backend can split it at all.
+    // 1 keeps everything on the calling thread, and 0, the default, uses one per core. Boards too
+    // small to be worth splitting stay on one thread either way. Built with the parallel feature,
+    // the work runs on rayon's global thread pool.
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads;
+    }
+
+    // How many threads a generation may be split across.
+    pub fn threads(&self) -> usize {
+        match self.threads {
+            0 => {
+                // Looking it up can mean reading cgroup files, too slow for every generation.
+                static CORES: OnceLock<usize> = OnceLock::new();
+                *CORES.get_or_init(|| thread::available_parallelism().map_or(1, usize::from))
             }
+            threads => threads,
+        }
+    }
 
-            self.current[x][y] = true
+    // The concrete backend the next iteration will run on, after resolving Backend::Auto and any
+    // fallbacks for the current rule.
+    pub fn resolved_backend(&self) -> Backend {
+        self.backend.resolve(&self.table, self.cache.activity())
+    }
+
+    // Count the live neighbors of the cell at (x, y) on the current board.
+    pub fn live_neighbors(&self, x: usize, y: usize) -> u8 {
+        self.current.live_neighbors(x, y)
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.invalidate();
+        Arc::make_mut(&mut self.current).clear();
+        Arc::make_mut(&mut self.previous).clear();
+    }
+
+    // Fill the board with a random soup, where each cell is live with probability `density`,
+    // returning the seed it came from. The same seed always gives the board Game::random() would,
+    // and without one a fresh seed is picked, which can be passed back in to get the soup again.
+    pub fn randomize(&mut self, density: f64, seed: Option<u64>) -> u64 {
+        let seed = seed.unwrap_or_else(rng::fresh_seed);
+        let mut rng = Rng::new(seed);
+        let board = self.board_mut();
+        for x in 0..board.x_size() {
+            for y in 0..board.y_size() {
+                board.set(x, y, rng.chance(density));
+            }
         }
+        seed
     }
 
-    pub fn cell(&mut self, x: usize, y: usize) -> &mut bool {
-        &mut self.current[x][y]
+    // Set up a fresh state, clearing any previous state from the game board. If any coordinate is
+    // off the board, the board is left as it was.
+    pub fn clear_and_set<I, P>(&mut self, pairs: I) -> Result<(), GolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<(usize, usize)>,
+    {
+        let points = self.on_board(pairs)?;
+        self.clear();
+        self.set_all(points);
+        Ok(())
     }
 
-    pub fn x_size(&self) -> usize {
-        self.size.x_size
+    // Set each of the given coordinates live, leaving the rest of the board untouched. Accepts
+    // anything that can be iterated into (x, y) pairs or Points, e.g. arrays, slices, or vectors.
+    // If any coordinate is off the board, none of them are set.
+    pub fn set<I, P>(&mut self, pairs: I) -> Result<(), GolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<(usize, usize)>,
+    {
+        let points = self.on_board(pairs)?;
+        self.set_all(points);
+        Ok(())
     }
 
-    pub fn y_size(&self) -> usize {
-        self.size.y_size
+    // The coordinates, once they've all been checked to be on the board.
+    fn on_board<I, P>(&self, pairs: I) -> Result<Vec<(usize, usize)>, GolError>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<(usize, usize)>,
+    {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let points: Vec<(usize, usize)> = pairs.into_iter().map(Into::into).collect();
+        match points.iter().find(|&&(x, y)| x >= x_size || y >= y_size) {
+            Some(&(x, y)) => Err(GolError::OutOfBounds {
+                x,
+                y,
+                x_size,
+                y_size,
+            }),
+            None => Ok(points),
+        }
     }
-}
 
-fn make_board(x_size: usize, y_size: usize) -> Vec<Vec<bool>> {
-    let mut board = Vec::with_capacity(x_size);
-    for _ in 0..x_size {
-        board.push(vec![false; y_size]);
+    fn set_all(&mut self, points: Vec<(usize, usize)>) {
+        self.cache.invalidate();
+        for (x, y) in points {
+            for point in self.orbit(x, y) {
+                Arc::make_mut(&mut self.current).set(point.x, point.y, true);
+            }
+        }
     }
-    board
-}
 
-fn clear_board(board: &mut [Vec<bool>]) {
-    for col in board {
-        for cell in col {
-            *cell = false
+    // Flip the cell at (x, y) between live and dead, returning its new state.
+    pub fn toggle(&mut self, x: usize, y: usize) -> bool {
+        self.cache.invalidate();
+        let alive = !self.current[(x, y)];
+        for point in self.orbit(x, y) {
+            Arc::make_mut(&mut self.current).set(point.x, point.y, alive);
         }
+        alive
     }
-}
 
-struct BoardSize {
-    x_size: usize,
-    y_size: usize,
-}
+    // The cells an edit to (x, y) has to make, to keep the board symmetric.
+    fn orbit(&self, x: usize, y: usize) -> Vec<Point> {
+        match &self.mirror {
+            Some(mirror) => mirror.orbit(x, y, self.x_size(), self.y_size()),
+            None => vec![Point::new(x, y)],
+        }
+    }
 
-impl BoardSize {
-    fn top_left(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (-1, -1)
-        (
-            if x == 0 { self.x_size - 1 } else { x - 1 },
-            if y == 0 { self.y_size - 1 } else { y - 1 },
-        )
+    // Fill the whole board with an agar, which then stands in for empty space. It refuses boards it
+    // doesn't fit a whole number of times, since it would meet itself out of step at the wrapped
+    // edges and react all along them.
+    pub fn fill_agar(&mut self, agar: Agar) -> Result<(), AgarSeam> {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        if !agar.seamless(x_size, y_size) {
+            return Err(AgarSeam {
+                agar,
+                x_size,
+                y_size,
+            });
+        }
+        agar.fill(self.board_mut(), Rect::new(0, 0, x_size, y_size));
+        Ok(())
     }
 
-    fn top(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (0, -1)
-        (x, if y == 0 { self.y_size - 1 } else { y - 1 })
+    // Flip every one of the given coordinates. A coordinate listed twice is flipped twice.
+    pub fn toggle_all<I, P>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<(usize, usize)>,
+    {
+        for (x, y) in pairs.into_iter().map(Into::into) {
+            self.toggle(x, y);
+        }
     }
 
-    fn top_right(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (1, -1)
-        (
-            if x == self.x_size - 1 { 0 } else { x + 1 },
-            if y == 0 { self.y_size - 1 } else { y - 1 },
-        )
+    pub fn cell(&mut self, x: usize, y: usize) -> CellMut<'_> {
+        self.cache.invalidate();
+        Arc::make_mut(&mut self.current).cell_mut(x, y)
     }
 
-    fn left(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (-1, 0)
-        (if x == 0 { self.x_size - 1 } else { x - 1 }, y)
+    // The current board.
+    pub fn board(&self) -> &Board {
+        &self.current
     }
 
-    fn right(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (1, 0)
-        (if x == self.x_size - 1 { 0 } else { x + 1 }, y)
+    // The current board, for editing in place.
+    pub fn board_mut(&mut self) -> &mut Board {
+        self.cache.invalidate();
+        Arc::make_mut(&mut self.current)
     }
 
-    fn bottom_left(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (-1, 1)
-        (
-            if x == 0 { self.x_size - 1 } else { x - 1 },
-            if y == self.y_size - 1 { 0 } else { y + 1 },
-        )
+    // An immutable copy of the current board that's cheap to take and to pass between threads.
+    // It shares the board's cells until the game next writes to them.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(Arc::clone(&self.current), self.generation)
     }
 
-    fn bottom(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (0, 1)
-        (x, if y == self.y_size - 1 { 0 } else { y + 1 })
+    // Snapshot the current board in row-major (visual) order, so grid[y][x] is the cell at (x, y).
+    pub fn to_grid(&self) -> Vec<Vec<bool>> {
+        self.current.to_grid()
     }
 
-    fn bottom_right(&self, x: usize, y: usize) -> (usize, usize) {
-        // translate (1, 1)
-        (
-            if x == self.x_size - 1 { 0 } else { x + 1 },
-            if y == self.y_size - 1 { 0 } else { y + 1 },
-        )
+    // Iterate over the rows of the current board from top to bottom, each yielding its cells from
+    // left to right.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        self.current.rows()
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    // Iterate over the columns of the current board from left to right, each yielding its cells
+    // from top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> + '_ {
+        self.current.columns()
+    }
 
-    #[test]
-    fn test_board_size_top_left() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((1, 1), size.top_left(0, 0));
-        assert_eq!((0, 0), size.top_left(1, 1));
-        assert_eq!((0, 1), size.top_left(1, 0));
-        assert_eq!((1, 0), size.top_left(0, 1));
+    // Whether the cell at (x, y) on the current board is live, for reading cells without the
+    // &mut Game that cell() needs. Panics if (x, y) is off the board, like indexing a Board.
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.current.get(x, y)
     }
 
-    #[test]
-    fn test_board_size_top() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((0, 1), size.top(0, 0));
-        assert_eq!((1, 0), size.top(1, 1));
-        assert_eq!((1, 1), size.top(1, 0));
-        assert_eq!((0, 0), size.top(0, 1));
+    // The coordinates of every live cell on the current board, column by column.
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.current.live_cells().map(|point| (point.x, point.y))
     }
 
-    #[test]
-    fn test_board_size_top_right() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((1, 1), size.top_right(0, 0));
-        assert_eq!((0, 0), size.top_right(1, 1));
-        assert_eq!((0, 1), size.top_right(1, 0));
-        assert_eq!((1, 0), size.top_right(0, 1));
+    // How many cells are live on the current board.
+    pub fn population(&self) -> usize {
+        self.current.population()
     }
 
-    #[test]
-    fn test_board_size_left() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((1, 0), size.left(0, 0));
-        assert_eq!((0, 1), size.left(1, 1));
-        assert_eq!((0, 0), size.left(1, 0));
-        assert_eq!((1, 1), size.left(0, 1));
+    pub fn x_size(&self) -> usize {
+        self.current.x_size()
     }
 
-    #[test]
-    fn test_board_size_right() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((1, 0), size.left(0, 0));
-        assert_eq!((0, 1), size.left(1, 1));
-        assert_eq!((0, 0), size.left(1, 0));
-        assert_eq!((1, 1), size.left(0, 1));
+    pub fn y_size(&self) -> usize {
+        self.current.y_size()
     }
 
-    #[test]
-    fn test_board_size_bottom_left() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((1, 1), size.bottom_left(0, 0));
-        assert_eq!((0, 0), size.bottom_left(1, 1));
-        assert_eq!((0, 1), size.bottom_left(1, 0));
-        assert_eq!((1, 0), size.bottom_left(0, 1));
+    // Shrink the board to the smallest rectangle holding every live cell, returning where that
+    // rectangle was on the old board. A board with no live cells is left as it is, rather than
+    // shrunk to the no-cell board try_new() and resize() refuse, and Pattern::trim() does the same.
+    pub fn trim(&mut self) -> Option<Rect> {
+        let bounds = self.current.bounding_box()?;
+        self.resize_to(self.current.crop(bounds));
+        Some(bounds)
     }
 
-    #[test]
-    fn test_board_size_bottom() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
-        };
-        assert_eq!((0, 1), size.bottom(0, 0));
-        assert_eq!((1, 0), size.bottom(1, 1));
-        assert_eq!((1, 1), size.bottom(1, 0));
-        assert_eq!((0, 0), size.bottom(0, 1));
+    // Grow the board by `margin` dead cells on every side, so everything on it moves `margin`
+    // cells right and down.
+    pub fn expand(&mut self, margin: usize) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let board = Board::from_fn(x_size + 2 * margin, y_size + 2 * margin, |x, y| {
+            (margin..margin + x_size).contains(&x)
+                && (margin..margin + y_size).contains(&y)
+                && self.current[(x - margin, y - margin)]
+        });
+        self.resize_to(board);
     }
 
-    #[test]
-    fn test_board_size_bottom_right() {
-        let size = BoardSize {
-            x_size: 2,
-            y_size: 2,
+    // Change the board's size, keeping the cells that still fit. The anchor says where the old
+    // board sits on the new one: at its top-left corner, or in the middle, so growing the board
+    // adds room on every side and shrinking it cuts the edges away evenly. Boards with no cells
+    // are refused, as try_new() refuses them.
+    pub fn resize(&mut self, x_size: usize, y_size: usize, anchor: Anchor) -> Result<(), GolError> {
+        if x_size == 0 || y_size == 0 {
+            return Err(GolError::EmptyBoard { x_size, y_size });
+        }
+        let (old_x, old_y) = (self.x_size() as isize, self.y_size() as isize);
+        let (dx, dy) = match anchor {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Center => ((x_size as isize - old_x) / 2, (y_size as isize - old_y) / 2),
         };
-        assert_eq!((1, 1), size.bottom_right(0, 0));
-        assert_eq!((0, 0), size.bottom_right(1, 1));
-        assert_eq!((0, 1), size.bottom_right(1, 0));
-        assert_eq!((1, 0), size.bottom_right(0, 1));
+        let board = Board::from_fn(x_size, y_size, |x, y| {
+            let (from_x, from_y) = (x as isize - dx, y as isize - dy);
+            (0..old_x).contains(&from_x)
+                && (0..old_y).contains(&from_y)
+                && self.current[(from_x as usize, from_y as usize)]
+        });
+        self.resize_to(board);
+        Ok(())
     }
 
-    #[test]
-    fn test_make_board() {
-        let board = make_board(1, 1);
-        assert_eq!(1, board.len());
-        assert_eq!(1, board[0].len());
+    // Move everything on the board dx cells right and dy cells down, or left and up for negative
+    // amounts. On a toroidal board, cells moved off one edge come back on the opposite one, and on
+    // any other they're gone, with dead cells moving in behind them.
+    pub fn translate(&mut self, dx: isize, dy: isize) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let wraps = self.topology == Topology::Toroidal;
+        let mut board = Board::from_fn(x_size, y_size, |x, y| {
+            let (from_x, from_y) = (x as isize - dx, y as isize - dy);
+            if wraps {
+                let from_x = from_x.rem_euclid(x_size as isize) as usize;
+                let from_y = from_y.rem_euclid(y_size as isize) as usize;
+                return self.current[(from_x, from_y)];
+            }
+            let inside = |at: isize, size: usize| (0..size as isize).contains(&at);
+            inside(from_x, x_size)
+                && inside(from_y, y_size)
+                && self.current[(from_x as usize, from_y as usize)]
+        });
+        board.set_topology(self.topology);
+        *self.board_mut() = board;
     }
 
-    #[test]
-    fn test_clear_board() {
-        let mut board = make_board(1, 1);
-        board[0][0] = true;
-        clear_board(&mut board);
-        assert!(!board[0][0]);
+    // Turn the board a quarter turn clockwise, swapping its width and height.
+    pub fn rotate90(&mut self) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let board = Board::from_fn(y_size, x_size, |x, y| self.current[(y, y_size - 1 - x)]);
+        self.resize_to(board);
     }
 
-    #[test]
-    fn test_board_set() {
-        let mut game = Game::new(1, 1);
-        game.set([(0, 0)].into_iter());
-        assert!(!game.previous[0][0]);
-        assert!(game.current[0][0]);
+    // Turn the board a half turn.
+    pub fn rotate180(&mut self) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let mut board = Board::from_fn(x_size, y_size, |x, y| {
+            self.current[(x_size - 1 - x, y_size - 1 - y)]
+        });
+        board.set_topology(self.topology);
+        *self.board_mut() = board;
     }
 
-    #[test]
-    fn test_iterate_block() {
-        let mut game = Game::new(4, 4);
-        game.set([(1, 1), (2, 1), (1, 2), (2, 2)].into_iter());
-        game.iterate();
-        assert_eq!(
-            &vec![
-                // NOTE: this is rotated from the actual board
-                vec![false, false, false, false],
-                vec![false, true, true, false],
-                vec![false, true, true, false],
-                vec![false, false, false, false]
-            ],
-            &game.current
-        );
+    // Turn the board a quarter turn counterclockwise, swapping its width and height.
+    pub fn rotate270(&mut self) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let board = Board::from_fn(y_size, x_size, |x, y| self.current[(x_size - 1 - y, x)]);
+        self.resize_to(board);
     }
 
-    #[test]
-    fn test_iterate_blinker() {
-        let mut game = Game::new(5, 5);
-        game.set([(2, 1), (2, 2), (2, 3)].into_iter());
-        game.iterate();
-        assert_eq!(
-            &vec![
-                vec![false, false, false, false, false],
-                vec![false, false, true, false, false],
-                vec![false, false, true, false, false],
-                vec![false, false, true, false, false],
-                vec![false, false, false, false, false]
-            ],
-            &game.current
-        );
+    // Mirror the board left to right.
+    pub fn flip_horizontal(&mut self) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let mut board = Board::from_fn(x_size, y_size, |x, y| self.current[(x_size - 1 - x, y)]);
+        board.set_topology(self.topology);
+        *self.board_mut() = board;
+    }
+
+    // Mirror the board top to bottom.
+    pub fn flip_vertical(&mut self) {
+        let (x_size, y_size) = (self.x_size(), self.y_size());
+        let mut board = Board::from_fn(x_size, y_size, |x, y| self.current[(x, y_size - 1 - y)]);
+        board.set_topology(self.topology);
+        *self.board_mut() = board;
+    }
+
+    // Every cell whose state differs between this game's board and another board of the same size,
+    // in column order.
+    pub fn difference(&self, other: &Board) -> Vec<Point> {
+        self.current.diff(other).collect()
+    }
+
+    // The number of cells that differ from another board of the same size.
+    pub fn hamming_distance(&self, other: &Board) -> usize {
+        self.current.hamming_distance(other)
+    }
+
+    // The fraction of cells that match another board of the same size, from 0.0 to 1.0.
+    pub fn similarity(&self, other: &Board) -> f64 {
+        self.current.similarity(other)
+    }
+
+    // Fill the region of same-state cells around (x, y) with `alive`, returning how many cells
+    // changed. See Board::flood_fill.
+    pub fn flood_fill(&mut self, x: usize, y: usize, alive: bool) -> usize {
+        self.board_mut().flood_fill(x, y, alive)
+    }
+
+    // The groups of live cells that touch each other, including diagonally, each with its size and
+    // bounding box. Groups don't join up across the board's edges.
+    pub fn components(&self) -> Vec<Component> {
+        component::components(&self.current)
+    }
+
+    // Every top-left corner where the pattern appears on the board as it is. The whole of the
+    // pattern's rectangle has to match, dead cells included, and matches can wrap around the edges.
+    pub fn find(&self, pattern: &Pattern) -> Vec<Point> {
+        search::find(&self.current, pattern, &[Orientation::Identity])
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    // Every place the pattern appears in any rotation or reflection, with the orientation it
+    // appears in. Symmetric patterns are reported once per place, in the first orientation that
+    // matches.
+    pub fn find_oriented(&self, pattern: &Pattern) -> Vec<(Point, Orientation)> {
+        search::find(&self.current, pattern, &Orientation::ALL)
+    }
+
+    // Combine another board of the same size into this one, cell by cell.
+    pub fn merge(&mut self, other: &Board, mode: BlendMode) -> Result<(), GridError> {
+        if (other.x_size(), other.y_size()) != (self.x_size(), self.y_size()) {
+            return Err(GridError::WrongSize {
+                expected: (self.x_size(), self.y_size()),
+                found: (other.x_size(), other.y_size()),
+            });
+        }
+        self.merge_at(other, Point::default(), mode)
+    }
+
+    // Stamp an RLE pattern, such as one copied from LifeWiki or Golly, onto the board with its
+    // top-left corner at (x, y). Its live cells are added to whatever's already there, wrapping
+    // around the edges or cut off at them like merge_at(), and the game keeps its own rule
+    // whatever the pattern's header says.
+    pub fn load_rle(&mut self, rle: &str, x: usize, y: usize) -> Result<(), PatternError> {
+        let pattern: Pattern = rle.parse()?;
+        self.stamp(&pattern, x, y)?;
+        Ok(())
+    }
+
+    // Stamp a pattern read from any format onto the board with its top-left corner at (x, y),
+    // adding its live cells to whatever's already there. Cells in any state but 0 are live.
+    pub fn stamp(&mut self, pattern: &Pattern, x: usize, y: usize) -> Result<(), GridError> {
+        self.merge_at(&pattern.to_board(), Point::new(x, y), BlendMode::Or)
+    }
+
+    // Stamp one of the built-in patterns that Pattern::named() knows, turned or mirrored first,
+    // with its top-left corner at (x, y), as stamp() would.
+    pub fn stamp_named(
+        &mut self,
+        name: &str,
+        x: usize,
+        y: usize,
+        orientation: Orientation,
+    ) -> Result<(), LayoutError> {
+        let placement = Placement {
+            name: name.to_string(),
+            at: Point::new(x, y),
+            orientation,
+        };
+        Layout::from(vec![placement]).place(self.board_mut(), Pattern::named)
+    }
+
+    // The whole board as RLE, with the rule in the header when it's Life-like, in the form Golly
+    // copies to the clipboard.
+    pub fn to_rle(&self) -> String {
+        Pattern::from(self).to_string()
+    }
+
+    // Combine a board no bigger than this one into it, with its top-left corner at `at`. Cells
+    // outside the placed board are left alone. It wraps around the edges of a toroidal board, and
+    // is cut off at the edges of any other, as Board::blend() is.
+    pub fn merge_at(&mut self, other: &Board, at: Point, mode: BlendMode) -> Result<(), GridError> {
+        self.board_mut().blend(other, at, mode)
+    }
+
+    // Swap in a board of a different size, dropping everything tied to the old one, constrained
+    // cells and rule zones included.
+    fn resize_to(&mut self, mut board: Board) {
+        board.set_topology(self.topology);
+        self.cache.invalidate();
+        self.constraints = Constraints::default();
+        self.zones.clear();
+        self.mirror = self
+            .mirror
+            .take()
+            .and_then(|mirror| Mirror::new(mirror.symmetry(), board.x_size(), board.y_size()).ok());
+        self.previous = Arc::new(Board::new(board.x_size(), board.y_size()));
+        self.current = Arc::new(board);
+        if let Some(tiles) = &mut self.tiles {
+            *tiles = Tiles::new(tiles.size(), &self.current);
+        }
+        if let Some(ages) = &mut self.ages {
+            *ages = Ages::new(&self.current);
+        }
+        if let Some(explanations) = &mut self.explanations {
+            explanations.clear();
+        }
+    }
+}
+
+// Give a board the game's topology if it doesn't have it already.
+fn keep_topology(board: &mut Arc<Board>, topology: Topology) {
+    if board.topology() != topology {
+        Arc::make_mut(board).set_topology(topology);
+    }
+}
+
+// Start a game from an existing board.
+impl From<Board> for Game {
+    fn from(board: Board) -> Self {
+        let topology = board.topology();
+        Game {
+            previous: Arc::new(Board::new(board.x_size(), board.y_size())),
+            current: Arc::new(board),
+            rule: Arc::new(LifeLike::CONWAY),
+            table: Arc::new(RuleTable::new(&LifeLike::CONWAY)),
+            backend: Backend::Auto,
+            threads: 0,
+            cache: Cache::default(),
+            explanations: None,
+            noise: None,
+            constraints: Constraints::default(),
+            zones: Zones::default(),
+            mirror: None,
+            topology,
+            generation: 0,
+            last_changes: Changes::default(),
+            tiles: None,
+            ages: None,
+            series: None,
+            cancel: None,
+            growth: None,
+            growth_offset: (0, 0),
+            history: None,
+            history_budget: (history::DEFAULT_BUDGET, HistoryPolicy::default()),
+            history_capacity: usize::MAX,
+        }
+    }
+}
+
+// Build a game from a row-major grid, the same layout produced by Game::to_grid. Every row must
+// have the same, non-zero length.
+impl TryFrom<Vec<Vec<bool>>> for Game {
+    type Error = GridError;
+
+    fn try_from(grid: Vec<Vec<bool>>) -> Result<Self, Self::Error> {
+        Board::try_from(grid).map(Game::from)
+    }
+}
+
+// A summary of what changed during a single iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub births: usize,
+    pub deaths: usize,
+}
+
+impl Changes {
+    // Whether any cell changed state at all. If not, the board is static and will stay that way.
+    pub fn changed(&self) -> bool {
+        self.births != 0 || self.deaths != 0
+    }
+}
+
+// A rectangle of cells with its top-left corner at (x, y).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // The smallest rectangle containing every one of the points, if there are any.
+    pub fn bounding<I: IntoIterator<Item = Point>>(points: I) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+        Some(Rect::new(
+            min.x,
+            min.y,
+            max.x - min.x + 1,
+            max.y - min.y + 1,
+        ))
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.xs().contains(&x) && self.ys().contains(&y)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    // The part of the rectangle inside another one, if they overlap at all.
+    pub fn intersection(&self, other: Rect) -> Option<Rect> {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        let rect = Rect::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y));
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    // The x coordinates covered by the rectangle.
+    pub fn xs(&self) -> Range<usize> {
+        self.x..self.x + self.width
+    }
+
+    // The y coordinates covered by the rectangle.
+    pub fn ys(&self) -> Range<usize> {
+        self.y..self.y + self.height
+    }
+}
+
+// Where the old board goes on the new one when a game is resized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    // The top-left corners line up, so the board grows or shrinks at the right and bottom.
+    #[default]
+    TopLeft,
+    // The middles line up, so the board grows or shrinks evenly on every side.
+    Center,
+}
+
+// A single (x, y) coordinate on the board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for (usize, usize) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<&Point> for (usize, usize) {
+    fn from(point: &Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_board_set() {
+        let mut game = Game::new(1, 1);
+        game.set([(0, 0)]).unwrap();
+        assert!(!game.previous[(0, 0)]);
+        assert!(game.current[(0, 0)]);
+    }
+
+    #[test]
+    fn test_board_set_points() {
+        let mut game = Game::new(2, 2);
+        game.set(vec![Point::new(0, 1), Point::new(1, 0)]).unwrap();
+        assert!(game.current[(0, 1)]);
+        assert!(game.current[(1, 0)]);
+        assert!(!game.current[(0, 0)]);
+
+        let points = [Point::new(1, 1)];
+        game.set(points.iter()).unwrap();
+        assert!(game.current[(1, 1)]);
+    }
+
+    #[test]
+    fn test_board_clear_and_set() {
+        let mut game = Game::new(2, 2);
+        game.set([(0, 0)]).unwrap();
+        game.clear_and_set(vec![(1, 1)]).unwrap();
+        assert!(!game.current[(0, 0)]);
+        assert!(game.current[(1, 1)]);
+    }
+
+    #[test]
+    fn test_board_set_out_of_bounds() {
+        let mut game = Game::new(3, 2);
+        game.set([(1, 1)]).unwrap();
+        let off = GolError::OutOfBounds {
+            x: 3,
+            y: 0,
+            x_size: 3,
+            y_size: 2,
+        };
+        // Nothing is set or cleared when any coordinate is off the board, even by one.
+        assert_eq!(Err(off), game.set([(0, 0), (3, 0)]));
+        assert_eq!(Err(off), game.clear_and_set([(0, 0), (3, 0)]));
+        assert_eq!(vec![(1, 1)], game.live_cells().collect::<Vec<_>>());
+        assert!(game.set([(0, 2)]).is_err());
+        assert_eq!("(3, 0) is off the 3x2 board", off.to_string());
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(4, Game::try_new(4, 2).unwrap().x_size());
+        assert!(matches!(
+            Game::try_new(0, 5),
+            Err(GolError::EmptyBoard {
+                x_size: 0,
+                y_size: 5
+            })
+        ));
+        assert!(Game::try_new(5, 0).is_err());
+        // A game with no cells can still be run, it just never changes.
+        let mut empty = Game::new(0, 0);
+        empty.run(3);
+        assert_eq!(0, empty.population());
+    }
+
+    #[test]
+    fn test_iterate_block() {
+        let mut game = Game::new(4, 4);
+        game.set([(1, 1), (2, 1), (1, 2), (2, 2)]).unwrap();
+        assert!(!game.iterate().changed());
+        assert_eq!(
+            "....\n\
+             .OO.\n\
+             .OO.\n\
+             ....\n",
+            game.board().to_string()
+        );
+    }
+
+    #[test]
+    fn test_iterate_blinker() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let changes = game.iterate();
+        assert_eq!(
+            Changes {
+                births: 2,
+                deaths: 2
+            },
+            changes
+        );
+        assert!(changes.changed());
+        assert_eq!(
+            ".....\n\
+             .....\n\
+             .OOO.\n\
+             .....\n\
+             .....\n",
+            game.board().to_string()
+        );
+    }
+
+    #[test]
+    fn test_grid_non_square() {
+        let game = Game::try_from(vec![vec![true, false, false]]).unwrap();
+        assert_eq!(3, game.x_size());
+        assert_eq!(1, game.y_size());
+        assert_eq!(vec![vec![true, false, false]], game.to_grid());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut game = Game::new(3, 2);
+        game.set([(0, 0), (2, 0), (1, 1)]).unwrap();
+
+        let rows: Vec<Vec<bool>> = game.rows().map(Iterator::collect).collect();
+        assert_eq!(
+            vec![vec![true, false, true], vec![false, true, false]],
+            rows
+        );
+
+        let columns: Vec<Vec<bool>> = game.columns().map(Iterator::collect).collect();
+        assert_eq!(
+            vec![vec![true, false], vec![false, true], vec![true, false]],
+            columns
+        );
+    }
+
+    #[test]
+    fn test_live_neighbors() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        assert_eq!(1, game.live_neighbors(2, 1));
+        assert_eq!(2, game.live_neighbors(2, 2));
+        assert_eq!(3, game.live_neighbors(1, 2));
+        assert_eq!(0, game.live_neighbors(4, 4));
+    }
+
+    #[test]
+    fn test_live_neighbors_wraps() {
+        let mut game = Game::new(3, 3);
+        game.set([(2, 2)]).unwrap();
+        assert_eq!(1, game.live_neighbors(0, 0));
+    }
+
+    #[test]
+    fn test_rle() {
+        let glider = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+        let lwss = "x = 5, y = 4, rule = B3/S23\nbo2bo$o$o3bo$4o!\n";
+        for rle in [glider, lwss] {
+            let pattern: Pattern = rle.parse().unwrap();
+            let mut game = Game::new(pattern.width(), pattern.height());
+            game.load_rle(rle, 0, 0).unwrap();
+            assert_eq!(rle, game.to_rle());
+        }
+
+        // The Gosper glider gun, wrapped at 70 characters the way Golly writes it.
+        let gun = "x = 36, y = 9, rule = B3/S23\n\
+                   24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b\n\
+                   obo$10bo5bo7bo$11bo3bo$12b2o!\n";
+        let mut game = Game::new(36, 9);
+        game.load_rle(gun, 0, 0).unwrap();
+        assert_eq!(36, game.board().population());
+        assert_eq!(gun, game.to_rle());
+
+        // Stamped onto a bigger board, the gun fires a glider every 30 generations.
+        let mut game = Game::new(60, 40);
+        game.load_rle(gun, 2, 2).unwrap();
+        assert!(game.board()[(2, 6)]);
+        for _ in 0..30 {
+            game.iterate();
+        }
+        assert_eq!(41, game.board().population());
+
+        assert_eq!(
+            Err(PatternError::Grid(GridError::TooLarge {
+                limit: (3, 3),
+                found: (5, 4)
+            })),
+            Game::new(3, 3).load_rle(lwss, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_stamp() {
+        let mut game = Game::new(5, 4);
+        let glider = Pattern::from_cells("!Name: Glider\n.O.\n..O\nOOO\n").unwrap();
+        game.stamp(&glider, 3, 2).unwrap();
+        let blinker = Pattern::from_life106("#Life 1.06\n-1 0\n0 0\n1 0\n").unwrap();
+        game.stamp(&blinker, 0, 0).unwrap();
+        assert_eq!("OOOOO\n.....\n....O\nO....\n", game.board().to_string());
+    }
+
+    #[test]
+    fn test_stamp_named() {
+        let mut game = Game::new(5, 4);
+        game.stamp_named("Glider", 1, 1, Orientation::Rotate90)
+            .unwrap();
+        assert_eq!(".....\n.O...\n.O.O.\n.OO..\n", game.board().to_string());
+        assert_eq!(
+            Err(LayoutError::UnknownPattern("queen bee".to_string())),
+            game.stamp_named("queen bee", 0, 0, Orientation::Identity)
+        );
+    }
+
+    #[test]
+    fn test_with_rule() {
+        // The middle cell has six live neighbors, which is only enough for a birth in HighLife.
+        let cells = [(1, 1), (2, 1), (3, 1), (1, 3), (2, 3), (3, 3)];
+        let mut highlife = Game::with_rule(5, 5, LifeLike::parse("B36/S23").unwrap());
+        highlife.set(cells).unwrap();
+        highlife.iterate();
+        assert!(highlife.board()[(2, 2)]);
+
+        let mut conway = Game::new(5, 5);
+        conway.set(cells).unwrap();
+        conway.iterate();
+        assert!(!conway.board()[(2, 2)]);
+
+        let day_and_night = Game::with_rule(5, 5, LifeLike::parse("B3678/S34678").unwrap());
+        assert_eq!(
+            Some("B3678/S34678".to_string()),
+            day_and_night.life_like().map(|rule| rule.to_string())
+        );
+    }
+
+    #[test]
+    fn test_topology() {
+        // A blinker standing against the left edge.
+        let blinker = |topology| {
+            let mut game = Game::new_with_topology(5, 5, topology);
+            game.set([(0, 1), (0, 2), (0, 3)]).unwrap();
+            game
+        };
+        assert_eq!(2, blinker(Topology::Toroidal).live_neighbors(0, 2));
+        assert_eq!(2, blinker(Topology::FixedDead).live_neighbors(0, 2));
+        // The mirror shows the cell and both its neighbors again past the edge.
+        assert_eq!(5, blinker(Topology::Mirror).live_neighbors(0, 2));
+
+        for topology in Topology::ALL {
+            let boards: Vec<Board> = Backend::available()
+                .into_iter()
+                .map(|backend| {
+                    let mut game = blinker(topology);
+                    game.set_backend(backend);
+                    for _ in 0..4 {
+                        game.iterate();
+                    }
+                    game.board().clone()
+                })
+                .collect();
+            assert!(boards.windows(2).all(|pair| pair[0] == pair[1]));
+        }
+
+        // On a torus the blinker carries on across the edge, but a dead edge cuts it in half.
+        let mut game = blinker(Topology::FixedDead);
+        game.iterate();
+        assert_eq!(
+            ".....\n.....\nOO...\n.....\n.....\n",
+            game.board().to_string()
+        );
+        game.iterate();
+        assert_eq!(0, game.board().population());
+        let mut game = blinker(Topology::Toroidal);
+        game.iterate();
+        game.iterate();
+        assert_eq!(3, game.board().population());
+
+        // Boards swapped in later follow the game's topology.
+        let mut game = Game::new_with_topology(3, 3, Topology::FixedDead);
+        game.expand(1);
+        assert_eq!(Topology::FixedDead, game.board().topology());
+        *game.board_mut() = "O....\n.....\n.....\n.....\nO...O\n".parse().unwrap();
+        game.iterate();
+        assert_eq!(Topology::FixedDead, game.board().topology());
+        assert_eq!(0, game.board().population());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut game = Game::new(2, 2);
+        assert!(game.toggle(1, 0));
+        assert!(game.current[(1, 0)]);
+        assert!(!game.toggle(1, 0));
+        assert!(!game.current[(1, 0)]);
+    }
+
+    #[test]
+    fn test_toggle_all() {
+        let mut game = Game::new(2, 2);
+        game.set([(0, 0)]).unwrap();
+        game.toggle_all([(0, 0), (1, 1), (0, 1), (0, 1)]);
+        assert_eq!(vec![vec![false, false], vec![false, true]], game.to_grid());
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let game = Game::from_fn(3, 2, |x, y| (x + y) % 2 == 0);
+        assert_eq!(3, game.x_size());
+        assert_eq!(2, game.y_size());
+        assert_eq!(
+            vec![vec![true, false, true], vec![false, true, false]],
+            game.to_grid()
+        );
+    }
+
+    #[test]
+    fn test_random() {
+        let game = Game::random(20, 10, 0.5, 1234);
+        assert_eq!(20, game.x_size());
+        assert_eq!(10, game.y_size());
+        assert_eq!(game.to_grid(), Game::random(20, 10, 0.5, 1234).to_grid());
+        assert_ne!(game.to_grid(), Game::random(20, 10, 0.5, 4321).to_grid());
+
+        let live = game.columns().flatten().filter(|&cell| cell).count();
+        assert!(live > 50 && live < 150);
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut game = Game::new(4, 4);
+        game.set([(2, 1), (0, 3), (2, 0)]).unwrap();
+        let game = &game;
+        assert!(game.is_alive(2, 1));
+        assert!(!game.is_alive(1, 2));
+        assert_eq!(
+            vec![(0, 3), (2, 0), (2, 1)],
+            game.live_cells().collect::<Vec<_>>()
+        );
+        assert_eq!(3, game.population());
+        assert_eq!(0, Game::new(3, 3).live_cells().count());
+    }
+
+    #[test]
+    fn test_randomize() {
+        let mut game = Game::new(20, 10);
+        assert_eq!(1234, game.randomize(0.5, Some(1234)));
+        assert_eq!(Game::random(20, 10, 0.5, 1234).board(), game.board());
+
+        let seed = game.randomize(0.5, None);
+        let mut again = Game::new(20, 10);
+        again.randomize(0.5, Some(seed));
+        assert_eq!(game.board(), again.board());
+        again.randomize(1.0, None);
+        assert_eq!(200, again.board().population());
+    }
+
+    #[test]
+    fn test_random_density_extremes() {
+        let empty = Game::random(5, 5, 0.0, 1);
+        assert!(empty.columns().flatten().all(|cell| !cell));
+        let full = Game::random(5, 5, 1.0, 1);
+        assert!(full.columns().flatten().all(|cell| cell));
+    }
+
+    #[test]
+    fn test_iterate_with_rule() {
+        // HighLife (B36/S23) births a cell with six neighbors where Conway's rule wouldn't.
+        let mut game = Game::new(5, 5);
+        game.set([(1, 1), (2, 1), (3, 1), (1, 3), (2, 3), (3, 3)])
+            .unwrap();
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        game.iterate();
+        assert!(game.board()[(2, 2)]);
+
+        game.clear_and_set([(1, 1), (2, 1), (3, 1), (1, 3), (2, 3), (3, 3)])
+            .unwrap();
+        game.set_rule(LifeLike::CONWAY);
+        game.iterate();
+        assert!(!game.board()[(2, 2)]);
+    }
+
+    #[test]
+    fn test_iterate_with_closure_rule() {
+        // Every cell copies its left neighbor, shifting the whole board right.
+        let mut game = Game::new(3, 1);
+        game.set([(0, 0)]).unwrap();
+        game.set_rule(|_: bool, neighborhood: Neighborhood| neighborhood.get(-1, 0));
+        game.iterate();
+        assert_eq!(".O.\n", game.board().to_string());
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let mut expected = Game::random(16, 12, 0.4, 99);
+        for _ in 0..10 {
+            expected.iterate();
+        }
+
+        for backend in Backend::available() {
+            let mut game = Game::random(16, 12, 0.4, 99);
+            game.set_backend(backend);
+            for _ in 0..10 {
+                game.iterate();
+            }
+            assert_eq!(expected.board(), game.board(), "{backend} diverged");
+        }
+    }
+
+    #[test]
+    fn test_symmetry() {
+        for symmetry in [
+            Symmetry::D2,
+            Symmetry::C2,
+            Symmetry::D4,
+            Symmetry::C4,
+            Symmetry::D8,
+        ] {
+            let mut game = Game::random(40, 40, 0.4, 3);
+            game.set_backend(Backend::Table);
+            game.set_symmetry(symmetry).unwrap();
+            assert_eq!(symmetry, game.symmetry());
+
+            // Stepping just the domain and mirroring it matches stepping the whole board.
+            let mut expected = Game::from(game.board().clone());
+            expected.set_backend(Backend::Naive);
+            for _ in 0..20 {
+                assert_eq!(expected.iterate(), game.iterate(), "{symmetry}");
+                assert_eq!(expected.board(), game.board(), "{symmetry}");
+            }
+        }
+
+        let mut game = Game::new(6, 4);
+        game.set_symmetry(Symmetry::D4).unwrap();
+        assert!(game.toggle(1, 0));
+        assert_eq!(".O..O.\n......\n......\n.O..O.\n", game.board().to_string());
+        assert!(game.set_symmetry(Symmetry::C4).is_err());
+        game.set_symmetry(Symmetry::C1).unwrap();
+        game.toggle(0, 0);
+        assert_eq!(5, game.board().population());
+    }
+
+    #[test]
+    fn test_threads() {
+        let mut expected = Game::random(512, 300, 0.3, 11);
+        expected.set_threads(1);
+        assert_eq!(1, expected.threads());
+        let mut game = expected.clone();
+        game.set_threads(0);
+        assert!(game.threads() >= 1);
+        game.set_threads(4);
+        for _ in 0..5 {
+            assert_eq!(expected.iterate(), game.iterate());
+            assert_eq!(expected.board(), game.board());
+        }
+    }
+
+    #[test]
+    fn test_backends_agree_across_edits() {
+        let mut expected = Game::random(10, 10, 0.3, 7);
+        expected.set_backend(Backend::Naive);
+        let mut game = Game::random(10, 10, 0.3, 7);
+        game.set_backend(Backend::NeighborCount);
+        for generation in 0..10 {
+            if generation % 3 == 0 {
+                expected.toggle_all([(generation, 2), (4, 4)]);
+                game.toggle_all([(generation, 2), (4, 4)]);
+            }
+            expected.iterate();
+            game.iterate();
+            assert_eq!(expected.board(), game.board());
+        }
+    }
+
+    #[test]
+    fn test_rect_bounding() {
+        assert_eq!(None, Rect::bounding([]));
+        assert_eq!(
+            Some(Rect::new(1, 2, 1, 1)),
+            Rect::bounding([Point::new(1, 2)])
+        );
+        let rect = Rect::bounding([Point::new(3, 1), Point::new(1, 4), Point::new(2, 2)]).unwrap();
+        assert_eq!(Rect::new(1, 1, 3, 4), rect);
+        assert!(rect.contains(1, 1));
+        assert!(rect.contains(3, 4));
+        assert!(!rect.contains(4, 4));
+        assert!(!rect.contains(0, 2));
+    }
+
+    #[test]
+    fn test_iterate_glider_far_from_activity() {
+        // A glider in the corner of a large board moves the same way whichever backend runs it.
+        let mut game = Game::new(40, 30);
+        game.set([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]).unwrap();
+        let mut expected = game.clone();
+        expected.set_backend(Backend::Naive);
+        game.set_backend(Backend::Table);
+        for _ in 0..120 {
+            assert_eq!(expected.iterate(), game.iterate());
+            assert_eq!(expected.board(), game.board());
+        }
+    }
+
+    #[test]
+    fn test_trim() {
+        let mut game = Game::new(10, 8);
+        game.set([(3, 2), (4, 3), (2, 4), (3, 4), (4, 4)]).unwrap();
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        assert_eq!(Some(Rect::new(2, 2, 3, 3)), game.trim());
+        assert_eq!(".O.\n..O\nOOO\n", game.board().to_string());
+        assert_eq!(Some(LifeLike::new(&[3, 6], &[2, 3])), game.life_like());
+
+        let mut empty = Game::new(4, 4);
+        assert_eq!(None, empty.trim());
+        assert_eq!((4, 4), (empty.x_size(), empty.y_size()));
+    }
+
+    #[test]
+    fn test_expand() {
+        let mut game = Game::from(".O.\n..O\nOOO\n".parse::<Board>().unwrap());
+        game.expand(2);
+        assert_eq!((7, 7), (game.x_size(), game.y_size()));
+        assert_eq!(Some(Rect::new(2, 2, 3, 3)), game.board().bounding_box());
+        game.trim();
+        assert_eq!(".O.\n..O\nOOO\n", game.board().to_string());
+
+        // With room around it, the glider moves one cell down and right every four generations.
+        game.expand(3);
+        game.run(4);
+        assert_eq!(Some(Rect::new(4, 4, 3, 3)), game.board().bounding_box());
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut game = Game::new(4, 3);
+        game.set([(0, 0), (3, 2)]).unwrap();
+        game.resize(6, 4, Anchor::TopLeft).unwrap();
+        assert_eq!((6, 4), (game.x_size(), game.y_size()));
+        assert_eq!(vec![(0, 0), (3, 2)], game.live_cells().collect::<Vec<_>>());
+
+        game.resize(10, 8, Anchor::Center).unwrap();
+        assert_eq!(vec![(2, 2), (5, 4)], game.live_cells().collect::<Vec<_>>());
+        // Shrinking cuts away whatever no longer fits.
+        game.resize(4, 4, Anchor::Center).unwrap();
+        assert_eq!(vec![(2, 2)], game.live_cells().collect::<Vec<_>>());
+        game.resize(2, 2, Anchor::TopLeft).unwrap();
+        assert_eq!(0, game.population());
+
+        assert_eq!(
+            Err(GolError::EmptyBoard {
+                x_size: 0,
+                y_size: 3
+            }),
+            game.resize(0, 3, Anchor::Center)
+        );
+        assert_eq!((2, 2), (game.x_size(), game.y_size()));
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut game = Game::new(5, 4);
+        game.set([(1, 1), (2, 1), (2, 2)]).unwrap();
+        game.translate(2, 1);
+        assert_eq!(
+            vec![Point::new(3, 2), Point::new(4, 2), Point::new(4, 3)],
+            game.board().live_cells().collect::<Vec<_>>()
+        );
+
+        // Cells pushed past an edge wrap around to the other side.
+        game.translate(-4, 2);
+        assert_eq!(
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(4, 0)],
+            game.board().live_cells().collect::<Vec<_>>()
+        );
+
+        // Unless the edges are dead, where they're lost.
+        let mut game = Game::new(5, 4);
+        game.set_topology(Topology::FixedDead);
+        game.set([(1, 1), (2, 1), (2, 2)]).unwrap();
+        game.translate(-2, 2);
+        assert_eq!(
+            vec![Point::new(0, 3)],
+            game.board().live_cells().collect::<Vec<_>>()
+        );
+        game.stamp(&Pattern::from_cells("OO\n").unwrap(), 4, 0)
+            .unwrap();
+        assert_eq!(
+            vec![Point::new(0, 3), Point::new(4, 0)],
+            game.board().live_cells().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rotate() {
+        let board: Board = "OO.\n..O\n".parse().unwrap();
+        let mut game = Game::from(board.clone());
+        game.rotate90();
+        assert_eq!(".O\n.O\nO.\n", game.board().to_string());
+        game.rotate90();
+        assert_eq!("O..\n.OO\n", game.board().to_string());
+
+        let mut half = Game::from(board.clone());
+        half.rotate180();
+        assert_eq!(game.board(), half.board());
+
+        game.rotate270();
+        game.rotate270();
+        assert_eq!(board, *game.board());
+    }
+
+    #[test]
+    fn test_flip() {
+        let board: Board = "OO.\n..O\n".parse().unwrap();
+        let mut game = Game::from(board.clone());
+        game.flip_horizontal();
+        assert_eq!(".OO\nO..\n", game.board().to_string());
+        game.flip_vertical();
+        assert_eq!("O..\n.OO\n", game.board().to_string());
+
+        // Flipping both ways is the same as a half turn.
+        let mut half = Game::from(board);
+        half.rotate180();
+        assert_eq!(game.board(), half.board());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut game = Game::from("OO.\n...\n".parse::<Board>().unwrap());
+        let layer: Board = ".O.\n..O\n".parse().unwrap();
+        game.merge(&layer, BlendMode::Xor).unwrap();
+        assert_eq!("O..\n..O\n", game.board().to_string());
+
+        game.merge_at(&"O\nO\n".parse().unwrap(), Point::new(1, 0), BlendMode::Or)
+            .unwrap();
+        assert_eq!("OO.\n.OO\n", game.board().to_string());
+
+        assert_eq!(
+            Err(GridError::WrongSize {
+                expected: (3, 2),
+                found: (2, 2)
+            }),
+            game.merge(&Board::new(2, 2), BlendMode::And)
+        );
+    }
+
+    #[test]
+    fn test_difference() {
+        // The same seed under two rules drifts apart as soon as a cell has six neighbors.
+        let mut conway = Game::random(16, 16, 0.5, 8);
+        let mut highlife = conway.clone();
+        highlife.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        assert_eq!(1.0, conway.similarity(highlife.board()));
+
+        let mut distances = Vec::new();
+        for _ in 0..10 {
+            conway.iterate();
+            highlife.iterate();
+            let distance = conway.hamming_distance(highlife.board());
+            assert_eq!(distance, conway.difference(highlife.board()).len());
+            assert_eq!(
+                1.0 - distance as f64 / 256.0,
+                conway.similarity(highlife.board())
+            );
+            distances.push(distance);
+        }
+        assert!(distances.iter().any(|&distance| distance > 0));
+    }
+
+    #[test]
+    fn test_find() {
+        // Two gliders, one flying each way, and a blinker.
+        let game = Game::from(
+            "..........\n\
+             ..O.......\n\
+             ...O...O..\n\
+             .OOO...O..\n\
+             .......O..\n\
+             ..........\n\
+             .OOO......\n\
+             ...O......\n\
+             ..O.......\n\
+             ..........\n"
+                .parse::<Board>()
+                .unwrap(),
+        );
+        let glider: Pattern = "x = 3, y = 3\nbo$2bo$3o!".parse().unwrap();
+        assert_eq!(vec![Point::new(1, 1)], game.find(&glider));
+        assert_eq!(
+            vec![
+                (Point::new(1, 1), Orientation::Identity),
+                (Point::new(1, 6), Orientation::FlipVertical)
+            ],
+            game.find_oriented(&glider)
+        );
+
+        // With its dead border, the blinker only matches where it's on its own, and its other
+        // orientations are the same blinker turned on its side, which isn't on the board.
+        let blinker: Pattern = "x = 3, y = 5\n$bo$bo$bo!".parse().unwrap();
+        assert_eq!(vec![Point::new(6, 1)], game.find(&blinker));
+        assert_eq!(
+            vec![(Point::new(6, 1), Orientation::Identity)],
+            game.find_oriented(&blinker)
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let game = Game::from(
+            "O.....O\n\
+             .O.....\n\
+             ...OO..\n\
+             ...O..O\n"
+                .parse::<Board>()
+                .unwrap(),
+        );
+        let components = game.components();
+        assert_eq!(
+            vec![2, 3, 1, 1],
+            components.iter().map(Component::size).collect::<Vec<_>>()
+        );
+        assert_eq!(Rect::new(0, 0, 2, 2), components[0].bounds);
+        assert_eq!(
+            vec![Point::new(3, 2), Point::new(3, 3), Point::new(4, 2)],
+            components[1].cells
+        );
+        // The corners would touch if groups wrapped around the edges.
+        assert_eq!(Rect::new(6, 0, 1, 1), components[2].bounds);
+        assert!(Game::new(3, 3).components().is_empty());
+    }
+
+    #[test]
+    fn test_noise() {
+        let block = [(1, 1), (2, 1), (1, 2), (2, 2)];
+        let mut quiet = Game::new(20, 20);
+        quiet.set(block).unwrap();
+        let mut noisy = quiet.clone();
+        noisy.set_noise(0.01, 5);
+        assert_eq!(0.01, noisy.noise());
+
+        let mut again = noisy.clone();
+        for _ in 0..20 {
+            assert_eq!(Changes::default(), quiet.iterate());
+            assert_eq!(noisy.iterate(), again.iterate());
+            assert_eq!(noisy.board(), again.board());
+        }
+        assert_ne!(quiet.board(), noisy.board());
+
+        noisy.set_noise(0.0, 5);
+        assert_eq!(0.0, noisy.noise());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut game = Game::new(5, 4);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        assert_eq!(
+            Stats {
+                population: 3,
+                ..Stats::default()
+            },
+            game.stats()
+        );
+
+        game.iterate();
+        assert_eq!(
+            Stats {
+                generation: 1,
+                population: 3,
+                births: 2,
+                deaths: 2,
+                temperature: 0.2,
+            },
+            game.stats()
+        );
+    }
+
+    #[test]
+    fn test_iterate_region() {
+        // Two blinkers, with only the left one inside the region.
+        let mut game = Game::new(10, 5);
+        game.set([(2, 1), (2, 2), (2, 3), (7, 1), (7, 2), (7, 3)])
+            .unwrap();
+        let changes = game.iterate_region(Rect::new(0, 0, 5, 5));
+        assert_eq!(
+            Changes {
+                births: 2,
+                deaths: 2
+            },
+            changes
+        );
+        assert_eq!(
+            "..........\n\
+             .......O..\n\
+             .OOO...O..\n\
+             .......O..\n\
+             ..........\n",
+            game.board().to_string()
+        );
+        assert_eq!(1, game.stats().generation);
+
+        // A region hanging off the board is clipped to it.
+        game.iterate_region(Rect::new(5, 0, 20, 20));
+        assert_eq!(
+            "..........\n\
+             ..........\n\
+             .OOO..OOO.\n\
+             ..........\n\
+             ..........\n",
+            game.board().to_string()
+        );
+    }
+
+    #[test]
+    fn test_frozen() {
+        // Freezing the middle of a blinker keeps it live, and its neighbors still see it.
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        game.set_frozen(2, 1, true);
+        assert!(game.is_frozen(2, 1));
+        assert!(!game.is_frozen(2, 2));
+
+        let changes = game.iterate();
+        assert_eq!(
+            Changes {
+                births: 2,
+                deaths: 1
+            },
+            changes
+        );
+        assert_eq!(
+            ".....\n..O..\n.OOO.\n.....\n.....\n",
+            game.board().to_string()
+        );
+
+        // A frozen dead cell stays dead even with three live neighbors.
+        game.set_frozen(2, 3, true);
+        game.iterate();
+        assert!(!game.board()[(2, 3)]);
+        assert!(game.board()[(2, 1)]);
+
+        game.clear_frozen();
+        assert!(!game.is_frozen(2, 1));
+    }
+
+    #[test]
+    fn test_walls() {
+        // A column of walls through the middle of a blinker splits it into two dying halves.
+        let mut game = Game::new(7, 7);
+        game.set([(2, 3), (3, 3), (4, 3)]).unwrap();
+        for y in 0..7 {
+            game.set_wall(3, y, true);
+        }
+        assert!(game.is_wall(3, 0));
+        assert!(!game.board()[(3, 3)]);
+
+        game.iterate();
+        assert_eq!(0, game.board().population());
+
+        // Cells with three live neighbors still can't be born in a wall.
+        let mut game = Game::new(6, 6);
+        game.set([(1, 1), (2, 1), (1, 2)]).unwrap();
+        game.set_wall(2, 2, true);
+        game.iterate();
+        assert!(!game.board()[(2, 2)]);
+
+        game.clear_walls();
+        assert!(!game.is_wall(2, 2));
+        game.iterate();
+        assert!(game.board()[(2, 2)]);
+    }
+
+    #[test]
+    fn test_sources() {
+        // A lone cell dies of loneliness, but a source stays live.
+        let mut game = Game::new(6, 6);
+        game.set_source(2, 2, true);
+        assert!(game.is_source(2, 2));
+        assert!(game.board()[(2, 2)]);
+        game.iterate();
+        assert!(game.board()[(2, 2)]);
+
+        // An overcrowded source survives, and isn't counted as dying.
+        let mut game = Game::new(7, 7);
+        game.set([(2, 1), (1, 2), (3, 2), (2, 3)]).unwrap();
+        game.set_source(2, 2, true);
+        assert_eq!(
+            Changes {
+                births: 4,
+                deaths: 0
+            },
+            game.iterate()
+        );
+        assert!(game.board()[(2, 2)]);
+
+        // A wall wins over a source.
+        game.set_source(3, 3, true);
+        game.set_wall(3, 3, true);
+        game.iterate();
+        assert!(!game.board()[(3, 3)]);
+
+        game.clear_sources();
+        assert!(!game.is_source(2, 2));
+    }
+
+    #[test]
+    fn test_zones() {
+        // A blinker on the left under Life, and another on the right under Seeds (B2/S), where
+        // every live cell dies.
+        let mut game = Game::new(12, 6);
+        game.set([(2, 1), (2, 2), (2, 3), (8, 1), (8, 2), (8, 3)])
+            .unwrap();
+        game.add_zone(Rect::new(6, 0, 6, 6), LifeLike::new(&[2], &[]));
+        assert!(game.has_zones());
+        let two = Neighborhood::from_bits(0b11);
+        assert!(game.rule_at(8, 2).transition(false, two));
+        assert!(!game.rule_at(2, 2).transition(false, two));
+
+        let mut expected = game.clone();
+        expected.clear_zones();
+        expected.iterate();
+        let changes = game.iterate();
+        for x in 0..6 {
+            for y in 0..6 {
+                assert_eq!(expected.board()[(x, y)], game.board()[(x, y)]);
+            }
+        }
+        // Under Seeds the blinker's three cells all die, and only the cells beside its ends have
+        // exactly two live neighbors to be born with.
+        assert_eq!(
+            vec![
+                Point::new(7, 1),
+                Point::new(7, 3),
+                Point::new(9, 1),
+                Point::new(9, 3)
+            ],
+            game.board()
+                .live_cells()
+                .filter(|point| point.x >= 6)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Changes {
+                births: 6,
+                deaths: 5
+            },
+            changes
+        );
+    }
+
+    #[test]
+    fn test_tile_stats() {
+        // A blinker in the top-left tile and a block in the bottom-right one, on a board that's
+        // two and a half tiles across.
+        let mut game = Game::new(10, 8);
+        game.set([(1, 2), (2, 2), (3, 2), (8, 5), (9, 5), (8, 6), (9, 6)])
+            .unwrap();
+        assert!(game.tile_stats().is_empty());
+        game.track_tiles(4);
+        assert_eq!(6, game.tile_stats().len());
+        assert_eq!(3, game.tile_stats()[0].population);
+        assert_eq!(Rect::new(8, 4, 2, 4), game.tile_stats()[5].tile);
+
+        game.run(2);
+        let stats = game.tile_stats();
+        assert_eq!(
+            TileStats {
+                tile: Rect::new(0, 0, 4, 4),
+                population: 3,
+                activity: 4.0 / 16.0,
+                last_changed: Some(2),
+            },
+            stats[0]
+        );
+        assert_eq!(
+            (4, 0.0, None),
+            (
+                stats[5].population,
+                stats[5].activity,
+                stats[5].last_changed
+            )
+        );
+        assert_eq!(0, stats[1].population);
+
+        game.track_tiles(0);
+        assert!(game.tile_stats().is_empty());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut game = Game::random(16, 12, 0.4, 3);
+        assert_eq!(Err(SeekError::NotRecording), game.seek(0));
+        game.iterate();
+        game.record_history(4);
+        let mut boards = vec![game.board().clone()];
+        for _ in 0..10 {
+            game.iterate();
+            boards.push(game.board().clone());
+        }
+        assert_eq!(Some((1, 11)), game.history_range());
+
+        for generation in [7, 1, 11, 3, 9] {
+            game.seek(generation).unwrap();
+            assert_eq!(generation, game.stats().generation);
+            assert_eq!(&boards[generation - 1], game.board());
+        }
+        assert!(game.seek(12).is_err());
+
+        // Running on from an earlier generation replaces what came after it.
+        game.seek(5).unwrap();
+        game.toggle(0, 0);
+        game.iterate();
+        assert_eq!(Some((1, 6)), game.history_range());
+        let edited = game.board().clone();
+        game.seek(2).unwrap();
+        game.seek(6).unwrap();
+        assert_eq!(&edited, game.board());
+
+        // Resizing keeps going back across the change.
+        game.expand(1);
+        game.iterate();
+        game.seek(3).unwrap();
+        assert_eq!(&boards[2], game.board());
+        game.seek(7).unwrap();
+        assert_eq!((18, 14), (game.x_size(), game.y_size()));
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut game = Game::random(12, 12, 0.4, 8);
+        assert_eq!(Err(SeekError::NotRecording), game.step_back());
+        game.set_history_capacity(4);
+        game.record_history(16);
+        assert_eq!(Err(SeekError::AtOldest(0)), game.step_back());
+
+        let mut boards = vec![game.board().clone()];
+        for _ in 0..6 {
+            game.iterate();
+            boards.push(game.board().clone());
+        }
+        // Only the last four generations are kept, so there are three to step back through.
+        assert_eq!(3, game.history_len());
+        for generation in [5, 4, 3] {
+            game.step_back().unwrap();
+            assert_eq!(generation, game.stats().generation);
+            assert_eq!(&boards[generation], game.board());
+        }
+        assert_eq!(0, game.history_len());
+        assert_eq!(Err(SeekError::AtOldest(3)), game.step_back());
+        game.iterate();
+        assert_eq!(&boards[4], game.board());
+        assert_eq!(1, game.history_len());
+    }
+
+    #[test]
+    fn test_history_budget() {
+        let mut game = Game::random(32, 32, 0.4, 11);
+        game.record_history(8);
+        game.run(40);
+        let memory = game.history_memory();
+        assert!(memory > 0);
+
+        game.set_history_budget(memory / 2, HistoryPolicy::DropOldest);
+        assert!(game.history_memory() <= memory / 2);
+        let (first, last) = game.history_range().unwrap();
+        assert!(first > 0);
+        assert_eq!(40, last);
+
+        // The budget carries over when recording starts again.
+        game.record_history(0);
+        assert_eq!(0, game.history_memory());
+        game.record_history(4);
+        game.run(40);
+        assert!(game.history_memory() <= memory / 2);
+    }
+
+    #[test]
+    fn test_explain_mode() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        game.iterate();
+        assert!(game.explanations().is_empty());
+
+        game.set_explain(true);
+        game.iterate();
+        let explanations = game.explanations();
+        assert_eq!(4, explanations.len());
+        assert_eq!(
+            2,
+            explanations
+                .iter()
+                .filter(|explanation| explanation.is_birth())
+                .count()
+        );
+
+        game.set_explain(false);
+        game.iterate();
+        assert!(game.explanations().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let before = game.snapshot();
+        assert!(before.ptr_eq(&game.snapshot()));
+        assert_eq!(game.board(), before.board());
+
+        game.iterate();
+        let after = game.snapshot();
+        assert_eq!((0, 1), (before.generation(), after.generation()));
+        assert!(before[(2, 1)] && !before[(1, 2)]);
+        assert!(!after[(2, 1)] && after[(1, 2)]);
+
+        // Writing to a board a snapshot holds leaves the snapshot alone.
+        game.toggle(0, 0);
+        assert!(!after[(0, 0)] && game.board()[(0, 0)]);
+
+        let population = std::thread::spawn(move || before.population())
+            .join()
+            .unwrap();
+        assert_eq!(3, population);
+    }
+
+    #[test]
+    fn test_run_for() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        assert_eq!(0, game.run_for(Duration::ZERO));
+
+        let steps = game.run_for(Duration::from_millis(5));
+        assert!(steps > 0);
+        assert_eq!(steps, game.stats().generation);
+        assert_eq!(steps % 2 == 1, game.board()[(1, 2)]);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let cancel = CancelHandle::new();
+        game.set_cancel(Some(cancel.clone()));
+        game.run(3);
+        assert_eq!(3, game.stats().generation);
+
+        cancel.cancel();
+        assert!(game.clone().is_cancelled());
+        game.run(100);
+        assert_eq!(0, game.run_for(Duration::from_millis(5)));
+        assert_eq!(3, game.stats().generation);
+
+        game.set_cancel(None);
+        game.run(2);
+        assert_eq!(5, game.stats().generation);
+    }
+
+    #[test]
+    fn test_growth() {
+        // A glider heading down and right, on a board that can grow to 20x20.
+        let mut game = Game::new(8, 8);
+        game.set([(4, 3), (5, 4), (3, 5), (4, 5), (5, 5)]).unwrap();
+        game.set_growth(Some(Growth::new(2, 6, 20)));
+        game.run(40);
+        assert_eq!((20, 20), (game.x_size(), game.y_size()));
+        assert_eq!((0, 0), game.growth_offset());
+        assert_eq!(5, game.board().population());
+
+        // A blinker reaching the left edge, where growing moves everything right.
+        let mut game = Game::new(8, 8);
+        game.set([(1, 3), (1, 4), (1, 5)]).unwrap();
+        game.set_growth(Some(Growth::new(1, 3, 20)));
+        game.iterate();
+        assert_eq!((11, 8), (game.x_size(), game.y_size()));
+        assert_eq!((3, 0), game.growth_offset());
+        game.iterate();
+        assert!(game.board()[(4, 3)] && game.board()[(4, 5)]);
+    }
+
+    #[test]
+    fn test_run_report() {
+        // A blinker and a cell that dies straight away.
+        let mut game = Game::new(10, 10);
+        game.set([(2, 1), (2, 2), (2, 3), (7, 7)]).unwrap();
+        let report = game.run(10);
+        assert_eq!((0, 10), (report.start, report.generations));
+        assert_eq!((4, 3), (report.peak_population, report.final_population));
+        assert_eq!(
+            (None, None, None),
+            (report.extinct_at, report.settled_at, report.period)
+        );
+        assert!(!report.cancelled);
+
+        // The blinker's population is the same every generation, but its board takes two.
+        let report = game.run_until_stable(100, 4);
+        assert_eq!(10, report.start);
+        assert_eq!((Some(10), Some(2)), (report.settled_at, report.period));
+        assert_eq!(3, report.final_population);
+
+        // A glider's board never repeats within the period, so its population's period is kept.
+        let mut game = Game::new(20, 20);
+        game.set([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]).unwrap();
+        let report = game.run_until_stable(100, 4);
+        assert_eq!(Some(1), report.period);
+
+        let mut game = Game::new(5, 5);
+        game.set([(1, 1), (3, 3)]).unwrap();
+        game.run(1);
+        let report = game.run(3);
+        assert_eq!((Some(1), 0), (report.extinct_at, report.peak_population));
     }
 }
@@ -0,0 +1,72 @@
+use crate::Pattern;
+
+// The patterns everyone reaches for first, as RLE, so they can be stamped or laid out by name
+// without a file to read them from. Pattern::named works as the lookup for Layout::place, too.
+const PATTERNS: [(&str, &str); 8] = [
+    ("glider", "x = 3, y = 3\nbo$2bo$3o!"),
+    ("blinker", "x = 3, y = 1\n3o!"),
+    ("toad", "x = 4, y = 2\nb3o$3o!"),
+    ("beacon", "x = 4, y = 4\n2o$2o$2b2o$2b2o!"),
+    (
+        "pulsar",
+        "x = 13, y = 13\n2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$\
+         o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    ),
+    ("lwss", "x = 5, y = 4\nbo2bo$o4b$o3bo$4o!"),
+    (
+        "gosper glider gun",
+        "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+         2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+    ("r-pentomino", "x = 3, y = 3\nb2o$2o$bo!"),
+];
+
+impl Pattern {
+    // The name of every built-in pattern, in the order a menu would list them.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        PATTERNS.iter().map(|&(name, _)| name)
+    }
+
+    // A built-in pattern by name, ignoring case, with the name as its #N comment.
+    pub fn named(name: &str) -> Option<Pattern> {
+        let &(name, rle) = PATTERNS
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name.trim()))?;
+        let mut pattern: Pattern = rle.parse().expect("built-in patterns are valid RLE");
+        pattern.add_comment(format!("N {name}"));
+        Some(pattern)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{analyze, Behavior, Game};
+
+    #[test]
+    fn test_named() {
+        let run = |name: &str| {
+            let pattern = Pattern::named(name).unwrap();
+            assert_eq!(Some(name), pattern.name());
+            // Plenty of room for spaceships to repeat before they wrap around into themselves.
+            let mut game = Game::new(pattern.width() + 20, pattern.height() + 20);
+            game.stamp(&pattern, 10, 10).unwrap();
+            let population = game.board().population();
+            (population, analyze(&mut game, 40).behavior)
+        };
+        let spaceship = |period, dx, dy| Behavior::Spaceship { period, dx, dy };
+        let oscillator = |period| Behavior::Oscillator { period };
+        assert_eq!((5, spaceship(4, 1, 1)), run("glider"));
+        assert_eq!((3, oscillator(2)), run("blinker"));
+        assert_eq!((6, oscillator(2)), run("toad"));
+        assert_eq!((8, oscillator(2)), run("beacon"));
+        assert_eq!((48, oscillator(3)), run("pulsar"));
+        assert_eq!((9, spaceship(4, -2, 0)), run("lwss"));
+        assert_eq!((36, Behavior::Unsettled), run("gosper glider gun"));
+        assert_eq!((5, Behavior::Unsettled), run("r-pentomino"));
+
+        assert_eq!(8, Pattern::names().count());
+        assert!(Pattern::named("LWSS").is_some());
+        assert_eq!(None, Pattern::named("queen bee"));
+    }
+}
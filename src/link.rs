@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Game, LifeLike, Pattern, PatternError, RuleStringError};
+
+// Boards can be shared as the fragment of a link, the part after the '#', so opening the link
+// shows the same board running the same rule. The fragment reads like
+//
+//     size=5x5&rule=B3/S23&cells=bo$2bo$3o!
+//
+// where the cells are the board's RLE with the line breaks taken out. RLE only uses letters,
+// digits, '$', and '!', none of which need escaping in a fragment, and runs of dead cells shrink
+// to a few characters, so a small pattern on a large board still makes a short link. The rule is
+// left out for rules that aren't Life-like, and a link without one runs Conway's Life.
+
+// The widest and tallest board a link can open. Links come from anyone, so this keeps a link from
+// asking for more memory than the page has; 4096x4096 is 2MB of cells.
+pub const MAX_LINK_SIDE: usize = 4096;
+
+// The fragment that opens the game's board and rule, without the leading '#'.
+pub fn encode_link(game: &Game) -> String {
+    let mut fragment = format!("size={}x{}", game.x_size(), game.y_size());
+    if let Some(rule) = game.life_like() {
+        fragment.push_str(&format!("&rule={}", rule.canonical_string()));
+    }
+    let rle = Pattern::from(game.board()).to_string();
+    // Everything after the header line is cells.
+    let cells: String = rle
+        .lines()
+        .skip(1)
+        .flat_map(|line| line.trim().chars())
+        .collect();
+    fragment.push_str("&cells=");
+    fragment.push_str(&cells);
+    fragment
+}
+
+// Rebuild a game from a link's fragment, with or without the leading '#'. The game starts at
+// generation 0 whatever generation the link was made on.
+pub fn decode_link(fragment: &str) -> Result<Game, LinkError> {
+    let fragment = fragment.trim().trim_start_matches('#');
+    let (mut size, mut rule, mut cells) = (None, None, None);
+    for field in fragment.split('&').filter(|field| !field.is_empty()) {
+        match field.split_once('=') {
+            Some(("size", value)) => size = Some(value),
+            Some(("rule", value)) => rule = Some(value),
+            Some(("cells", value)) => cells = Some(value),
+            // Fields from newer links are skipped, so old pages can still open what they can.
+            Some(_) => {}
+            None => return Err(LinkError::InvalidField(field.to_string())),
+        }
+    }
+
+    let size = size.ok_or(LinkError::Missing("size"))?;
+    let (x_size, y_size) = size
+        .split_once('x')
+        .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+        .filter(|&(x, y)| x > 0 && y > 0)
+        .ok_or_else(|| LinkError::InvalidSize(size.to_string()))?;
+    if x_size > MAX_LINK_SIDE || y_size > MAX_LINK_SIDE {
+        return Err(LinkError::TooLarge { x_size, y_size });
+    }
+    let cells = cells.ok_or(LinkError::Missing("cells"))?;
+    let pattern: Pattern = format!("x = {x_size}, y = {y_size}\n{cells}")
+        .parse()
+        .map_err(|err| match err {
+            PatternError::OutOfBounds { .. } => LinkError::Overflow { x_size, y_size },
+            err => LinkError::Cells(err),
+        })?;
+
+    let mut game = Game::from(pattern.to_board());
+    if let Some(rule) = rule {
+        game.set_rule(LifeLike::parse(rule)?);
+    }
+    Ok(game)
+}
+
+// A link fragment that doesn't describe a board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkError {
+    // A field without an '=' in it.
+    InvalidField(String),
+    // A field every link needs, such as the size or the cells, isn't there.
+    Missing(&'static str),
+    // The size isn't in the "WIDTHxHEIGHT" form, or one side is 0.
+    InvalidSize(String),
+    // The size is bigger than MAX_LINK_SIDE on a side.
+    TooLarge { x_size: usize, y_size: usize },
+    // The cells run past the edges of the board the link gives the size of.
+    Overflow { x_size: usize, y_size: usize },
+    Cells(PatternError),
+    Rule(RuleStringError),
+}
+
+impl From<PatternError> for LinkError {
+    fn from(err: PatternError) -> Self {
+        LinkError::Cells(err)
+    }
+}
+
+impl From<RuleStringError> for LinkError {
+    fn from(err: RuleStringError) -> Self {
+        LinkError::Rule(err)
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkError::InvalidField(field) => {
+                write!(f, "invalid link field {field:?}, expected name=value")
+            }
+            LinkError::Missing(name) => write!(f, "the link has no {name}"),
+            LinkError::InvalidSize(size) => {
+                write!(f, "invalid link size {size:?}, expected the form 64x48")
+            }
+            LinkError::TooLarge { x_size, y_size } => write!(
+                f,
+                "the link's {x_size}x{y_size} board is too large, the most is \
+                 {MAX_LINK_SIDE}x{MAX_LINK_SIDE}"
+            ),
+            LinkError::Overflow { x_size, y_size } => {
+                write!(f, "the link's cells don't fit its {x_size}x{y_size} board")
+            }
+            LinkError::Cells(err) => write!(f, "couldn't read the link's cells: {err}"),
+            LinkError::Rule(err) => write!(f, "couldn't read the link's rule: {err}"),
+        }
+    }
+}
+
+impl Error for LinkError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut game = Game::new(40, 30);
+        game.set_rule(LifeLike::parse("B36/S23").unwrap());
+        for (x, y) in [(21, 10), (22, 11), (20, 12), (21, 12), (22, 12)] {
+            *game.cell(x, y) = true;
+        }
+        let link = encode_link(&game);
+        assert_eq!("size=40x30&rule=B36/S23&cells=10$21bo$22bo$20b3o!", link);
+
+        let opened = decode_link(&format!("#{link}")).unwrap();
+        assert_eq!(game.board(), opened.board());
+        assert_eq!(game.life_like(), opened.life_like());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            Err(LinkError::Missing("size")),
+            decode_link("cells=o!").map(|_| ())
+        );
+        assert_eq!(
+            Err(LinkError::InvalidSize("0x5".to_string())),
+            decode_link("size=0x5&cells=!").map(|_| ())
+        );
+        assert_eq!(
+            Err(LinkError::Overflow {
+                x_size: 2,
+                y_size: 2
+            }),
+            decode_link("size=2x2&cells=3o!").map(|_| ())
+        );
+        assert!(matches!(
+            decode_link("size=2x2&rule=B9/S&cells=o!"),
+            Err(LinkError::Rule(_))
+        ));
+        // Sizes and runs from a hand-made link can't ask for more than the cap allows.
+        assert_eq!(
+            Err(LinkError::TooLarge {
+                x_size: 100_000,
+                y_size: 100_000
+            }),
+            decode_link("size=100000x100000&cells=!").map(|_| ())
+        );
+        assert!(matches!(
+            decode_link("size=99999999999999999999x3&cells=!"),
+            Err(LinkError::InvalidSize(_))
+        ));
+        assert_eq!(
+            Err(LinkError::Overflow {
+                x_size: 3,
+                y_size: 3
+            }),
+            decode_link("size=3x3&cells=99999999999999999999b!").map(|_| ())
+        );
+        assert_eq!(
+            Err(LinkError::Overflow {
+                x_size: 3,
+                y_size: 3
+            }),
+            decode_link("size=3x3&cells=4000000000o!").map(|_| ())
+        );
+        assert!(matches!(
+            decode_link("size=3x3&cells=o$x!"),
+            Err(LinkError::Cells(_))
+        ));
+        // No rule means Conway's Life, and unknown fields are skipped.
+        let game = decode_link("size=3x3&speed=fast&cells=o!").unwrap();
+        assert_eq!(Some(LifeLike::CONWAY), game.life_like());
+    }
+}
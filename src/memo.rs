@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+// The memory a memo may use unless it's given a different budget.
+pub(crate) const DEFAULT_BUDGET: usize = 64 << 20;
+
+// A memo table that stays within a memory budget. Answers live in a pool of slots that's allocated
+// once and reused, and when the pool is full the answer that's gone longest without being used is
+// dropped to make room, approximately: each slot has a used bit that a sweeping hand clears, and
+// the hand takes the first slot it finds still clear (the "clock" approximation of LRU).
+#[derive(Clone)]
+pub(crate) struct Memo<K, V> {
+    index: HashMap<K, usize>,
+    slots: Vec<Slot<K, V>>,
+    // The most slots the budget allows.
+    capacity: usize,
+    budget: usize,
+    hand: usize,
+    evictions: u64,
+}
+
+#[derive(Clone)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+    used: bool,
+}
+
+impl<K: Hash + Eq + Copy, V: Copy> Memo<K, V> {
+    pub(crate) fn new(budget: usize) -> Self {
+        Memo {
+            index: HashMap::new(),
+            slots: Vec::new(),
+            capacity: Self::capacity(budget),
+            budget,
+            hand: 0,
+            evictions: 0,
+        }
+    }
+
+    // Roughly how many bytes each answer takes, counting its slot and its entry in the index.
+    fn entry_size() -> usize {
+        mem::size_of::<Slot<K, V>>() + mem::size_of::<(K, usize)>() + 1
+    }
+
+    fn capacity(budget: usize) -> usize {
+        (budget / Self::entry_size()).max(1)
+    }
+
+    pub(crate) fn budget(&self) -> usize {
+        self.budget
+    }
+
+    // Change the budget. Shrinking it below what's stored throws everything away.
+    pub(crate) fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.capacity = Self::capacity(budget);
+        if self.slots.len() > self.capacity {
+            self.clear();
+            self.slots.shrink_to(self.capacity);
+            self.index.shrink_to(self.capacity);
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    // How many answers have been dropped to stay within the budget.
+    pub(crate) fn evictions(&self) -> u64 {
+        self.evictions
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.index.clear();
+        self.slots.clear();
+        self.hand = 0;
+    }
+
+    // The answer for `key`, working it out with `compute` if it isn't already known.
+    pub(crate) fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, compute: F) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = compute();
+        self.insert(key, value);
+        value
+    }
+
+    // The answer for `key`, if it's known, for callers that can't work it out inside a closure.
+    pub(crate) fn get(&mut self, key: &K) -> Option<V> {
+        let &slot = self.index.get(key)?;
+        let slot = &mut self.slots[slot];
+        slot.used = true;
+        Some(slot.value)
+    }
+
+    // Remember the answer for `key`, making room for it if the pool is full.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        let slot = Slot {
+            key,
+            value,
+            used: true,
+        };
+        if let Some(&index) = self.index.get(&key) {
+            self.slots[index] = slot;
+            return;
+        }
+        if self.slots.len() < self.capacity {
+            self.index.insert(key, self.slots.len());
+            self.slots.push(slot);
+            return;
+        }
+
+        loop {
+            let victim = &mut self.slots[self.hand];
+            if victim.used {
+                victim.used = false;
+                self.hand = (self.hand + 1) % self.slots.len();
+                continue;
+            }
+            self.index.remove(&victim.key);
+            *victim = slot;
+            self.index.insert(key, self.hand);
+            self.hand = (self.hand + 1) % self.slots.len();
+            self.evictions += 1;
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        let size = Memo::<u32, u32>::entry_size();
+        let mut memo = Memo::new(4 * size);
+        for key in 0..4 {
+            assert_eq!(key * 2, memo.get_or_insert_with(key, || key * 2));
+        }
+        assert_eq!((4, 0), (memo.len(), memo.evictions()));
+
+        // Every slot has been used, so the hand clears them all and comes back around to the
+        // first. Then key 1 is used again, so it's passed over when the next one goes.
+        memo.get_or_insert_with(10, || 20);
+        memo.get_or_insert_with(1, || unreachable!());
+        memo.get_or_insert_with(11, || 22);
+        assert_eq!((4, 2), (memo.len(), memo.evictions()));
+        assert_eq!(20, memo.get_or_insert_with(10, || unreachable!()));
+        assert_eq!(2, memo.get_or_insert_with(1, || unreachable!()));
+        assert_eq!(22, memo.get_or_insert_with(11, || unreachable!()));
+        assert_eq!(0, memo.get_or_insert_with(0, || 0));
+        assert_eq!(3, memo.evictions());
+
+        memo.set_budget(2 * size);
+        assert_eq!(0, memo.len());
+        for key in 0..10 {
+            memo.get_or_insert_with(key, || key);
+        }
+        assert_eq!(2, memo.len());
+    }
+}
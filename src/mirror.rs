@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Board, Changes, Point, Rect, Symmetry};
+
+// Keeps a board exactly symmetric from one generation to the next. Every cell has a source cell,
+// the first of its orbit under the symmetry, and the sources together make up the fundamental
+// domain: half the board for a mirror or a half turn, a quarter for four-fold symmetries. Only
+// the domain needs stepping, and copying each source onto the rest of its orbit fills in the
+// other cells.
+#[derive(Clone, Debug)]
+pub(crate) struct Mirror {
+    symmetry: Symmetry,
+    // The flat index of each cell's source.
+    sources: Vec<usize>,
+    // The bounding box of the fundamental domain.
+    domain: Rect,
+}
+
+impl Mirror {
+    pub(crate) fn new(
+        symmetry: Symmetry,
+        x_size: usize,
+        y_size: usize,
+    ) -> Result<Self, UnfitSymmetry> {
+        if !symmetry.fits(x_size, y_size) {
+            return Err(UnfitSymmetry {
+                symmetry,
+                x_size,
+                y_size,
+            });
+        }
+        let mut sources = vec![0; x_size * y_size];
+        let mut domain = Vec::new();
+        for x in 0..x_size {
+            for y in 0..y_size {
+                let (source_x, source_y) = symmetry
+                    .orbit(x, y, x_size, y_size)
+                    .into_iter()
+                    .min()
+                    .unwrap_or((x, y));
+                sources[x * y_size + y] = source_x * y_size + source_y;
+                if (source_x, source_y) == (x, y) {
+                    domain.push(Point::new(x, y));
+                }
+            }
+        }
+        Ok(Mirror {
+            symmetry,
+            sources,
+            domain: Rect::bounding(domain).unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    // The part of the board that has to be stepped for the rest to be filled in from it.
+    pub(crate) fn domain(&self) -> Rect {
+        self.domain
+    }
+
+    // The cells that (x, y) is copied to or from, including (x, y) itself, each once.
+    pub(crate) fn orbit(&self, x: usize, y: usize, x_size: usize, y_size: usize) -> Vec<Point> {
+        let mut orbit: Vec<Point> = self
+            .symmetry
+            .orbit(x, y, x_size, y_size)
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        orbit.sort_by_key(|point| (point.x, point.y));
+        orbit.dedup();
+        orbit
+    }
+
+    // Copy every source cell onto the rest of its orbit, returning whether that changed anything.
+    pub(crate) fn symmetrize(&self, board: &mut Board) -> bool {
+        let mut changed = false;
+        for (index, &source) in self.sources.iter().enumerate() {
+            let alive = board.alive_at(source);
+            if board.alive_at(index) != alive {
+                board.set_at(index, alive);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    // Make the step from `previous` to `current` symmetric, recounting the births and deaths in
+    // `changes` across the whole board. Returns whether any cell changed.
+    pub(crate) fn apply(
+        &self,
+        previous: &Board,
+        current: &mut Board,
+        changes: &mut Changes,
+    ) -> bool {
+        if !self.symmetrize(current) {
+            return false;
+        }
+        *changes = Changes::default();
+        for point in previous.diff(current) {
+            if current.get(point.x, point.y) {
+                changes.births += 1;
+            } else {
+                changes.deaths += 1;
+            }
+        }
+        true
+    }
+}
+
+// A symmetry the board's shape can't have, such as a quarter turn on a board that isn't square.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnfitSymmetry {
+    pub symmetry: Symmetry,
+    pub x_size: usize,
+    pub y_size: usize,
+}
+
+impl fmt::Display for UnfitSymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} symmetry needs a square board, not {}x{}",
+            self.symmetry, self.x_size, self.y_size
+        )
+    }
+}
+
+impl Error for UnfitSymmetry {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_domain() {
+        let domain =
+            |symmetry, x_size, y_size| Mirror::new(symmetry, x_size, y_size).unwrap().domain();
+        assert_eq!(Rect::new(0, 0, 7, 6), domain(Symmetry::C1, 7, 6));
+        assert_eq!(Rect::new(0, 0, 4, 6), domain(Symmetry::D2, 7, 6));
+        assert_eq!(Rect::new(0, 0, 4, 6), domain(Symmetry::C2, 7, 6));
+        assert_eq!(Rect::new(0, 0, 4, 3), domain(Symmetry::D4, 7, 6));
+        assert_eq!(Rect::new(0, 0, 3, 3), domain(Symmetry::D8, 6, 6));
+        assert_eq!(
+            Err(UnfitSymmetry {
+                symmetry: Symmetry::C4,
+                x_size: 7,
+                y_size: 6
+            }),
+            Mirror::new(Symmetry::C4, 7, 6).map(|mirror| mirror.symmetry())
+        );
+    }
+
+    #[test]
+    fn test_symmetrize() {
+        let mirror = Mirror::new(Symmetry::D4, 5, 3).unwrap();
+        let mut board: Board = "O....\n.O...\n.....\n".parse().unwrap();
+        assert!(mirror.symmetrize(&mut board));
+        assert_eq!("O...O\n.O.O.\nO...O\n", board.to_string());
+        assert!(!mirror.symmetrize(&mut board));
+        assert_eq!(
+            vec![Point::new(1, 1), Point::new(3, 1)],
+            mirror.orbit(1, 1, 5, 3)
+        );
+    }
+}
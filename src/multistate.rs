@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use crate::board::WrapTable;
+use crate::memo::{self, Memo};
+use crate::{Palette, Topology};
+
+// A rule for automata whose cells have more than two states, such as Wireworld or the rules Golly
+// loads from .rule files. States run from 0 to states() - 1, with 0 as the empty background.
+// Like Rule, it must be pure, since the game memoizes its answers.
+pub trait StateRule: Send + Sync {
+    fn states(&self) -> u8;
+
+    // The next state of a cell given its current state and its neighbors' states, ordered north,
+    // north-east, east, south-east, south, south-west, west, north-west.
+    fn transition(&self, cell: u8, neighbors: [u8; 8]) -> u8;
+
+    // The colors to draw each state in. Unless a rule has its own, its states get a gradient.
+    fn palette(&self) -> Palette {
+        Palette::gradient(self.states())
+    }
+}
+
+// A board of multi-state cells stepped by a StateRule, wrapping around its edges like a toroidal
+// Game.
+#[derive(Clone)]
+pub struct StateGame {
+    x_size: usize,
+    y_size: usize,
+    wrap: Arc<WrapTable>,
+
+    // Stored column by column like Board, so (x, y) is at x * y_size + y.
+    previous: Vec<u8>,
+    current: Vec<u8>,
+
+    rule: Arc<dyn StateRule>,
+    // Answers already computed for each (cell, neighbors) combination. Rules like Golly's tables
+    // are slow to evaluate, but only a small number of combinations show up in practice. Chaotic
+    // patterns with many states can turn up far more, so the memo is kept within a budget.
+    memo: Memo<[u8; 9], u8>,
+}
+
+impl StateGame {
+    pub fn new<R: StateRule + 'static>(x_size: usize, y_size: usize, rule: R) -> Self {
+        StateGame {
+            x_size,
+            y_size,
+            wrap: Arc::new(WrapTable::new(x_size, y_size, Topology::Toroidal)),
+            previous: vec![0; x_size * y_size],
+            current: vec![0; x_size * y_size],
+            rule: Arc::new(rule),
+            memo: Memo::new(memo::DEFAULT_BUDGET),
+        }
+    }
+
+    pub fn x_size(&self) -> usize {
+        self.x_size
+    }
+
+    pub fn y_size(&self) -> usize {
+        self.y_size
+    }
+
+    pub fn rule(&self) -> &dyn StateRule {
+        self.rule.as_ref()
+    }
+
+    pub fn states(&self) -> u8 {
+        self.rule.states()
+    }
+
+    pub fn palette(&self) -> Palette {
+        self.rule.palette()
+    }
+
+    // Replace the rule, keeping the board. Cells in states the new rule doesn't have are cleared.
+    pub fn set_rule<R: StateRule + 'static>(&mut self, rule: R) {
+        let states = rule.states();
+        for cell in &mut self.current {
+            if *cell >= states {
+                *cell = 0;
+            }
+        }
+        self.rule = Arc::new(rule);
+        self.memo.clear();
+    }
+
+    // The most memory, in bytes, the memo of rule answers may take. Once it's full, the answers
+    // used least recently are dropped to make room. It starts at 64 MiB.
+    pub fn memory_budget(&self) -> usize {
+        self.memo.budget()
+    }
+
+    pub fn set_memory_budget(&mut self, bytes: usize) {
+        self.memo.set_budget(bytes);
+    }
+
+    // How many rule answers are memoized, and how many have been dropped to stay in budget.
+    pub fn memo_entries(&self) -> usize {
+        self.memo.len()
+    }
+
+    pub fn memo_evictions(&self) -> u64 {
+        self.memo.evictions()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.current[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, state: u8) {
+        assert!(
+            state < self.states(),
+            "state {state} is out of range for a {}-state rule",
+            self.states()
+        );
+        let index = self.index(x, y);
+        self.current[index] = state;
+    }
+
+    pub fn clear(&mut self) {
+        self.current.fill(0);
+        self.previous.fill(0);
+    }
+
+    // How many cells are in a state other than the background.
+    pub fn population(&self) -> usize {
+        self.current.iter().filter(|&&state| state != 0).count()
+    }
+
+    // Run a single iteration, returning how many cells changed state.
+    pub fn iterate(&mut self) -> usize {
+        std::mem::swap(&mut self.current, &mut self.previous);
+
+        let mut changed = 0;
+        for x in 0..self.x_size {
+            let (left, right) = (self.wrap.left[x], self.wrap.right[x]);
+            for y in 0..self.y_size {
+                let (up, down) = (self.wrap.up[y], self.wrap.down[y]);
+                let at = |x: Option<usize>, y: Option<usize>| match (x, y) {
+                    (Some(x), Some(y)) => self.previous[x * self.y_size + y],
+                    _ => 0,
+                };
+                let key = [
+                    self.previous[x * self.y_size + y],
+                    at(Some(x), up),
+                    at(right, up),
+                    at(right, Some(y)),
+                    at(right, down),
+                    at(Some(x), down),
+                    at(left, down),
+                    at(left, Some(y)),
+                    at(left, up),
+                ];
+
+                let rule = &self.rule;
+                let next = self.memo.get_or_insert_with(key, || {
+                    let [cell, neighbors @ ..] = key;
+                    rule.transition(cell, neighbors)
+                });
+                if next != key[0] {
+                    changed += 1;
+                }
+                self.current[x * self.y_size + y] = next;
+            }
+        }
+        changed
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.x_size && y < self.y_size,
+            "coordinate ({x}, {y}) is outside of the {}x{} board",
+            self.x_size,
+            self.y_size
+        );
+        x * self.y_size + y
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Wireworld: 0 empty, 1 electron head, 2 electron tail, 3 conductor.
+    struct Wireworld;
+
+    impl StateRule for Wireworld {
+        fn states(&self) -> u8 {
+            4
+        }
+
+        fn transition(&self, cell: u8, neighbors: [u8; 8]) -> u8 {
+            match cell {
+                1 => 2,
+                2 => 3,
+                3 => {
+                    let heads = neighbors.iter().filter(|&&state| state == 1).count();
+                    if heads == 1 || heads == 2 {
+                        1
+                    } else {
+                        3
+                    }
+                }
+                _ => 0,
+            }
+        }
+    }
+
+    #[test]
+    fn test_wireworld_wire() {
+        let mut game = StateGame::new(6, 3, Wireworld);
+        for x in 0..6 {
+            game.set(x, 1, 3);
+        }
+        game.set(1, 1, 1);
+        game.set(0, 1, 2);
+
+        assert_eq!(3, game.iterate());
+        assert_eq!(
+            vec![3, 2, 1, 3, 3, 3],
+            (0..6).map(|x| game.get(x, 1)).collect::<Vec<_>>()
+        );
+        game.iterate();
+        assert_eq!(
+            vec![3, 3, 2, 1, 3, 3],
+            (0..6).map(|x| game.get(x, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(6, game.population());
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        // A wire with a signal running round it, stepped with plenty of memory and with barely
+        // any, should come out the same.
+        let wire = || {
+            let mut game = StateGame::new(8, 3, Wireworld);
+            for x in 0..8 {
+                game.set(x, 1, 3);
+            }
+            game.set(1, 1, 1);
+            game.set(0, 1, 2);
+            game
+        };
+        let mut roomy = wire();
+        let mut cramped = wire();
+        cramped.set_memory_budget(64);
+        for _ in 0..20 {
+            roomy.iterate();
+            cramped.iterate();
+        }
+        assert!(cramped.memo_entries() < roomy.memo_entries());
+        assert!(cramped.memo_evictions() > 0);
+        assert_eq!(roomy.current, cramped.current);
+    }
+
+    #[test]
+    fn test_neighbor_order() {
+        // Each cell takes the state of its north-east neighbor, moving everything south-west.
+        struct FromNorthEast;
+        impl StateRule for FromNorthEast {
+            fn states(&self) -> u8 {
+                2
+            }
+            fn transition(&self, _: u8, neighbors: [u8; 8]) -> u8 {
+                neighbors[1]
+            }
+        }
+
+        let mut game = StateGame::new(4, 4, FromNorthEast);
+        game.set(2, 1, 1);
+        game.iterate();
+        assert_eq!(1, game.get(1, 2));
+        assert_eq!(1, game.population());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_out_of_range_state() {
+        StateGame::new(2, 2, Wireworld).set(0, 0, 4);
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+use crate::{Board, Changes, Game, Point};
+
+// Which cells each line lists, on top of the generation's counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CellList {
+    // Just the counts.
+    #[default]
+    None,
+    // Every live cell, as "live": [[x, y], ...].
+    Live,
+    // The cells born and died since the last line, as "born" and "died" lists. The first line
+    // lists every live cell as born, so the stream alone is enough to rebuild each board.
+    Diff,
+}
+
+// Streams a game's history as newline-delimited JSON, one object per generation:
+//
+//     {"gen":1,"population":5,"births":2,"deaths":2}
+//
+// Each line is flushed as it's written, so the output can be piped straight into other tools.
+pub struct NdjsonWriter<W: Write> {
+    out: W,
+    cells: CellList,
+    generation: u64,
+    // The board from the last line written, for listing what changed since.
+    last: Option<Board>,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(out: W) -> Self {
+        NdjsonWriter {
+            out,
+            cells: CellList::None,
+            generation: 0,
+            last: None,
+        }
+    }
+
+    pub fn set_cells(&mut self, cells: CellList) {
+        self.cells = cells;
+    }
+
+    // The generation number the next line will carry.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // Write a line for the game's current board, given the changes that produced it, then move on
+    // to the next generation number.
+    pub fn write(&mut self, game: &Game, changes: Changes) -> io::Result<()> {
+        let board = game.board();
+        let mut line = format!(
+            "{{\"gen\":{},\"population\":{},\"births\":{},\"deaths\":{}",
+            self.generation,
+            board.population(),
+            changes.births,
+            changes.deaths
+        );
+        match self.cells {
+            CellList::None => {}
+            CellList::Live => {
+                let live = board.live_cells();
+                line.push_str(&format!(",\"live\":{}", points(live)));
+            }
+            CellList::Diff => {
+                let (born, died): (Vec<Point>, Vec<Point>) = match &self.last {
+                    Some(last)
+                        if last.x_size() == board.x_size() && last.y_size() == board.y_size() =>
+                    {
+                        last.diff(board).partition(|&point| board[point.into()])
+                    }
+                    _ => (board.live_cells().collect(), Vec::new()),
+                };
+                line.push_str(&format!(
+                    ",\"born\":{},\"died\":{}",
+                    points(born),
+                    points(died)
+                ));
+                self.last = Some(board.clone());
+            }
+        }
+        line.push('}');
+
+        writeln!(self.out, "{line}")?;
+        self.out.flush()?;
+        self.generation += 1;
+        Ok(())
+    }
+
+    // Write the game's current board, then iterate it the given number of times, writing a line
+    // after each.
+    pub fn run(&mut self, game: &mut Game, iters: usize) -> io::Result<()> {
+        self.write(game, Changes::default())?;
+        for _ in 0..iters {
+            let changes = game.iterate();
+            self.write(game, changes)?;
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+// Points as a JSON array of [x, y] pairs.
+fn points<I: IntoIterator<Item = Point>>(points: I) -> String {
+    let pairs: Vec<String> = points
+        .into_iter()
+        .map(|Point { x, y }| format!("[{x},{y}]"))
+        .collect();
+    format!("[{}]", pairs.join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn blinker() -> Game {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        game
+    }
+
+    fn lines(writer: NdjsonWriter<Vec<u8>>) -> Vec<String> {
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        out.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_counts() {
+        let mut game = blinker();
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.run(&mut game, 2).unwrap();
+        assert_eq!(3, writer.generation());
+        assert_eq!(
+            vec![
+                r#"{"gen":0,"population":3,"births":0,"deaths":0}"#,
+                r#"{"gen":1,"population":3,"births":2,"deaths":2}"#,
+                r#"{"gen":2,"population":3,"births":2,"deaths":2}"#,
+            ],
+            lines(writer)
+        );
+    }
+
+    #[test]
+    fn test_live_cells() {
+        let mut game = blinker();
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.set_cells(CellList::Live);
+        writer.run(&mut game, 1).unwrap();
+        assert_eq!(
+            vec![
+                r#"{"gen":0,"population":3,"births":0,"deaths":0,"live":[[2,1],[2,2],[2,3]]}"#,
+                r#"{"gen":1,"population":3,"births":2,"deaths":2,"live":[[1,2],[2,2],[3,2]]}"#,
+            ],
+            lines(writer)
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut game = blinker();
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer.set_cells(CellList::Diff);
+        writer.run(&mut game, 1).unwrap();
+        assert_eq!(
+            vec![
+                r#"{"gen":0,"population":3,"births":0,"deaths":0,"born":[[2,1],[2,2],[2,3]],"died":[]}"#,
+                r#"{"gen":1,"population":3,"births":2,"deaths":2,"born":[[1,2],[3,2]],"died":[[2,1],[2,3]]}"#,
+            ],
+            lines(writer)
+        );
+    }
+}
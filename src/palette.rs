@@ -0,0 +1,172 @@
+// A color and a name for each state of a multi-state automaton, for drawing it with
+// render_states() and labelling a legend. State 0 is the background.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: Vec<[u8; 3]>,
+    names: Vec<String>,
+}
+
+const BACKGROUND: [u8; 3] = [0x10, 0x10, 0x10];
+
+impl Palette {
+    // A palette with the given colors, state 0's first, and the states named by number.
+    pub fn new(colors: Vec<[u8; 3]>) -> Self {
+        let names = (0..colors.len())
+            .map(|state| match state {
+                0 => "background".to_string(),
+                state => format!("state {state}"),
+            })
+            .collect();
+        Palette { colors, names }
+    }
+
+    // A dark background and live states fading from yellow through red to a deep purple, so the
+    // order of the states shows, as in Generations rules where cells age through them.
+    pub fn gradient(states: u8) -> Self {
+        let stops = [[0xff, 0xf0, 0x60], [0xe0, 0x30, 0x30], [0x50, 0x18, 0x70]];
+        let live = usize::from(states.max(2)) - 1;
+        let mut colors = vec![BACKGROUND];
+        for index in 0..live {
+            let position = if live == 1 {
+                0.0
+            } else {
+                index as f64 / (live - 1) as f64 * (stops.len() - 1) as f64
+            };
+            let low = (position.floor() as usize).min(stops.len() - 2);
+            let fraction = position - low as f64;
+            colors.push(std::array::from_fn(|channel| {
+                let (from, to) = (stops[low][channel] as f64, stops[low + 1][channel] as f64);
+                (from + (to - from) * fraction).round() as u8
+            }));
+        }
+        Palette::new(colors)
+    }
+
+    // Wireworld's usual colors: blue electron heads, white tails and orange wire.
+    pub fn wireworld() -> Self {
+        let mut palette = Palette::new(vec![
+            BACKGROUND,
+            [0x00, 0x80, 0xff],
+            [0xff, 0xff, 0xff],
+            [0xff, 0x80, 0x00],
+        ]);
+        palette.set_names(["empty", "electron head", "electron tail", "conductor"]);
+        palette
+    }
+
+    // Brian's Brain: white firing cells leaving blue refractory ones behind.
+    pub fn brians_brain() -> Self {
+        let mut palette = Palette::new(vec![BACKGROUND, [0xff, 0xff, 0xff], [0x30, 0x60, 0xe0]]);
+        palette.set_names(["off", "firing", "refractory"]);
+        palette
+    }
+
+    // The palette that suits a rule, going by its name: Wireworld and Brian's Brain get their own,
+    // and anything else a gradient over its states.
+    pub fn for_rule(rule: Option<&str>, states: u8) -> Self {
+        let name: String = rule
+            .unwrap_or_default()
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase();
+        match name.as_str() {
+            "wireworld" if states <= 4 => Palette::wireworld(),
+            "briansbrain" if states <= 3 => Palette::brians_brain(),
+            _ => Palette::gradient(states),
+        }
+    }
+
+    // How many states have colors.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    // The color for a state. States past the end of the palette get its last color.
+    pub fn color(&self, state: u8) -> [u8; 3] {
+        let index = usize::from(state).min(self.colors.len().saturating_sub(1));
+        self.colors.get(index).copied().unwrap_or(BACKGROUND)
+    }
+
+    // Change a state's color, adding states up to it if the palette is too short.
+    pub fn set_color(&mut self, state: u8, color: [u8; 3]) {
+        let state = usize::from(state);
+        while self.colors.len() <= state {
+            let next = self.colors.len();
+            self.colors.push(BACKGROUND);
+            self.names.push(format!("state {next}"));
+        }
+        self.colors[state] = color;
+    }
+
+    pub fn name(&self, state: u8) -> Option<&str> {
+        self.names.get(usize::from(state)).map(String::as_str)
+    }
+
+    // Name the states in order, starting from state 0.
+    pub fn set_names<I, S>(&mut self, names: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for (slot, name) in self.names.iter_mut().zip(names) {
+            *slot = name.into();
+        }
+    }
+
+    // Each state with its name and color, for drawing a legend.
+    pub fn entries(&self) -> impl Iterator<Item = (u8, &str, [u8; 3])> + '_ {
+        self.colors
+            .iter()
+            .zip(&self.names)
+            .enumerate()
+            .map(|(state, (&color, name))| (state as u8, name.as_str(), color))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        let palette = Palette::gradient(5);
+        assert_eq!(5, palette.len());
+        assert_eq!(BACKGROUND, palette.color(0));
+        assert_eq!([0xff, 0xf0, 0x60], palette.color(1));
+        assert_eq!([0x50, 0x18, 0x70], palette.color(4));
+        assert_eq!(palette.color(4), palette.color(9));
+        assert_eq!(Some("state 2"), palette.name(2));
+        assert_eq!(2, Palette::gradient(0).len());
+    }
+
+    #[test]
+    fn test_for_rule() {
+        assert_eq!(
+            Palette::wireworld(),
+            Palette::for_rule(Some("WireWorld"), 4)
+        );
+        assert_eq!(
+            Palette::brians_brain(),
+            Palette::for_rule(Some("Brian's Brain"), 3)
+        );
+        assert_eq!(Palette::gradient(6), Palette::for_rule(Some("B2/S/C6"), 6));
+        assert_eq!(Palette::gradient(3), Palette::for_rule(None, 3));
+
+        let mut palette = Palette::wireworld();
+        palette.set_color(5, [1, 2, 3]);
+        let entries: Vec<_> = palette.entries().skip(3).collect();
+        assert_eq!(
+            vec![
+                (3, "conductor", [0xff, 0x80, 0x00]),
+                (4, "state 4", BACKGROUND),
+                (5, "state 5", [1, 2, 3]),
+            ],
+            entries
+        );
+    }
+}
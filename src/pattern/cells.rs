@@ -0,0 +1,111 @@
+use super::{Pattern, PatternError};
+
+// Plaintext patterns, the .cells files LifeWiki offers next to RLE: one line of text per row of
+// cells, with '.' for dead cells and 'O' for live ones, and comment lines starting with '!'. Rows
+// can stop short where the rest of the row is dead, and an empty line is a row of dead cells.
+//
+// "!Name:" and "!Author:" comments are kept as the name and author, so they come back out as
+// "#N" and "#O" lines when the pattern is written as RLE, and as they were when it's written as
+// plaintext. Other comments become "#C" lines.
+impl Pattern {
+    pub fn from_cells(text: &str) -> Result<Self, PatternError> {
+        let mut comments = Vec::new();
+        let mut rows = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if let Some(comment) = line.strip_prefix('!') {
+                let comment = comment.trim();
+                comments.push(if let Some(name) = comment.strip_prefix("Name:") {
+                    format!("N {}", name.trim())
+                } else if let Some(author) = comment.strip_prefix("Author:") {
+                    format!("O {}", author.trim())
+                } else if comment.is_empty() {
+                    "C".to_string()
+                } else {
+                    format!("C {comment}")
+                });
+                continue;
+            }
+            let row = line
+                .chars()
+                .map(|found| match found {
+                    '.' => Ok(false),
+                    // Some older files use '*' for live cells.
+                    'O' | '*' => Ok(true),
+                    _ => Err(PatternError::InvalidSymbol {
+                        line: index + 1,
+                        found,
+                    }),
+                })
+                .collect::<Result<Vec<bool>, _>>()?;
+            rows.push(row);
+        }
+        // Blank lines at the end are only the end of the file.
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut pattern = Pattern::from_fn(width, rows.len(), |x, y| {
+            rows[y].get(x).copied().unwrap_or(false) as u8
+        });
+        pattern.comments = comments;
+        Ok(pattern)
+    }
+
+    // The pattern as plaintext, with every row written out in full. Cells in states above 1 are
+    // written live, since plaintext only has two.
+    pub fn to_cells(&self) -> String {
+        let mut text = String::new();
+        for comment in &self.comments {
+            let line = match comment.split_at_checked(1) {
+                Some(("N", name)) => format!("Name:{name}"),
+                Some(("O", author)) => format!("Author:{author}"),
+                Some(("C" | "c", comment)) => comment.trim_start().to_string(),
+                _ => comment.clone(),
+            };
+            text.push('!');
+            text.push_str(&line);
+            text.push('\n');
+        }
+        for y in 0..self.height {
+            text.extend((0..self.width).map(|x| if self.is_alive(x, y) { 'O' } else { '.' }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cells_round_trip() {
+        let text = "!Name: Glider\n!Author: Richard K. Guy\n!The smallest spaceship.\n\
+                    .O.\n..O\nOOO\n";
+        let pattern = Pattern::from_cells(text).unwrap();
+        assert_eq!(Some("Glider"), pattern.name());
+        assert_eq!(Some("Richard K. Guy"), pattern.author());
+        assert_eq!(
+            vec!["The smallest spaceship."],
+            pattern.description().collect::<Vec<_>>()
+        );
+        assert_eq!(".O.\n..O\nOOO\n", pattern.to_board().to_string());
+        assert_eq!(text, pattern.to_cells());
+    }
+
+    #[test]
+    fn test_cells_short_rows() {
+        let pattern = Pattern::from_cells("O\n\n..*\n\n").unwrap();
+        assert_eq!((3, 3), (pattern.width(), pattern.height()));
+        assert_eq!("O..\n...\n..O\n", pattern.to_board().to_string());
+        assert_eq!(
+            Err(PatternError::InvalidSymbol {
+                line: 2,
+                found: 'x'
+            }),
+            Pattern::from_cells("!x\n.x\n")
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use super::{check_size, Pattern, PatternError};
+
+// The first line of every Life 1.06 file.
+const HEADER: &str = "#Life 1.06";
+
+// Life 1.06 patterns: a "#Life 1.06" line, then the x and y of one live cell per line. The
+// coordinates can be negative, since they're measured from wherever the pattern's author put the
+// origin, so the pattern is moved to put its leftmost and topmost cells on its edges. Other lines
+// starting with '#' are kept as comments.
+impl Pattern {
+    pub fn from_life106(text: &str) -> Result<Self, PatternError> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim() == HEADER => {}
+            line => {
+                let line = line.map_or("", |(_, line)| line);
+                return Err(PatternError::InvalidHeader(line.to_string()));
+            }
+        }
+
+        let mut comments = Vec::new();
+        let mut cells = Vec::new();
+        for (index, line) in lines {
+            let line = line.trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                comments.push(comment.to_string());
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || PatternError::InvalidCoordinates {
+                line: index + 1,
+                found: line.to_string(),
+            };
+            let mut fields = line.split_whitespace().map(str::parse::<isize>);
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => cells.push((x, y)),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let left = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let top = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let right = cells.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let bottom = cells.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+        // Coordinates at both ends of isize's range are further apart than isize can count.
+        let extent = |low: isize, high: isize| {
+            let extent = high.checked_sub(low)?.checked_add(1)?;
+            usize::try_from(extent).ok()
+        };
+        let (width, height) = extent(left, right)
+            .zip(extent(top, bottom))
+            .ok_or(PatternError::TooLarge)?;
+        check_size(width, height)?;
+        let mut pattern = Pattern::new(width, height);
+        for (x, y) in cells {
+            pattern.set((x - left) as usize, (y - top) as usize, 1);
+        }
+        pattern.comments = comments;
+        Ok(pattern)
+    }
+
+    // The pattern as Life 1.06, with its top-left corner at the origin and the live cells listed
+    // column by column.
+    pub fn to_life106(&self) -> String {
+        let mut text = format!("{HEADER}\n");
+        for comment in &self.comments {
+            text.push_str(&format!("#{comment}\n"));
+        }
+        for (x, y, _) in self.live_cells() {
+            text.push_str(&format!("{x} {y}\n"));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MAX_PATTERN_CELLS;
+
+    #[test]
+    fn test_life106_round_trip() {
+        let text = "#Life 1.06\n#N Glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+        let pattern = Pattern::from_life106(text).unwrap();
+        assert_eq!(Some("Glider"), pattern.name());
+        assert_eq!(".O.\n..O\nOOO\n", pattern.to_board().to_string());
+        assert_eq!(
+            "#Life 1.06\n#N Glider\n0 2\n1 0\n1 2\n2 1\n2 2\n",
+            pattern.to_life106()
+        );
+        assert_eq!(
+            pattern,
+            Pattern::from_life106(&pattern.to_life106()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_life106_errors() {
+        assert_eq!(
+            Err(PatternError::InvalidHeader("0 0".to_string())),
+            Pattern::from_life106("0 0\n")
+        );
+        assert_eq!(
+            Err(PatternError::InvalidCoordinates {
+                line: 3,
+                found: "1 2 3".to_string()
+            }),
+            Pattern::from_life106("#Life 1.06\n0 0\n1 2 3\n")
+        );
+        assert_eq!(
+            Err(PatternError::TooLarge),
+            Pattern::from_life106(&format!("#Life 1.06\n{} 0\n{} 0\n", isize::MIN, isize::MAX))
+        );
+        assert_eq!(
+            Err(PatternError::TooLarge),
+            Pattern::from_life106(&format!("#Life 1.06\n0 {}\n0 {}\n", isize::MIN, isize::MAX))
+        );
+        assert_eq!(
+            Err(PatternError::TooLarge),
+            Pattern::from_life106(&format!("#Life 1.06\n0 0\n{0} {0}\n", isize::MAX))
+        );
+        // Two cells far enough apart make a pattern too big to hold, even though neither
+        // coordinate is anywhere near overflowing.
+        assert_eq!(
+            Err(PatternError::TooLarge),
+            Pattern::from_life106("#Life 1.06\n0 0\n200000 200000\n")
+        );
+        assert_eq!(
+            Err(PatternError::TooLarge),
+            Pattern::from_life106(&format!("#Life 1.06\n0 0\n0 {}\n", MAX_PATTERN_CELLS))
+        );
+        let tall = format!("#Life 1.06\n0 0\n0 {}\n", MAX_PATTERN_CELLS - 1);
+        assert_eq!(
+            MAX_PATTERN_CELLS,
+            Pattern::from_life106(&tall).unwrap().height()
+        );
+        let empty = Pattern::from_life106("#Life 1.06\n").unwrap();
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+    }
+}
@@ -0,0 +1,416 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Board, Game, GridError, Orientation, Point, Rect, StateGame};
+
+mod cells;
+mod life106;
+mod rle;
+
+// The most cells a pattern read from a file can cover, dead ones included, about 64MB of them.
+// Files can come from anyone, and a file of a few bytes can ask for a pattern too big to make, so
+// those are refused rather than taking the process down when the allocation fails.
+pub const MAX_PATTERN_CELLS: usize = 1 << 26;
+
+// A rectangle of cells lifted off a board or read from a pattern file, along with the rule and
+// comments that came with it. Cells hold states like StateGame, with 0 as dead, so the same
+// pattern can carry two-state and multi-state patterns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    width: usize,
+    height: usize,
+    // The number of states the pattern's rule has, which decides how it's written out.
+    states: u8,
+    // Stored column by column like Board, so (x, y) is at x * height + y.
+    cells: Vec<u8>,
+    rule: Option<String>,
+    // Comment lines, kept as written, without their leading '#'.
+    comments: Vec<String>,
+}
+
+// Refuse a width x height pattern with more than MAX_PATTERN_CELLS cells.
+fn check_size(width: usize, height: usize) -> Result<(), PatternError> {
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_PATTERN_CELLS => Ok(()),
+        _ => Err(PatternError::TooLarge),
+    }
+}
+
+impl Pattern {
+    pub fn new(width: usize, height: usize) -> Self {
+        Pattern {
+            width,
+            height,
+            states: 2,
+            cells: vec![0; width * height],
+            rule: None,
+            comments: Vec::new(),
+        }
+    }
+
+    // Build a pattern by asking f for the state of each cell.
+    pub fn from_fn<F: FnMut(usize, usize) -> u8>(width: usize, height: usize, mut f: F) -> Self {
+        let mut pattern = Pattern::new(width, height);
+        for x in 0..width {
+            for y in 0..height {
+                pattern.set(x, y, f(x, y));
+            }
+        }
+        pattern
+    }
+
+    // Read and parse a pattern file in any of the formats read().
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PatternError> {
+        Pattern::read(&fs::read_to_string(path)?)
+    }
+
+    // Parse a pattern as RLE, plaintext, or Life 1.06, telling them apart by how they start: Life
+    // 1.06 with its "#Life 1.06" line, plaintext with a '!' comment or a row of cells, and RLE with
+    // anything else, such as its header or '#' comments.
+    pub fn read(text: &str) -> Result<Self, PatternError> {
+        let first = text.lines().map(str::trim).find(|line| !line.is_empty());
+        match first {
+            Some(line) if line.starts_with("#Life 1.06") => Pattern::from_life106(text),
+            Some(line)
+                if line.starts_with('!') || line.chars().all(|c| matches!(c, '.' | 'O' | '*')) =>
+            {
+                Pattern::from_cells(text)
+            }
+            _ => text.parse(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.cells[self.index(x, y)]
+    }
+
+    pub fn is_alive(&self, x: usize, y: usize) -> bool {
+        self.get(x, y) != 0
+    }
+
+    // Set a cell's state, raising the number of states if it's past the current count.
+    pub fn set(&mut self, x: usize, y: usize, state: u8) {
+        let index = self.index(x, y);
+        self.cells[index] = state;
+        self.states = self.states.max(state.saturating_add(1));
+    }
+
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    // Set the number of states the pattern's rule has. Patterns with more than two are written
+    // in the multi-state form even when they only hold 0s and 1s.
+    pub fn set_states(&mut self, states: u8) {
+        let highest = self.cells.iter().copied().max().unwrap_or_default();
+        self.states = states.max(highest.saturating_add(1)).max(2);
+    }
+
+    pub fn rule(&self) -> Option<&str> {
+        self.rule.as_deref()
+    }
+
+    pub fn set_rule<S: Into<String>>(&mut self, rule: S) {
+        self.rule = Some(rule.into());
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    pub fn add_comment<S: Into<String>>(&mut self, comment: S) {
+        self.comments.push(comment.into());
+    }
+
+    // The pattern's name, from its "#N" line.
+    pub fn name(&self) -> Option<&str> {
+        self.tagged(&['N']).next()
+    }
+
+    pub fn set_name<S: Into<String>>(&mut self, name: S) {
+        self.set_tagged('N', name.into());
+    }
+
+    // Who found or built the pattern, often with when, from its "#O" line.
+    pub fn author(&self) -> Option<&str> {
+        self.tagged(&['O']).next()
+    }
+
+    pub fn set_author<S: Into<String>>(&mut self, author: S) {
+        self.set_tagged('O', author.into());
+    }
+
+    // The lines describing the pattern, from its "#C" and "#c" lines, in order.
+    pub fn description(&self) -> impl Iterator<Item = &str> {
+        self.tagged(&['C', 'c'])
+    }
+
+    // The text of the comments starting with one of the given tags, such as "N" in "#N Glider".
+    fn tagged<'a>(&'a self, tags: &'a [char]) -> impl Iterator<Item = &'a str> {
+        self.comments
+            .iter()
+            .filter_map(move |comment| tagged(comment, tags))
+    }
+
+    // Replace the first comment with the given tag, or add one above the description if there
+    // isn't one, keeping the name first.
+    fn set_tagged(&mut self, tag: char, text: String) {
+        let comment = format!("{tag} {text}");
+        let existing = self
+            .comments
+            .iter()
+            .position(|comment| tagged(comment, &[tag]).is_some());
+        match existing {
+            Some(index) => self.comments[index] = comment,
+            None => {
+                let index = usize::from(tag != 'N' && self.name().is_some());
+                self.comments.insert(index, comment);
+            }
+        }
+    }
+
+    // The coordinates and states of every live cell.
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize, u8)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &state)| state != 0)
+            .map(|(index, &state)| (index / self.height, index % self.height, state))
+    }
+
+    // Shrink the pattern to the smallest rectangle holding every live cell, returning where that
+    // rectangle was. A pattern with no live cells is left as it is, as Game::trim() leaves a board.
+    pub fn trim(&mut self) -> Option<Rect> {
+        let bounds = Rect::bounding(self.live_cells().map(|(x, y, _)| Point::new(x, y)))?;
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = bounds;
+        let cells = (x..x + width)
+            .flat_map(|x| (y..y + height).map(move |y| (x, y)))
+            .map(|(x, y)| self.get(x, y))
+            .collect();
+        (self.width, self.height, self.cells) = (width, height, cells);
+        Some(bounds)
+    }
+
+    // A copy of the pattern turned or mirrored into the given orientation.
+    pub fn oriented(&self, orientation: Orientation) -> Pattern {
+        let (width, height) = if orientation.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let mut pattern = Pattern::from_fn(width, height, |x, y| {
+            let (x, y) = orientation.source(x, y, self.width, self.height);
+            self.get(x, y)
+        });
+        pattern.states = self.states;
+        pattern.rule.clone_from(&self.rule);
+        pattern.comments.clone_from(&self.comments);
+        pattern
+    }
+
+    // A board with every cell in a state other than 0 live.
+    pub fn to_board(&self) -> Board {
+        Board::from_fn(self.width, self.height, |x, y| self.is_alive(x, y))
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the {}x{} pattern",
+            self.width,
+            self.height
+        );
+        x * self.height + y
+    }
+}
+
+// The text of a comment if it starts with one of the given tags followed by a space or nothing.
+fn tagged<'a>(comment: &'a str, tags: &[char]) -> Option<&'a str> {
+    let text = comment.strip_prefix(tags)?;
+    (text.is_empty() || text.starts_with(' ')).then(|| text.trim())
+}
+
+impl From<&Board> for Pattern {
+    fn from(board: &Board) -> Self {
+        Pattern::from_fn(board.x_size(), board.y_size(), |x, y| board.get(x, y) as u8)
+    }
+}
+
+// The game's board, along with its rulestring when the rule is Life-like.
+impl From<&Game> for Pattern {
+    fn from(game: &Game) -> Self {
+        let mut pattern = Pattern::from(game.board());
+        if let Some(rule) = game.life_like() {
+            pattern.set_rule(rule.to_string());
+        }
+        pattern
+    }
+}
+
+impl From<&StateGame> for Pattern {
+    fn from(game: &StateGame) -> Self {
+        let mut pattern = Pattern::from_fn(game.x_size(), game.y_size(), |x, y| game.get(x, y));
+        pattern.set_states(game.states());
+        pattern
+    }
+}
+
+// The ways pattern text can fail to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    // The RLE header line isn't in the "x = .., y = .., rule = .." form.
+    InvalidHeader(String),
+    // A character that doesn't belong in the pattern, on the given line (counting from 1).
+    InvalidSymbol {
+        line: usize,
+        found: char,
+    },
+    // A Life 1.06 line that isn't an x and a y, on the given line (counting from 1).
+    InvalidCoordinates {
+        line: usize,
+        found: String,
+    },
+    // Live cells past the size the header gives, on the given line (counting from 1).
+    OutOfBounds {
+        line: usize,
+        width: usize,
+        height: usize,
+    },
+    // The pattern covers more than MAX_PATTERN_CELLS cells.
+    TooLarge,
+    // The pattern file couldn't be read.
+    Io(String),
+    // The pattern doesn't fit on the board it's placed on.
+    Grid(GridError),
+}
+
+impl From<io::Error> for PatternError {
+    fn from(err: io::Error) -> Self {
+        PatternError::Io(err.to_string())
+    }
+}
+
+impl From<GridError> for PatternError {
+    fn from(err: GridError) -> Self {
+        PatternError::Grid(err)
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::InvalidHeader(header) => write!(f, "invalid header line: {header}"),
+            PatternError::InvalidSymbol { line, found } => {
+                write!(f, "unexpected {found:?} on line {line}")
+            }
+            PatternError::InvalidCoordinates { line, found } => {
+                write!(f, "expected \"x y\" on line {line}, found {found:?}")
+            }
+            PatternError::OutOfBounds {
+                line,
+                width,
+                height,
+            } => write!(
+                f,
+                "cells on line {line} are outside the {width}x{height} size in the header"
+            ),
+            PatternError::TooLarge => write!(
+                f,
+                "the pattern covers more than the {MAX_PATTERN_CELLS} cells a pattern can"
+            ),
+            PatternError::Io(err) => write!(f, "{err}"),
+            PatternError::Grid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let board = |text: &str| Pattern::read(text).unwrap().to_board().to_string();
+        let glider = ".O.\n..O\nOOO\n";
+        assert_eq!(glider, board("#N Glider\nx = 3, y = 3\nbo$2bo$3o!\n"));
+        assert_eq!(glider, board("!Name: Glider\n.O.\n..O\nOOO\n"));
+        assert_eq!("OO\nOO\n", board("OO\nOO\n"));
+        assert_eq!(glider, board("#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n"));
+    }
+
+    #[test]
+    fn test_set_raises_states() {
+        let mut pattern = Pattern::new(2, 2);
+        assert_eq!(2, pattern.states());
+        pattern.set(1, 0, 1);
+        assert_eq!(2, pattern.states());
+        pattern.set(0, 1, 4);
+        assert_eq!(5, pattern.states());
+        assert_eq!(
+            vec![(0, 1, 4), (1, 0, 1)],
+            pattern.live_cells().collect::<Vec<_>>()
+        );
+
+        pattern.set_states(3);
+        assert_eq!(5, pattern.states());
+        pattern.set_states(8);
+        assert_eq!(8, pattern.states());
+    }
+
+    #[test]
+    fn test_from_board() {
+        let board: Board = ".O\nO.\n.O\n".parse().unwrap();
+        let pattern = Pattern::from(&board);
+        assert_eq!((2, 3), (pattern.width(), pattern.height()));
+        assert!(pattern.is_alive(1, 0));
+        assert!(pattern.is_alive(0, 1));
+        assert!(!pattern.is_alive(0, 0));
+        assert_eq!(2, pattern.states());
+        assert_eq!(board, pattern.to_board());
+    }
+
+    #[test]
+    fn test_from_game() {
+        let mut game = Game::new(3, 3);
+        game.set([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]).unwrap();
+        assert_eq!(
+            "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n",
+            Pattern::from(&game).to_string()
+        );
+    }
+
+    #[test]
+    fn test_trim() {
+        let mut pattern = Pattern::new(6, 5);
+        pattern.set(2, 1, 1);
+        pattern.set(4, 3, 2);
+        assert_eq!(Some(Rect::new(2, 1, 3, 3)), pattern.trim());
+        assert_eq!((3, 3), (pattern.width(), pattern.height()));
+        assert_eq!(
+            vec![(0, 0, 1), (2, 2, 2)],
+            pattern.live_cells().collect::<Vec<_>>()
+        );
+
+        let mut empty = Pattern::new(4, 4);
+        assert_eq!(None, empty.trim());
+        assert_eq!((4, 4), (empty.width(), empty.height()));
+    }
+}
@@ -0,0 +1,412 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{check_size, Pattern, PatternError};
+
+// Golly wraps RLE data so no line is longer than this, never splitting a run across lines.
+const LINE_LENGTH: usize = 70;
+
+// Patterns print as RLE, byte for byte the way Golly puts a selection on the clipboard: comment
+// lines, the "x = .., y = .., rule = .." header, then runs of cells wrapped at 70 characters and
+// ended with '!'. Two-state patterns use 'b' and 'o' for dead and live cells, while multi-state
+// patterns use '.' for 0 and 'A' to 'X' for 1 to 24, with a 'p' to 'y' prefix for the states
+// above that.
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for comment in &self.comments {
+            writeln!(f, "#{comment}")?;
+        }
+        write!(f, "x = {}, y = {}", self.width, self.height)?;
+        if let Some(rule) = &self.rule {
+            write!(f, ", rule = {rule}")?;
+        }
+        writeln!(f)?;
+
+        let mut writer = RunWriter { f, line: 0 };
+        let multistate = self.states > 2;
+        let mut blank_rows = 0;
+        let mut started = false;
+        for y in 0..self.height {
+            let Some(end) = (0..self.width).rposition(|x| self.is_alive(x, y)) else {
+                blank_rows += 1;
+                continue;
+            };
+
+            // Each '$' ends a row, so blank rows above the first live one need one fewer.
+            let rows = if started { blank_rows + 1 } else { blank_rows };
+            if rows > 0 {
+                writer.run(rows, "$")?;
+            }
+            started = true;
+            blank_rows = 0;
+
+            let mut x = 0;
+            while x <= end {
+                let state = self.get(x, y);
+                let run = (x..=end).take_while(|&x| self.get(x, y) == state).count();
+                writer.run(run, &symbol(state, multistate))?;
+                x += run;
+            }
+        }
+        writer.run(1, "!")?;
+        writeln!(f)
+    }
+}
+
+// Writes runs of cells, starting a new line whenever the next run wouldn't fit on this one.
+struct RunWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    line: usize,
+}
+
+impl RunWriter<'_, '_> {
+    fn run(&mut self, count: usize, symbol: &str) -> fmt::Result {
+        let count = if count > 1 {
+            count.to_string()
+        } else {
+            String::new()
+        };
+        let length = count.len() + symbol.len();
+        if self.line + length > LINE_LENGTH {
+            writeln!(self.f)?;
+            self.line = 0;
+        }
+        self.line += length;
+        write!(self.f, "{count}{symbol}")
+    }
+}
+
+fn symbol(state: u8, multistate: bool) -> String {
+    match (state, multistate) {
+        (0, false) => "b".to_string(),
+        (_, false) => "o".to_string(),
+        (0, true) => ".".to_string(),
+        (1..=24, true) => char::from(b'A' + state - 1).to_string(),
+        _ => {
+            let high = (state - 25) / 24;
+            let low = (state - 25) % 24;
+            format!("{}{}", char::from(b'p' + high), char::from(b'A' + low))
+        }
+    }
+}
+
+// Parse RLE in the form Golly writes it, along with the looser files found in the wild: runs may
+// be split across lines anywhere. Live cells past the size in the header are refused before
+// anything is made for them, so a crafted run count can't ask for more memory than the header
+// does, and a header asking for more than MAX_PATTERN_CELLS is refused too. Comment lines are
+// kept as they are, so the name, author, and description they carry are written back out with
+// the pattern.
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut comments = Vec::new();
+        let mut header = None;
+        let mut cells = Vec::new();
+        let mut multistate = false;
+
+        let (mut x, mut y) = (0usize, 0usize);
+        let mut count: Option<usize> = None;
+        let mut prefix: Option<u8> = None;
+        'lines: for (index, line) in s.lines().enumerate() {
+            let number = index + 1;
+            if header.is_none() {
+                let line = line.trim();
+                if let Some(comment) = line.strip_prefix('#') {
+                    comments.push(comment.to_string());
+                } else if !line.is_empty() {
+                    header = Some(parse_header(line)?);
+                }
+                continue;
+            }
+
+            let (width, height) = header.as_ref().map_or((0, 0), |&(w, h, _)| (w, h));
+            let outside = PatternError::OutOfBounds {
+                line: number,
+                width,
+                height,
+            };
+            for found in line.chars() {
+                let invalid = PatternError::InvalidSymbol {
+                    line: number,
+                    found,
+                };
+                if prefix.is_some() && !found.is_ascii_uppercase() {
+                    return Err(invalid);
+                }
+
+                let state = match found {
+                    '0'..='9' => {
+                        let digit = found as usize - '0' as usize;
+                        count = count
+                            .unwrap_or_default()
+                            .checked_mul(10)
+                            .and_then(|count| count.checked_add(digit));
+                        if count.is_none() {
+                            return Err(outside);
+                        }
+                        continue;
+                    }
+                    'b' => 0,
+                    'o' => 1,
+                    '.' => {
+                        multistate = true;
+                        0
+                    }
+                    'A'..='X' => {
+                        multistate = true;
+                        let low = found as u8 - b'A' + 1;
+                        match prefix.take() {
+                            Some(high) => (high - b'p' + 1)
+                                .checked_mul(24)
+                                .and_then(|state| state.checked_add(low))
+                                .ok_or(invalid)?,
+                            None => low,
+                        }
+                    }
+                    'p'..='y' => {
+                        prefix = Some(found as u8);
+                        continue;
+                    }
+                    '$' => {
+                        y = y
+                            .checked_add(count.take().unwrap_or(1))
+                            .ok_or(outside.clone())?;
+                        x = 0;
+                        continue;
+                    }
+                    '!' => break 'lines,
+                    _ if found.is_whitespace() => continue,
+                    _ => return Err(invalid),
+                };
+
+                let run = count.take().unwrap_or(1);
+                let end = x.checked_add(run).ok_or(outside.clone())?;
+                if state != 0 {
+                    if end > width || y >= height {
+                        return Err(outside);
+                    }
+                    cells.extend((x..end).map(|x| (x, y, state)));
+                }
+                x = end;
+            }
+        }
+
+        let Some((width, height, rule)) = header else {
+            return Err(PatternError::InvalidHeader(String::new()));
+        };
+        let mut pattern = Pattern::new(width, height);
+        for (x, y, state) in cells {
+            pattern.set(x, y, state);
+        }
+        // Multi-state symbols mean a rule with more than two states, even when only states 0 and
+        // 1 show up, so keep writing the pattern back out that way.
+        if multistate {
+            pattern.set_states(3);
+        }
+        pattern.comments = comments;
+        // Older files give the rule on a "#r" line rather than in the header.
+        pattern.rule = rule.or_else(|| pattern.tagged(&['r']).next().map(str::to_string));
+        Ok(pattern)
+    }
+}
+
+// Parse the "x = .., y = .., rule = .." header line into the size and rule. The rule comes last
+// and runs to the end of the line, since rules for bounded grids like "B3/S23:T20,30" hold commas.
+fn parse_header(line: &str) -> Result<(usize, usize, Option<String>), PatternError> {
+    let invalid = || PatternError::InvalidHeader(line.to_string());
+    let (size, rule) = match line.split_once(", rule") {
+        Some((size, rule)) => {
+            let rule = rule.trim_start().strip_prefix('=').ok_or_else(invalid)?;
+            (size, Some(rule.trim().to_string()))
+        }
+        None => (line, None),
+    };
+
+    let (mut width, mut height) = (None, None);
+    for field in size.split(',') {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = Some(value.parse().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse().map_err(|_| invalid())?),
+            _ => return Err(invalid()),
+        }
+    }
+    let (width, height): (usize, usize) = (width.ok_or_else(invalid)?, height.ok_or_else(invalid)?);
+    // The pattern has a cell for every one the header asks for, so the header can't ask for more
+    // than a pattern can hold.
+    check_size(width, height)?;
+    Ok((width, height, rule))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glider_round_trip() {
+        let text = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!((3, 3), (pattern.width(), pattern.height()));
+        assert_eq!(Some("B3/S23"), pattern.rule());
+        assert_eq!(2, pattern.states());
+        assert_eq!(
+            vec![(0, 2, 1), (1, 0, 1), (1, 2, 1), (2, 1, 1), (2, 2, 1)],
+            pattern.live_cells().collect::<Vec<_>>()
+        );
+        assert_eq!(text, pattern.to_string());
+
+        let text = "x = 2, y = 1, rule = B3/S23:T20,30\n2o!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!(Some("B3/S23:T20,30"), pattern.rule());
+        assert_eq!(text, pattern.to_string());
+    }
+
+    #[test]
+    fn test_blank_rows_and_trailing_cells() {
+        let text = "x = 4, y = 5, rule = B3/S23\n2o4$3bo!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert!(pattern.is_alive(3, 4));
+        assert_eq!(3, pattern.live_cells().count());
+        assert_eq!(text, pattern.to_string());
+
+        // Blank rows above the first live cell.
+        let text = "x = 3, y = 4\n2$2bo$o!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert!(pattern.is_alive(2, 2));
+        assert_eq!(text, pattern.to_string());
+    }
+
+    #[test]
+    fn test_multistate_round_trip() {
+        let text = "x = 5, y = 2, rule = WireWorld\n.AB2C$pAyO.A!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!(0, pattern.get(0, 0));
+        assert_eq!(1, pattern.get(1, 0));
+        assert_eq!(3, pattern.get(4, 0));
+        assert_eq!(25, pattern.get(0, 1));
+        assert_eq!(255, pattern.get(1, 1));
+        assert_eq!(1, pattern.get(3, 1));
+        assert_eq!(text, pattern.to_string());
+
+        // Only states 0 and 1, but written in the multi-state form, as Golly does for Wireworld.
+        let text = "x = 2, y = 1, rule = WireWorld\n.A!\n";
+        assert_eq!(text, text.parse::<Pattern>().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_long_lines_wrap() {
+        let pattern = Pattern::from_fn(100, 1, |x, _| (x % 2 == 0) as u8);
+        let text = pattern.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!("x = 100, y = 1", lines[0]);
+        assert_eq!(3, lines.len());
+        assert!(lines[1..].iter().all(|line| line.len() <= 70));
+        assert_eq!(70, lines[1].len());
+        assert!(text.ends_with("bo!\n"));
+        assert_eq!(pattern, text.parse().unwrap());
+    }
+
+    #[test]
+    fn test_comments_and_loose_data() {
+        let text = "#N Glider\n#C A small spaceship.\nx = 3, y = 3\nb\no$\n2\nbo$3o!";
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!(["N Glider", "C A small spaceship."], pattern.comments());
+        assert_eq!(None, pattern.rule());
+        assert_eq!(
+            "#N Glider\n#C A small spaceship.\nx = 3, y = 3\nbo$2bo$3o!\n",
+            pattern.to_string()
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        let text = "#N Gosper glider gun\n#O Bill Gosper, 1970\n#C The first known gun.\n\
+                    #c Fires a glider every 30 generations.\n#r 23/3\nx = 2, y = 2\n2o$2o!\n";
+        let pattern: Pattern = text.parse().unwrap();
+        assert_eq!(Some("Gosper glider gun"), pattern.name());
+        assert_eq!(Some("Bill Gosper, 1970"), pattern.author());
+        assert_eq!(
+            vec![
+                "The first known gun.",
+                "Fires a glider every 30 generations."
+            ],
+            pattern.description().collect::<Vec<_>>()
+        );
+        assert_eq!(Some("23/3"), pattern.rule());
+        let written: Pattern = pattern.to_string().parse().unwrap();
+        assert_eq!(pattern, written);
+
+        let mut pattern = Pattern::new(1, 1);
+        pattern.add_comment("C [0,0] a label");
+        pattern.set_author("someone");
+        pattern.set_name("dot");
+        pattern.set_name("Dot");
+        assert_eq!(
+            ["N Dot", "O someone", "C [0,0] a label"],
+            pattern.comments()
+        );
+        assert_eq!(None, Pattern::new(1, 1).name());
+    }
+
+    #[test]
+    fn test_cells_past_header() {
+        let outside = |line| {
+            Err(PatternError::OutOfBounds {
+                line,
+                width: 3,
+                height: 3,
+            })
+        };
+        assert_eq!(outside(2), "x = 3, y = 3\n4o!".parse::<Pattern>());
+        assert_eq!(outside(3), "x = 3, y = 3\n$2$\no!".parse::<Pattern>());
+        // Runs too long to count, or too long to make, are refused the same way.
+        assert_eq!(
+            outside(2),
+            "x = 3, y = 3\n99999999999999999999b!".parse::<Pattern>()
+        );
+        assert_eq!(
+            outside(2),
+            "x = 3, y = 3\n9999999999999999999o!".parse::<Pattern>()
+        );
+        // Dead cells past the edge are harmless, and some files in the wild end rows with them.
+        let pattern: Pattern = "x = 3, y = 3\no5b$3o!".parse().unwrap();
+        assert_eq!((3, 3), (pattern.width(), pattern.height()));
+
+        // Nor can the header ask for more cells than a pattern holds, whether or not the count
+        // would overflow.
+        for header in ["x = 100000, y = 100000", "x = 99999999999, y = 99999999999"] {
+            assert_eq!(
+                Err(PatternError::TooLarge),
+                format!("{header}\n!").parse::<Pattern>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err(PatternError::InvalidHeader("x = 3, z = 3".to_string())),
+            "x = 3, z = 3\no!".parse::<Pattern>()
+        );
+        assert_eq!(
+            Err(PatternError::InvalidSymbol {
+                line: 3,
+                found: 'z'
+            }),
+            "x = 3, y = 3\nbo$\n2z!".parse::<Pattern>()
+        );
+        assert_eq!(
+            Err(PatternError::InvalidSymbol {
+                line: 2,
+                found: 'o'
+            }),
+            "x = 3, y = 3\npo!".parse::<Pattern>()
+        );
+        assert_eq!(
+            Err(PatternError::InvalidHeader(String::new())),
+            "#C nothing here".parse::<Pattern>()
+        );
+    }
+}
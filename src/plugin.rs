@@ -0,0 +1,198 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::{render, Game, Raster, Rect, Ticker};
+
+// A Bevy plugin that runs a Game as a resource and shows it on a sprite, for dropping Life into a
+// Bevy scene as an effect or a mechanic. The game steps at a fixed number of generations a second
+// of Bevy's virtual time, so pausing or slowing the app's clock does the same to the game, and
+// each board is drawn onto a texture one pixel per cell:
+//
+//     App::new()
+//         .add_plugins((DefaultPlugins, GamePlugin::new(Game::random(128, 96, 0.3, 1))))
+//         .add_systems(Startup, |mut commands: Commands| {
+//             commands.spawn(Camera2d);
+//         })
+//         .run();
+//
+// The sprite is spawned at the origin with a LifeSprite marker, so it can be moved, scaled, or
+// parented like any other entity.
+pub struct GamePlugin {
+    game: Game,
+    rate: f64,
+    cell_size: f32,
+    background: [u8; 3],
+    live: [u8; 3],
+}
+
+impl GamePlugin {
+    // Run `game` at 10 generations a second, with white cells 4 units across on black.
+    pub fn new(game: Game) -> Self {
+        GamePlugin {
+            game,
+            rate: 10.0,
+            cell_size: 4.0,
+            background: [0; 3],
+            live: [255; 3],
+        }
+    }
+
+    // The number of generations to run each second.
+    pub fn with_rate(mut self, rate: f64) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    // How many world units across each cell is drawn.
+    pub fn with_cell_size(mut self, cell_size: f32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn with_colors(mut self, background: [u8; 3], live: [u8; 3]) -> Self {
+        self.background = background;
+        self.live = live;
+        self
+    }
+}
+
+// The game a GamePlugin is running. Systems can change the game to edit its board or rule, and
+// the ticker to speed it up or pause it with a rate of 0; the sprite catches up on the next frame.
+#[derive(Resource)]
+pub struct LifeGame {
+    pub game: Game,
+    pub ticker: Ticker,
+    cell_size: f32,
+    raster: Raster,
+    image: Handle<Image>,
+}
+
+// The sprite a GamePlugin draws its game on.
+#[derive(Component)]
+pub struct LifeSprite;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LifeGame {
+            game: self.game.clone(),
+            ticker: Ticker::new(self.rate),
+            cell_size: self.cell_size,
+            raster: Raster::new(1, self.background, self.live),
+            image: Handle::default(),
+        })
+        .add_systems(Startup, spawn_sprite)
+        .add_systems(Update, (step, draw).chain());
+    }
+}
+
+fn spawn_sprite(
+    mut commands: Commands,
+    mut life: ResMut<LifeGame>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let mut image = Image::new_fill(
+        Extent3d::default(),
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Cells are drawn as hard-edged squares however far the sprite is scaled up.
+    image.sampler = ImageSampler::nearest();
+    life.image = images.add(image);
+    commands.spawn((Sprite::from_image(life.image.clone()), LifeSprite));
+}
+
+fn step(mut life: ResMut<LifeGame>, time: Res<Time>) {
+    // Only a step counts as a change to the game, so the board isn't redrawn every frame.
+    let steps = life.bypass_change_detection().ticker.steps(time.elapsed());
+    for _ in 0..steps {
+        life.game.iterate();
+    }
+}
+
+// Draw the board onto the sprite's texture whenever the game has changed, by a step or by another
+// system, resizing the texture and sprite to follow the board.
+fn draw(
+    mut life: ResMut<LifeGame>,
+    mut images: ResMut<Assets<Image>>,
+    mut sprites: Query<&mut Sprite, With<LifeSprite>>,
+) {
+    if !life.is_changed() {
+        return;
+    }
+    let life = &mut *life;
+    let board = life.game.board();
+    render(
+        board,
+        Rect::new(0, 0, board.x_size(), board.y_size()),
+        &mut life.raster,
+    );
+    let Some(image) = images.get_mut(&life.image) else {
+        return;
+    };
+    let size = Extent3d {
+        width: life.raster.width() as u32,
+        height: life.raster.height() as u32,
+        depth_or_array_layers: 1,
+    };
+    if image.texture_descriptor.size != size {
+        image.resize(size);
+    }
+    image.data = life.raster.to_rgba();
+    let custom_size = Vec2::new(size.width as f32, size.height as f32) * life.cell_size;
+    for mut sprite in &mut sprites {
+        sprite.custom_size = Some(custom_size);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn test_plugin() {
+        let mut game = Game::new(5, 5);
+        game.set([(1, 2), (2, 2), (3, 2)]).unwrap();
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            GamePlugin::new(game).with_cell_size(2.0),
+        ))
+        .init_asset::<Image>()
+        // A tenth of a second a frame, at 10 generations a second.
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+        // The first frame only starts the clock, and then each one runs a generation.
+        for _ in 0..4 {
+            app.update();
+        }
+        let life = app.world().resource::<LifeGame>();
+        assert_eq!(3, life.game.stats().generation);
+        assert!(life.game.is_alive(2, 1));
+
+        let image = app
+            .world()
+            .resource::<Assets<Image>>()
+            .get(&life.image)
+            .unwrap();
+        assert_eq!(UVec2::new(5, 5), image.size());
+        // The blinker is standing up, so (2, 1) is live and (1, 2) isn't.
+        let pixel = |x: usize, y: usize| &image.data[(y * 5 + x) * 4..][..4];
+        assert_eq!([255, 255, 255, 255], pixel(2, 1));
+        assert_eq!([0, 0, 0, 255], pixel(1, 2));
+
+        let mut sprites = app.world_mut().query::<&Sprite>();
+        let sprite = sprites.single(app.world());
+        assert_eq!(Some(Vec2::new(10.0, 10.0)), sprite.custom_size);
+    }
+}
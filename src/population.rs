@@ -0,0 +1,96 @@
+use std::io::{self, Write};
+
+use crate::Game;
+
+// Streams the population of every generation of a run as CSV, a row at a time, for plotting or for
+// checking against a known sequence such as the R-pentomino's:
+//
+//     generation,population
+//     0,5
+//     1,6
+//     2,7
+//
+// Nothing is kept between rows, so runs of any length take the same memory. Wrap the output in a
+// BufWriter when it's a file.
+pub struct PopulationWriter<W: Write> {
+    out: W,
+    // Whether the header's been written yet.
+    started: bool,
+}
+
+impl<W: Write> PopulationWriter<W> {
+    pub fn new(out: W) -> Self {
+        PopulationWriter {
+            out,
+            started: false,
+        }
+    }
+
+    // Write a row for the game's current generation, counting its live cells.
+    pub fn write(&mut self, game: &Game) -> io::Result<()> {
+        self.row(game.stats().generation, game.board().population())
+    }
+
+    // Write the game's current generation, then iterate it the given number of times, writing a
+    // row after each. The population is followed from each iteration's births and deaths rather
+    // than counted.
+    pub fn run(&mut self, game: &mut Game, iters: usize) -> io::Result<()> {
+        let mut population = game.board().population();
+        self.row(game.stats().generation, population)?;
+        for _ in 0..iters {
+            let changes = game.iterate();
+            population = population + changes.births - changes.deaths;
+            self.row(game.stats().generation, population)?;
+        }
+        self.out.flush()
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn row(&mut self, generation: usize, population: usize) -> io::Result<()> {
+        if !self.started {
+            writeln!(self.out, "generation,population")?;
+            self.started = true;
+        }
+        writeln!(self.out, "{generation},{population}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_r_pentomino() {
+        let mut game = Game::new(64, 64);
+        game.set([(31, 30), (32, 30), (30, 31), (31, 31), (31, 32)])
+            .unwrap();
+        let mut writer = PopulationWriter::new(Vec::new());
+        writer.run(&mut game, 5).unwrap();
+        assert_eq!(
+            "generation,population\n0,5\n1,6\n2,7\n3,9\n4,8\n5,9\n",
+            String::from_utf8(writer.into_inner()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let mut writer = PopulationWriter::new(Vec::new());
+        writer.write(&game).unwrap();
+        game.iterate();
+        game.toggle(0, 0);
+        writer.write(&game).unwrap();
+        assert_eq!(
+            "generation,population\n0,3\n1,4\n",
+            String::from_utf8(writer.into_inner()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,538 @@
+use std::fmt::Write;
+
+use crate::{Board, Game, Palette, Pattern, Rect};
+
+// Something a board can be drawn on: an image, a terminal, a GUI. render() walks the board and
+// calls these in order, so a new kind of output only has to say how to draw a run of live cells.
+// Coordinates are in cells, counted from the top-left corner of the region being drawn.
+pub trait RenderTarget {
+    // Start drawing a region of the given size, everything in it dead.
+    fn begin_frame(&mut self, width: usize, height: usize);
+
+    // Draw `length` live cells in a row, starting at (x, y) and running right.
+    fn draw_run(&mut self, x: usize, y: usize, length: usize);
+
+    fn draw_cell(&mut self, x: usize, y: usize) {
+        self.draw_run(x, y, 1);
+    }
+
+    // Draw `length` cells of a multi-state automaton, all in the same state, which the palette
+    // says to draw in `color`. Targets that can only draw live and dead cells draw them live.
+    fn draw_state_run(&mut self, x: usize, y: usize, length: usize, _state: u8, _color: [u8; 3]) {
+        self.draw_run(x, y, length);
+    }
+
+    fn end_frame(&mut self) {}
+}
+
+// Draw a region of the board on a target, one run of live cells at a time. The region is clipped
+// to the board.
+pub fn render<T: RenderTarget + ?Sized>(board: &Board, region: Rect, target: &mut T) {
+    let size = (board.x_size(), board.y_size());
+    draw_runs(
+        size,
+        region,
+        target,
+        |x, y| board[(x, y)] as u8,
+        |target, x, y, length, _| match length {
+            1 => target.draw_cell(x, y),
+            length => target.draw_run(x, y, length),
+        },
+    );
+}
+
+// Draw a region of a multi-state pattern on a target, each run of cells in the same state in the
+// palette's color for it. State 0 is the background and isn't drawn. A StateGame can be drawn by
+// turning it into a pattern first.
+pub fn render_states<T: RenderTarget + ?Sized>(
+    pattern: &Pattern,
+    region: Rect,
+    palette: &Palette,
+    target: &mut T,
+) {
+    let size = (pattern.width(), pattern.height());
+    let state = |x, y| pattern.get(x, y);
+    draw_runs(
+        size,
+        region,
+        target,
+        state,
+        |target, x, y, length, state| {
+            target.draw_state_run(x, y, length, state, palette.color(state))
+        },
+    );
+}
+
+// Draw a region of a game's board on a target with each live cell colored by its age, as
+// Game::track_ages() counts it, using the palette's colors from state 1 on. Each state is for cells
+// twice as old as the one before: state 1 for cells born in the last generation, state 2 for ones
+// two or three generations old, state 3 for four to seven, and so on, with cells older than that in
+// the palette's last state. Palette::gradient() makes a good one.
+pub fn render_ages<T: RenderTarget + ?Sized>(
+    game: &Game,
+    region: Rect,
+    palette: &Palette,
+    target: &mut T,
+) {
+    let oldest = palette.len().saturating_sub(1).clamp(1, u8::MAX as usize) as u8;
+    let state = |x, y| match game.age(x, y) {
+        0 => 0,
+        age => (age.ilog2() as u8 + 1).min(oldest),
+    };
+    draw_runs(
+        (game.x_size(), game.y_size()),
+        region,
+        target,
+        state,
+        |target, x, y, length, state| {
+            target.draw_state_run(x, y, length, state, palette.color(state))
+        },
+    );
+}
+
+// Walk the part of a grid of states inside the region, clipped to the grid, handing each run of
+// cells in the same state other than 0 to `draw`.
+fn draw_runs<T: RenderTarget + ?Sized>(
+    (x_size, y_size): (usize, usize),
+    region: Rect,
+    target: &mut T,
+    state: impl Fn(usize, usize) -> u8,
+    mut draw: impl FnMut(&mut T, usize, usize, usize, u8),
+) {
+    let right = (region.x + region.width).min(x_size);
+    let bottom = (region.y + region.height).min(y_size);
+    let (left, top) = (region.x.min(right), region.y.min(bottom));
+    target.begin_frame(right - left, bottom - top);
+    for y in top..bottom {
+        let mut x = left;
+        while x < right {
+            let run = state(x, y);
+            let start = x;
+            while x < right && state(x, y) == run {
+                x += 1;
+            }
+            if run != 0 {
+                draw(target, start - left, y - top, x - start, run);
+            }
+        }
+    }
+    target.end_frame();
+}
+
+// Draws into RGB pixels, row by row, each cell a square `scale` pixels across.
+pub struct Raster {
+    scale: usize,
+    background: [u8; 3],
+    live: [u8; 3],
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Raster {
+    pub fn new(scale: usize, background: [u8; 3], live: [u8; 3]) -> Self {
+        Raster {
+            scale: scale.max(1),
+            background,
+            live,
+            width: 0,
+            height: 0,
+            pixels: Vec::new(),
+        }
+    }
+
+    // The size of the image in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    // The pixels with an opaque alpha channel added, the layout game engines and GPU textures
+    // usually want.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels
+            .chunks(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+            .collect()
+    }
+}
+
+impl RenderTarget for Raster {
+    fn begin_frame(&mut self, width: usize, height: usize) {
+        self.width = width * self.scale;
+        self.height = height * self.scale;
+        self.pixels.clear();
+        for _ in 0..self.width * self.height {
+            self.pixels.extend_from_slice(&self.background);
+        }
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        let live = self.live;
+        self.draw_state_run(x, y, length, 1, live);
+    }
+
+    fn draw_state_run(&mut self, x: usize, y: usize, length: usize, _state: u8, color: [u8; 3]) {
+        let (left, right) = (x * self.scale, (x + length) * self.scale);
+        for py in y * self.scale..(y + 1) * self.scale {
+            let row = &mut self.pixels[py * self.width * 3..(py + 1) * self.width * 3];
+            for pixel in row[left * 3..right * 3].chunks_mut(3) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+// Draws an SVG image, with each run of live cells as one rectangle.
+pub struct Svg {
+    scale: usize,
+    background: [u8; 3],
+    live: [u8; 3],
+    legend: Option<Palette>,
+    // Where the legend starts, below the cells.
+    legend_top: usize,
+    text: String,
+}
+
+// The height of a line of the legend, and the least width that leaves its names room.
+const LEGEND_ROW: usize = 18;
+const LEGEND_WIDTH: usize = 160;
+
+impl Svg {
+    pub fn new(scale: usize, background: [u8; 3], live: [u8; 3]) -> Self {
+        Svg {
+            scale: scale.max(1),
+            background,
+            live,
+            legend: None,
+            legend_top: 0,
+            text: String::new(),
+        }
+    }
+
+    // List each of the palette's states under the image, with its color and name. The background
+    // state is left out, since the image has its own background.
+    pub fn set_legend(&mut self, palette: &Palette) {
+        self.legend = Some(palette.clone());
+    }
+
+    // The finished document.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl RenderTarget for Svg {
+    fn begin_frame(&mut self, width: usize, height: usize) {
+        let (mut width, mut height) = (width * self.scale, height * self.scale);
+        self.legend_top = height;
+        if let Some(legend) = &self.legend {
+            width = width.max(LEGEND_WIDTH);
+            height += legend.len().saturating_sub(1) * LEGEND_ROW + LEGEND_ROW / 2;
+        }
+        self.text.clear();
+        let _ = writeln!(
+            self.text,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">"
+        );
+        let _ = writeln!(
+            self.text,
+            "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>",
+            hex(self.background)
+        );
+        let _ = writeln!(self.text, "<g fill=\"{}\">", hex(self.live));
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        let _ = writeln!(
+            self.text,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+            x * self.scale,
+            y * self.scale,
+            length * self.scale,
+            self.scale
+        );
+    }
+
+    fn draw_state_run(&mut self, x: usize, y: usize, length: usize, _state: u8, color: [u8; 3]) {
+        let _ = writeln!(
+            self.text,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            x * self.scale,
+            y * self.scale,
+            length * self.scale,
+            self.scale,
+            hex(color)
+        );
+    }
+
+    fn end_frame(&mut self) {
+        self.text.push_str("</g>\n");
+        if let Some(legend) = &self.legend {
+            let _ = writeln!(
+                self.text,
+                "<g font-family=\"sans-serif\" font-size=\"12\" fill=\"{}\">",
+                hex(self.live)
+            );
+            for (state, name, color) in legend.entries().skip(1) {
+                let top = self.legend_top + LEGEND_ROW / 2 + usize::from(state - 1) * LEGEND_ROW;
+                let _ = writeln!(
+                    self.text,
+                    "<rect x=\"4\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\" \
+                     stroke=\"{}\"/>",
+                    top + 2,
+                    hex(color),
+                    hex(self.live)
+                );
+                let _ = writeln!(
+                    self.text,
+                    "<text x=\"22\" y=\"{}\">{state}: {}</text>",
+                    top + 13,
+                    escape(name)
+                );
+            }
+            self.text.push_str("</g>\n");
+        }
+        self.text.push_str("</svg>\n");
+    }
+}
+
+// Text safe to put in an SVG element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn hex([red, green, blue]: [u8; 3]) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+// Draws text for a terminal, one line per row of cells.
+pub struct Text {
+    live: char,
+    dead: char,
+    rows: Vec<Vec<char>>,
+}
+
+impl Text {
+    pub fn new(live: char, dead: char) -> Self {
+        Text {
+            live,
+            dead,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.rows.iter().map(|row| row.iter().collect())
+    }
+}
+
+impl RenderTarget for Text {
+    fn begin_frame(&mut self, width: usize, height: usize) {
+        self.rows = vec![vec![self.dead; width]; height];
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        self.rows[y][x..x + length].fill(self.live);
+    }
+
+    // States are drawn as their digits, or letters from 10 on, up to 35.
+    fn draw_state_run(&mut self, x: usize, y: usize, length: usize, state: u8, _color: [u8; 3]) {
+        let symbol = char::from_digit(u32::from(state), 36).unwrap_or(self.live);
+        self.rows[y][x..x + length].fill(symbol);
+    }
+}
+
+// Draws text for a terminal with two rows of cells to each line, using the half-block characters,
+// so cells come out about square in most fonts and twice as much of the board fits on the screen.
+pub struct Blocks {
+    rows: Vec<Vec<bool>>,
+}
+
+impl Blocks {
+    pub fn new() -> Self {
+        Blocks { rows: Vec::new() }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.rows.chunks(2).map(|pair| {
+            let bottom = pair.get(1);
+            (0..pair[0].len())
+                .map(|x| match (pair[0][x], bottom.is_some_and(|row| row[x])) {
+                    (false, false) => ' ',
+                    (true, false) => '\u{2580}',
+                    (false, true) => '\u{2584}',
+                    (true, true) => '\u{2588}',
+                })
+                .collect()
+        })
+    }
+}
+
+impl Default for Blocks {
+    fn default() -> Self {
+        Blocks::new()
+    }
+}
+
+impl RenderTarget for Blocks {
+    fn begin_frame(&mut self, width: usize, height: usize) {
+        self.rows = vec![vec![false; width]; height];
+    }
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        self.rows[y][x..x + length].fill(true);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Records the calls it gets.
+    #[derive(Default)]
+    struct Calls(Vec<String>);
+
+    impl RenderTarget for Calls {
+        fn begin_frame(&mut self, width: usize, height: usize) {
+            self.0.push(format!("begin {width}x{height}"));
+        }
+
+        fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+            self.0.push(format!("run {x},{y} {length}"));
+        }
+
+        fn draw_cell(&mut self, x: usize, y: usize) {
+            self.0.push(format!("cell {x},{y}"));
+        }
+
+        fn end_frame(&mut self) {
+            self.0.push("end".to_string());
+        }
+    }
+
+    fn board() -> Board {
+        "O..OOO\n.OO...\n......".parse().unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let mut calls = Calls::default();
+        render(&board(), Rect::new(0, 0, 6, 3), &mut calls);
+        assert_eq!(
+            vec!["begin 6x3", "cell 0,0", "run 3,0 3", "run 1,1 2", "end"],
+            calls.0
+        );
+
+        // Runs are cut off at the region's edges, and the region at the board's.
+        let mut calls = Calls::default();
+        render(&board(), Rect::new(2, 0, 10, 2), &mut calls);
+        assert_eq!(vec!["begin 4x2", "run 1,0 3", "cell 0,1", "end"], calls.0);
+    }
+
+    #[test]
+    fn test_targets() {
+        let mut text = Text::new('#', '.');
+        render(&board(), Rect::new(0, 0, 6, 2), &mut text);
+        assert_eq!(vec!["#..###", ".##..."], text.lines().collect::<Vec<_>>());
+
+        let mut blocks = Blocks::new();
+        render(&board(), Rect::new(0, 0, 6, 3), &mut blocks);
+        assert_eq!(
+            vec!["\u{2580}\u{2584}\u{2584}\u{2580}\u{2580}\u{2580}", "      "],
+            blocks.lines().collect::<Vec<_>>()
+        );
+
+        let mut raster = Raster::new(2, [0; 3], [255; 3]);
+        render(&board(), Rect::new(0, 0, 2, 2), &mut raster);
+        assert_eq!((4, 4), (raster.width(), raster.height()));
+        let lit: Vec<bool> = raster.pixels().chunks(3).map(|p| p[0] == 255).collect();
+        let expected = [
+            true, true, false, false, //
+            true, true, false, false, //
+            false, false, true, true, //
+            false, false, true, true,
+        ];
+        assert_eq!(expected.as_slice(), lit.as_slice());
+        assert_eq!([255, 255, 255, 255, 0, 0], raster.to_rgba()[4..10]);
+
+        let mut svg = Svg::new(10, [255; 3], [0x20; 3]);
+        render(&board(), Rect::new(0, 0, 6, 3), &mut svg);
+        assert!(svg
+            .as_str()
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\""));
+        assert!(svg
+            .as_str()
+            .contains("<rect x=\"30\" y=\"0\" width=\"30\" height=\"10\"/>"));
+        assert!(svg.as_str().ends_with("</g>\n</svg>\n"));
+    }
+
+    #[test]
+    fn test_render_ages() {
+        // A block, which never changes, next to a blinker, whose ends come and go.
+        let mut game = Game::new(12, 6);
+        game.set([(1, 1), (2, 1), (1, 2), (2, 2), (7, 2), (7, 3), (7, 4)])
+            .unwrap();
+        game.track_ages(true);
+        game.run(5);
+        let mut text = Text::new('#', '.');
+        render_ages(
+            &game,
+            Rect::new(0, 0, 12, 5),
+            &Palette::gradient(4),
+            &mut text,
+        );
+        assert_eq!(
+            vec![
+                "............",
+                ".33.........",
+                ".33.........",
+                "......131...",
+                "............"
+            ],
+            text.lines().collect::<Vec<_>>()
+        );
+        assert_eq!((6, 1, 0), (game.age(1, 1), game.age(6, 3), game.age(7, 2)));
+    }
+
+    #[test]
+    fn test_render_states() {
+        // A Wireworld wire with a signal on it.
+        let pattern = Pattern::from_fn(5, 2, |x, y| [3, 2, 1, 3, 0][x] * (y == 0) as u8);
+        let palette = Palette::wireworld();
+        let mut text = Text::new('#', '.');
+        render_states(&pattern, Rect::new(0, 0, 5, 2), &palette, &mut text);
+        assert_eq!(vec!["3213.", "....."], text.lines().collect::<Vec<_>>());
+
+        let mut raster = Raster::new(1, [0; 3], [255; 3]);
+        render_states(&pattern, Rect::new(1, 0, 4, 1), &palette, &mut raster);
+        let pixels: Vec<&[u8]> = raster.pixels().chunks(3).collect();
+        assert_eq!(
+            vec![
+                &[0xff, 0xff, 0xff],
+                &[0x00, 0x80, 0xff],
+                &[0xff, 0x80, 0x00],
+                &[0, 0, 0]
+            ],
+            pixels
+        );
+
+        let mut svg = Svg::new(10, [0; 3], [255; 3]);
+        svg.set_legend(&palette);
+        render_states(&pattern, Rect::new(0, 0, 5, 2), &palette, &mut svg);
+        let svg = svg.as_str();
+        assert!(svg.contains("width=\"160\" height=\"83\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ff8000\"/>"));
+        assert!(svg.contains(">1: electron head</text>"));
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use crate::Game;
+
+// A summary of a finished run, from Game::run() or Game::run_until_stable(), so scripts don't have
+// to watch every generation to find out what happened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RunReport {
+    // The generation the run started from, and how many generations it ran.
+    pub start: usize,
+    pub generations: usize,
+    pub elapsed: Duration,
+    // The most live cells at any point in the run, counting the board it started with.
+    pub peak_population: usize,
+    pub final_population: usize,
+    // The first generation with no live cells, if every cell died.
+    pub extinct_at: Option<usize>,
+    // The generation the population settled from and the period it settled into, when
+    // run_until_stable() found one. Plain runs don't look for them.
+    pub settled_at: Option<usize>,
+    pub period: Option<usize>,
+    // The game's cancel handle stopped the run before it was done.
+    pub cancelled: bool,
+}
+
+// Builds a report as a run goes.
+pub(crate) struct Recorder {
+    started: Instant,
+    report: RunReport,
+}
+
+impl Recorder {
+    pub(crate) fn new(game: &Game) -> Self {
+        let population = game.board().population();
+        let generation = game.stats().generation;
+        Recorder {
+            started: Instant::now(),
+            report: RunReport {
+                start: generation,
+                generations: 0,
+                elapsed: Duration::ZERO,
+                peak_population: population,
+                final_population: population,
+                extinct_at: (population == 0).then_some(generation),
+                settled_at: None,
+                period: None,
+                cancelled: false,
+            },
+        }
+    }
+
+    pub(crate) fn population(&self) -> usize {
+        self.report.final_population
+    }
+
+    // Note the population at a generation of the run.
+    pub(crate) fn observe(&mut self, generation: usize, population: usize) {
+        let report = &mut self.report;
+        report.generations = generation - report.start;
+        report.peak_population = report.peak_population.max(population);
+        report.final_population = population;
+        if population == 0 && report.extinct_at.is_none() {
+            report.extinct_at = Some(generation);
+        }
+    }
+
+    pub(crate) fn finish(mut self, game: &Game) -> RunReport {
+        self.report.elapsed = self.started.elapsed();
+        self.report.cancelled = game.is_cancelled();
+        self.report
+    }
+
+    pub(crate) fn settled(&mut self, settled_at: usize, period: usize) {
+        self.report.settled_at = Some(settled_at);
+        self.report.period = Some(period);
+    }
+}
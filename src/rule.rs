@@ -0,0 +1,505 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// The live/dead states of the eight cells surrounding a cell, one bit per neighbor. Bits are
+// ordered top-left, top, top-right, left, right, bottom-left, bottom, bottom-right, with the
+// top-left neighbor in the lowest bit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Neighborhood(u8);
+
+// The (dx, dy) offset of each neighbor, in bit order.
+pub(crate) const OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl Neighborhood {
+    pub fn from_bits(bits: u8) -> Self {
+        Neighborhood(bits)
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    // How many of the neighbors are live.
+    pub fn count(&self) -> u8 {
+        self.0.count_ones() as u8
+    }
+
+    // Whether the neighbor at offset (dx, dy) is live. Both offsets must be in -1..=1 and not both
+    // zero, since a cell isn't its own neighbor.
+    pub fn get(&self, dx: isize, dy: isize) -> bool {
+        let bit = OFFSETS
+            .iter()
+            .position(|&offset| offset == (dx, dy))
+            .expect("neighbor offset out of range");
+        self.0 & (1 << bit) != 0
+    }
+}
+
+// A rule decides whether a cell is live in the next generation from its current state and the
+// states of its neighbors. Anything implementing it can drive a Game, including plain closures.
+// Rules must be pure: the same inputs always give the same answer, which lets backends precompute
+// a rule's whole transition table up front.
+pub trait Rule: Send + Sync {
+    fn transition(&self, alive: bool, neighborhood: Neighborhood) -> bool;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(bool, Neighborhood) -> bool + Send + Sync,
+{
+    fn transition(&self, alive: bool, neighborhood: Neighborhood) -> bool {
+        self(alive, neighborhood)
+    }
+}
+
+// A "Life-like" rule, where only the number of live neighbors matters. A dead cell is born when
+// its neighbor count is in the birth set and a live cell survives when its count is in the
+// survival set; everything else dies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LifeLike {
+    // Bit n is set when n neighbors are in the set.
+    birth: u16,
+    survival: u16,
+}
+
+impl LifeLike {
+    // Conway's Game of Life: B3/S23.
+    pub const CONWAY: LifeLike = LifeLike {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    // Build a rule from the neighbor counts that cause birth and survival. Counts above 8 are
+    // ignored since a cell can't have more than eight neighbors.
+    pub fn new(birth: &[u8], survival: &[u8]) -> Self {
+        LifeLike {
+            birth: to_mask(birth),
+            survival: to_mask(survival),
+        }
+    }
+
+    // Read a B/S rulestring, saying just what's wrong with it if it can't be read. This is the
+    // same as parsing it with str::parse.
+    pub fn parse(rule: &str) -> Result<Self, RuleStringError> {
+        rule.parse()
+    }
+
+    // The rule's rulestring in the one form every way of writing it comes out as: birth before
+    // survival, capital letters, and counts in ascending order. Two rulestrings name the same rule
+    // exactly when their canonical strings match, so "s32/b3" and "23/3" both give "B3/S23".
+    pub fn canonical_string(&self) -> String {
+        self.to_string()
+    }
+
+    // Recover the Life-like form of any rule that only depends on how many neighbors are live,
+    // or None for rules that look at where the live neighbors are.
+    pub fn from_rule(rule: &dyn Rule) -> Option<Self> {
+        RuleTable::new(rule).life_like()
+    }
+
+    pub fn births_on(&self, count: u8) -> bool {
+        count <= 8 && self.birth & (1 << count) != 0
+    }
+
+    pub fn survives_on(&self, count: u8) -> bool {
+        count <= 8 && self.survival & (1 << count) != 0
+    }
+
+    // Add a neighbor count to the birth set, or take it out. Counts above 8 are ignored.
+    pub fn set_birth(&mut self, count: u8, born: bool) {
+        set_bit(&mut self.birth, count, born);
+    }
+
+    // Add a neighbor count to the survival set, or take it out. Counts above 8 are ignored.
+    pub fn set_survival(&mut self, count: u8, survives: bool) {
+        set_bit(&mut self.survival, count, survives);
+    }
+}
+
+fn set_bit(mask: &mut u16, count: u8, on: bool) {
+    if count > 8 {
+        return;
+    }
+    if on {
+        *mask |= 1 << count;
+    } else {
+        *mask &= !(1 << count);
+    }
+}
+
+// Life-like rules print as the usual B/S rulestring, such as B3/S23 for Conway's Life.
+impl fmt::Display for LifeLike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in (0..=8).filter(|&count| self.births_on(count)) {
+            write!(f, "{count}")?;
+        }
+        write!(f, "/S")?;
+        for count in (0..=8).filter(|&count| self.survives_on(count)) {
+            write!(f, "{count}")?;
+        }
+        Ok(())
+    }
+}
+
+// Parse a B/S rulestring such as "B3/S23" in either case and either order. Golly's older S/B form
+// without letters, such as "23/3", is read as survival then birth.
+impl FromStr for LifeLike {
+    type Err = RuleStringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |problem| RuleStringError {
+            rule: s.to_string(),
+            problem,
+        };
+        let trimmed = s.trim();
+        let leading = s.chars().count() - s.trim_start().chars().count();
+        if let Some((index, found)) = trimmed
+            .chars()
+            .enumerate()
+            .find(|&(_, c)| !(c.is_ascii_digit() || "/BbSs".contains(c)))
+        {
+            return Err(error(RuleProblem::UnknownCharacter {
+                position: leading + index,
+                found,
+            }));
+        }
+        let (first, second) = trimmed
+            .split_once('/')
+            .ok_or_else(|| error(RuleProblem::Form))?;
+
+        let upper = (first.to_ascii_uppercase(), second.to_ascii_uppercase());
+        let (birth, survival) = match (upper.0.split_at_checked(1), upper.1.split_at_checked(1)) {
+            (Some(("B", birth)), Some(("S", survival)))
+            | (Some(("S", survival)), Some(("B", birth))) => (birth, survival),
+            _ if !trimmed.contains(|c: char| c.is_ascii_alphabetic()) => (second, first),
+            _ => return Err(error(RuleProblem::Form)),
+        };
+        let counts = |digits: &str, birth: bool| -> Result<u16, RuleStringError> {
+            let mut mask = 0;
+            for c in digits.chars() {
+                let count = c.to_digit(10).ok_or_else(|| error(RuleProblem::Form))? as u8;
+                if count > 8 {
+                    return Err(error(RuleProblem::NoSuchCount { count }));
+                }
+                if mask & (1 << count) != 0 {
+                    return Err(error(RuleProblem::DuplicateCount { count, birth }));
+                }
+                mask |= 1 << count;
+            }
+            Ok(mask)
+        };
+        Ok(LifeLike {
+            birth: counts(birth, true)?,
+            survival: counts(survival, false)?,
+        })
+    }
+}
+
+// A rulestring that couldn't be read, and what's wrong with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleStringError {
+    pub rule: String,
+    pub problem: RuleProblem,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleProblem {
+    // The rulestring isn't in the B/S form, such as a missing slash or two birth sets.
+    Form,
+    // A character that doesn't belong in a rulestring, at the given position counting characters
+    // from 0.
+    UnknownCharacter { position: usize, found: char },
+    // A neighbor count listed twice in the birth set, or twice in the survival set.
+    DuplicateCount { count: u8, birth: bool },
+    // A neighbor count higher than the eight neighbors a cell has.
+    NoSuchCount { count: u8 },
+}
+
+impl fmt::Display for RuleStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rulestring {:?}: ", self.rule)?;
+        match self.problem {
+            RuleProblem::Form => write!(f, "expected the form B3/S23"),
+            RuleProblem::UnknownCharacter { position, found } => write!(
+                f,
+                "unexpected {found:?} at position {position}, only B, S, /, and the digits 0 to \
+                 8 belong in a rulestring"
+            ),
+            RuleProblem::DuplicateCount { count, birth } => write!(
+                f,
+                "{count} is listed twice in the {} counts",
+                if birth { "birth" } else { "survival" }
+            ),
+            RuleProblem::NoSuchCount { count } => write!(
+                f,
+                "a cell has eight neighbors, so it can never have {count} live ones"
+            ),
+        }
+    }
+}
+
+impl Error for RuleStringError {}
+
+impl Default for LifeLike {
+    fn default() -> Self {
+        LifeLike::CONWAY
+    }
+}
+
+impl Rule for LifeLike {
+    fn transition(&self, alive: bool, neighborhood: Neighborhood) -> bool {
+        let count = neighborhood.count();
+        if alive {
+            self.survives_on(count)
+        } else {
+            self.births_on(count)
+        }
+    }
+}
+
+// Every answer a rule can give, precomputed for all 2 * 256 combinations of cell state and
+// neighborhood, so backends never have to call back into the rule.
+#[derive(Clone, Debug)]
+pub(crate) struct RuleTable {
+    next: [bool; 512],
+
+    // When the rule only depends on the number of live neighbors, its answer for a dead and a
+    // live cell with each count, indexed as [alive][count].
+    totalistic: Option<[[bool; 9]; 2]>,
+}
+
+impl RuleTable {
+    pub(crate) fn new(rule: &dyn Rule) -> Self {
+        let mut next = [false; 512];
+        for (index, next) in next.iter_mut().enumerate() {
+            *next = rule.transition(index >= 256, Neighborhood::from_bits(index as u8));
+        }
+
+        let mut totalistic = [[false; 9]; 2];
+        let mut seen = [[false; 9]; 2];
+        let mut is_totalistic = true;
+        for (index, &next) in next.iter().enumerate() {
+            let alive = index / 256;
+            let count = (index as u8).count_ones() as usize;
+            if !seen[alive][count] {
+                seen[alive][count] = true;
+                totalistic[alive][count] = next;
+            } else if totalistic[alive][count] != next {
+                is_totalistic = false;
+                break;
+            }
+        }
+
+        RuleTable {
+            next,
+            totalistic: is_totalistic.then_some(totalistic),
+        }
+    }
+
+    pub(crate) fn next(&self, alive: bool, neighborhood: Neighborhood) -> bool {
+        self.next[(alive as usize) << 8 | neighborhood.bits() as usize]
+    }
+
+    pub(crate) fn totalistic(&self) -> Option<&[[bool; 9]; 2]> {
+        self.totalistic.as_ref()
+    }
+
+    pub(crate) fn life_like(&self) -> Option<LifeLike> {
+        let [dead, live] = self.totalistic?;
+        let counts = |next: [bool; 9]| {
+            (0..=8)
+                .filter(|&count| next[count as usize])
+                .collect::<Vec<u8>>()
+        };
+        Some(LifeLike::new(&counts(dead), &counts(live)))
+    }
+}
+
+fn to_mask(counts: &[u8]) -> u16 {
+    counts
+        .iter()
+        .filter(|&&count| count <= 8)
+        .fold(0, |mask, &count| mask | 1 << count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_neighborhood() {
+        let neighborhood = Neighborhood::from_bits(0b1000_0011);
+        assert_eq!(3, neighborhood.count());
+        assert!(neighborhood.get(-1, -1));
+        assert!(neighborhood.get(0, -1));
+        assert!(!neighborhood.get(1, -1));
+        assert!(neighborhood.get(1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_neighborhood_self_offset() {
+        Neighborhood::default().get(0, 0);
+    }
+
+    #[test]
+    fn test_conway() {
+        let conway = LifeLike::CONWAY;
+        assert_eq!(LifeLike::new(&[3], &[2, 3]), conway);
+        for count in 0..=8 {
+            let neighborhood = Neighborhood::from_bits((1u16 << count).wrapping_sub(1) as u8);
+            assert_eq!(count == 3, conway.transition(false, neighborhood));
+            assert_eq!(
+                count == 2 || count == 3,
+                conway.transition(true, neighborhood)
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_birth_and_survival() {
+        let mut rule = LifeLike::CONWAY;
+        rule.set_birth(6, true);
+        rule.set_survival(2, false);
+        rule.set_survival(9, true);
+        assert_eq!("B36/S3", rule.to_string());
+        rule.set_birth(6, false);
+        rule.set_survival(2, true);
+        assert_eq!(LifeLike::CONWAY, rule);
+    }
+
+    #[test]
+    fn test_life_like_display() {
+        assert_eq!("B3/S23", LifeLike::CONWAY.to_string());
+        assert_eq!("B36/S23", LifeLike::new(&[6, 3], &[2, 3]).to_string());
+        assert_eq!("B/S", LifeLike::new(&[], &[]).to_string());
+    }
+
+    #[test]
+    fn test_life_like_parse() {
+        assert_eq!(Ok(LifeLike::CONWAY), "B3/S23".parse());
+        assert_eq!(Ok(LifeLike::CONWAY), "s23/b3".parse());
+        assert_eq!(Ok(LifeLike::CONWAY), "23/3".parse());
+        assert_eq!(Ok(LifeLike::new(&[], &[])), "B/S".parse());
+        assert_eq!(
+            Ok(LifeLike::new(&[3, 6], &[2, 3])),
+            "B36/S23".parse::<LifeLike>()
+        );
+        for invalid in ["", "B3", "B3/B23", "3/S23"] {
+            assert_eq!(
+                Err(RuleStringError {
+                    rule: invalid.to_string(),
+                    problem: RuleProblem::Form
+                }),
+                invalid.parse::<LifeLike>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_life_like_problems() {
+        let problem = |rule: &str| LifeLike::parse(rule).unwrap_err().problem;
+        assert_eq!(
+            RuleProblem::UnknownCharacter {
+                position: 0,
+                found: 'X'
+            },
+            problem("X3/S23")
+        );
+        assert_eq!(
+            RuleProblem::UnknownCharacter {
+                position: 4,
+                found: ' '
+            },
+            problem(" B3/ S23")
+        );
+        assert_eq!(
+            RuleProblem::UnknownCharacter {
+                position: 0,
+                found: 'W'
+            },
+            problem("WireWorld")
+        );
+        assert_eq!(RuleProblem::NoSuchCount { count: 9 }, problem("B39/S23"));
+        assert_eq!(
+            RuleProblem::DuplicateCount {
+                count: 3,
+                birth: false
+            },
+            problem("B3/S233")
+        );
+        assert_eq!(
+            "invalid rulestring \"B33/S23\": 3 is listed twice in the birth counts",
+            LifeLike::parse("B33/S23").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_canonical_string() {
+        for rule in ["B3/S23", "s32/b3", "23/3", " b3/S32 "] {
+            assert_eq!("B3/S23", LifeLike::parse(rule).unwrap().canonical_string());
+        }
+        assert_eq!("B/S", LifeLike::parse("S/B").unwrap().canonical_string());
+    }
+
+    #[test]
+    fn test_closure_rule() {
+        let rule = |alive: bool, neighborhood: Neighborhood| !alive && neighborhood.get(0, -1);
+        assert!(rule.transition(false, Neighborhood::from_bits(0b10)));
+        assert!(!rule.transition(true, Neighborhood::from_bits(0b10)));
+        assert!(!rule.transition(false, Neighborhood::from_bits(0b1)));
+    }
+
+    #[test]
+    fn test_rule_table() {
+        let table = RuleTable::new(&LifeLike::CONWAY);
+        assert!(table.next(false, Neighborhood::from_bits(0b111)));
+        assert!(!table.next(false, Neighborhood::from_bits(0b11)));
+        assert!(table.next(true, Neighborhood::from_bits(0b11)));
+
+        let totalistic = table.totalistic().unwrap();
+        assert_eq!(
+            [false, false, false, true, false, false, false, false, false],
+            totalistic[0]
+        );
+        assert_eq!(
+            [false, false, true, true, false, false, false, false, false],
+            totalistic[1]
+        );
+    }
+
+    #[test]
+    fn test_life_like_from_rule() {
+        let high_life = LifeLike::new(&[3, 6], &[2, 3]);
+        assert_eq!(Some(high_life), LifeLike::from_rule(&high_life));
+
+        let counting = |alive: bool, neighborhood: Neighborhood| alive || neighborhood.count() == 1;
+        assert_eq!(
+            Some(LifeLike::new(&[1], &[0, 1, 2, 3, 4, 5, 6, 7, 8])),
+            LifeLike::from_rule(&counting)
+        );
+
+        let positional = |_: bool, neighborhood: Neighborhood| neighborhood.get(0, 1);
+        assert_eq!(None, LifeLike::from_rule(&positional));
+    }
+
+    #[test]
+    fn test_rule_table_not_totalistic() {
+        let rule = |_: bool, neighborhood: Neighborhood| neighborhood.get(-1, 0);
+        let table = RuleTable::new(&rule);
+        assert!(table.totalistic().is_none());
+        assert!(table.next(false, Neighborhood::from_bits(1 << 3)));
+        assert!(!table.next(false, Neighborhood::from_bits(1 << 4)));
+    }
+}
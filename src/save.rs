@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::de::{self, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Game, LifeLike, Pattern, Topology};
+
+// Games saved to pick up where they left off: the generation, the rule, the topology, and both
+// boards, so the next generation comes out the same as it would have. Everything else a game can
+// be set up with, such as its backend, zones, and history, is left out, and a loaded game starts
+// with the defaults for those.
+//
+// Boards are written as RLE, which keeps saved games small and readable by anything else that reads
+// patterns. Rules are written as rulestrings, so only games running Life-like rules can be saved.
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    generation: usize,
+    rule: LifeLike,
+    topology: Topology,
+    previous: Board,
+    current: Board,
+}
+
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rule = self
+            .life_like()
+            .ok_or_else(|| ser::Error::custom("only games with Life-like rules can be saved"))?;
+        Saved {
+            generation: self.generation,
+            rule,
+            topology: self.topology,
+            previous: Board::clone(&self.previous),
+            current: Board::clone(&self.current),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Saved {
+            generation,
+            rule,
+            topology,
+            mut previous,
+            current,
+        } = Saved::deserialize(deserializer)?;
+        let sizes = [&previous, &current].map(|board| (board.x_size(), board.y_size()));
+        if sizes[0] != sizes[1] {
+            return Err(de::Error::custom(format!(
+                "the previous board is {}x{} but the current one is {}x{}",
+                sizes[0].0, sizes[0].1, sizes[1].0, sizes[1].1
+            )));
+        }
+
+        let mut game = Game::from(current);
+        game.set_rule(rule);
+        game.set_topology(topology);
+        previous.set_topology(topology);
+        game.previous = Arc::new(previous);
+        game.generation = generation;
+        Ok(game)
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Pattern::from(self).to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let pattern: Pattern = text.parse().map_err(de::Error::custom)?;
+        Ok(pattern.to_board())
+    }
+}
+
+impl Serialize for LifeLike {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for LifeLike {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        LifeLike::parse(&text).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Topology {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Topology {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Game {
+    // Write the game to a file as TOML, in the form described on Saved.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        let text = toml::to_string(self).map_err(|err| SaveError::Format(err.to_string()))?;
+        fs::write(path, text).map_err(SaveError::Io)
+    }
+
+    // Read a game written by save_to_file().
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Game, SaveError> {
+        let text = fs::read_to_string(path).map_err(SaveError::Io)?;
+        toml::from_str(&text).map_err(|err| SaveError::Format(err.to_string()))
+    }
+}
+
+// Why a game couldn't be saved or loaded.
+#[derive(Debug)]
+pub enum SaveError {
+    // The file couldn't be read or written.
+    Io(io::Error),
+    // The game couldn't be written as a saved game, or the file doesn't hold one.
+    Format(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "{err}"),
+            SaveError::Format(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Neighborhood;
+
+    #[test]
+    fn test_round_trip() {
+        let mut game = Game::random(13, 9, 0.4, 17);
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        game.set_topology(Topology::Mirror);
+        game.run(5);
+
+        let text = toml::to_string(&game).unwrap();
+        let mut loaded: Game = toml::from_str(&text).unwrap();
+        assert_eq!(game.board(), loaded.board());
+        assert_eq!(game.stats().generation, loaded.stats().generation);
+        assert_eq!(game.life_like(), loaded.life_like());
+        assert_eq!(Topology::Mirror, loaded.topology());
+        // Both games carry on the same way.
+        assert_eq!(game.iterate(), loaded.iterate());
+        assert_eq!(game.board(), loaded.board());
+    }
+
+    #[test]
+    fn test_files() {
+        let path = std::env::temp_dir().join(format!("gol-save-{}.toml", std::process::id()));
+        let game = Game::random(8, 8, 0.5, 2);
+        game.save_to_file(&path).unwrap();
+        let loaded = Game::load_from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(game.board(), loaded.board());
+
+        assert!(matches!(Game::load_from_file(&path), Err(SaveError::Io(_))));
+        let mut custom = Game::new(4, 4);
+        // Cells copying their left neighbor don't only care how many neighbors are live.
+        custom.set_rule(|_, neighborhood: Neighborhood| neighborhood.get(-1, 0));
+        assert!(matches!(
+            custom.save_to_file(&path),
+            Err(SaveError::Format(_))
+        ));
+        assert!(toml::from_str::<Game>("generation = 0\nrule = \"B3/S23\"").is_err());
+    }
+}
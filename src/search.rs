@@ -0,0 +1,124 @@
+use crate::{Board, Pattern, Point};
+
+// One of the eight ways to turn and mirror a pattern. Rotations are clockwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // Mirrored left to right.
+    FlipHorizontal,
+    // Mirrored top to bottom.
+    FlipVertical,
+    // Mirrored across the diagonal from the top-left corner, swapping x and y.
+    Transpose,
+    // Mirrored across the diagonal from the top-right corner.
+    AntiTranspose,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+        Orientation::Transpose,
+        Orientation::AntiTranspose,
+    ];
+
+    // Whether a width x height rectangle comes out height x width.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90
+                | Orientation::Rotate270
+                | Orientation::Transpose
+                | Orientation::AntiTranspose
+        )
+    }
+
+    // The cell of a width x height rectangle that lands at (x, y) once it's been oriented.
+    pub(crate) fn source(&self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        let (right, bottom) = (width - 1, height - 1);
+        match self {
+            Orientation::Identity => (x, y),
+            Orientation::Rotate90 => (y, bottom - x),
+            Orientation::Rotate180 => (right - x, bottom - y),
+            Orientation::Rotate270 => (right - y, x),
+            Orientation::FlipHorizontal => (right - x, y),
+            Orientation::FlipVertical => (x, bottom - y),
+            Orientation::Transpose => (y, x),
+            Orientation::AntiTranspose => (right - y, bottom - x),
+        }
+    }
+}
+
+// Every top-left corner where the pattern sits on the board in each of the given orientations,
+// with the orientation it was found in. A match has to agree on every cell of the pattern's
+// rectangle, dead ones included, and can wrap around the board's edges. Orientations that give the
+// same cells as an earlier one are skipped, so a symmetric pattern is only found once per spot.
+pub(crate) fn find(
+    board: &Board,
+    pattern: &Pattern,
+    orientations: &[Orientation],
+) -> Vec<(Point, Orientation)> {
+    let mut tried: Vec<Pattern> = Vec::new();
+    let mut found = Vec::new();
+    for &orientation in orientations {
+        let oriented = pattern.oriented(orientation);
+        if tried.contains(&oriented) {
+            continue;
+        }
+        for x in 0..board.x_size() {
+            for y in 0..board.y_size() {
+                if matches_at(board, &oriented, x, y) {
+                    found.push((Point::new(x, y), orientation));
+                }
+            }
+        }
+        tried.push(oriented);
+    }
+    found
+}
+
+fn matches_at(board: &Board, pattern: &Pattern, x: usize, y: usize) -> bool {
+    let (x_size, y_size) = (board.x_size(), board.y_size());
+    if pattern.width() > x_size || pattern.height() > y_size {
+        return false;
+    }
+    (0..pattern.width()).all(|px| {
+        (0..pattern.height())
+            .all(|py| board[((x + px) % x_size, (y + py) % y_size)] == pattern.is_alive(px, py))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_orientations() {
+        let pattern = Pattern::from_fn(3, 2, |x, y| (x == 0 || y == 1) as u8);
+        let shapes: Vec<String> = Orientation::ALL
+            .iter()
+            .map(|&orientation| pattern.oriented(orientation).to_board().to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "O..\nOOO\n",
+                "OO\nO.\nO.\n",
+                "OOO\n..O\n",
+                ".O\n.O\nOO\n",
+                "..O\nOOO\n",
+                "OOO\nO..\n",
+                "OO\n.O\n.O\n",
+                "O.\nO.\nOO\n",
+            ],
+            shapes
+        );
+    }
+}
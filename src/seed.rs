@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek};
+use std::path::PathBuf;
+
+use crate::rng::Rng;
+use crate::{soup, Board, Pattern, PatternError, Symmetry};
+
+// Somewhere a game's first board comes from. Games, the command line and the GUI's setup screen all
+// take their starting cells from one of these, so a new kind of starting point only has to be
+// written once. Closures taking (x, y) are sources too, with a cell live wherever they return true.
+pub trait SeedSource {
+    // Build a board of the given size. A source that doesn't fit, like a pattern bigger than the
+    // board, gets a board big enough to hold it instead.
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError>;
+}
+
+impl<F: FnMut(usize, usize) -> bool> SeedSource for F {
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError> {
+        Ok(Board::from_fn(x_size, y_size, self))
+    }
+}
+
+// A pattern in the middle of the board.
+impl SeedSource for Pattern {
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError> {
+        Ok(centered(&self.to_board(), x_size, y_size))
+    }
+}
+
+// A random soup, where each cell is live with probability `density`. The same seed always gives the
+// same soup.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Soup {
+    density: f64,
+    seed: u64,
+    symmetry: Symmetry,
+}
+
+impl Soup {
+    pub fn new(density: f64, seed: u64) -> Self {
+        Soup {
+            density,
+            seed,
+            symmetry: Symmetry::C1,
+        }
+    }
+
+    // Build the soup with a symmetry. A symmetric soup needs a square, so on a board that isn't one
+    // it's the biggest square that fits, in the middle.
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) {
+        self.symmetry = symmetry;
+    }
+}
+
+impl SeedSource for Soup {
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError> {
+        if self.symmetry == Symmetry::C1 {
+            let mut rng = Rng::new(self.seed);
+            return Ok(Board::from_fn(x_size, y_size, |_, _| {
+                rng.chance(self.density)
+            }));
+        }
+        let size = x_size.min(y_size);
+        let square = soup(size, self.density, self.symmetry, self.seed);
+        Ok(centered(&square, x_size, y_size))
+    }
+}
+
+// A pattern file, read each time a board is seeded from it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternFile {
+    path: PathBuf,
+}
+
+impl PatternFile {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        PatternFile { path: path.into() }
+    }
+}
+
+impl SeedSource for PatternFile {
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError> {
+        Pattern::load(&self.path)?.seed(x_size, y_size)
+    }
+}
+
+// A PNG image, one cell per pixel, in the middle of the board. Dark pixels are live cells and light
+// or transparent ones are dead, so a black-on-white drawing comes out as it looks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    path: PathBuf,
+    threshold: u8,
+}
+
+impl Image {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Image {
+            path: path.into(),
+            threshold: 128,
+        }
+    }
+
+    // Pixels with a brightness below the threshold are live. It's 128 to start with.
+    pub fn set_threshold(&mut self, threshold: u8) {
+        self.threshold = threshold;
+    }
+}
+
+impl SeedSource for Image {
+    fn seed(&mut self, x_size: usize, y_size: usize) -> Result<Board, SeedError> {
+        let file = File::open(&self.path).map_err(|err| SeedError::Image(err.to_string()))?;
+        let image = decode(BufReader::new(file), self.threshold)?;
+        Ok(centered(&image, x_size, y_size))
+    }
+}
+
+// Read a PNG into a board, with the pixels darker than `threshold` live.
+fn decode<R: BufRead + Seek>(reader: R, threshold: u8) -> Result<Board, SeedError> {
+    let image = |err: png::DecodingError| SeedError::Image(err.to_string());
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(image)?;
+    let size = reader
+        .output_buffer_size()
+        .ok_or_else(|| SeedError::Image("the image is too large".to_string()))?;
+    let mut pixels = vec![0; size];
+    let info = reader.next_frame(&mut pixels).map_err(image)?;
+
+    let samples = info.color_type.samples();
+    let (width, height) = (info.width as usize, info.height as usize);
+    Ok(Board::from_fn(width, height, |x, y| {
+        let pixel = &pixels[y * info.line_size + x * samples..][..samples];
+        let (brightness, alpha) = match *pixel {
+            [gray] => (u32::from(gray), 255),
+            [gray, alpha] => (u32::from(gray), alpha),
+            [red, green, blue] => (
+                (u32::from(red) + u32::from(green) + u32::from(blue)) / 3,
+                255,
+            ),
+            [red, green, blue, alpha] => {
+                let brightness = (u32::from(red) + u32::from(green) + u32::from(blue)) / 3;
+                (brightness, alpha)
+            }
+            _ => (255, 0),
+        };
+        alpha >= 128 && brightness < u32::from(threshold)
+    }))
+}
+
+// A board of at least the given size with `board` in the middle of it.
+fn centered(board: &Board, x_size: usize, y_size: usize) -> Board {
+    let (x_size, y_size) = (x_size.max(board.x_size()), y_size.max(board.y_size()));
+    let left = (x_size - board.x_size()) / 2;
+    let top = (y_size - board.y_size()) / 2;
+    Board::from_fn(x_size, y_size, |x, y| {
+        (left..left + board.x_size()).contains(&x)
+            && (top..top + board.y_size()).contains(&y)
+            && board[(x - left, y - top)]
+    })
+}
+
+// Why a board couldn't be seeded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeedError {
+    // The pattern file couldn't be read.
+    Pattern(PatternError),
+    // The image couldn't be read or decoded.
+    Image(String),
+}
+
+impl From<PatternError> for SeedError {
+    fn from(err: PatternError) -> Self {
+        SeedError::Pattern(err)
+    }
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedError::Pattern(err) => err.fmt(f),
+            SeedError::Image(err) => write!(f, "couldn't read the image: {err}"),
+        }
+    }
+}
+
+impl Error for SeedError {}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_sources() {
+        let mut diagonal = |x: usize, y: usize| x == y;
+        let board = diagonal.seed(3, 3).unwrap();
+        assert_eq!("O..\n.O.\n..O\n", board.to_string());
+
+        // Patterns go in the middle, and a board too small for one grows to fit it.
+        let mut glider: Pattern = "x = 3, y = 3\nbo$2bo$3o!".parse().unwrap();
+        let board = glider.seed(5, 5).unwrap();
+        assert_eq!(".....\n..O..\n...O.\n.OOO.\n.....\n", board.to_string());
+        assert_eq!((3, 4), board_size(&glider.seed(2, 4).unwrap()));
+
+        let mut soup = Soup::new(0.5, 7);
+        assert_eq!(soup.seed(20, 10), soup.seed(20, 10));
+        assert_ne!(soup.seed(20, 10), Soup::new(0.5, 8).seed(20, 10));
+        soup.set_symmetry(Symmetry::D8);
+        let board = soup.seed(12, 8).unwrap();
+        assert!((0..8).all(|y| !board[(0, y)] && !board[(1, y)] && !board[(10, y)]));
+        assert!((0..8).all(|y| (0..8).all(|x| board[(2 + x, y)] == board[(9 - x, y)])));
+        assert!(board.population() > 0);
+
+        let mut missing = PatternFile::new("no/such/pattern.rle");
+        assert!(matches!(
+            missing.seed(5, 5),
+            Err(SeedError::Pattern(PatternError::Io(_)))
+        ));
+    }
+
+    fn board_size(board: &Board) -> (usize, usize) {
+        (board.x_size(), board.y_size())
+    }
+
+    #[test]
+    fn test_decode() {
+        // A 3x2 image: black, white, dark gray on top, then transparent black, light gray, black.
+        let pixels: [u8; 24] = [
+            0, 0, 0, 255, 255, 255, 255, 255, 40, 40, 40, 255, //
+            0, 0, 0, 0, 200, 200, 200, 255, 0, 0, 0, 255,
+        ];
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 3, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+        writer.finish().unwrap();
+
+        let board = decode(Cursor::new(&png), 128).unwrap();
+        assert_eq!("O.O\n..O\n", board.to_string());
+        let board = decode(Cursor::new(&png), 255).unwrap();
+        assert_eq!("O.O\n.OO\n", board.to_string());
+        assert!(matches!(
+            decode(Cursor::new(b"not a png"), 128),
+            Err(SeedError::Image(_))
+        ));
+    }
+}
@@ -0,0 +1,40 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::Board;
+
+// A game's board as it was at one generation. Taking one doesn't copy the board, and neither does
+// cloning one, so a simulation thread can hand every generation to a UI thread to draw. It reads
+// like the Board it dereferences to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    board: Arc<Board>,
+    generation: usize,
+}
+
+impl Snapshot {
+    pub(crate) fn new(board: Arc<Board>, generation: usize) -> Self {
+        Snapshot { board, generation }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // Whether two snapshots share the same board, without comparing their cells.
+    pub fn ptr_eq(&self, other: &Snapshot) -> bool {
+        Arc::ptr_eq(&self.board, &other.board)
+    }
+}
+
+impl Deref for Snapshot {
+    type Target = Board;
+
+    fn deref(&self) -> &Board {
+        &self.board
+    }
+}
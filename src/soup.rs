@@ -0,0 +1,182 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::rng::Rng;
+use crate::Board;
+
+// The symmetry a soup is built with, named after apgsearch's symmetry classes. Each class is the
+// group of rotations and reflections of the square soup that leave it unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    // No symmetry.
+    #[default]
+    C1,
+    // Unchanged by a half turn.
+    C2,
+    // Unchanged by a quarter turn.
+    C4,
+    // Mirrored left to right.
+    D2,
+    // Mirrored across the diagonal.
+    D2x,
+    // Mirrored left to right and top to bottom.
+    D4,
+    // Mirrored across both diagonals.
+    D4x,
+    // Unchanged by every rotation and reflection.
+    D8,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::C1,
+        Symmetry::C2,
+        Symmetry::C4,
+        Symmetry::D2,
+        Symmetry::D2x,
+        Symmetry::D4,
+        Symmetry::D4x,
+        Symmetry::D8,
+    ];
+
+    // Whether an x_size by y_size board can have this symmetry. Quarter turns and diagonal
+    // mirrors need a square.
+    pub fn fits(&self, x_size: usize, y_size: usize) -> bool {
+        match self {
+            Symmetry::C1 | Symmetry::C2 | Symmetry::D2 | Symmetry::D4 => true,
+            _ => x_size == y_size,
+        }
+    }
+
+    // Every image of (x, y) on an x_size by y_size board under this symmetry's group, including
+    // (x, y) itself. The board has to fit the symmetry.
+    pub(crate) fn orbit(
+        &self,
+        x: usize,
+        y: usize,
+        x_size: usize,
+        y_size: usize,
+    ) -> Vec<(usize, usize)> {
+        let (flip_x, flip_y) = (x_size - 1 - x, y_size - 1 - y);
+        match self {
+            Symmetry::C1 => vec![(x, y)],
+            Symmetry::C2 => vec![(x, y), (flip_x, flip_y)],
+            Symmetry::C4 => vec![(x, y), (flip_y, x), (flip_x, flip_y), (y, flip_x)],
+            Symmetry::D2 => vec![(x, y), (flip_x, y)],
+            Symmetry::D2x => vec![(x, y), (y, x)],
+            Symmetry::D4 => vec![(x, y), (flip_x, y), (x, flip_y), (flip_x, flip_y)],
+            Symmetry::D4x => vec![(x, y), (y, x), (flip_y, flip_x), (flip_x, flip_y)],
+            Symmetry::D8 => vec![
+                (x, y),
+                (flip_y, x),
+                (flip_x, flip_y),
+                (y, flip_x),
+                (flip_x, y),
+                (x, flip_y),
+                (y, x),
+                (flip_y, flip_x),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = UnknownSymmetry;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symmetry::ALL
+            .into_iter()
+            .find(|symmetry| symmetry.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownSymmetry(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownSymmetry(pub String);
+
+impl fmt::Display for UnknownSymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = Symmetry::ALL.iter().map(Symmetry::to_string).collect();
+        write!(
+            f,
+            "unknown symmetry {:?}, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownSymmetry {}
+
+// Build a size x size random soup with the given symmetry, where each cell is live with
+// probability `density`. Every cell takes the random value of the first cell in its orbit, so the
+// result is unchanged by each of the symmetry's transformations. The same seed always produces the
+// same soup.
+pub fn soup(size: usize, density: f64, symmetry: Symmetry, seed: u64) -> Board {
+    let mut rng = Rng::new(seed);
+    let random = Board::from_fn(size, size, |_, _| rng.chance(density));
+    Board::from_fn(size, size, |x, y| {
+        let (x, y) = symmetry
+            .orbit(x, y, size, size)
+            .into_iter()
+            .min()
+            .unwrap_or((x, y));
+        random[(x, y)]
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transforms(board: &Board) -> [Board; 3] {
+        let size = board.x_size();
+        let last = size - 1;
+        [
+            Board::from_fn(size, size, |x, y| board[(last - x, y)]),
+            Board::from_fn(size, size, |x, y| board[(y, x)]),
+            Board::from_fn(size, size, |x, y| board[(last - y, x)]),
+        ]
+    }
+
+    #[test]
+    fn test_soup_is_deterministic() {
+        let board = soup(16, 0.5, Symmetry::C1, 3);
+        assert_eq!(board, soup(16, 0.5, Symmetry::C1, 3));
+        assert_ne!(board, soup(16, 0.5, Symmetry::C1, 4));
+    }
+
+    #[test]
+    fn test_soup_symmetries() {
+        for size in [7, 16] {
+            let d8 = soup(size, 0.5, Symmetry::D8, 11);
+            assert!(transforms(&d8).iter().all(|image| *image == d8));
+
+            let [mirrored, transposed, rotated] = transforms(&soup(size, 0.5, Symmetry::D2, 11));
+            assert_eq!(soup(size, 0.5, Symmetry::D2, 11), mirrored);
+            assert_ne!(mirrored, transposed);
+            assert_ne!(mirrored, rotated);
+
+            let c4 = soup(size, 0.5, Symmetry::C4, 11);
+            assert_eq!(c4, transforms(&c4)[2]);
+            assert_ne!(c4, transforms(&c4)[0]);
+        }
+    }
+
+    #[test]
+    fn test_parse_symmetry() {
+        assert_eq!(Ok(Symmetry::D2x), "d2x".parse());
+        assert_eq!(Ok(Symmetry::C1), "C1".parse());
+        assert_eq!(
+            Err(UnknownSymmetry("D3".to_string())),
+            "D3".parse::<Symmetry>()
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::rule::{RuleTable, OFFSETS};
+use crate::{Board, Changes, LifeLike, Neighborhood, Pattern, Rule};
+
+// A game on an unbounded plane, like Golly's. Only the live cells are stored, by their (x, y)
+// coordinates, so there are no edges for patterns to wrap around or break up against: gliders fly
+// off forever and puffers leave as long a trail as they like. Coordinates can be negative, and y
+// grows downwards like it does on a Board. Strictly, the plane is 2^64 cells across and wraps
+// around at the ends of i64's range, so a cell at i64::MAX has neighbors at i64::MIN rather than
+// overflowing, but nothing gets that far by running.
+//
+// Each iteration only visits the live cells and their neighbors, so it costs time in proportion to
+// the population rather than to the area the pattern covers. That makes it a poor fit for dense
+// soups, where a Game's packed boards are much faster, and a good one for sparse patterns that
+// spread out over a large area.
+#[derive(Clone)]
+pub struct SparseGame {
+    cells: HashSet<(i64, i64)>,
+    table: RuleTable,
+}
+
+impl SparseGame {
+    // Build a new, empty game running Conway's rule.
+    pub fn new() -> Self {
+        SparseGame {
+            cells: HashSet::new(),
+            table: RuleTable::new(&LifeLike::CONWAY),
+        }
+    }
+
+    // Replace the rule used for every following iteration. Rules that birth dead cells with no
+    // live neighbors are refused, since they'd fill the whole plane in a single generation.
+    pub fn set_rule<R: Rule>(&mut self, rule: R) -> Result<(), UnboundedRule> {
+        let table = RuleTable::new(&rule);
+        if table.next(false, Neighborhood::default()) {
+            return Err(UnboundedRule);
+        }
+        self.table = table;
+        Ok(())
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        self.cells.contains(&(x, y))
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        if alive {
+            self.cells.insert((x, y));
+        } else {
+            self.cells.remove(&(x, y));
+        }
+    }
+
+    // Flip the cell at (x, y) between live and dead, returning its new state.
+    pub fn toggle(&mut self, x: i64, y: i64) -> bool {
+        let alive = !self.get(x, y);
+        self.set(x, y, alive);
+        alive
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    // How many cells are live.
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    // The coordinates of every live cell, in no particular order.
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.iter().copied()
+    }
+
+    // The smallest box holding every live cell, as its left and top edges and its width and
+    // height, or None if every cell is dead. Sizes too big for a usize, which only patterns at
+    // both ends of i64's range have, come out as usize::MAX.
+    pub fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        let mut cells = self.cells.iter();
+        let &(x, y) = cells.next()?;
+        let (mut left, mut top, mut right, mut bottom) = (x, y, x, y);
+        for &(x, y) in cells {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+        let extent =
+            |low: i64, high: i64| (high.wrapping_sub(low) as u64 as usize).saturating_add(1);
+        Some((left, top, extent(left, right), extent(top, bottom)))
+    }
+
+    // Run a single iteration of the game, reporting how many cells were born and died.
+    pub fn iterate(&mut self) -> Changes {
+        // Each live cell sets its bit in the neighborhoods of the cells around it. Live cells get
+        // an entry of their own too, so the ones with no live neighbors still get a chance to die.
+        let mut neighborhoods: HashMap<(i64, i64), u8> = HashMap::with_capacity(self.cells.len());
+        for &(x, y) in &self.cells {
+            neighborhoods.entry((x, y)).or_default();
+            for (bit, &(dx, dy)) in OFFSETS.iter().enumerate() {
+                *neighborhoods
+                    .entry((x.wrapping_sub(dx as i64), y.wrapping_sub(dy as i64)))
+                    .or_default() |= 1 << bit;
+            }
+        }
+
+        let mut changes = Changes::default();
+        let mut next = HashSet::with_capacity(self.cells.len());
+        for (cell, bits) in neighborhoods {
+            let alive = self.cells.contains(&cell);
+            let is_live = self.table.next(alive, Neighborhood::from_bits(bits));
+            match (alive, is_live) {
+                (false, true) => changes.births += 1,
+                (true, false) => changes.deaths += 1,
+                _ => {}
+            }
+            if is_live {
+                next.insert(cell);
+            }
+        }
+        self.cells = next;
+        changes
+    }
+
+    // Run the simulation for a set number of iterations.
+    pub fn run(&mut self, iters: usize) {
+        for _ in 0..iters {
+            self.iterate();
+        }
+    }
+
+    // Stamp a pattern onto the plane with its top-left corner at (x, y), adding its live cells to
+    // whatever's already there. Cells in any state but 0 are live.
+    pub fn stamp(&mut self, pattern: &Pattern, x: i64, y: i64) {
+        for (dx, dy, _) in pattern.live_cells() {
+            self.cells
+                .insert((x.wrapping_add(dx as i64), y.wrapping_add(dy as i64)));
+        }
+    }
+
+    // A dense copy of the width x height cells with their top-left corner at (x, y), for
+    // everything that works on Boards.
+    pub fn crop(&self, x: i64, y: i64, width: usize, height: usize) -> Board {
+        Board::from_fn(width, height, |dx, dy| {
+            self.get(x.wrapping_add(dx as i64), y.wrapping_add(dy as i64))
+        })
+    }
+
+    // A dense copy of the bounding box of the live cells, which is empty if every cell is dead.
+    // The box's top-left corner is at (0, 0) on the board.
+    pub fn to_board(&self) -> Board {
+        match self.bounding_box() {
+            Some((x, y, width, height)) => self.crop(x, y, width, height),
+            None => Board::new(0, 0),
+        }
+    }
+}
+
+impl Default for SparseGame {
+    fn default() -> Self {
+        SparseGame::new()
+    }
+}
+
+// Copy a board's live cells onto the plane at the same coordinates. The board's edges don't come
+// along, so whatever was going to wrap around them carries on past them instead.
+impl From<&Board> for SparseGame {
+    fn from(board: &Board) -> Self {
+        let mut game = SparseGame::new();
+        game.cells = board
+            .live_cells()
+            .map(|point| (point.x as i64, point.y as i64))
+            .collect();
+        game
+    }
+}
+
+// Sparse games print like Boards, covering the bounding box of the live cells.
+impl fmt::Display for SparseGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_board())
+    }
+}
+
+// A rule that births cells with no live neighbors, which a SparseGame can't run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnboundedRule;
+
+impl fmt::Display for UnboundedRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the rule births cells with no live neighbors, which would fill an unbounded plane"
+        )
+    }
+}
+
+impl Error for UnboundedRule {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BlendMode, Game, Point, Topology};
+
+    #[test]
+    fn test_glider_leaves() {
+        let glider = Pattern::from_cells(".O.\n..O\nOOO\n").unwrap();
+        let mut game = SparseGame::new();
+        game.stamp(&glider, -2, -2);
+        assert_eq!(Some((-2, -2, 3, 3)), game.bounding_box());
+
+        // A glider moves one cell diagonally every four generations, and nothing stops it.
+        for _ in 0..1000 {
+            game.iterate();
+        }
+        assert_eq!(Some((248, 248, 3, 3)), game.bounding_box());
+        assert_eq!(5, game.population());
+        assert_eq!(".O.\n..O\nOOO\n", game.to_string());
+        assert!(game.get(249, 248));
+        assert!(!game.toggle(249, 248));
+    }
+
+    #[test]
+    fn test_matches_game() {
+        let board = Game::random(20, 16, 0.4, 5).board().clone();
+        let mut sparse = SparseGame::from(&board);
+        sparse.set_rule(LifeLike::new(&[3, 6], &[2, 3])).unwrap();
+
+        // A board with dead edges and plenty of room around the pattern sees the same plane.
+        let mut dense = Board::new(80, 76);
+        dense
+            .blend(&board, Point::new(30, 30), BlendMode::Or)
+            .unwrap();
+        let mut game = Game::from(dense);
+        game.set_topology(Topology::FixedDead);
+        game.set_rule(LifeLike::new(&[3, 6], &[2, 3]));
+        for _ in 0..12 {
+            assert_eq!(game.iterate(), sparse.iterate());
+            assert_eq!(*game.board(), sparse.crop(-30, -30, 80, 76));
+        }
+    }
+
+    #[test]
+    fn test_plane_edges() {
+        // A lone cell in the far corner dies like any other.
+        let mut game = SparseGame::new();
+        game.set(i64::MAX, i64::MAX, true);
+        game.iterate();
+        assert_eq!(0, game.population());
+
+        // A blinker lying across the seam at the ends of the range turns like any other, with its
+        // middle at i64::MIN.
+        for x in [i64::MAX, i64::MIN, i64::MIN + 1] {
+            game.set(x, 0, true);
+        }
+        assert_eq!(Some((i64::MIN, 0, usize::MAX, 1)), game.bounding_box());
+        game.iterate();
+        let mut cells: Vec<_> = game.live_cells().collect();
+        cells.sort();
+        assert_eq!(vec![(i64::MIN, -1), (i64::MIN, 0), (i64::MIN, 1)], cells);
+        assert_eq!(Some((i64::MIN, -1, 1, 3)), game.bounding_box());
+
+        let mut game = SparseGame::new();
+        game.stamp(&Pattern::from_cells("OO\n").unwrap(), i64::MAX, i64::MIN);
+        assert!(game.get(i64::MIN, i64::MIN));
+        assert_eq!("OO\n", game.crop(i64::MAX, i64::MIN, 2, 1).to_string());
+    }
+
+    #[test]
+    fn test_unbounded_rule() {
+        let mut game = SparseGame::new();
+        assert_eq!(
+            Err(UnboundedRule),
+            game.set_rule(LifeLike::new(&[0, 3], &[2, 3]))
+        );
+        assert_eq!(Board::new(0, 0), game.to_board());
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+
+use crate::Changes;
+
+// A snapshot of how the game is doing after its last iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    // The number of iterations run so far.
+    pub generation: usize,
+    pub population: usize,
+    // The cells born and the cells that died in the last iteration.
+    pub births: usize,
+    pub deaths: usize,
+    // The fraction of cells whose state changed in the last iteration, from 0.0 on a board that's
+    // stopped changing to 1.0 when every cell flipped. It falls as a pattern settles, and a rule's
+    // typical temperature says how lively it is.
+    pub temperature: f64,
+}
+
+impl Stats {
+    pub(crate) fn new(
+        generation: usize,
+        population: usize,
+        cells: usize,
+        changes: Changes,
+    ) -> Self {
+        let changed = changes.births + changes.deaths;
+        Stats {
+            generation,
+            population,
+            births: changes.births,
+            deaths: changes.deaths,
+            temperature: if cells == 0 {
+                0.0
+            } else {
+                changed as f64 / cells as f64
+            },
+        }
+    }
+}
+
+impl Stats {
+    // How much the population changed in the last iteration, from its births and deaths.
+    pub fn delta(&self) -> isize {
+        self.births as isize - self.deaths as isize
+    }
+}
+
+// The stats of every generation since Game::record_stats() was called, oldest first, for plotting
+// how a pattern grows and settles: an R-pentomino's population wanders for over a thousand
+// generations before it levels off. Only the most recent `capacity` generations are kept, so a game
+// left running doesn't fill up memory.
+#[derive(Clone, Debug)]
+pub struct StatsSeries {
+    stats: VecDeque<Stats>,
+    capacity: usize,
+}
+
+impl StatsSeries {
+    pub(crate) fn new(capacity: usize, first: Stats) -> Self {
+        let mut series = StatsSeries {
+            stats: VecDeque::new(),
+            capacity,
+        };
+        series.record(first);
+        series
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // Add a generation's stats, dropping the oldest once there are more than the capacity.
+    pub(crate) fn record(&mut self, stats: Stats) {
+        if self.stats.len() == self.capacity {
+            self.stats.pop_front();
+        }
+        self.stats.push_back(stats);
+    }
+
+    // Forget the generations after `generation`, once the game has gone back to it.
+    pub(crate) fn rewind(&mut self, generation: usize) {
+        while self
+            .stats
+            .back()
+            .is_some_and(|stats| stats.generation > generation)
+        {
+            self.stats.pop_back();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Stats> + '_ {
+        self.stats.iter()
+    }
+
+    // The generation and population of every generation kept, the points of a population plot.
+    pub fn populations(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.stats
+            .iter()
+            .map(|stats| (stats.generation, stats.population))
+    }
+
+    // The generation with the most live cells, the earliest if there's a tie.
+    pub fn peak(&self) -> Option<&Stats> {
+        self.stats.iter().rev().max_by_key(|stats| stats.population)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Game;
+
+    #[test]
+    fn test_series() {
+        // The R-pentomino, which grows from 5 cells to 9 in its first few generations.
+        let mut game = Game::new(64, 64);
+        game.set([(31, 30), (32, 30), (30, 31), (31, 31), (31, 32)])
+            .unwrap();
+        game.record_stats(4);
+        game.run(5);
+        let series = game.stats_series().unwrap();
+        assert_eq!(4, series.len());
+        assert_eq!(
+            vec![(2, 7), (3, 9), (4, 8), (5, 9)],
+            series.populations().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 2, -1, 1],
+            series.iter().map(|stats| stats.delta()).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(3), series.peak().map(|stats| stats.generation));
+
+        game.record_stats(0);
+        assert!(game.stats_series().is_none());
+    }
+
+    #[test]
+    fn test_series_rewinds() {
+        let mut game = Game::random(16, 16, 0.4, 3);
+        game.record_history(4);
+        game.record_stats(100);
+        game.run(10);
+        game.seek(6).unwrap();
+        let series = game.stats_series().unwrap();
+        assert_eq!(Some(6), series.iter().last().map(|stats| stats.generation));
+        game.run(2);
+        assert_eq!(9, game.stats_series().unwrap().len());
+    }
+}
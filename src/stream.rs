@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use futures_core::Stream;
+
+use crate::{Game, Snapshot};
+
+// How many generations the stepping thread runs ahead of whoever's reading the stream.
+const BUFFER: usize = 8;
+
+// The generations of a game as an async stream of snapshots, starting with the board it had when
+// the stream was made, from Game::stream(). The game is stepped on a thread of its own, so a slow
+// generation never holds up the async runtime, and it stays a few generations ahead of the reader
+// at most. The stream runs forever unless the game's cancel handle is cancelled; dropping it stops
+// the thread.
+pub struct Generations {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    // Signalled when the queue has room again, or the stream's been dropped.
+    space: Condvar,
+}
+
+struct Queue {
+    snapshots: VecDeque<Snapshot>,
+    // The reader waiting for the next snapshot, if it's waiting.
+    waker: Option<Waker>,
+    // The game stopped, so no more snapshots are coming.
+    finished: bool,
+    // The stream was dropped, so nobody wants any more snapshots.
+    dropped: bool,
+}
+
+impl Shared {
+    fn queue(&self) -> MutexGuard<'_, Queue> {
+        // A panic while holding the lock can't leave the queue half-changed, so a poisoned lock is
+        // still fine to use.
+        self.queue.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Generations {
+    pub(crate) fn new(mut game: Game) -> Self {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                snapshots: VecDeque::with_capacity(BUFFER),
+                waker: None,
+                finished: false,
+                dropped: false,
+            }),
+            space: Condvar::new(),
+        });
+
+        let stepper = Arc::clone(&shared);
+        thread::spawn(move || {
+            let mut snapshot = game.snapshot();
+            loop {
+                let mut queue = stepper.queue();
+                while queue.snapshots.len() >= BUFFER && !queue.dropped {
+                    queue = stepper
+                        .space
+                        .wait(queue)
+                        .unwrap_or_else(|err| err.into_inner());
+                }
+                if queue.dropped {
+                    return;
+                }
+                queue.snapshots.push_back(snapshot);
+                if let Some(waker) = queue.waker.take() {
+                    waker.wake();
+                }
+                drop(queue);
+
+                if game.is_cancelled() {
+                    break;
+                }
+                game.iterate();
+                snapshot = game.snapshot();
+            }
+
+            let mut queue = stepper.queue();
+            queue.finished = true;
+            if let Some(waker) = queue.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Generations { shared }
+    }
+}
+
+impl Stream for Generations {
+    type Item = Snapshot;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Snapshot>> {
+        let mut queue = self.shared.queue();
+        if let Some(snapshot) = queue.snapshots.pop_front() {
+            self.shared.space.notify_one();
+            return Poll::Ready(Some(snapshot));
+        }
+        if queue.finished {
+            return Poll::Ready(None);
+        }
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for Generations {
+    fn drop(&mut self) {
+        self.shared.queue().dropped = true;
+        self.shared.space.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::{poll_fn, Future};
+    use std::task::Wake;
+
+    use super::*;
+    use crate::CancelHandle;
+
+    // Unparks the test thread when woken, so the stream can be read without an async runtime.
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn next(stream: &mut Generations) -> Option<Snapshot> {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut next = poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx));
+        loop {
+            if let Poll::Ready(snapshot) = Pin::new(&mut next).poll(&mut cx) {
+                return snapshot;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut game = Game::new(5, 5);
+        game.set([(2, 1), (2, 2), (2, 3)]).unwrap();
+        let cancel = CancelHandle::new();
+        game.set_cancel(Some(cancel.clone()));
+
+        let mut stream = game.stream();
+        for generation in 0..20 {
+            let snapshot = next(&mut stream).unwrap();
+            assert_eq!(generation, snapshot.generation());
+            assert_eq!(generation % 2 == 0, snapshot[(2, 1)]);
+        }
+
+        // Whatever was already stepped still comes through, then the stream ends.
+        cancel.cancel();
+        while next(&mut stream).is_some() {}
+        assert!(next(&mut stream).is_none());
+    }
+}
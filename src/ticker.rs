@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+// Turns a target speed in generations per second into a number of generations to run each frame,
+// however fast frames are coming. Time is passed in rather than read from a clock, so it works with
+// whatever clock a front end has, such as performance.now() in a browser:
+//
+//     let steps = ticker.steps(now);
+//     for _ in 0..steps {
+//         game.iterate();
+//     }
+//
+// The time left over after each whole step carries into the next frame, so a speed that doesn't
+// divide evenly into the frame rate still averages out right.
+#[derive(Clone, Debug)]
+pub struct Ticker {
+    rate: f64,
+    last: Option<Duration>,
+    // Time that's passed but hasn't been turned into steps yet.
+    owed: Duration,
+    max_steps: usize,
+}
+
+impl Ticker {
+    pub fn new(rate: f64) -> Self {
+        Ticker {
+            rate: rate.max(0.0),
+            last: None,
+            owed: Duration::ZERO,
+            max_steps: 1000,
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    // Change the speed, in generations per second. Time already owed is kept.
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate.max(0.0);
+    }
+
+    // The most steps a single frame will be asked to run. When the simulation can't keep up,
+    // whatever's owed past this is dropped rather than piling up, so the game slows down instead of
+    // freezing the front end while it catches up. It's 1000 to start with.
+    pub fn set_max_steps(&mut self, max_steps: usize) {
+        self.max_steps = max_steps;
+    }
+
+    // Forget the time since the last frame, for when stepping pauses and resumes.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.owed = Duration::ZERO;
+    }
+
+    // How many steps to run for a frame at time `now`, measured from any fixed point. The first
+    // frame after a reset only notes the time and runs nothing.
+    pub fn steps(&mut self, now: Duration) -> usize {
+        let elapsed = self
+            .last
+            .map_or(Duration::ZERO, |last| now.saturating_sub(last));
+        self.last = Some(now);
+        if self.rate == 0.0 {
+            self.owed = Duration::ZERO;
+            return 0;
+        }
+
+        self.owed += elapsed;
+        let step = self.step_time();
+        let steps = (self.owed.as_secs_f64() / step.as_secs_f64()) as usize;
+        if steps > self.max_steps {
+            self.owed = Duration::ZERO;
+            return self.max_steps;
+        }
+        self.owed = self.owed.saturating_sub(step.mul_f64(steps as f64));
+        steps
+    }
+
+    // How long after the last frame the next step falls due, for scheduling a wakeup.
+    pub fn until_next(&self) -> Option<Duration> {
+        (self.rate > 0.0).then(|| self.step_time().saturating_sub(self.owed))
+    }
+
+    fn step_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.rate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_steps() {
+        // 25 generations a second is one every 40ms, which 60 frames a second don't line up with.
+        let mut ticker = Ticker::new(25.0);
+        assert_eq!(0, ticker.steps(ms(1000)));
+        let total: usize = (1..=60)
+            .map(|frame| ticker.steps(ms(1000 + frame * 1000 / 60)))
+            .sum();
+        assert_eq!(25, total);
+
+        assert_eq!(Some(ms(40)), ticker.until_next());
+        assert_eq!(0, ticker.steps(ms(2030)));
+        assert_eq!(Some(ms(10)), ticker.until_next());
+        assert_eq!(2, ticker.steps(ms(2085)));
+
+        // A long stall is capped rather than repaid.
+        ticker.set_max_steps(10);
+        assert_eq!(10, ticker.steps(ms(60_000)));
+        assert_eq!(1, ticker.steps(ms(60_040)));
+
+        ticker.reset();
+        assert_eq!(0, ticker.steps(ms(90_000)));
+        ticker.set_rate(0.0);
+        assert_eq!(0, ticker.steps(ms(95_000)));
+        assert_eq!(None, ticker.until_next());
+    }
+}
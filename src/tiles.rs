@@ -0,0 +1,88 @@
+use crate::{Board, Rect};
+
+// What's been happening in one square tile of the board, as of the last iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TileStats {
+    // The cells the tile covers. Tiles along the right and bottom edges are cut short when the
+    // board isn't a whole number of tiles across.
+    pub tile: Rect,
+    pub population: usize,
+    // The fraction of the tile's cells that changed in the last iteration.
+    pub activity: f64,
+    // The last generation any of the tile's cells changed in, if they ever have.
+    pub last_changed: Option<usize>,
+}
+
+// Statistics for every tile of the board, refreshed as the game runs so front ends can find the
+// busy parts of a huge board without going over it cell by cell themselves.
+#[derive(Clone, Debug)]
+pub(crate) struct Tiles {
+    size: usize,
+    // How many tiles fit across the board.
+    columns: usize,
+    // Row by row, from the top-left tile.
+    stats: Vec<TileStats>,
+}
+
+impl Tiles {
+    pub(crate) fn new(size: usize, board: &Board) -> Self {
+        let columns = board.x_size().div_ceil(size);
+        let rows = board.y_size().div_ceil(size);
+        let mut stats = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let (x, y) = (column * size, row * size);
+                let tile = Rect::new(
+                    x,
+                    y,
+                    size.min(board.x_size() - x),
+                    size.min(board.y_size() - y),
+                );
+                let population = tile
+                    .xs()
+                    .flat_map(|x| tile.ys().map(move |y| (x, y)))
+                    .filter(|&point| board[point])
+                    .count();
+                stats.push(TileStats {
+                    tile,
+                    population,
+                    ..TileStats::default()
+                });
+            }
+        }
+        Tiles {
+            size,
+            columns,
+            stats,
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn stats(&self) -> &[TileStats] {
+        &self.stats
+    }
+
+    // Recount every tile after an iteration from `previous` to `current`, the given generation.
+    pub(crate) fn update(&mut self, previous: &Board, current: &Board, generation: usize) {
+        let mut counts = vec![(0, 0); self.stats.len()];
+        for (x, (before, after)) in previous.columns().zip(current.columns()).enumerate() {
+            let column = x / self.size;
+            for (y, (before, after)) in before.zip(after).enumerate() {
+                let (population, changed) = &mut counts[(y / self.size) * self.columns + column];
+                *population += after as usize;
+                *changed += (before != after) as usize;
+            }
+        }
+
+        for (stats, (population, changed)) in self.stats.iter_mut().zip(counts) {
+            stats.population = population;
+            stats.activity = changed as f64 / (stats.tile.width * stats.tile.height) as f64;
+            if changed > 0 {
+                stats.last_changed = Some(generation);
+            }
+        }
+    }
+}
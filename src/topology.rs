@@ -0,0 +1,110 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// What lies past the edges of a board, as far as the cells along them are concerned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    // Each edge joins up with the opposite one, so the board is the surface of a torus and
+    // anything leaving one side comes back on the other.
+    #[default]
+    Toroidal,
+    // Everything past the edges is dead, as if the board were a window onto an empty plane that
+    // cells can never be born in. Gliders leaving the board break up against the edge rather than
+    // coming back.
+    FixedDead,
+    // The edges are mirrors: the cell past an edge is the same as the cell just inside it, so
+    // patterns see a reflection of themselves there.
+    Mirror,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 3] = [Topology::Toroidal, Topology::FixedDead, Topology::Mirror];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Topology::Toroidal => "toroidal",
+            Topology::FixedDead => "dead",
+            Topology::Mirror => "mirror",
+        }
+    }
+
+    // The neighbor of position `at` one step back along an axis of length `size`, or None if it's
+    // past the edge and dead.
+    pub(crate) fn before(&self, at: usize, size: usize) -> Option<usize> {
+        match (at, self) {
+            (0, Topology::Toroidal) => Some(size - 1),
+            (0, Topology::FixedDead) => None,
+            (0, Topology::Mirror) => Some(0),
+            _ => Some(at - 1),
+        }
+    }
+
+    // The neighbor of position `at` one step on along an axis of length `size`, or None if it's
+    // past the edge and dead.
+    pub(crate) fn after(&self, at: usize, size: usize) -> Option<usize> {
+        if at + 1 < size {
+            return Some(at + 1);
+        }
+        match self {
+            Topology::Toroidal => Some(0),
+            Topology::FixedDead => None,
+            Topology::Mirror => Some(at),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Topology {
+    type Err = UnknownTopology;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Topology::ALL
+            .into_iter()
+            .find(|topology| topology.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownTopology(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownTopology(pub String);
+
+impl fmt::Display for UnknownTopology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = Topology::ALL.iter().map(Topology::name).collect();
+        write!(
+            f,
+            "unknown topology {:?}, expected one of {}",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownTopology {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_edges() {
+        let edges = |topology: Topology| {
+            (
+                topology.before(0, 4),
+                topology.after(3, 4),
+                topology.before(2, 4),
+            )
+        };
+        assert_eq!((Some(3), Some(0), Some(1)), edges(Topology::Toroidal));
+        assert_eq!((None, None, Some(1)), edges(Topology::FixedDead));
+        assert_eq!((Some(0), Some(3), Some(1)), edges(Topology::Mirror));
+        assert_eq!(Ok(Topology::FixedDead), "Dead".parse());
+        assert!("klein".parse::<Topology>().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+// Events about what the engine is doing, sent to `tracing` when the "tracing" feature is on: a span
+// for each run with its start and end, progress checkpoints during long runs, and the backend each
+// game settles on. Without the feature these do nothing, so the library never writes anywhere a
+// caller didn't ask for.
+
+#[cfg(feature = "tracing")]
+use tracing::span::EnteredSpan;
+
+use crate::Backend;
+
+// How many generations a run goes between checkpoints.
+pub(crate) const CHECKPOINT: usize = 1000;
+
+// A run in progress, ended by passing it to run_finished.
+pub(crate) struct Run {
+    #[cfg(feature = "tracing")]
+    span: EnteredSpan,
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn run_started(iterations: usize, generation: usize) -> Run {
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::debug_span!("run", iterations, start = generation).entered();
+        tracing::debug!("run started");
+        Run { span }
+    }
+    #[cfg(not(feature = "tracing"))]
+    Run {}
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn checkpoint(generation: usize, population: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(generation, population, "checkpoint");
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn run_finished(run: Run, generation: usize, population: usize) {
+    #[cfg(feature = "tracing")]
+    {
+        tracing::debug!(generation, population, "run finished");
+        drop(run.span);
+    }
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn backend_selected(requested: Backend, resolved: Backend) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(%requested, %resolved, "backend selected");
+}
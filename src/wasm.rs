@@ -0,0 +1,193 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{decode_link, encode_link, Game, LifeLike, Pattern};
+
+// A game for JavaScript, exported as Game. Coordinates and sizes are the same as the Rust Game's,
+// and errors come back as thrown JavaScript Errors.
+#[wasm_bindgen(js_name = Game)]
+pub struct JsGame {
+    game: Game,
+}
+
+#[wasm_bindgen(js_class = Game)]
+impl JsGame {
+    // Build a new, empty game.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize) -> JsGame {
+        JsGame {
+            game: Game::new(width, height),
+        }
+    }
+
+    // Build a game filled with a random soup. The same seed always produces the same board.
+    pub fn random(width: usize, height: usize, density: f64, seed: u32) -> JsGame {
+        JsGame {
+            game: Game::random(width, height, density, seed.into()),
+        }
+    }
+
+    // Build a game holding an RLE pattern, running the pattern's rule if it has a B/S one.
+    #[wasm_bindgen(js_name = fromRle)]
+    pub fn from_rle(rle: &str) -> Result<JsGame, JsError> {
+        let pattern: Pattern = rle.parse()?;
+        let mut game = Game::from(pattern.to_board());
+        if let Some(rule) = pattern.rule() {
+            game.set_rule(rule.parse::<LifeLike>()?);
+        }
+        Ok(JsGame { game })
+    }
+
+    // Build a game from a link's fragment, such as location.hash when the page loads.
+    #[wasm_bindgen(js_name = fromLink)]
+    pub fn from_link(fragment: &str) -> Result<JsGame, JsError> {
+        Ok(JsGame {
+            game: decode_link(fragment)?,
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> usize {
+        self.game.x_size()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.game.y_size()
+    }
+
+    // Replace the rule with a B/S rulestring such as "B36/S23".
+    #[wasm_bindgen(js_name = setRule)]
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), JsError> {
+        self.game.set_rule(rule.parse::<LifeLike>()?);
+        Ok(())
+    }
+
+    // The current rule's rulestring, or undefined if it isn't Life-like.
+    pub fn rule(&self) -> Option<String> {
+        self.game.life_like().map(|rule| rule.to_string())
+    }
+
+    // Run a single iteration, returning whether any cell changed.
+    pub fn iterate(&mut self) -> bool {
+        self.game.iterate().changed()
+    }
+
+    // Run several iterations in one call, to keep the number of calls across the boundary down.
+    pub fn step(&mut self, generations: u32) {
+        for _ in 0..generations {
+            self.game.iterate();
+        }
+    }
+
+    // Whether the cell at (x, y) is live. Cells off the board are dead.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.on_board(x, y) && self.game.is_alive(x, y)
+    }
+
+    // Set the cell at (x, y) live or dead, returning false and changing nothing if it's off the
+    // board.
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) -> bool {
+        if !self.on_board(x, y) {
+            return false;
+        }
+        *self.game.cell(x, y) = alive;
+        true
+    }
+
+    // Flip the cell at (x, y) between live and dead, returning its new state, or undefined if it's
+    // off the board.
+    pub fn toggle(&mut self, x: usize, y: usize) -> Option<bool> {
+        self.on_board(x, y).then(|| self.game.toggle(x, y))
+    }
+
+    pub fn clear(&mut self) {
+        self.game.clear();
+    }
+
+    pub fn population(&self) -> usize {
+        self.game.board().population()
+    }
+
+    // Every cell in row-major order as a Uint8Array of 1s and 0s, so cells[y * width + x] is the
+    // cell at (x, y). Drawing a frame from this takes one call instead of one per cell.
+    #[wasm_bindgen(js_name = cellsAsUint8Array)]
+    pub fn cells(&self) -> Vec<u8> {
+        self.game.rows().flat_map(|row| row.map(u8::from)).collect()
+    }
+
+    // Stamp an RLE pattern onto this game's board with its top-left corner at (x, y), adding its
+    // live cells to what's there and keeping the game's rule, as Game::load_rle() does.
+    #[wasm_bindgen(js_name = loadRle)]
+    pub fn load_rle(&mut self, rle: &str, x: usize, y: usize) -> Result<(), JsError> {
+        self.game.load_rle(rle, x, y)?;
+        Ok(())
+    }
+
+    // The board as RLE, in the same form Golly copies to the clipboard.
+    #[wasm_bindgen(js_name = toRle)]
+    pub fn to_rle(&self) -> String {
+        Pattern::from(&self.game).to_string()
+    }
+
+    // The board and rule as a link fragment, without the '#', for sharing the page as it is.
+    #[wasm_bindgen(js_name = toLink)]
+    pub fn to_link(&self) -> String {
+        encode_link(&self.game)
+    }
+}
+
+impl JsGame {
+    fn on_board(&self, x: usize, y: usize) -> bool {
+        x < self.game.x_size() && y < self.game.y_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cells_row_major() {
+        let mut game = JsGame::new(3, 2);
+        game.set(2, 0, true);
+        game.set(0, 1, true);
+        assert_eq!(vec![0, 0, 1, 1, 0, 0], game.cells());
+        assert_eq!(2, game.population());
+    }
+
+    #[test]
+    fn test_off_board() {
+        let mut game = JsGame::new(3, 2);
+        assert!(!game.set(3, 0, true));
+        assert!(!game.set(0, usize::MAX, true));
+        assert!(!game.get(0, 2));
+        assert_eq!(None, game.toggle(5, 5));
+        assert_eq!(Some(true), game.toggle(2, 1));
+        assert_eq!(1, game.population());
+    }
+
+    #[test]
+    fn test_load_rle() {
+        let mut game = JsGame::new(5, 5);
+        game.set_rule("B36/S23").unwrap();
+        game.load_rle("x = 3, y = 1, rule = B3/S23\n3o!", 1, 2)
+            .unwrap();
+        assert_eq!(vec![0, 0, 0, 0, 0], game.cells()[5..10]);
+        assert_eq!(vec![0, 1, 1, 1, 0], game.cells()[10..15]);
+        assert_eq!(Some("B36/S23".to_string()), game.rule());
+    }
+
+    #[test]
+    fn test_rle_round_trip() {
+        let game = JsGame::from_rle("x = 3, y = 3, rule = B36/S23\nbo$2bo$3o!").unwrap();
+        assert_eq!(Some("B36/S23".to_string()), game.rule());
+        assert_eq!("x = 3, y = 3, rule = B36/S23\nbo$2bo$3o!\n", game.to_rle());
+    }
+
+    #[test]
+    fn test_link_round_trip() {
+        let game = JsGame::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+        let opened = JsGame::from_link(&game.to_link()).unwrap();
+        assert_eq!(game.cells(), opened.cells());
+    }
+}
@@ -0,0 +1,395 @@
+use egui::{vec2, Color32, Id, Painter, PointerButton, Pos2, Response, Sense, Ui, Vec2};
+
+use crate::{render, render_ages, Board, Game, Palette, Rect, RenderTarget};
+
+// The smallest and largest a cell can be zoomed to, in points.
+const MIN_CELL: f32 = 0.5;
+const MAX_CELL: f32 = 64.0;
+
+// How many colors cells fade through as they age, each for cells twice as old as the last.
+const AGE_COLORS: u8 = 8;
+
+// An egui widget showing a board, for embedding a game in any egui application. Scrolling pans the
+// view, ctrl+scrolling or pinching zooms it around the pointer, and dragging with the secondary or
+// middle button pans too. Clicking or dragging with the primary button paints cells: live if the
+// first cell touched was dead, dead if it was live. Only the cells in view are drawn, so boards of
+// any size stay quick to show. The widget never changes the board itself, it reports the cells
+// painted as edits for the application to apply:
+//
+//     let output = GolWidget::new(game.board()).show(ui);
+//     for edit in output.edits {
+//         edit.apply(&mut game);
+//     }
+//
+// Snapshots can be shown too, since they deref to boards. The zoom and pan are kept in egui's
+// memory, under the widget's id.
+pub struct GolWidget<'a> {
+    board: &'a Board,
+    id: Id,
+    size: Option<Vec2>,
+    live: Option<Color32>,
+    background: Option<Color32>,
+    painting: bool,
+    scroll_zoom: bool,
+    ages: Option<&'a Game>,
+}
+
+// A cell painted by the user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edit {
+    pub x: usize,
+    pub y: usize,
+    pub alive: bool,
+}
+
+impl Edit {
+    pub fn apply(&self, game: &mut Game) {
+        game.board_mut().set(self.x, self.y, self.alive);
+    }
+}
+
+// What happened to the widget this frame.
+pub struct WidgetOutput {
+    pub response: Response,
+    // The cells painted this frame, in the order they were painted.
+    pub edits: Vec<Edit>,
+    // The cell under the pointer, and the cell clicked with the primary button this frame.
+    pub hovered: Option<(usize, usize)>,
+    pub clicked: Option<(usize, usize)>,
+    // The cells in view, within the board.
+    pub visible: Rect,
+    // Where the cells were drawn, for drawing over them.
+    pub cells: CellRects,
+}
+
+// Where a widget drew each cell of its board this frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellRects {
+    // The top-left corner of the cell at (0, 0).
+    origin: Pos2,
+    cell: f32,
+}
+
+impl CellRects {
+    // The square the cell at (x, y) fills. Positions past the board's edges are fine, for drawing
+    // just outside it.
+    pub fn rect(&self, x: isize, y: isize) -> egui::Rect {
+        let min = self.origin + vec2(x as f32, y as f32) * self.cell;
+        egui::Rect::from_min_size(min, Vec2::splat(self.cell))
+    }
+
+    // The size of a cell in points.
+    pub fn cell_size(&self) -> f32 {
+        self.cell
+    }
+}
+
+// The zoom and pan, kept between frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct View {
+    // The size of a cell in points.
+    cell: f32,
+    // The board position at the top-left corner of the widget, in cells.
+    offset: Vec2,
+    // While painting, whether cells are being made live or dead, and the last cell painted.
+    painting: Option<(bool, (usize, usize))>,
+    // The size of the board the view was made for, so a board of another size is fitted afresh.
+    board: (usize, usize),
+}
+
+impl View {
+    // A view fitting the whole board into the given size.
+    fn fit(board: &Board, size: Vec2) -> Self {
+        let cell = (size.x / board.x_size() as f32).min(size.y / board.y_size() as f32);
+        View {
+            cell: cell.clamp(MIN_CELL, MAX_CELL),
+            offset: Vec2::ZERO,
+            painting: None,
+            board: (board.x_size(), board.y_size()),
+        }
+    }
+
+    // The board position at a point in the widget, measured from its top-left corner.
+    fn position(&self, point: Vec2) -> Vec2 {
+        self.offset + point / self.cell
+    }
+
+    // Zoom by a factor, keeping the board position under `point` where it is.
+    fn zoom(&mut self, factor: f32, point: Vec2) {
+        let anchor = self.position(point);
+        self.cell = (self.cell * factor).clamp(MIN_CELL, MAX_CELL);
+        self.offset = anchor - point / self.cell;
+    }
+
+    // The cell under a point in the widget, if it's on the board.
+    fn cell_at(&self, point: Vec2, board: &Board) -> Option<(usize, usize)> {
+        let position = self.position(point);
+        let (x, y) = (position.x.floor(), position.y.floor());
+        (x >= 0.0 && y >= 0.0 && (x as usize) < board.x_size() && (y as usize) < board.y_size())
+            .then_some((x as usize, y as usize))
+    }
+
+    // The cells that can be seen in a widget of the given size, some of which may be past the
+    // board's right and bottom edges.
+    fn visible(&self, size: Vec2) -> Rect {
+        let start = self.offset.max(Vec2::ZERO);
+        let end = (self.offset + size / self.cell).ceil().max(Vec2::ZERO);
+        let (x, y) = (start.x as usize, start.y as usize);
+        Rect::new(
+            x,
+            y,
+            (end.x as usize).saturating_sub(x),
+            (end.y as usize).saturating_sub(y),
+        )
+    }
+
+    // Where the cells are drawn in a widget whose top-left corner is at `min`.
+    fn cells(&self, min: Pos2) -> CellRects {
+        CellRects {
+            origin: min - self.offset * self.cell,
+            cell: self.cell,
+        }
+    }
+}
+
+impl<'a> GolWidget<'a> {
+    pub fn new(board: &'a Board) -> Self {
+        GolWidget {
+            board,
+            id: Id::new("gol board"),
+            size: None,
+            live: None,
+            background: None,
+            painting: true,
+            scroll_zoom: false,
+            ages: None,
+        }
+    }
+
+    // Tell two widgets apart, so each keeps its own zoom and pan.
+    pub fn set_id(&mut self, id: Id) {
+        self.id = id;
+    }
+
+    // The size of the widget in points. It takes all the space available unless this is set.
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = Some(size);
+    }
+
+    // The colors of live cells and the background, which otherwise follow the egui theme.
+    pub fn set_colors(&mut self, live: Color32, background: Color32) {
+        self.live = Some(live);
+        self.background = Some(background);
+    }
+
+    // Whether the primary button paints cells, which it does unless this is turned off. With
+    // painting off, dragging with the primary button pans the view, and clicks are only reported.
+    pub fn set_painting(&mut self, painting: bool) {
+        self.painting = painting;
+    }
+
+    // Make scrolling zoom around the pointer instead of panning, for applications where dragging
+    // is the way to pan.
+    pub fn set_scroll_zoom(&mut self, scroll_zoom: bool) {
+        self.scroll_zoom = scroll_zoom;
+    }
+
+    // Color live cells by how long they've been live, as the game counts it with
+    // Game::track_ages(), from yellow for newborns to purple for cells that have been there for 128
+    // generations or more. The game's board should be the one being shown.
+    pub fn set_ages(&mut self, game: &'a Game) {
+        self.ages = Some(game);
+    }
+
+    // Forget the zoom and pan, fitting the whole board in the widget again next frame.
+    pub fn reset_view(ui: &Ui, id: Id) {
+        ui.data_mut(|data| data.remove::<View>(id));
+    }
+
+    pub fn show(self, ui: &mut Ui) -> WidgetOutput {
+        let size = self.size.unwrap_or_else(|| ui.available_size());
+        let (area, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+        let board = (self.board.x_size(), self.board.y_size());
+        let mut view = ui
+            .data(|data| data.get_temp::<View>(self.id))
+            .filter(|view| view.board == board)
+            .unwrap_or_else(|| View::fit(self.board, size));
+        let mut edits = Vec::new();
+
+        if response.hovered() {
+            let (mut zoom, scroll, pointer) = ui.input(|input| {
+                (
+                    input.zoom_delta(),
+                    input.smooth_scroll_delta,
+                    input.pointer.hover_pos(),
+                )
+            });
+            if self.scroll_zoom {
+                // Each notch of a mouse wheel, about 50 points, zooms by about a quarter.
+                zoom *= (scroll.y / 200.0).exp();
+            } else {
+                view.offset -= scroll / view.cell;
+            }
+            if let Some(pointer) = pointer {
+                if zoom != 1.0 {
+                    view.zoom(zoom, pointer - area.min);
+                }
+            }
+        }
+        if response.dragged_by(PointerButton::Secondary)
+            || response.dragged_by(PointerButton::Middle)
+            || !self.painting && response.dragged_by(PointerButton::Primary)
+        {
+            view.offset -= response.drag_delta() / view.cell;
+        }
+
+        let hovered = response
+            .hover_pos()
+            .and_then(|pointer| view.cell_at(pointer - area.min, self.board));
+        let clicked = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked())
+            .and_then(|pointer| view.cell_at(pointer - area.min, self.board));
+
+        let primary = self.painting
+            && response.is_pointer_button_down_on()
+            && ui.input(|input| input.pointer.primary_down());
+        match response.interact_pointer_pos() {
+            Some(pointer) if primary => {
+                if let Some((x, y)) = view.cell_at(pointer - area.min, self.board) {
+                    let alive = match view.painting {
+                        Some((_, last)) if last == (x, y) => None,
+                        Some((alive, _)) => Some(alive),
+                        None => Some(!self.board[(x, y)]),
+                    };
+                    if let Some(alive) = alive {
+                        edits.push(Edit { x, y, alive });
+                        view.painting = Some((alive, (x, y)));
+                    }
+                }
+            }
+            _ => view.painting = None,
+        }
+
+        let visuals = ui.visuals();
+        let painter = ui.painter_at(area);
+        painter.rect_filled(
+            area,
+            0.0,
+            self.background.unwrap_or(visuals.extreme_bg_color),
+        );
+        let region = view.visible(size);
+        let mut target = Cells {
+            painter: &painter,
+            origin: area.min + (vec2(region.x as f32, region.y as f32) - view.offset) * view.cell,
+            cell: view.cell,
+            live: self.live.unwrap_or(visuals.strong_text_color()),
+        };
+        match self.ages {
+            Some(game) => render_ages(
+                game,
+                region,
+                &Palette::gradient(AGE_COLORS + 1),
+                &mut target,
+            ),
+            None => render(self.board, region, &mut target),
+        }
+
+        let visible = Rect::new(
+            region.x.min(board.0),
+            region.y.min(board.1),
+            region.width.min(board.0.saturating_sub(region.x)),
+            region.height.min(board.1.saturating_sub(region.y)),
+        );
+        let cells = view.cells(area.min);
+        ui.data_mut(|data| data.insert_temp(self.id, view));
+        WidgetOutput {
+            response,
+            edits,
+            hovered,
+            clicked,
+            visible,
+            cells,
+        }
+    }
+}
+
+// Draws live cells as squares on a painter, leaving a gap around each once they're big enough for
+// the gaps to show.
+struct Cells<'a> {
+    painter: &'a Painter,
+    origin: Pos2,
+    cell: f32,
+    live: Color32,
+}
+
+impl RenderTarget for Cells<'_> {
+    fn begin_frame(&mut self, _width: usize, _height: usize) {}
+
+    fn draw_run(&mut self, x: usize, y: usize, length: usize) {
+        self.fill(x, y, length, self.live);
+    }
+
+    fn draw_state_run(&mut self, x: usize, y: usize, length: usize, _state: u8, color: [u8; 3]) {
+        let [r, g, b] = color;
+        self.fill(x, y, length, Color32::from_rgb(r, g, b));
+    }
+}
+
+impl Cells<'_> {
+    fn fill(&self, x: usize, y: usize, length: usize, color: Color32) {
+        let min = self.origin + vec2(x as f32, y as f32) * self.cell;
+        if self.cell < 4.0 {
+            let area = egui::Rect::from_min_size(min, vec2(length as f32, 1.0) * self.cell);
+            self.painter.rect_filled(area, 0.0, color);
+            return;
+        }
+        for offset in 0..length {
+            let min = min + vec2(offset as f32 * self.cell, 0.0);
+            let area = egui::Rect::from_min_size(min, Vec2::splat(self.cell));
+            self.painter
+                .rect_filled(area.shrink(self.cell * 0.05), 0.0, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let board = Board::new(20, 10);
+        let mut view = View::fit(&board, vec2(400.0, 400.0));
+        assert_eq!(20.0, view.cell);
+        assert_eq!(Some((3, 9)), view.cell_at(vec2(70.0, 199.0), &board));
+        assert_eq!(None, view.cell_at(vec2(70.0, 201.0), &board));
+        assert_eq!(Rect::new(0, 0, 20, 20), view.visible(vec2(400.0, 400.0)));
+
+        // Zooming keeps the cell under the pointer where it was.
+        view.zoom(2.0, vec2(70.0, 190.0));
+        assert_eq!(40.0, view.cell);
+        assert_eq!(Some((3, 9)), view.cell_at(vec2(70.0, 199.0), &board));
+        assert_eq!(Some((3, 9)), view.cell_at(vec2(70.0, 190.0), &board));
+        assert_eq!(Rect::new(1, 4, 11, 11), view.visible(vec2(400.0, 400.0)));
+
+        view.zoom(1000.0, Vec2::ZERO);
+        assert_eq!(MAX_CELL, view.cell);
+    }
+
+    #[test]
+    fn test_cell_rects() {
+        let mut view = View::fit(&Board::new(20, 10), vec2(400.0, 400.0));
+        view.offset = vec2(2.0, 1.0);
+        let cells = view.cells(Pos2::new(10.0, 50.0));
+        assert_eq!(
+            egui::Rect::from_min_size(Pos2::new(-30.0, 30.0), Vec2::splat(20.0)),
+            cells.rect(0, 0)
+        );
+        // The cell drawn at a point is the one cell_at finds there.
+        let rect = cells.rect(3, 9);
+        let point = rect.center() - Pos2::new(10.0, 50.0);
+        assert_eq!(Some((3, 9)), view.cell_at(point, &Board::new(20, 10)));
+        assert_eq!(-50.0, cells.rect(-1, 0).min.x);
+    }
+}
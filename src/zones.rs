@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::constraints::force;
+use crate::rule::RuleTable;
+use crate::{Board, Changes, Rect, Rule};
+
+// A rectangle of the board that runs its own rule.
+#[derive(Clone)]
+struct Zone {
+    region: Rect,
+    rule: Arc<dyn Rule>,
+    table: Arc<RuleTable>,
+}
+
+// The rule zones laid over a game's board, in the order they were added. Where zones overlap, the
+// last one added wins.
+//
+// Each cell follows the rule of the zone it's in, or the game's own rule outside every zone. A
+// cell's neighbors are counted the same way wherever they are, so cells along a zone's edge see
+// across it, and what they see was decided by the rule on the other side.
+#[derive(Clone, Default)]
+pub(crate) struct Zones {
+    zones: Vec<Zone>,
+}
+
+impl Zones {
+    pub(crate) fn add<R: Rule + 'static>(&mut self, region: Rect, rule: R) {
+        self.zones.push(Zone {
+            region,
+            table: Arc::new(RuleTable::new(&rule)),
+            rule: Arc::new(rule),
+        });
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.zones.clear();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    // The zone covering (x, y), if there is one.
+    fn zone_at(&self, x: usize, y: usize) -> Option<&Zone> {
+        self.zones
+            .iter()
+            .rev()
+            .find(|zone| zone.region.contains(x, y))
+    }
+
+    pub(crate) fn rule_at(&self, x: usize, y: usize) -> Option<&dyn Rule> {
+        self.zone_at(x, y).map(|zone| zone.rule.as_ref())
+    }
+
+    pub(crate) fn table_at(&self, x: usize, y: usize) -> Option<&RuleTable> {
+        self.zone_at(x, y).map(|zone| zone.table.as_ref())
+    }
+
+    // Redo the step from `previous` to `current` for every cell inside a zone, with the zone's
+    // rule, keeping the births and deaths in `changes` in line. Returns whether any cell changed.
+    pub(crate) fn apply(
+        &self,
+        previous: &Board,
+        current: &mut Board,
+        changes: &mut Changes,
+    ) -> bool {
+        let mut changed = false;
+        for (index, zone) in self.zones.iter().enumerate() {
+            let later = &self.zones[index + 1..];
+            for x in zone.region.xs().take_while(|&x| x < previous.x_size()) {
+                for y in zone.region.ys().take_while(|&y| y < previous.y_size()) {
+                    if later.iter().any(|zone| zone.region.contains(x, y)) {
+                        continue;
+                    }
+                    let next = zone
+                        .table
+                        .next(previous[(x, y)], previous.neighborhood(x, y));
+                    let index = previous.flat_index(x, y);
+                    changed |= force(previous, current, index, next, changes);
+                }
+            }
+        }
+        changed
+    }
+}